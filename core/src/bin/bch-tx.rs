@@ -0,0 +1,170 @@
+//! Command line companion of the editor, for the same workflows in scripts and CI.
+
+use std::io::{Read, Write};
+use std::process::ExitCode;
+
+use anyhow::{bail, Context as _, Result};
+use bch_tx_edit_core::json::TxJson;
+use bch_tx_edit_core::partially_signed::{PartiallySignedTransaction, SignatureType};
+use bitcoincash::hashes::hex::{FromHex, ToHex};
+use bitcoincash::psbt::serialize::Serialize as _;
+
+const USAGE: &str = "\
+Usage: bch-tx <command> [file]
+
+Reads the transaction from the file, or standard input without one or with -. Transactions are
+read as JSON, hex or binary, signed or in the Electron Cash unsigned format.
+
+Commands:
+    decode              Print the transaction as JSON
+    encode              Print the transaction as hex
+    fee [--schnorr]     Print the fee, size and fee rate, unsigned inputs estimated as signed
+                        with ECDSA or with Schnorr signatures
+    convert <format>    Print the transaction as json, hex or bin";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Format {
+    Json,
+    Hex,
+    Bin,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self> {
+        Ok(match s {
+            "json" => Format::Json,
+            "hex" => Format::Hex,
+            "bin" => Format::Bin,
+            _ => bail!("Unknown format {s:?}, expected json, hex or bin"),
+        })
+    }
+
+    /// Format of `input`: JSON if it starts with `{`, hex if it's only hex digits and whitespace.
+    fn detect(input: &[u8]) -> Self {
+        let trimmed = input.trim_ascii();
+        if trimmed.starts_with(b"{") {
+            Format::Json
+        } else if trimmed
+            .iter()
+            .all(|b| b.is_ascii_hexdigit() || b.is_ascii_whitespace())
+        {
+            Format::Hex
+        } else {
+            Format::Bin
+        }
+    }
+}
+
+fn parse_tx(input: &[u8]) -> Result<PartiallySignedTransaction> {
+    match Format::detect(input) {
+        Format::Json => {
+            let json: TxJson = serde_json::from_slice(input)?;
+            (&json).try_into()
+        }
+        Format::Hex => {
+            let mut hex = String::from_utf8(input.to_vec())?;
+            hex.retain(|c| !c.is_ascii_whitespace());
+            Ok(PartiallySignedTransaction::deserialize_any(
+                &Vec::from_hex(&hex)?,
+            )?)
+        }
+        Format::Bin => Ok(PartiallySignedTransaction::deserialize_any(input)?),
+    }
+}
+
+fn format_tx(tx: &PartiallySignedTransaction, format: Format) -> Result<Vec<u8>> {
+    Ok(match format {
+        Format::Json => {
+            let mut json = serde_json::to_vec_pretty(&TxJson::from(tx))?;
+            json.push(b'\n');
+            json
+        }
+        Format::Hex => format!("{}\n", tx.serialize().to_hex()).into_bytes(),
+        Format::Bin => tx.serialize(),
+    })
+}
+
+fn fee_report(tx: &PartiallySignedTransaction, signatures: SignatureType) -> String {
+    let size = tx.estimated_size(signatures);
+    let size = match tx.is_signed() {
+        true => format!("{size} bytes"),
+        false => format!("about {size} bytes once signed"),
+    };
+    match tx.fee() {
+        Some(fee) => format!(
+            "Fee: {fee} sats\nSize: {size}\nFee rate: {:.2} sats/byte\n",
+            fee as f64 / tx.estimated_size(signatures) as f64
+        ),
+        None => format!("Fee: unknown, signed inputs don't carry their value\nSize: {size}\n"),
+    }
+}
+
+fn read_input(path: Option<&str>) -> Result<Vec<u8>> {
+    match path {
+        None | Some("-") => {
+            let mut input = vec![];
+            std::io::stdin().read_to_end(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => std::fs::read(path).with_context(|| format!("Can't read {path}")),
+    }
+}
+
+fn run(args: &[String]) -> Result<Vec<u8>> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let ecdsa = SignatureType::Ecdsa;
+    let (format, signatures, path) = match args.as_slice() {
+        ["decode", path @ ..] => (Some(Format::Json), ecdsa, path),
+        ["encode", path @ ..] => (Some(Format::Hex), ecdsa, path),
+        ["fee", "--schnorr", path @ ..] => (None, SignatureType::Schnorr, path),
+        ["fee", path @ ..] => (None, ecdsa, path),
+        ["convert", format, path @ ..] => (Some(Format::parse(format)?), ecdsa, path),
+        _ => bail!("{USAGE}"),
+    };
+    let path = match path {
+        [] => None,
+        [path] => Some(*path),
+        _ => bail!("{USAGE}"),
+    };
+    let tx = parse_tx(&read_input(path)?)?;
+    match format {
+        Some(format) => format_tx(&tx, format),
+        None => Ok(fee_report(&tx, signatures).into_bytes()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args).and_then(|output| Ok(std::io::stdout().write_all(&output)?)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_tx, parse_tx, Format};
+
+    const TX_HEX: &str = concat!(
+        "01000000013c3b636f926cb2c5a8f971d7e06e488aa3d10f42202b293f936bafdf63d7908a1800000057",
+        "01ff4c53ff0488b21e0000000000000000005d2f27f71323296d52bf8475ad8dad79d6239fcd640629fd",
+        "dc8ef9a7229258a4023f72ac51c65717e8d44e8d86afacff3eed27ce00cea7b5a6fd1e6297fcbd4df901",
+        "00fe15feffffff20090600000000000262e80200000000001976a914c9226d620fe088b4d84a4ab0ca6b",
+        "4fe6dfb3193488ace31f0300000000001976a914795b6a18d92f888df281f85373288a6834a7d31a88ac",
+        "81cc0c00",
+    );
+
+    #[test]
+    fn test_formats() {
+        let tx = parse_tx(format!(" {TX_HEX}\n").as_bytes()).unwrap();
+        assert_eq!(tx.fee(), Some(219));
+        for format in [Format::Json, Format::Hex, Format::Bin] {
+            let output = format_tx(&tx, format).unwrap();
+            assert_eq!(Format::detect(&output), format);
+            assert_eq!(parse_tx(&output).unwrap(), tx);
+        }
+    }
+}
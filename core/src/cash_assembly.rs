@@ -0,0 +1,627 @@
+//! CashAssembly, the script language of Libauth, without its evaluations.
+//!
+//! Opcodes are written by name, `<...>` pushes its contents with the smallest possible opcode,
+//! and literals outside of a push are inserted as they are: hex like `0xabcd`, numbers like `-1`
+//! encoded as script numbers, and UTF-8 strings like `"hi"`. `//` and `/* */` are comments.
+//! Variables of templates, like `owner.public_key`, have to be [substituted](substitute) with
+//! their data before assembling. Every function knowing opcodes takes the
+//! [instruction set](InstructionSet) of the network edited for, as the opcodes of the next
+//! upgrade are only known on chipnet, which activates it first.
+//!
+//! * [CashAssembly](https://ide.bitauth.com/guides/cashassembly)
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use bitcoincash::{
+    blockdata::script::Builder,
+    hashes::hex::{FromHex, ToHex},
+    Network, Script,
+};
+
+use crate::util::{push_minimal, script_num};
+
+/// Names of the opcodes from `OP_NOP` (0x61) to `OP_OUTPUTTOKENAMOUNT` (0xd3), empty where the
+/// byte has no opcode.
+const NAMES: [&str; 0xd4 - 0x61] = [
+    "OP_NOP",
+    "OP_VER",
+    "OP_IF",
+    "OP_NOTIF",
+    "OP_BEGIN",
+    "OP_UNTIL",
+    "OP_ELSE",
+    "OP_ENDIF",
+    "OP_VERIFY",
+    "OP_RETURN",
+    "OP_TOALTSTACK",
+    "OP_FROMALTSTACK",
+    "OP_2DROP",
+    "OP_2DUP",
+    "OP_3DUP",
+    "OP_2OVER",
+    "OP_2ROT",
+    "OP_2SWAP",
+    "OP_IFDUP",
+    "OP_DEPTH",
+    "OP_DROP",
+    "OP_DUP",
+    "OP_NIP",
+    "OP_OVER",
+    "OP_PICK",
+    "OP_ROLL",
+    "OP_ROT",
+    "OP_SWAP",
+    "OP_TUCK",
+    "OP_CAT",
+    "OP_SPLIT",
+    "OP_NUM2BIN",
+    "OP_BIN2NUM",
+    "OP_SIZE",
+    "OP_INVERT",
+    "OP_AND",
+    "OP_OR",
+    "OP_XOR",
+    "OP_EQUAL",
+    "OP_EQUALVERIFY",
+    "OP_DEFINE",
+    "OP_INVOKE",
+    "OP_1ADD",
+    "OP_1SUB",
+    "OP_LSHIFTNUM",
+    "OP_RSHIFTNUM",
+    "OP_NEGATE",
+    "OP_ABS",
+    "OP_NOT",
+    "OP_0NOTEQUAL",
+    "OP_ADD",
+    "OP_SUB",
+    "OP_MUL",
+    "OP_DIV",
+    "OP_MOD",
+    "OP_LSHIFTBIN",
+    "OP_RSHIFTBIN",
+    "OP_BOOLAND",
+    "OP_BOOLOR",
+    "OP_NUMEQUAL",
+    "OP_NUMEQUALVERIFY",
+    "OP_NUMNOTEQUAL",
+    "OP_LESSTHAN",
+    "OP_GREATERTHAN",
+    "OP_LESSTHANOREQUAL",
+    "OP_GREATERTHANOREQUAL",
+    "OP_MIN",
+    "OP_MAX",
+    "OP_WITHIN",
+    "OP_RIPEMD160",
+    "OP_SHA1",
+    "OP_SHA256",
+    "OP_HASH160",
+    "OP_HASH256",
+    "OP_CODESEPARATOR",
+    "OP_CHECKSIG",
+    "OP_CHECKSIGVERIFY",
+    "OP_CHECKMULTISIG",
+    "OP_CHECKMULTISIGVERIFY",
+    "OP_NOP1",
+    "OP_CHECKLOCKTIMEVERIFY",
+    "OP_CHECKSEQUENCEVERIFY",
+    "OP_NOP4",
+    "OP_NOP5",
+    "OP_NOP6",
+    "OP_NOP7",
+    "OP_NOP8",
+    "OP_NOP9",
+    "OP_NOP10",
+    "OP_CHECKDATASIG",
+    "OP_CHECKDATASIGVERIFY",
+    "OP_REVERSEBYTES",
+    "",
+    "",
+    "",
+    "OP_INPUTINDEX",
+    "OP_ACTIVEBYTECODE",
+    "OP_TXVERSION",
+    "OP_TXINPUTCOUNT",
+    "OP_TXOUTPUTCOUNT",
+    "OP_TXLOCKTIME",
+    "OP_UTXOVALUE",
+    "OP_UTXOBYTECODE",
+    "OP_OUTPOINTTXHASH",
+    "OP_OUTPOINTINDEX",
+    "OP_INPUTBYTECODE",
+    "OP_INPUTSEQUENCENUMBER",
+    "OP_OUTPUTVALUE",
+    "OP_OUTPUTBYTECODE",
+    "OP_UTXOTOKENCATEGORY",
+    "OP_UTXOTOKENCOMMITMENT",
+    "OP_UTXOTOKENAMOUNT",
+    "OP_OUTPUTTOKENCATEGORY",
+    "OP_OUTPUTTOKENCOMMITMENT",
+    "OP_OUTPUTTOKENAMOUNT",
+];
+
+/// Opcodes of the next upgrade, with the bytes they take, unassigned in [`NAMES`]. Empty while
+/// chipnet runs the same rules as mainnet, since the May 2026 upgrade.
+const UPCOMING_NAMES: &[(u8, &str)] = &[];
+
+/// The opcodes known when assembling and disassembling.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum InstructionSet {
+    /// Those of the upgrades activated on mainnet.
+    #[default]
+    Current,
+    /// With those of the next upgrade too, for testing them on chipnet.
+    Upcoming,
+}
+
+impl InstructionSet {
+    pub fn for_network(network: Network) -> Self {
+        match network {
+            Network::Chipnet => InstructionSet::Upcoming,
+            _ => InstructionSet::Current,
+        }
+    }
+
+    /// The opcodes added to [`NAMES`].
+    fn added(self) -> &'static [(u8, &'static str)] {
+        match self {
+            InstructionSet::Current => &[],
+            InstructionSet::Upcoming => UPCOMING_NAMES,
+        }
+    }
+}
+
+/// The Libauth name of the opcode `byte` in the instruction set `set`, `OP_UNKNOWN` followed by
+/// the byte for unassigned ones.
+pub fn opcode_name_in(byte: u8, set: InstructionSet) -> String {
+    opcode_name_with(byte, set.added())
+}
+
+/// The name of `byte` with the opcodes of `added` assigned on top of [`NAMES`].
+fn opcode_name_with(byte: u8, added: &[(u8, &str)]) -> String {
+    if let Some((_, name)) = added.iter().find(|(b, _)| *b == byte) {
+        return name.to_string();
+    }
+    match byte {
+        0x00 => "OP_0".into(),
+        0x01..=0x4b => format!("OP_PUSHBYTES_{byte}"),
+        0x4c => "OP_PUSHDATA_1".into(),
+        0x4d => "OP_PUSHDATA_2".into(),
+        0x4e => "OP_PUSHDATA_4".into(),
+        0x4f => "OP_1NEGATE".into(),
+        0x50 => "OP_RESERVED".into(),
+        0x51..=0x60 => format!("OP_{}", byte - 0x50),
+        0x61..=0xd3 if !NAMES[usize::from(byte - 0x61)].is_empty() => {
+            NAMES[usize::from(byte - 0x61)].into()
+        }
+        _ => format!("OP_UNKNOWN{byte}"),
+    }
+}
+
+/// The opcode named `name` in the instruction set `set`, the inverse of [`opcode_name_in`].
+pub fn opcode_by_name_in(name: &str, set: InstructionSet) -> Option<u8> {
+    (0..=u8::MAX).find(|&byte| opcode_name_in(byte, set) == name)
+}
+
+/// Names of the opcodes starting with `prefix`, with or without its `OP_`, in any case. Pushes of a
+/// given size and unassigned bytes are left out, being written otherwise.
+pub fn opcode_completions(prefix: &str, set: InstructionSet) -> Vec<String> {
+    let prefix = prefix.to_ascii_uppercase();
+    if prefix.is_empty() {
+        return vec![];
+    }
+    (0..=u8::MAX)
+        .filter(|byte| !(0x01..=0x4b).contains(byte))
+        .map(|byte| opcode_name_in(byte, set))
+        .filter(|name| !name.starts_with("OP_UNKNOWN"))
+        .filter(|name| name.starts_with(&prefix) || name[3..].starts_with(&prefix))
+        .collect()
+}
+
+/// An assembly error, at a 1-based line and column counted in characters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error {
+    fn new(asm: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &asm[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Error {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CashAssembly error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Compile CashAssembly into a script, with the opcodes of the instruction set `set`.
+pub fn assemble_in(asm: &str, set: InstructionSet) -> Result<Script, Error> {
+    compile(asm, set, &mut vec![])
+}
+
+/// Byte ranges of the words of `asm` that aren't opcodes, unknown opcodes and variables alike, to
+/// point them all out where [`assemble_in`] fails at the first. Words after another error aren't
+/// checked.
+pub fn unknown_opcodes(asm: &str, set: InstructionSet) -> Vec<Range<usize>> {
+    let mut unknown = vec![];
+    let _ = compile(asm, set, &mut unknown);
+    unknown
+}
+
+/// The variables of the template `asm`, the words that aren't opcodes and don't look like one
+/// either, each once in the order they first appear.
+pub fn variables(asm: &str, set: InstructionSet) -> Vec<String> {
+    let mut variables = Vec::<String>::new();
+    for range in unknown_opcodes(asm, set) {
+        let word = &asm[range];
+        if !word.starts_with("OP_") && !variables.iter().any(|v| v == word) {
+            variables.push(word.to_string());
+        }
+    }
+    variables
+}
+
+/// Instantiate the template `asm`, replacing its variables that have a value in `values` with it
+/// as a hex literal.
+pub fn substitute(asm: &str, values: &BTreeMap<String, Vec<u8>>, set: InstructionSet) -> String {
+    let mut result = asm.to_string();
+    for range in unknown_opcodes(asm, set).into_iter().rev() {
+        if let Some(value) = values.get(&asm[range.clone()]) {
+            result.replace_range(range, &format!("0x{}", value.to_hex()));
+        }
+    }
+    result
+}
+
+/// [`assemble_in`], going on past unknown opcodes to collect them in
+/// `unknown`, but still failing at the first.
+fn compile(
+    asm: &str,
+    set: InstructionSet,
+    unknown: &mut Vec<Range<usize>>,
+) -> Result<Script, Error> {
+    let unknown_error = |unknown: &[Range<usize>]| {
+        unknown.first().map(|range| {
+            let word = &asm[range.clone()];
+            let message = match word.starts_with("OP_") {
+                true => format!("Unknown opcode {word}"),
+                false => format!("Variable {word} has no value"),
+            };
+            Error::new(asm, range.start, message)
+        })
+    };
+    // The bytecode of the script, then that of each open push with the offset of its `<`
+    let mut stack = vec![(0, vec![])];
+    let mut rest = asm;
+    loop {
+        rest = rest.trim_start();
+        let offset = asm.len() - rest.len();
+        let error = |message: String| {
+            unknown_error(unknown).unwrap_or_else(|| Error::new(asm, offset, message))
+        };
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        let bytecode = &mut stack.last_mut().unwrap().1;
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let Some((_, after)) = comment.split_once("*/") else {
+                return Err(error("Unterminated comment".into()));
+            };
+            rest = after;
+        } else if rest.starts_with("$(") {
+            return Err(error("Evaluations are not supported".into()));
+        } else if c == '<' {
+            stack.push((offset, vec![]));
+            rest = &rest[1..];
+        } else if c == '>' {
+            if stack.len() == 1 {
+                return Err(error("Unexpected `>` outside of a push".into()));
+            }
+            let (_, data) = stack.pop().unwrap();
+            let push = push_minimal(Builder::new(), &data).into_script();
+            stack.last_mut().unwrap().1.extend(push.as_bytes());
+            rest = &rest[1..];
+        } else if c == '"' || c == '\'' {
+            let Some((string, after)) = rest[1..].split_once(c) else {
+                return Err(error("Unterminated string".into()));
+            };
+            bytecode.extend(string.as_bytes());
+            rest = after;
+        } else if let Some(hex) = rest.strip_prefix("0x") {
+            let end = hex.find(|c| !is_word_char(c)).unwrap_or(hex.len());
+            let data = Vec::<u8>::from_hex(&hex[..end])
+                .map_err(|_| error(format!("Invalid hex literal 0x{}", &hex[..end])))?;
+            bytecode.extend(data);
+            rest = &hex[end..];
+        } else if c == '-' || c.is_ascii_digit() {
+            let end = rest[1..]
+                .find(|c| !is_word_char(c))
+                .map_or(rest.len(), |i| i + 1);
+            let n = rest[..end]
+                .replace('_', "")
+                .parse::<i64>()
+                .map_err(|_| error(format!("Invalid number {}", &rest[..end])))?;
+            bytecode.extend(script_num(n));
+            rest = &rest[end..];
+        } else if is_word_char(c) {
+            let end = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            match opcode_by_name_in(word, set) {
+                Some(opcode) => bytecode.push(opcode),
+                None => unknown.push(offset..offset + end),
+            }
+            rest = &rest[end..];
+        } else {
+            return Err(error(format!("Unexpected character {c:?}")));
+        }
+    }
+    if let Some(error) = unknown_error(unknown) {
+        return Err(error);
+    }
+    if let Some(&(offset, _)) = stack.get(1) {
+        return Err(Error::new(asm, offset, "Unclosed push"));
+    }
+    Ok(Script::from(stack.pop().unwrap().1))
+}
+
+/// Disassemble a script like Libauth does: pushes as their opcode followed by the pushed bytes in
+/// hex, so the result assembles back into the same script. Missing bytes at the end of a
+/// truncated push are noted in brackets. Opcodes outside of the instruction set `set` are written
+/// as unknown.
+pub fn disassemble_in(script: &Script, set: InstructionSet) -> String {
+    let mut bytes = script.as_bytes();
+    let mut words = vec![];
+    while let Some((&opcode, rest)) = bytes.split_first() {
+        bytes = rest;
+        words.push(opcode_name_in(opcode, set));
+        let length_size = match opcode {
+            0x01..=0x4b => 0,
+            0x4c => 1,
+            0x4d => 2,
+            0x4e => 4,
+            _ => continue,
+        };
+        if bytes.len() < length_size {
+            words.push(format!("0x{}[missing bytes]", bytes.to_hex()));
+            break;
+        }
+        let (length_bytes, rest) = bytes.split_at(length_size);
+        bytes = rest;
+        let length = match length_size {
+            0 => usize::from(opcode),
+            _ => {
+                words.push(format!("0x{}", length_bytes.to_hex()));
+                length_bytes
+                    .iter()
+                    .rev()
+                    .fold(0, |length, &b| length << 8 | usize::from(b))
+            }
+        };
+        if bytes.len() < length {
+            let missing = length - bytes.len();
+            let s = if missing == 1 { "" } else { "s" };
+            words.push(format!("0x{}[missing {missing} byte{s}]", bytes.to_hex()));
+            break;
+        }
+        let (data, rest) = bytes.split_at(length);
+        bytes = rest;
+        if !data.is_empty() {
+            words.push(format!("0x{}", data.to_hex()));
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bitcoincash::{hashes::hex::ToHex, Script};
+
+    use super::{
+        assemble_in, compile, disassemble_in, opcode_by_name_in, opcode_completions,
+        opcode_name_in, opcode_name_with, substitute, unknown_opcodes, variables, Error,
+        InstructionSet,
+    };
+
+    const CURRENT: InstructionSet = InstructionSet::Current;
+
+    fn assemble(asm: &str) -> Result<Script, Error> {
+        assemble_in(asm, CURRENT)
+    }
+
+    fn disassemble(script: &Script) -> String {
+        disassemble_in(script, CURRENT)
+    }
+
+    fn asm_hex(asm: &str) -> String {
+        assemble(asm).unwrap().to_hex()
+    }
+
+    #[test]
+    fn test_opcode_names() {
+        for set in [CURRENT, InstructionSet::Upcoming] {
+            for byte in 0..=u8::MAX {
+                assert_eq!(
+                    opcode_by_name_in(&opcode_name_in(byte, set), set),
+                    Some(byte)
+                );
+            }
+        }
+        assert_eq!(opcode_name_in(0xa9, CURRENT), "OP_HASH160");
+        assert_eq!(opcode_name_in(0xd3, CURRENT), "OP_OUTPUTTOKENAMOUNT");
+        assert_eq!(opcode_name_in(0xbd, CURRENT), "OP_UNKNOWN189");
+        assert_eq!(opcode_by_name_in("OP_HASH161", CURRENT), None);
+    }
+
+    #[test]
+    fn test_instruction_sets() {
+        // Loops, functions and bitwise shifts are on every network since the May 2026 upgrade
+        let asm = "<0> OP_BEGIN OP_1ADD OP_DUP <3> OP_EQUAL OP_UNTIL <1> <2> OP_LSHIFTNUM";
+        for set in [CURRENT, InstructionSet::Upcoming] {
+            let script = assemble_in(asm, set).unwrap();
+            assert_eq!(script.to_hex(), "00658b7653876651528d");
+            assert_eq!(
+                disassemble_in(&script, set),
+                "OP_0 OP_BEGIN OP_1ADD OP_DUP OP_3 OP_EQUAL OP_UNTIL OP_1 OP_2 OP_LSHIFTNUM"
+            );
+        }
+        assert_eq!(opcode_by_name_in("OP_VERIF", CURRENT), None);
+        // No upgrade is scheduled after May 2026, so the lookup of added opcodes is checked with
+        // made up ones
+        let added = [(0xd4, "OP_FUTURE"), (0xd5, "OP_LATER")];
+        assert_eq!(opcode_name_with(0xd4, &added), "OP_FUTURE");
+        assert_eq!(opcode_name_with(0xd5, &added), "OP_LATER");
+        assert_eq!(opcode_name_with(0xd6, &added), "OP_UNKNOWN214");
+        assert_eq!(opcode_name_with(0xac, &added), "OP_CHECKSIG");
+        assert_eq!(opcode_name_in(0xd4, CURRENT), "OP_UNKNOWN212");
+        let mut unknown = vec![];
+        assert!(compile("OP_DUP OP_2MUL OP_FOO", CURRENT, &mut unknown).is_err());
+        assert_eq!(unknown, [7..14, 15..21]);
+    }
+
+    #[test]
+    fn test_opcode_completions() {
+        assert_eq!(
+            opcode_completions("op_checksig", CURRENT),
+            ["OP_CHECKSIG", "OP_CHECKSIGVERIFY"]
+        );
+        assert_eq!(opcode_completions("hash1", CURRENT), ["OP_HASH160"]);
+        assert!(opcode_completions("OP_PUSHB", CURRENT).is_empty());
+        assert!(opcode_completions("OP_UNK", CURRENT).is_empty());
+        assert!(opcode_completions("", CURRENT).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_opcodes() {
+        let asm = "OP_DUP OP_FOO <0x01> 'OP_BAR' OP_BAZ // OP_QUX";
+        assert_eq!(unknown_opcodes(asm, CURRENT), [7..13, 30..36]);
+        assert_eq!(assemble(asm).unwrap_err().message, "Unknown opcode OP_FOO");
+        assert!(unknown_opcodes("OP_1 OP_2", CURRENT).is_empty());
+        // Pointed out before the push they're in is found unclosed
+        assert_eq!(
+            assemble("<owner.key").unwrap_err().message,
+            "Variable owner.key has no value"
+        );
+    }
+
+    #[test]
+    fn test_variables() {
+        let template = "OP_DUP OP_HASH160 <owner.public_key_hash> OP_EQUALVERIFY OP_CHECKSIG \
+            // owner.comment
+            <owner.public_key_hash> 'owner.string' OP_FOO <timeout>";
+        assert_eq!(
+            variables(template, CURRENT),
+            ["owner.public_key_hash", "timeout"]
+        );
+
+        let values = BTreeMap::from([
+            ("owner.public_key_hash".to_string(), vec![0xab; 20]),
+            ("timeout".to_string(), vec![]),
+        ]);
+        let instance = substitute(template, &values, CURRENT);
+        assert_eq!(
+            instance,
+            template
+                .replace(
+                    "<owner.public_key_hash>",
+                    &format!("<0x{}>", "ab".repeat(20))
+                )
+                .replace("<timeout>", "<0x>")
+        );
+        assert_eq!(variables(&instance, CURRENT), Vec::<String>::new());
+        assert_eq!(
+            substitute(
+                "<a> <b>",
+                &BTreeMap::from([("b".to_string(), vec![1])]),
+                CURRENT
+            ),
+            "<a> <0x01>"
+        );
+    }
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(
+            asm_hex("OP_DUP OP_HASH160 <0x0102030405060708090a0b0c0d0e0f1011121314> OP_EQUALVERIFY OP_CHECKSIG"),
+            "76a9140102030405060708090a0b0c0d0e0f101112131488ac"
+        );
+        assert_eq!(asm_hex("<0> <1> <16> <17> <-1> <''>"), "00516001114f00");
+        assert_eq!(
+            asm_hex("<\"hi\"> 'hi' 0xabcd -1 1_000"),
+            "0268696869abcd81e803"
+        );
+        assert_eq!(asm_hex("<<OP_1 OP_2>>"), "03025152");
+        assert_eq!(asm_hex("OP_1 // one\n/* two\n */ OP_2"), "5152");
+        assert_eq!(asm_hex(""), "");
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let error = |asm| assemble(asm).unwrap_err();
+        let at = |line, column, message: &str| Error {
+            line,
+            column,
+            message: message.into(),
+        };
+        assert_eq!(error("OP_1\n  OP_FOO"), at(2, 3, "Unknown opcode OP_FOO"));
+        assert_eq!(error("OP_1 <0x01"), at(1, 6, "Unclosed push"));
+        assert_eq!(error("'€' >"), at(1, 5, "Unexpected `>` outside of a push"));
+        assert_eq!(error("0xabc"), at(1, 1, "Invalid hex literal 0xabc"));
+        assert_eq!(error("'hi"), at(1, 1, "Unterminated string"));
+        assert_eq!(error("<$(<1>)>"), at(1, 2, "Evaluations are not supported"));
+        assert_eq!(
+            error("OP_1\nOP_FOO").to_string(),
+            "CashAssembly error at line 2, column 1: Unknown opcode OP_FOO"
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let script = |hex: &str| hex.parse::<Script>().unwrap();
+        for (hex, asm) in [
+            (
+                "76a9140102030405060708090a0b0c0d0e0f101112131488ac",
+                "OP_DUP OP_HASH160 OP_PUSHBYTES_20 0x0102030405060708090a0b0c0d0e0f1011121314 OP_EQUALVERIFY OP_CHECKSIG",
+            ),
+            ("004f51606a", "OP_0 OP_1NEGATE OP_1 OP_16 OP_RETURN"),
+            ("4c03abcdef4d0000", "OP_PUSHDATA_1 0x03 0xabcdef OP_PUSHDATA_2 0x0000"),
+            ("bdd3ff", "OP_UNKNOWN189 OP_OUTPUTTOKENAMOUNT OP_UNKNOWN255"),
+        ] {
+            assert_eq!(disassemble(&script(hex)), asm);
+            assert_eq!(assemble(asm).unwrap(), script(hex));
+        }
+        assert_eq!(
+            disassemble(&script("0301")),
+            "OP_PUSHBYTES_3 0x01[missing 2 bytes]"
+        );
+        assert_eq!(
+            disassemble(&script("4d01")),
+            "OP_PUSHDATA_2 0x01[missing bytes]"
+        );
+    }
+}
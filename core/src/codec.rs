@@ -0,0 +1,184 @@
+//! Converting whole transactions between hex and bytes. Large transactions take long enough that
+//! the editor does this in a web worker, [`TxCodec`] with the `worker` feature, to stay responsive.
+
+use std::ops::ControlFlow;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::hex_format::{HexDecoder, HexFormat};
+use crate::partially_signed::PartiallySignedTransaction;
+
+/// Hex characters decoded between progress reports.
+pub const CHUNK_SIZE: usize = 1 << 16;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CodecRequest {
+    /// Parse hex in any case and with any whitespace, checking that it's a transaction.
+    Decode(String),
+    /// Format the fields of a serialized transaction as hex.
+    Encode {
+        fields: Vec<Vec<u8>>,
+        format: HexFormat,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CodecResponse {
+    /// Characters of the hex being decoded done so far, out of the total.
+    Progress {
+        done: usize,
+        total: usize,
+    },
+    /// The serialized transaction, signed or in the Electron Cash format, or why it isn't one.
+    Decoded(Result<Vec<u8>, String>),
+    Encoded(String),
+}
+
+/// Hex being decoded a chunk of [`CHUNK_SIZE`] characters at a time, then deserialized.
+pub struct Decoding {
+    hex: String,
+    done: usize,
+    decoder: HexDecoder,
+}
+
+impl Decoding {
+    pub fn new(hex: String) -> Self {
+        Decoding {
+            hex,
+            done: 0,
+            decoder: HexDecoder::default(),
+        }
+    }
+
+    /// Decode the next chunk, continuing with the progress, or breaking with the result after the
+    /// last chunk.
+    pub fn step(&mut self) -> ControlFlow<CodecResponse, CodecResponse> {
+        let total = self.hex.len();
+        let mut end = (self.done + CHUNK_SIZE).min(total);
+        while !self.hex.is_char_boundary(end) {
+            end += 1;
+        }
+        if let Err(e) = self.decoder.push(&self.hex[self.done..end]) {
+            return ControlFlow::Break(CodecResponse::Decoded(Err(e.to_string())));
+        }
+        self.done = end;
+        if end < total {
+            return ControlFlow::Continue(CodecResponse::Progress { done: end, total });
+        }
+        let decoded = std::mem::take(&mut self.decoder)
+            .finish()
+            .and_then(|bytes| {
+                PartiallySignedTransaction::deserialize_any(&bytes)?;
+                Ok(bytes)
+            });
+        ControlFlow::Break(CodecResponse::Decoded(decoded.map_err(|e| e.to_string())))
+    }
+}
+
+/// Run `request` to the end, without progress reports.
+pub fn handle(request: CodecRequest) -> CodecResponse {
+    match request {
+        CodecRequest::Decode(hex) => {
+            let mut decoding = Decoding::new(hex);
+            loop {
+                if let ControlFlow::Break(response) = decoding.step() {
+                    return response;
+                }
+            }
+        }
+        CodecRequest::Encode { fields, format } => CodecResponse::Encoded(format.format(&fields)),
+    }
+}
+
+/// Web worker answering requests with their progress and result. A decoding stops between chunks
+/// once its bridge is dropped, so bridges are meant to be forked for each request.
+#[cfg(feature = "worker")]
+#[gloo_worker::reactor::reactor]
+pub async fn TxCodec(scope: gloo_worker::reactor::ReactorScope<CodecRequest, CodecResponse>) {
+    use std::pin::Pin;
+
+    use futures::{FutureExt as _, SinkExt as _, StreamExt as _};
+    use gloo_timers::future::TimeoutFuture;
+
+    let mut scope = scope.peekable();
+    while let Some(request) = scope.next().await {
+        let response = match request {
+            CodecRequest::Decode(hex) => {
+                let mut decoding = Decoding::new(hex);
+                loop {
+                    match decoding.step() {
+                        ControlFlow::Continue(progress) => {
+                            if scope.send(progress).await.is_err() {
+                                return;
+                            }
+                        }
+                        ControlFlow::Break(response) => break response,
+                    }
+                    // Let messages arrive, and stop if the bridge was dropped
+                    TimeoutFuture::new(0).await;
+                    if let Some(None) = Pin::new(&mut scope).peek().now_or_never() {
+                        return;
+                    }
+                }
+            }
+            request => handle(request),
+        };
+        if scope.send(response).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::{handle, CodecRequest, CodecResponse, Decoding, CHUNK_SIZE};
+    use crate::hex_format::{HexFormat, HexSpacing};
+
+    #[test]
+    fn test_handle() {
+        let fields = vec![
+            vec![0x01, 0, 0, 0],
+            vec![0x00],
+            vec![0x00],
+            vec![0, 0, 0, 0],
+        ];
+        let format = HexFormat {
+            spacing: HexSpacing::Field,
+            ..Default::default()
+        };
+        let CodecResponse::Encoded(hex) = handle(CodecRequest::Encode { fields, format }) else {
+            panic!("Expected encoded hex");
+        };
+        assert_eq!(hex, "01000000 00 00 00000000");
+        assert_eq!(
+            handle(CodecRequest::Decode(hex)),
+            CodecResponse::Decoded(Ok(vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0]))
+        );
+        let CodecResponse::Decoded(Err(_)) = handle(CodecRequest::Decode("0100".into())) else {
+            panic!("Expected a decoding error");
+        };
+    }
+
+    #[test]
+    fn test_decoding_progress() {
+        // Whitespace padding the transaction out to a bit over two chunks
+        let hex = format!("{}01000000000000000000", " ".repeat(2 * CHUNK_SIZE));
+        let total = hex.len();
+        let mut decoding = Decoding::new(hex);
+        for done in [CHUNK_SIZE, 2 * CHUNK_SIZE] {
+            assert_eq!(
+                decoding.step(),
+                ControlFlow::Continue(CodecResponse::Progress { done, total })
+            );
+        }
+        assert_eq!(
+            decoding.step(),
+            ControlFlow::Break(CodecResponse::Decoded(Ok(vec![
+                1, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ])))
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Showing serialized transactions as hex, and reading them back.
+
+use anyhow::{bail, Result};
+use bitcoincash::hashes::hex::ToHex;
+use serde::{Deserialize, Serialize};
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum HexCase {
+        #[default]
+        Lower = "lower",
+        Upper = "upper",
+    }
+}
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum HexSpacing {
+        #[default]
+        None = "none",
+        Byte = "byte",
+        /// Between the top level fields of a transaction.
+        Field = "field",
+    }
+}
+
+/// How serialized transactions are shown as hex.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct HexFormat {
+    pub case: HexCase,
+    pub spacing: HexSpacing,
+    /// Maximum line length in characters, or 0 to not wrap.
+    pub wrap: usize,
+}
+
+impl HexFormat {
+    /// Hex of `fields`, the serialization split into its fields.
+    pub fn format(self, fields: &[Vec<u8>]) -> String {
+        let groups = match self.spacing {
+            HexSpacing::None => vec![fields.concat().to_hex()],
+            HexSpacing::Byte => fields.concat().iter().map(|b| format!("{b:02x}")).collect(),
+            HexSpacing::Field => fields.iter().map(|f| f.to_hex()).collect(),
+        };
+        let hex = if self.wrap == 0 {
+            groups.join(" ")
+        } else {
+            wrap(&groups, self.wrap)
+        };
+        match self.case {
+            HexCase::Lower => hex,
+            HexCase::Upper => hex.to_uppercase(),
+        }
+    }
+}
+
+/// Lays out space separated `groups` in lines of at most `width` characters, splitting groups
+/// longer than a line between bytes.
+fn wrap(groups: &[String], width: usize) -> String {
+    let width = (width & !1).max(2);
+    let mut lines = Vec::<String>::new();
+    let mut line = String::new();
+    for group in groups {
+        for chunk in group.as_bytes().chunks(width) {
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            if !line.is_empty() && line.len() + 1 + chunk.len() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(chunk);
+        }
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+/// Parse hex in any case, ignoring whitespace, `0x` prefixes of the words and comments from `#`
+/// to the end of the line, as in hex copied out of source code.
+pub fn parse(s: &str) -> Result<Vec<u8>> {
+    let mut decoder = HexDecoder::default();
+    decoder.push(s)?;
+    decoder.finish()
+}
+
+/// Parses hex like [`parse`], a piece at a time, so that long hex can be parsed in chunks.
+#[derive(Clone, Debug)]
+pub struct HexDecoder {
+    bytes: Vec<u8>,
+    /// High half of a byte whose low half is in the next piece.
+    high: Option<u8>,
+    /// Characters pushed so far, for error positions.
+    position: usize,
+    /// Whether the last character was whitespace, or there was none, so a word starts here.
+    separated: bool,
+    /// Whether the last character was a `0` starting a word on a byte boundary, which an `x`
+    /// turns into a prefix.
+    prefix_zero: bool,
+    /// Whether the characters up to the end of the line are a comment.
+    comment: bool,
+}
+
+impl Default for HexDecoder {
+    fn default() -> Self {
+        HexDecoder {
+            bytes: vec![],
+            high: None,
+            position: 0,
+            separated: true,
+            prefix_zero: false,
+            comment: false,
+        }
+    }
+}
+
+impl HexDecoder {
+    pub fn push(&mut self, hex: &str) -> Result<()> {
+        for c in hex.chars() {
+            self.position += 1;
+            let (separated, prefix_zero) = (self.separated, self.prefix_zero);
+            self.separated = false;
+            self.prefix_zero = false;
+            if self.comment {
+                self.comment = c != '\n';
+                self.separated = true;
+                continue;
+            }
+            if c.is_whitespace() || c == '#' {
+                self.comment = c == '#';
+                self.separated = true;
+                continue;
+            }
+            if prefix_zero && (c == 'x' || c == 'X') {
+                self.high = None;
+                continue;
+            }
+            let Some(digit) = c.to_digit(16) else {
+                bail!("Invalid hex character {c:?} at position {}", self.position);
+            };
+            let digit = digit as u8;
+            match self.high.take() {
+                Some(high) => self.bytes.push(high << 4 | digit),
+                None => {
+                    self.prefix_zero = separated && digit == 0;
+                    self.high = Some(digit);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>> {
+        if self.high.is_some() {
+            bail!("Odd number of hex digits");
+        }
+        Ok(self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, HexCase, HexDecoder, HexFormat, HexSpacing};
+
+    #[test]
+    fn test_format() {
+        let fields = vec![vec![0x02, 0, 0, 0], vec![0x01], vec![0xab; 6]];
+        let format = |case, spacing, wrap| {
+            HexFormat {
+                case,
+                spacing,
+                wrap,
+            }
+            .format(&fields)
+        };
+        assert_eq!(
+            format(HexCase::Lower, HexSpacing::None, 0),
+            "0200000001abababababab"
+        );
+        assert_eq!(
+            format(HexCase::Upper, HexSpacing::Field, 0),
+            "02000000 01 ABABABABABAB"
+        );
+        assert_eq!(
+            format(HexCase::Lower, HexSpacing::Byte, 12),
+            "02 00 00 00\n01 ab ab ab\nab ab ab"
+        );
+        assert_eq!(
+            format(HexCase::Lower, HexSpacing::None, 8),
+            "02000000\n01ababab\nababab"
+        );
+        assert_eq!(
+            format(HexCase::Lower, HexSpacing::Field, 13),
+            "02000000 01\nabababababab"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let bytes = vec![0x02, 0, 0, 0, 0xab];
+        for s in ["02000000ab", "02 00 00 00 AB", " 02000000\n\tAb\n"] {
+            assert_eq!(parse(s).unwrap(), bytes);
+        }
+        assert!(parse("0 2").is_ok());
+        assert!(parse("0g").is_err());
+        assert!(parse("020").is_err());
+    }
+
+    #[test]
+    fn test_parse_source() {
+        let bytes = vec![0x02, 0, 0, 0, 0xab];
+        for s in [
+            "0x02000000ab",
+            "0X0200 0x0000ab",
+            "# version\n0x02000000 # LE\r\n\n  # one byte\nab",
+            "0x02000000\n0xAB#",
+        ] {
+            assert_eq!(parse(s).unwrap(), bytes);
+        }
+        assert!(parse("0x").unwrap().is_empty());
+        // Only at the start of a word on a byte boundary
+        assert!(parse("00x02").is_err());
+        assert!(parse("0 0x02").is_err());
+        assert!(parse("0xx02").is_err());
+        assert!(parse("0x0x02").is_err());
+    }
+
+    #[test]
+    fn test_decoder() {
+        let mut decoder = HexDecoder::default();
+        for piece in ["0", "20 0", "0 00\n", "", "0ab"] {
+            decoder.push(piece).unwrap();
+        }
+        assert_eq!(decoder.finish().unwrap(), vec![0x02, 0, 0, 0, 0xab]);
+        let mut decoder = HexDecoder::default();
+        for piece in ["0", "x02 # a ", "comment\n 0", "xab"] {
+            decoder.push(piece).unwrap();
+        }
+        assert_eq!(decoder.finish().unwrap(), vec![0x02, 0xab]);
+        let mut decoder = HexDecoder::default();
+        decoder.push("02 ").unwrap();
+        assert_eq!(
+            decoder.push("0g").unwrap_err().to_string(),
+            "Invalid hex character 'g' at position 5"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! The JSON codec exported to JavaScript, for wallets using the wasm without the editor.
+
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::json::{self, TxJson};
+
+/// Decode a hex partially signed or signed transaction into its JSON representation.
+#[wasm_bindgen(js_name = decodePartiallySignedTx)]
+pub fn decode_partially_signed_tx(hex: &str) -> Result<String, JsError> {
+    let tx = json::decode_hex(hex).map_err(|e| JsError::new(&format!("{e:#}")))?;
+    Ok(serde_json::to_string(&tx)?)
+}
+
+/// Encode the JSON representation of a transaction into hex, in the Electron Cash format if it
+/// has unsigned inputs.
+#[wasm_bindgen(js_name = encodePartiallySignedTx)]
+pub fn encode_partially_signed_tx(json: &str) -> Result<String, JsError> {
+    let tx: TxJson = serde_json::from_str(json)?;
+    json::encode_hex(&tx).map_err(|e| JsError::new(&format!("{e:#}")))
+}
@@ -0,0 +1,315 @@
+//! A JSON representation of partially signed transactions, for programs that would rather not
+//! deal with the binary encoding. Scripts, categories and commitments are hex, and token amounts
+//! are strings since they don't fit in a JavaScript number.
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{
+    blockdata::token::{Capability, OutputData, Structure},
+    hashes::hex::{FromHex, ToHex},
+    psbt::serialize::Serialize as _,
+    OutPoint, PackedLockTime, Script, Sequence, TokenID, TxIn, TxOut,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::partially_signed::{
+    MaybeUnsignedTxIn, PartiallySignedTransaction, UnsignedScriptSig, UnsignedTxIn,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxJson {
+    pub version: i32,
+    pub locktime: u32,
+    pub inputs: Vec<InputJson>,
+    pub outputs: Vec<OutputJson>,
+}
+
+/// A signed input has `scriptSig`, an unsigned one `unsignedScriptSig`, `value` and `token`.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputJson {
+    pub txid: String,
+    pub vout: u32,
+    pub sequence: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_sig: Option<String>,
+    /// The Electron Cash form of the scriptSig, naming the keys that will sign.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unsigned_script_sig: Option<String>,
+    /// Value of the spent output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    /// Tokens of the spent output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenJson>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputJson {
+    pub value: u64,
+    pub locking_bytecode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenJson>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenJson {
+    pub category: String,
+    /// Fungible amount, `"0"` for none.
+    pub amount: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nft: Option<NftJson>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftJson {
+    /// `none`, `mutable` or `minting`.
+    pub capability: String,
+    pub commitment: String,
+}
+
+impl From<&OutputData> for TokenJson {
+    fn from(token: &OutputData) -> Self {
+        let capability = match token.capability() {
+            c if c == Capability::Mutable as u8 => "mutable",
+            c if c == Capability::Minting as u8 => "minting",
+            _ => "none",
+        };
+        TokenJson {
+            category: token.id.to_hex(),
+            amount: token.amount.to_string(),
+            nft: token.has_nft().then(|| NftJson {
+                capability: capability.to_string(),
+                commitment: token.commitment.to_hex(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<&TokenJson> for OutputData {
+    type Error = anyhow::Error;
+
+    fn try_from(token: &TokenJson) -> Result<Self> {
+        let amount: i64 = token.amount.parse().context("Invalid token amount")?;
+        if amount < 0 {
+            bail!("Negative token amount");
+        }
+        let mut bitfield = 0;
+        if amount > 0 {
+            bitfield |= Structure::HasAmount as u8;
+        }
+        let mut commitment = vec![];
+        if let Some(nft) = &token.nft {
+            bitfield |= Structure::HasNFT as u8;
+            bitfield |= match nft.capability.as_str() {
+                "none" => Capability::None,
+                "mutable" => Capability::Mutable,
+                "minting" => Capability::Minting,
+                c => bail!("Unknown NFT capability {c:?}"),
+            } as u8;
+            commitment = Vec::from_hex(&nft.commitment).context("Invalid NFT commitment")?;
+            if !commitment.is_empty() {
+                bitfield |= Structure::HasCommitmentLength as u8;
+            }
+        } else if amount == 0 {
+            bail!("A token needs a fungible amount or an NFT");
+        }
+        Ok(OutputData {
+            id: TokenID::from_hex(&token.category).context("Invalid token category")?,
+            bitfield,
+            amount,
+            commitment,
+        })
+    }
+}
+
+impl From<&PartiallySignedTransaction> for TxJson {
+    fn from(tx: &PartiallySignedTransaction) -> Self {
+        let inputs = tx
+            .input
+            .iter()
+            .map(|txin| {
+                let outpoint = txin.previous_output();
+                let mut input = InputJson {
+                    txid: outpoint.txid.to_string(),
+                    vout: outpoint.vout,
+                    sequence: txin.sequence().0,
+                    script_sig: None,
+                    unsigned_script_sig: None,
+                    value: None,
+                    token: None,
+                };
+                match txin {
+                    MaybeUnsignedTxIn::Signed(txin) => {
+                        input.script_sig = Some(txin.script_sig.to_hex());
+                    }
+                    MaybeUnsignedTxIn::Unsigned(txin) => {
+                        input.unsigned_script_sig =
+                            Some(txin.unsigned_script_sig.raw_script().to_hex());
+                        input.value = Some(txin.value);
+                        input.token = txin.token.as_ref().map(TokenJson::from);
+                    }
+                }
+                input
+            })
+            .collect();
+        let outputs = tx
+            .output
+            .iter()
+            .map(|txout| OutputJson {
+                value: txout.value,
+                locking_bytecode: txout.script_pubkey.to_hex(),
+                token: txout.token.as_ref().map(TokenJson::from),
+            })
+            .collect();
+        TxJson {
+            version: tx.version,
+            locktime: tx.lock_time.0,
+            inputs,
+            outputs,
+        }
+    }
+}
+
+impl TryFrom<&TxJson> for PartiallySignedTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(json: &TxJson) -> Result<Self> {
+        let input = json
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let convert = || -> Result<MaybeUnsignedTxIn> {
+                    let previous_output =
+                        OutPoint::new(input.txid.parse().context("Invalid txid")?, input.vout);
+                    let sequence = Sequence(input.sequence);
+                    Ok(match (&input.script_sig, &input.unsigned_script_sig) {
+                        (Some(script_sig), None) => MaybeUnsignedTxIn::Signed(TxIn {
+                            previous_output,
+                            script_sig: Script::from_hex(script_sig)
+                                .context("Invalid scriptSig")?,
+                            sequence,
+                            witness: Default::default(),
+                        }),
+                        (None, Some(unsigned_script_sig)) => {
+                            MaybeUnsignedTxIn::Unsigned(UnsignedTxIn {
+                                previous_output,
+                                unsigned_script_sig: UnsignedScriptSig::from_raw_script(
+                                    Script::from_hex(unsigned_script_sig)
+                                        .context("Invalid unsignedScriptSig")?,
+                                ),
+                                sequence,
+                                value: input
+                                    .value
+                                    .context("An unsigned input needs the value it spends")?,
+                                token: input.token.as_ref().map(TryInto::try_into).transpose()?,
+                            })
+                        }
+                        _ => bail!("Needs either a scriptSig or an unsignedScriptSig"),
+                    })
+                };
+                convert().with_context(|| format!("Input {i}"))
+            })
+            .collect::<Result<_>>()?;
+        let output = json
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let convert = || -> Result<TxOut> {
+                    Ok(TxOut {
+                        value: output.value,
+                        script_pubkey: Script::from_hex(&output.locking_bytecode)
+                            .context("Invalid lockingBytecode")?,
+                        token: output.token.as_ref().map(TryInto::try_into).transpose()?,
+                    })
+                };
+                convert().with_context(|| format!("Output {i}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(PartiallySignedTransaction {
+            version: json.version,
+            lock_time: PackedLockTime(json.locktime),
+            input,
+            output,
+        })
+    }
+}
+
+/// Decode a hex partially signed or signed transaction, ignoring whitespace.
+pub fn decode_hex(hex: &str) -> Result<TxJson> {
+    let mut hex = hex.to_string();
+    hex.retain(|c| !c.is_ascii_whitespace());
+    let tx = PartiallySignedTransaction::deserialize_any(&Vec::from_hex(&hex)?)?;
+    Ok(TxJson::from(&tx))
+}
+
+pub fn encode_hex(json: &TxJson) -> Result<String> {
+    Ok(PartiallySignedTransaction::try_from(json)?
+        .serialize()
+        .to_hex())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_hex, encode_hex, TxJson};
+
+    const CATEGORY: &str = "7955dd3bdbdd0a4f1ff3316865a0995416dd9d9b05e0d075b075069428e64cc4";
+
+    #[test]
+    fn test_round_trip() {
+        // Electron Cash unsigned transaction spending and sending tokens
+        let hex = concat!(
+            "01000000022a4f73d341cb70ef826a2d1942f0acda9bb059536da7be352d54bc45a8c0f1040000000057",
+            "01ff4c53ff0488b21e0000000000000000005d2f27f71323296d52bf8475ad8dad79d6239fcd640629fd",
+            "dc8ef9a7229258a4023f72ac51c65717e8d44e8d86afacff3eed27ce00cea7b5a6fd1e6297fcbd4df900",
+            "003c00feffffffdd73e9020000000062b76b5bb69fa5f572cf1de7c0972e12cd9584128b14cb03317e45",
+            "4011ca9a6c000000005701ff4c53ff0488b21e0000000000000000005d2f27f71323296d52bf8475ad8d",
+            "ad79d6239fcd640629fddc8ef9a7229258a4023f72ac51c65717e8d44e8d86afacff3eed27ce00cea7b5",
+            "a6fd1e6297fcbd4df900003800fefffffffffffffffffffffffde80325efc44ce628940675b075d0e005",
+            "9b9ddd165499a0656831f31f4f0adddb3bdd557910fd8c050320030000000000003eefc44ce628940675",
+            "b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd557910fd8b0576a91403266ab5b02f4eebee6c",
+            "43bf9fb9d4421cb67d5588ac20030000000000003cefc44ce628940675b075d0e0059b9ddd165499a065",
+            "6831f31f4f0adddb3bdd5579100176a914795b6a18d92f888df281f85373288a6834a7d31a88acb36fe9",
+            "02000000001976a91403266ab5b02f4eebee6c43bf9fb9d4421cb67d5588ac23cf0c00"
+        );
+        let json = decode_hex(hex).unwrap();
+        assert_eq!(json.inputs.len(), 2);
+        assert!(json.inputs.iter().all(|i| i.unsigned_script_sig.is_some()));
+        let token = json.outputs[0].token.as_ref().unwrap();
+        assert_eq!(token.category, CATEGORY);
+        assert_eq!(token.amount, "1419");
+        assert!(token.nft.is_none());
+        assert_eq!(encode_hex(&json).unwrap(), hex);
+
+        let text = serde_json::to_string(&json).unwrap();
+        assert!(text.contains("\"lockingBytecode\""));
+        assert_eq!(serde_json::from_str::<TxJson>(&text).unwrap(), json);
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let json = |input: &str| {
+            serde_json::from_str::<TxJson>(&format!(
+                r#"{{"version": 2, "locktime": 0, "inputs": [{input}], "outputs": []}}"#
+            ))
+            .unwrap()
+        };
+        let signed = json(&format!(
+            r#"{{"txid": "{CATEGORY}", "vout": 0, "sequence": 0, "scriptSig": ""}}"#
+        ));
+        assert!(encode_hex(&signed).is_ok());
+        let neither = json(&format!(
+            r#"{{"txid": "{CATEGORY}", "vout": 0, "sequence": 0}}"#
+        ));
+        assert!(encode_hex(&neither).is_err());
+        let no_value = json(&format!(
+            r#"{{"txid": "{CATEGORY}", "vout": 0, "sequence": 0, "unsignedScriptSig": ""}}"#
+        ));
+        assert!(encode_hex(&no_value).is_err());
+    }
+}
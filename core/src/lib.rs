@@ -0,0 +1,20 @@
+//! The transaction model of bch-tx-editor, free of UI and browser dependencies so backends and
+//! other wallets can reuse it.
+
+#![deny(rust_2018_idioms)]
+#[macro_use]
+pub mod macros;
+pub mod cash_assembly;
+pub mod codec;
+pub mod hex_format;
+#[cfg(feature = "js")]
+pub mod js;
+pub mod json;
+pub mod partially_signed;
+pub mod signing;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod util;
+pub mod validation;
+pub mod verification;
+pub mod vm_limits;
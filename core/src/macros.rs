@@ -0,0 +1,60 @@
+use bitcoincash::Network;
+
+pub trait StrEnum: Sized {
+    fn to_str(self) -> &'static str;
+    fn from_str(s: &str) -> Option<Self>;
+}
+
+/// Declares an enum whose variants convert to and from fixed strings, for `<select>` values and
+/// serialized names.
+#[macro_export]
+macro_rules! str_enum {
+    ($( #[$attrs:meta] )* $vis:vis enum $name:ident {
+        $( $( #[$variant_attrs:meta] )* $variant:ident = $str_value:literal ),* $(,)?
+    }) => {
+        $(#[$attrs])* $vis enum $name {
+            $(
+                $(#[$variant_attrs])* $variant
+            ),*
+        }
+        impl $crate::macros::StrEnum for $name {
+            fn to_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $str_value,)*
+                }
+            }
+
+            fn from_str(s: &str) -> Option<Self> {
+                match s {
+                    $($str_value => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl StrEnum for Network {
+    fn to_str(self) -> &'static str {
+        match self {
+            Network::Bitcoin => "mainnet",
+            Network::Testnet => "testnet3",
+            Network::Regtest => "regtest",
+            Network::Testnet4 => "testnet4",
+            Network::Scalenet => "scalenet",
+            Network::Chipnet => "chipnet",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "mainnet" => Some(Network::Bitcoin),
+            "testnet3" => Some(Network::Testnet),
+            "regtest" => Some(Network::Regtest),
+            "testnet4" => Some(Network::Testnet4),
+            "scalenet" => Some(Network::Scalenet),
+            "chipnet" => Some(Network::Chipnet),
+            _ => None,
+        }
+    }
+}
@@ -16,7 +16,8 @@ use bitcoincash::{
     psbt::serialize::{Deserialize, Serialize},
     secp256k1::{Secp256k1, Verification},
     util::bip32::{ChildNumber, ExtendedPubKey},
-    Address, Network, OutPoint, PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, VarInt,
+    Address, Network, OutPoint, PackedLockTime, PublicKey, Script, Sequence, Transaction, TxIn,
+    TxOut, VarInt,
 };
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
@@ -83,6 +84,10 @@ impl UnsignedScriptSig {
             for ins in Script::from(fake_redeem_script.to_vec()).instructions() {
                 match ins.ok()? {
                     Instruction::Op(op) => redeem_script = redeem_script.push_opcode(op),
+                    // Keys not from a wallet's xpub appear as they are
+                    Instruction::PushBytes(pubkey @ [0x02..=0x04, ..]) => {
+                        redeem_script = redeem_script.push_key(&PublicKey::from_slice(pubkey).ok()?)
+                    }
                     Instruction::PushBytes(xpubkey) => {
                         redeem_script =
                             redeem_script.push_key(&ec_ff_parse_xpubkey(secp, xpubkey)?.to_pub())
@@ -115,6 +120,76 @@ impl UnsignedScriptSig {
     pub fn from_raw_script(s: Script) -> Self {
         Self(s)
     }
+
+    /// The scriptSig as signing would complete it, with placeholders of the same sizes for the
+    /// signatures and keys, to estimate the size of the signed input. `None` if it's neither the
+    /// P2PKH nor the multisig form.
+    pub fn dummy_signed(&self, signatures: SignatureType) -> Option<Script> {
+        let pushes = self
+            .0
+            .instructions()
+            .map(|ins| match ins {
+                Ok(Instruction::PushBytes(bytes)) => Some(bytes),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let signature = vec![0; signatures.size()];
+        match &pushes[..] {
+            [[0xff], payload] => Some(
+                script::Builder::new()
+                    .push_slice(&signature)
+                    .push_slice(&dummy_pubkey(payload))
+                    .into_script(),
+            ),
+            [[], placeholders @ .., fake_redeem_script] => {
+                let mut redeem_script = script::Builder::new();
+                let mut num_keys = 0usize;
+                for ins in Script::from(fake_redeem_script.to_vec()).instructions() {
+                    match ins.ok()? {
+                        Instruction::Op(op) => redeem_script = redeem_script.push_opcode(op),
+                        Instruction::PushBytes(payload) => {
+                            num_keys += 1;
+                            redeem_script = redeem_script.push_slice(&dummy_pubkey(payload));
+                        }
+                    }
+                }
+                let mut script_sig = match signatures {
+                    SignatureType::Ecdsa => script::Builder::new().push_slice(&[]),
+                    // Schnorr multisig replaces the dummy element with a bitfield of the keys
+                    // signing, assumed to be the first ones
+                    SignatureType::Schnorr => {
+                        let mut bitfield = vec![0u8; num_keys.div_ceil(8)];
+                        for i in 0..placeholders.len().min(num_keys) {
+                            bitfield[i / 8] |= 1 << (i % 8);
+                        }
+                        match bitfield[..] {
+                            [n @ 1..=16] => script::Builder::new().push_int(n.into()),
+                            _ => script::Builder::new().push_slice(&bitfield),
+                        }
+                    }
+                };
+                for _ in placeholders.iter() {
+                    script_sig = script_sig.push_slice(&signature);
+                }
+                Some(
+                    script_sig
+                        .push_slice(redeem_script.into_script().as_bytes())
+                        .into_script(),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A placeholder for the public key `payload` stands for: uncompressed for uncompressed keys and
+/// old Electrum master keys, compressed otherwise, including for the 0xFD form where the key
+/// isn't known.
+fn dummy_pubkey(payload: &[u8]) -> Vec<u8> {
+    match payload {
+        [0x04 | 0xfe, ..] => vec![0; 65],
+        _ => vec![0; 33],
+    }
 }
 
 /// Parse the 0xFF prefixed extended public key, which consists of the bip32 xpub and the
@@ -447,6 +522,35 @@ impl MyDecodable for Vec<MaybeUnsignedTxIn> {
     }
 }
 
+/// Size of a signed P2PKH input, with an ECDSA signature of the usual length.
+pub const P2PKH_INPUT_SIZE: usize = 148;
+
+str_enum! {
+    /// How unsigned inputs are expected to be signed, for estimating their size.
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+    pub enum SignatureType {
+        #[default]
+        Ecdsa = "ecdsa",
+        Schnorr = "schnorr",
+    }
+}
+
+impl SignatureType {
+    /// Size of a signature with its sighash type, the usual one for DER encoded ECDSA signatures
+    /// which vary by a byte or two.
+    pub fn size(self) -> usize {
+        match self {
+            SignatureType::Ecdsa => 72,
+            SignatureType::Schnorr => 65,
+        }
+    }
+
+    /// Size of a signed P2PKH input with a compressed key.
+    pub fn p2pkh_input_size(self) -> usize {
+        P2PKH_INPUT_SIZE - SignatureType::Ecdsa.size() + self.size()
+    }
+}
+
 /// Partially signed Bitcoin Cash transaction.
 ///
 /// Compatible with Electron Cash.
@@ -467,6 +571,75 @@ pub struct PartiallySignedTransaction {
     pub output: Vec<TxOut>,
 }
 
+impl PartiallySignedTransaction {
+    /// Input value minus output value, negative if the outputs spend more than the inputs.
+    ///
+    /// Signed inputs don't carry their value, so this is `None` if any input is signed.
+    pub fn fee(&self) -> Option<i64> {
+        let mut fee = 0i64;
+        for txin in &self.input {
+            let MaybeUnsignedTxIn::Unsigned(txin) = txin else {
+                return None;
+            };
+            fee = fee.checked_add(txin.value.try_into().ok()?)?;
+        }
+        for txout in &self.output {
+            fee = fee.checked_sub(txout.value.try_into().ok()?)?;
+        }
+        Some(fee)
+    }
+
+    /// The serialization split at its top level fields: version, input count, each input, output
+    /// count, each output and lock time.
+    pub fn fields(&self) -> Vec<Vec<u8>> {
+        let mut fields = vec![encode::serialize(&self.version)];
+        fields.push(encode::serialize(&VarInt(self.input.len() as u64)));
+        fields.extend(self.input.iter().map(encode::serialize));
+        fields.push(encode::serialize(&VarInt(self.output.len() as u64)));
+        fields.extend(self.output.iter().map(encode::serialize));
+        fields.push(encode::serialize(&self.lock_time));
+        fields
+    }
+
+    /// Whether every input is signed, making [`Self::estimated_size`] exact.
+    pub fn is_signed(&self) -> bool {
+        self.input
+            .iter()
+            .all(|txin| matches!(txin, MaybeUnsignedTxIn::Signed(_)))
+    }
+
+    /// Size of the transaction once signed, estimating unsigned inputs as signed with
+    /// `signatures` from their scriptSig template, and as P2PKH inputs if it isn't recognized.
+    pub fn estimated_size(&self, signatures: SignatureType) -> usize {
+        let inputs = self
+            .input
+            .iter()
+            .map(|txin| match txin {
+                MaybeUnsignedTxIn::Unsigned(txin) => {
+                    match txin.unsigned_script_sig.dummy_signed(signatures) {
+                        Some(script_sig) => {
+                            let len = script_sig.len();
+                            // Outpoint, scriptSig with its length and sequence
+                            36 + VarInt(len as u64).len() + len + 4
+                        }
+                        None => signatures.p2pkh_input_size(),
+                    }
+                }
+                MaybeUnsignedTxIn::Signed(txin) => encode::serialize(txin).len(),
+            })
+            .sum::<usize>();
+        let outputs = self
+            .output
+            .iter()
+            .map(|txout| encode::serialize(txout).len())
+            .sum::<usize>();
+        8 + VarInt(self.input.len() as u64).len()
+            + inputs
+            + VarInt(self.output.len() as u64).len()
+            + outputs
+    }
+}
+
 impl Encodable for PartiallySignedTransaction {
     fn consensus_encode<W: std::io::Write + ?Sized>(
         &self,
@@ -503,6 +676,13 @@ impl From<Transaction> for PartiallySignedTransaction {
     }
 }
 
+impl PartiallySignedTransaction {
+    /// Deserialize a transaction in the Electron Cash format, or failing that a signed one.
+    pub fn deserialize_any(bytes: &[u8]) -> Result<Self, encode::Error> {
+        Self::deserialize(bytes).or_else(|_| Ok(Transaction::deserialize(bytes)?.into()))
+    }
+}
+
 impl Deserialize for PartiallySignedTransaction {
     fn deserialize(bytes: &[u8]) -> Result<Self, encode::Error> {
         bitcoincash::consensus::deserialize(bytes)
@@ -522,7 +702,7 @@ mod tests {
         hashes::hex::FromHex,
     };
 
-    use super::PartiallySignedTransaction;
+    use super::{PartiallySignedTransaction, SignatureType};
 
     #[test]
     fn test_unsigned_transaction() {
@@ -537,6 +717,10 @@ mod tests {
         .unwrap();
         let tx: PartiallySignedTransaction = deserialize(&tx_bytes).unwrap();
         assert_eq!(tx_bytes, serialize(&tx));
+        assert_eq!(tx.fee(), Some(219));
+        assert_eq!(tx.estimated_size(SignatureType::Ecdsa), 226);
+        assert_eq!(tx.estimated_size(SignatureType::Schnorr), 219);
+        assert!(!tx.is_signed());
     }
 
     #[test]
@@ -573,6 +757,11 @@ mod tests {
         .unwrap();
         let tx: PartiallySignedTransaction = deserialize(&tx_bytes).unwrap();
         assert_eq!(tx_bytes, serialize(&tx));
+        assert_eq!(tx.fee(), None);
+        assert_eq!(tx.estimated_size(SignatureType::Ecdsa), tx_bytes.len());
+        assert_eq!(tx.estimated_size(SignatureType::Schnorr), tx_bytes.len());
+        assert_eq!(tx.fields().concat(), tx_bytes);
+        assert!(tx.is_signed());
     }
 
     #[test]
@@ -603,5 +792,9 @@ mod tests {
         .unwrap();
         let tx: PartiallySignedTransaction = deserialize(&tx_bytes).unwrap();
         assert_eq!(tx_bytes, serialize(&tx));
+        // 2-of-2: OP_0, two signatures and the 71 byte redeem script, 219 bytes
+        assert_eq!(tx.estimated_size(SignatureType::Ecdsa), 334);
+        // The bitfield 0b11 is pushed as OP_3, 205 bytes
+        assert_eq!(tx.estimated_size(SignatureType::Schnorr), 320);
     }
 }
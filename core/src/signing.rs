@@ -0,0 +1,189 @@
+//! Signing of P2PKH inputs the way Bitcoin Cash requires, with SIGHASH_FORKID over the BIP143
+//! style digest, which also commits to the tokens of the output spent. The digest also follows the
+//! other sighash types, for [`crate::verification`] to check signatures made with them.
+
+use bitcoincash::{
+    blockdata::{opcodes::all::OP_SPECIAL_TOKEN_PREFIX, script::Builder, token::OutputData},
+    consensus::Encodable,
+    hashes::{sha256, sha256d, Hash},
+    secp256k1::{Message, Secp256k1, Signing},
+    PrivateKey, Script, Transaction, TxOut,
+};
+
+/// SIGHASH_ALL with SIGHASH_FORKID, the sighash type of the signatures made here.
+pub const SIGHASH_ALL_FORKID: u32 = 0x41;
+pub const SIGHASH_ALL: u32 = 0x01;
+pub const SIGHASH_NONE: u32 = 0x02;
+pub const SIGHASH_SINGLE: u32 = 0x03;
+/// Commits to every output spent by the transaction, from the May 2023 upgrade.
+pub const SIGHASH_UTXOS: u32 = 0x20;
+pub const SIGHASH_FORKID: u32 = 0x40;
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// Hash of the consensus encodings of `items` one after the other.
+fn hash_all<T: Encodable>(items: impl IntoIterator<Item = T>) -> sha256d::Hash {
+    let mut engine = sha256d::Hash::engine();
+    for item in items {
+        item.consensus_encode(&mut engine)
+            .expect("engines don't error");
+    }
+    sha256d::Hash::from_engine(engine)
+}
+
+/// The digest a SIGHASH_ALL|SIGHASH_FORKID signature of input `input_index` commits to, for a
+/// previous output of `value` sats with `token`, spent with `script_code`.
+pub fn signature_hash(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &Script,
+    value: u64,
+    token: Option<&OutputData>,
+) -> sha256d::Hash {
+    digest(
+        tx,
+        input_index,
+        script_code,
+        value,
+        token,
+        &[],
+        SIGHASH_ALL_FORKID,
+    )
+}
+
+/// The BIP143 digest with `sighash_type`, and the token prefix of the output spent before the
+/// script code. `utxos` are the outputs spent by every input, only read with [`SIGHASH_UTXOS`].
+#[allow(clippy::too_many_arguments)]
+pub fn digest(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &Script,
+    value: u64,
+    token: Option<&OutputData>,
+    utxos: &[TxOut],
+    sighash_type: u32,
+) -> sha256d::Hash {
+    let txin = &tx.input[input_index];
+    let base_type = sighash_type & 0x1f;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    let zero = sha256d::Hash::all_zeros();
+    let encode = |e: &mut sha256::HashEngine| -> std::io::Result<()> {
+        tx.version.consensus_encode(e)?;
+        match anyone_can_pay {
+            true => zero,
+            false => hash_all(tx.input.iter().map(|i| i.previous_output)),
+        }
+        .consensus_encode(e)?;
+        if sighash_type & SIGHASH_UTXOS != 0 {
+            hash_all(utxos).consensus_encode(e)?;
+        }
+        match anyone_can_pay || base_type == SIGHASH_SINGLE || base_type == SIGHASH_NONE {
+            true => zero,
+            false => hash_all(tx.input.iter().map(|i| i.sequence)),
+        }
+        .consensus_encode(e)?;
+        txin.previous_output.consensus_encode(e)?;
+        if let Some(token) = token {
+            OP_SPECIAL_TOKEN_PREFIX.to_u8().consensus_encode(e)?;
+            token.consensus_encode(e)?;
+        }
+        script_code.consensus_encode(e)?;
+        value.consensus_encode(e)?;
+        txin.sequence.consensus_encode(e)?;
+        match tx.output.get(input_index) {
+            _ if base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE => hash_all(&tx.output),
+            Some(output) if base_type == SIGHASH_SINGLE => hash_all([output]),
+            _ => zero,
+        }
+        .consensus_encode(e)?;
+        tx.lock_time.consensus_encode(e)?;
+        sighash_type.consensus_encode(e)?;
+        Ok(())
+    };
+    let mut engine = sha256d::Hash::engine();
+    encode(&mut engine).expect("engines don't error");
+    sha256d::Hash::from_engine(engine)
+}
+
+/// Sign input `input_index` of `tx`, spending a P2PKH output of `key` with `value` and `token`,
+/// setting its scriptSig to the signature and public key.
+pub fn sign_p2pkh<C: Signing>(
+    secp: &Secp256k1<C>,
+    tx: &mut Transaction,
+    input_index: usize,
+    key: &PrivateKey,
+    value: u64,
+    token: Option<&OutputData>,
+) {
+    let pubkey = key.public_key(secp);
+    let script_code = Script::new_p2pkh(&pubkey.pubkey_hash());
+    let sighash = signature_hash(tx, input_index, &script_code, value, token);
+    let message = Message::from_slice(&sighash[..]).expect("sighashes are 32 bytes");
+    let mut signature = secp
+        .sign_ecdsa(&message, &key.inner)
+        .serialize_der()
+        .to_vec();
+    signature.push(SIGHASH_ALL_FORKID as u8);
+    tx.input[input_index].script_sig = Builder::new()
+        .push_slice(&signature)
+        .push_key(&pubkey)
+        .into_script();
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{
+        blockdata::script::Instruction,
+        consensus::deserialize,
+        hashes::hex::{FromHex, ToHex},
+        secp256k1::{ecdsa::Signature, Message, Secp256k1},
+        Network, PrivateKey, Script, Transaction,
+    };
+
+    use super::{digest, sign_p2pkh, signature_hash};
+
+    #[test]
+    fn test_digest() {
+        // The native P2WPKH example of BIP143, which Bitcoin Cash's digest is based on
+        let tx: Transaction = deserialize(&Vec::from_hex("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000").unwrap()).unwrap();
+        let script_code = Script::from(
+            Vec::from_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap(),
+        );
+        assert_eq!(
+            digest(&tx, 1, &script_code, 600_000_000, None, &[], 1)[..].to_hex(),
+            "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+        );
+    }
+
+    #[test]
+    fn test_sign_p2pkh() {
+        let secp = Secp256k1::new();
+        let key =
+            PrivateKey::from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").unwrap();
+        assert_eq!(key.network, Network::Bitcoin);
+        let mut tx: Transaction = deserialize(&Vec::from_hex("0100000001fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000ffffffff01202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac00000000").unwrap()).unwrap();
+        sign_p2pkh(&secp, &mut tx, 0, &key, 1000, None);
+
+        let pushes = tx.input[0]
+            .script_sig
+            .instructions()
+            .map(|i| match i.unwrap() {
+                Instruction::PushBytes(bytes) => bytes.to_vec(),
+                Instruction::Op(op) => panic!("Unexpected {op:?}"),
+            })
+            .collect::<Vec<_>>();
+        let [signature, pubkey] = &pushes[..] else {
+            panic!("Expected a signature and a public key");
+        };
+        assert_eq!(*pubkey, key.public_key(&secp).to_bytes());
+        let (sighash_type, der) = signature.split_last().unwrap();
+        assert_eq!(*sighash_type, 0x41);
+        let script_code = Script::new_p2pkh(&key.public_key(&secp).pubkey_hash());
+        let sighash = signature_hash(&tx, 0, &script_code, 1000, None);
+        secp.verify_ecdsa(
+            &Message::from_slice(&sighash[..]).unwrap(),
+            &Signature::from_der(der).unwrap(),
+            &key.inner.public_key(&secp),
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+//! Transactions to test against, shared with the editor's tests through the `test-util` feature.
+
+use bitcoincash::{
+    blockdata::token::OutputData, OutPoint, PackedLockTime, Script, Sequence, TokenID, TxIn, TxOut,
+    Txid,
+};
+
+use crate::partially_signed::{
+    MaybeUnsignedTxIn, PartiallySignedTransaction, UnsignedScriptSig, UnsignedTxIn,
+};
+
+pub const TXID: &str = "efc44ce628940675b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd55";
+
+/// Signed input spending output `vout` of [`TXID`], whose token, if any, is unknown.
+pub fn signed_input(vout: u32) -> MaybeUnsignedTxIn {
+    MaybeUnsignedTxIn::Signed(TxIn {
+        previous_output: OutPoint::new(TXID.parse().unwrap(), vout),
+        script_sig: Script::new(),
+        sequence: Sequence::MAX,
+        witness: Default::default(),
+    })
+}
+
+/// Unsigned input spending 1000 sats and `token`.
+pub fn unsigned_input(txid: Txid, vout: u32, token: Option<OutputData>) -> MaybeUnsignedTxIn {
+    MaybeUnsignedTxIn::Unsigned(UnsignedTxIn {
+        previous_output: OutPoint::new(txid, vout),
+        unsigned_script_sig: UnsignedScriptSig::from_raw_script(Script::new()),
+        sequence: Sequence::MAX,
+        value: 1000,
+        token,
+    })
+}
+
+pub fn token(category: TokenID, bitfield: u8, amount: i64) -> OutputData {
+    OutputData {
+        id: category,
+        bitfield,
+        amount,
+        commitment: vec![],
+    }
+}
+
+/// Output of 1000 sats with an empty locking script.
+pub fn output(token: Option<OutputData>) -> TxOut {
+    TxOut {
+        value: 1000,
+        script_pubkey: Script::new(),
+        token,
+    }
+}
+
+/// Output of a single fungible token of `category`.
+pub fn token_output(category: TokenID) -> TxOut {
+    output(Some(token(category, 0x10, 1)))
+}
+
+pub fn nft_output(category: TokenID, capability: u8, commitment: &[u8]) -> TxOut {
+    output(Some(OutputData {
+        commitment: commitment.to_vec(),
+        ..token(category, 0x60 | capability, 0)
+    }))
+}
+
+pub fn tx(input: Vec<MaybeUnsignedTxIn>, output: Vec<TxOut>) -> PartiallySignedTransaction {
+    PartiallySignedTransaction {
+        version: 2,
+        lock_time: PackedLockTime(0),
+        input,
+        output,
+    }
+}
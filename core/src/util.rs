@@ -0,0 +1,633 @@
+use bitcoincash::{
+    blockdata::{
+        opcodes::{self, Class, ClassifyContext},
+        script::{Builder, Instruction},
+    },
+    consensus::serialize,
+    hashes::{hash160, sha256d, Hash},
+    Address, Network, Script, TxOut,
+};
+use cashaddr::{CashEnc, HashType};
+use serde::{Deserialize, Serialize};
+
+str_enum! {
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum ScriptType {
+        P2pkh = "P2PKH",
+        P2sh20 = "P2SH20",
+        P2sh32 = "P2SH32",
+        OpReturn = "OP_RETURN",
+        P2pk = "P2PK",
+        BareMultisig = "Bare multisig",
+        Nonstandard = "Nonstandard",
+    }
+}
+
+/// Classify a locking script by its template.
+pub fn classify_script(s: &Script) -> ScriptType {
+    if s.is_p2pkh() {
+        ScriptType::P2pkh
+    } else if s.is_p2sh() {
+        ScriptType::P2sh20
+    } else if is_p2sh32(s) {
+        ScriptType::P2sh32
+    } else if s.is_op_return() {
+        ScriptType::OpReturn
+    } else if s.is_p2pk() {
+        ScriptType::P2pk
+    } else if parse_bare_multisig(s).is_some() {
+        ScriptType::BareMultisig
+    } else {
+        ScriptType::Nonstandard
+    }
+}
+
+pub fn is_p2sh32(s: &Script) -> bool {
+    let s = s.as_bytes();
+    s.len() == 35
+        && s[0] == opcodes::all::OP_HASH256.to_u8()
+        && s[1] == opcodes::all::OP_PUSHBYTES_32.to_u8()
+        && s[34] == opcodes::all::OP_EQUAL.to_u8()
+}
+
+/// The P2SH32 locking script paying to `redeem_script`, by its double SHA-256.
+pub fn to_p2sh32(redeem_script: &Script) -> Script {
+    Builder::new()
+        .push_opcode(opcodes::all::OP_HASH256)
+        .push_slice(&sha256d::Hash::hash(redeem_script.as_bytes())[..])
+        .push_opcode(opcodes::all::OP_EQUAL)
+        .into_script()
+}
+
+/// Whether the script is an OP_RETURN carrying the SLP Lokad ID.
+pub fn is_slp_op_return(s: &Script) -> bool {
+    let s = s.as_bytes();
+    s.len() >= 6
+        && s[0] == opcodes::all::OP_RETURN.to_u8()
+        && s[1] == opcodes::all::OP_PUSHBYTES_4.to_u8()
+        && s[2..6] == *b"SLP\0"
+}
+
+/// Parse a bare multisig locking script, returning m and n.
+pub fn parse_bare_multisig(s: &Script) -> Option<(usize, usize)> {
+    let instructions: Vec<_> = s.instructions().collect::<Result<_, _>>().ok()?;
+    let [Instruction::Op(m), pubkeys @ .., Instruction::Op(n), Instruction::Op(checkmultisig)] =
+        &instructions[..]
+    else {
+        return None;
+    };
+    let Class::PushNum(m) = m.classify(ClassifyContext::Legacy) else {
+        return None;
+    };
+    let Class::PushNum(n) = n.classify(ClassifyContext::Legacy) else {
+        return None;
+    };
+    let (m, n) = (usize::try_from(m).ok()?, usize::try_from(n).ok()?);
+    let all_pubkeys = pubkeys
+        .iter()
+        .all(|p| matches!(p, Instruction::PushBytes(p) if p.len() == 33 || p.len() == 65));
+    (*checkmultisig == opcodes::all::OP_CHECKMULTISIG
+        && all_pubkeys
+        && n == pubkeys.len()
+        && (1..=n).contains(&m))
+    .then_some((m, n))
+}
+
+/// Minimal script number encoding: little endian, with the sign in the top bit of the last byte.
+pub fn script_num(n: i64) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut abs = n.unsigned_abs();
+    while abs > 0 {
+        result.push(abs as u8);
+        abs >>= 8;
+    }
+    if let Some(&last) = result.last() {
+        if last & 0x80 != 0 {
+            result.push(if n < 0 { 0x80 } else { 0 });
+        } else if n < 0 {
+            *result.last_mut().unwrap() |= 0x80;
+        }
+    }
+    result
+}
+
+/// Push `data` with the smallest possible opcode.
+pub fn push_minimal(builder: Builder, data: &[u8]) -> Builder {
+    match data {
+        [] => builder.push_opcode(opcodes::all::OP_PUSHBYTES_0),
+        [n @ 1..=16] => builder.push_opcode(opcodes::All::from(
+            opcodes::all::OP_PUSHNUM_1.to_u8() + n - 1,
+        )),
+        [0x81] => builder.push_opcode(opcodes::all::OP_PUSHNUM_NEG1),
+        data => builder.push_slice(data),
+    }
+}
+
+/// The pushes in `script` not made with the smallest possible opcode, as [`push_minimal`] would,
+/// by the byte offset of their opcode and the data they push. Ends at a truncated push.
+pub fn non_minimal_pushes(script: &Script) -> Vec<(usize, Vec<u8>)> {
+    let bytes = script.as_bytes();
+    let mut pushes = vec![];
+    let mut i = 0;
+    while let Some(&op) = bytes.get(i) {
+        let (size_len, len) = match op {
+            0x01..=0x4b => (0, usize::from(op)),
+            0x4c..=0x4e => {
+                let size_len = 1 << (op - 0x4c);
+                let Some(size) = bytes.get(i + 1..i + 1 + size_len) else {
+                    break;
+                };
+                let len = size
+                    .iter()
+                    .rev()
+                    .fold(0, |len, &b| (len << 8) | usize::from(b));
+                (size_len, len)
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let start = i + 1 + size_len;
+        let Some(data) = bytes.get(start..start.saturating_add(len)) else {
+            break;
+        };
+        let minimal = push_minimal(Builder::new(), data).into_script();
+        if minimal.as_bytes() != &bytes[i..start + len] {
+            pushes.push((i, data.to_vec()));
+        }
+        i = start + len;
+    }
+    pushes
+}
+
+/// CashAddr prefixes, tried in turn for addresses written without one.
+const CASH_ADDR_PREFIXES: [&str; 3] = ["bitcoincash", "bchtest", "bchreg"];
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum AddressFormat {
+        #[default]
+        Prefixed = "prefixed",
+        Bare = "bare",
+    }
+}
+
+impl AddressFormat {
+    pub fn apply(self, address: String) -> String {
+        match (self, address.split_once(':')) {
+            (AddressFormat::Bare, Some((_, bare))) => bare.to_string(),
+            _ => address,
+        }
+    }
+}
+
+fn parse_cash_addr(addr: &str) -> Result<cashaddr::Payload, cashaddr::DecodeError> {
+    addr.parse::<cashaddr::Payload>().or_else(|e| {
+        CASH_ADDR_PREFIXES
+            .iter()
+            .find_map(|prefix| format!("{prefix}:{addr}").parse().ok())
+            .ok_or(e)
+    })
+}
+
+/// Whether `addr` is a token-aware CashAddr, signaling that its wallet accepts CashTokens.
+pub fn is_token_aware_addr(addr: &str) -> bool {
+    parse_cash_addr(addr.trim()).is_ok_and(|a| matches!(a.hash_type().numeric_value(), 2 | 3))
+}
+
+pub fn cash_addr_to_script(addr: &str) -> anyhow::Result<Script> {
+    let payload = parse_cash_addr(addr);
+    match payload {
+        Ok(addr) => match addr.hash_type().numeric_value() {
+            0 | 2 => {
+                // p2pkh, token-aware p2pkh
+                Ok(Builder::new()
+                    .push_opcode(opcodes::all::OP_DUP)
+                    .push_opcode(opcodes::all::OP_HASH160)
+                    .push_slice(&addr)
+                    .push_opcode(opcodes::all::OP_EQUALVERIFY)
+                    .push_opcode(opcodes::all::OP_CHECKSIG)
+                    .into_script())
+            }
+            1 | 3 => match addr.len() {
+                // p2sh, token-aware p2sh
+                20 => Ok(Builder::new()
+                    .push_opcode(opcodes::all::OP_HASH160)
+                    .push_slice(&addr)
+                    .push_opcode(opcodes::all::OP_EQUAL)
+                    .into_script()),
+                32 => Ok(Builder::new()
+                    .push_opcode(opcodes::all::OP_HASH256)
+                    .push_slice(&addr)
+                    .push_opcode(opcodes::all::OP_EQUAL)
+                    .into_script()),
+                _ => anyhow::bail!("unknown CashAddress type"),
+            },
+            _ => anyhow::bail!("unknown CashAddress type"),
+        },
+        Err(e) => {
+            let Ok(addr) = addr.parse::<Address>() else {
+                Err(e)?
+            };
+            Ok(addr.script_pubkey())
+        }
+    }
+}
+
+/// CashAddr prefix of a built-in network. Custom networks have their own.
+pub fn cash_addr_prefix(network: Network) -> &'static str {
+    match network {
+        Network::Bitcoin => "bitcoincash",
+        Network::Regtest => "bchreg",
+        Network::Testnet | Network::Testnet4 | Network::Scalenet | Network::Chipnet => "bchtest",
+    }
+}
+
+/// CashAddr of a locking script with `prefix`, see [`cash_addr_prefix`].
+pub fn script_to_cash_addr(s: &Script, prefix: &str) -> anyhow::Result<String> {
+    encode_cash_addr(s, prefix, false)
+}
+
+/// Token-aware CashAddr (hash type 2 or 3) of a locking script, for outputs carrying tokens.
+pub fn script_to_token_addr(s: &Script, prefix: &str) -> anyhow::Result<String> {
+    encode_cash_addr(s, prefix, true)
+}
+
+fn encode_cash_addr(s: &Script, prefix: &str, token_aware: bool) -> anyhow::Result<String> {
+    let (p2pkh, p2sh) = match token_aware {
+        false => (HashType::P2PKH, HashType::P2SH),
+        true => (HashType::try_from(2)?, HashType::try_from(3)?),
+    };
+    if is_p2sh32(s) {
+        let hash = &s.as_bytes()[2..34];
+        Ok(hash.encode(prefix, p2sh)?)
+    } else if s.is_p2sh() {
+        let hash = &s.as_bytes()[2..22];
+        Ok(hash.encode(prefix, p2sh)?)
+    } else if s.is_p2pkh() {
+        let hash = &s.as_bytes()[3..23];
+        Ok(hash.encode(prefix, p2pkh)?)
+    } else if s.is_p2pk() {
+        let pubkey = &s.as_bytes()[1..s.len() - 1];
+        Ok(hash160::Hash::hash(pubkey)[..].encode(prefix, p2pkh)?)
+    } else if let Some((m, n)) = parse_bare_multisig(s) {
+        Ok(format!("{m}-of-{n} bare multisig"))
+    } else {
+        anyhow::bail!("Unknown script type");
+    }
+}
+
+/// Legacy base58 address of a P2PKH or P2SH20 locking script, for systems predating CashAddr.
+/// P2PK has none, the address of its key would be a different script.
+pub fn script_to_legacy_addr(s: &Script, network: Network) -> anyhow::Result<String> {
+    if s.is_p2pk() {
+        anyhow::bail!("P2PK pays to a public key, not to an address");
+    }
+    if !s.is_p2pkh() && !s.is_p2sh() {
+        anyhow::bail!("Only P2PKH and P2SH20 have legacy addresses");
+    }
+    Ok(Address::from_script(s, network)?.to_string())
+}
+
+/// Minimum value an output must carry to not be considered dust, in satoshis.
+///
+/// This follows the BCHN relay policy: three times the cost, at 1 sat/byte, of creating the output
+/// and later spending it with a typical 148 byte input. The token prefix is part of the serialized
+/// output, so outputs carrying tokens need more sats than their bare locking script would suggest.
+pub fn dust_threshold(txout: &TxOut) -> u64 {
+    if txout.script_pubkey.is_op_return() {
+        return 0;
+    }
+    3 * (serialize(txout).len() as u64 + 148)
+}
+
+/// Parse a u32 such as a sequence number or locktime, written in decimal or as `0x`-prefixed hex.
+pub fn parse_u32(s: &str) -> anyhow::Result<u32> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => Ok(u32::from_str_radix(hex, 16)?),
+        None => Ok(s.parse()?),
+    }
+}
+
+/// Format a u32 in decimal, or in `0x`-prefixed hex that [`parse_u32`] reads back.
+pub fn format_u32(n: u32, hex: bool) -> String {
+    match hex {
+        true => format!("{n:#x}"),
+        false => n.to_string(),
+    }
+}
+
+/// Shorten a long hex string such as a txid to its first 8 characters.
+pub fn short_hex(s: &str) -> String {
+    match s.get(..8) {
+        Some(prefix) if s.len() > 16 => format!("{prefix}…"),
+        _ => s.to_string(),
+    }
+}
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+    pub enum AmountUnit {
+        #[default]
+        Sats = "sats",
+        Bch = "BCH",
+    }
+}
+
+const BCH_DECIMALS: u8 = 8;
+
+/// Format an amount in sats as decimal BCH, without trailing zeros.
+pub fn format_bch(sats: u64) -> String {
+    format_decimal(sats, BCH_DECIMALS)
+}
+
+/// Parse decimal BCH into sats exactly, rejecting amounts with more than 8 decimals.
+pub fn parse_bch(s: &str) -> anyhow::Result<u64> {
+    parse_decimal(s, BCH_DECIMALS)
+}
+
+/// Format an amount in base units with `decimals` (at most 19) decimal places, without trailing
+/// zeros.
+pub fn format_decimal(amount: u64, decimals: u8) -> String {
+    let scale = 10u64.pow(decimals.into());
+    let whole = amount / scale;
+    let frac = amount % scale;
+    if frac == 0 {
+        return whole.to_string();
+    }
+    let frac = format!("{frac:0width$}", width = usize::from(decimals));
+    format!("{whole}.{}", frac.trim_end_matches('0'))
+}
+
+/// Parse a decimal amount into base units exactly, rejecting more than `decimals` decimals.
+pub fn parse_decimal(s: &str, decimals: u8) -> anyhow::Result<u64> {
+    let s = s.trim();
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty() && frac.is_empty() {
+        anyhow::bail!("Empty amount");
+    }
+    if !whole
+        .chars()
+        .chain(frac.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        anyhow::bail!("Invalid amount");
+    }
+    let width = usize::from(decimals);
+    if frac.len() > width {
+        anyhow::bail!("Too many decimals");
+    }
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>()?
+    };
+    let frac = match width {
+        0 => 0,
+        _ => format!("{frac:0<width$}").parse::<u64>()?,
+    };
+    10u64
+        .checked_pow(decimals.into())
+        .and_then(|scale| whole.checked_mul(scale))
+        .and_then(|w| w.checked_add(frac))
+        .ok_or_else(|| anyhow::anyhow!("Amount too large"))
+}
+
+impl AmountUnit {
+    pub fn format(self, sats: u64) -> String {
+        match self {
+            AmountUnit::Sats => sats.to_string(),
+            AmountUnit::Bch => format_bch(sats),
+        }
+    }
+
+    /// Parse an amount in this unit, or in the unit it's suffixed with, so "0.0001 BCH" can be
+    /// pasted while entering sats and the other way around.
+    pub fn parse(self, s: &str) -> anyhow::Result<u64> {
+        let s = s.trim();
+        let (amount, suffix) =
+            s.split_at(s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len()));
+        let unit = match suffix.to_ascii_lowercase().as_str() {
+            "" => self,
+            "bch" => AmountUnit::Bch,
+            "sat" | "sats" | "satoshi" | "satoshis" => AmountUnit::Sats,
+            _ => anyhow::bail!("Unknown unit {suffix}"),
+        };
+        match unit {
+            AmountUnit::Sats => Ok(amount.trim().parse()?),
+            AmountUnit::Bch => parse_bch(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{
+        blockdata::token::OutputData, hashes::hex::FromHex, Network, PublicKey, Script, TokenID,
+        TxOut,
+    };
+
+    use super::{
+        cash_addr_prefix, cash_addr_to_script, dust_threshold, format_bch, format_decimal,
+        format_u32, is_p2sh32, is_token_aware_addr, non_minimal_pushes, parse_bch, parse_decimal,
+        parse_u32, script_num, script_to_cash_addr, script_to_legacy_addr, script_to_token_addr,
+        to_p2sh32, AddressFormat, AmountUnit,
+    };
+
+    #[test]
+    fn test_script_num() {
+        assert_eq!(script_num(0), Vec::<u8>::new());
+        assert_eq!(script_num(127), vec![0x7f]);
+        assert_eq!(script_num(128), vec![0x80, 0x00]);
+        assert_eq!(script_num(-1), vec![0x81]);
+        assert_eq!(script_num(-255), vec![0xff, 0x80]);
+    }
+
+    #[test]
+    fn test_dust_threshold() {
+        let p2pkh = Script::from_hex("76a914795b6a18d92f888df281f85373288a6834a7d31a88ac").unwrap();
+        let mut txout = TxOut {
+            value: 0,
+            script_pubkey: p2pkh,
+            token: None,
+        };
+        assert_eq!(dust_threshold(&txout), 546);
+
+        txout.token = Some(OutputData {
+            id: TokenID::from_hex(
+                "efc44ce628940675b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd55",
+            )
+            .unwrap(),
+            bitfield: 0x10,
+            amount: 1000,
+            commitment: vec![],
+        });
+        // 1 byte prefix, 32 byte category, 1 byte bitfield, 3 byte amount
+        assert_eq!(dust_threshold(&txout), 546 + 3 * 37);
+    }
+
+    #[test]
+    fn test_p2pk_and_bare_multisig_addr() {
+        let pubkey = "030a72c3eb8d023aa1638587293e427819265fd307db1d67de8e5c4129f654bf49";
+        let p2pk = Script::from_hex(&format!("21{pubkey}ac")).unwrap();
+        let p2pkh = Script::new_p2pkh(&pubkey.parse::<PublicKey>().unwrap().pubkey_hash());
+        assert_eq!(
+            script_to_cash_addr(&p2pk, "bitcoincash").unwrap(),
+            script_to_cash_addr(&p2pkh, "bitcoincash").unwrap(),
+        );
+
+        let multisig = Script::from_hex(&format!("5121{pubkey}21{pubkey}52ae")).unwrap();
+        assert_eq!(
+            script_to_cash_addr(&multisig, "bitcoincash").unwrap(),
+            "1-of-2 bare multisig"
+        );
+    }
+
+    #[test]
+    fn test_bare_cash_addr() {
+        let p2pkh = Script::from_hex("76a914795b6a18d92f888df281f85373288a6834a7d31a88ac").unwrap();
+        for network in [Network::Bitcoin, Network::Chipnet, Network::Regtest] {
+            let address = script_to_cash_addr(&p2pkh, cash_addr_prefix(network)).unwrap();
+            let bare = AddressFormat::Bare.apply(address.clone());
+            assert!(!bare.contains(':'));
+            assert_eq!(AddressFormat::Prefixed.apply(address.clone()), address);
+            assert_eq!(cash_addr_to_script(&bare).unwrap(), p2pkh);
+        }
+        assert!(cash_addr_to_script("qqqqqqqq").is_err());
+    }
+
+    #[test]
+    fn test_non_minimal_pushes() {
+        let pushes = |hex: &str| non_minimal_pushes(&Script::from_hex(hex).unwrap());
+        // OP_0, OP_5, a direct push of 0xff and OP_PUSHDATA1 of 76 bytes are all minimal
+        let minimal = format!("005501ff4c4c{}", "00".repeat(76));
+        assert!(pushes(&minimal).is_empty());
+        // 0x05 pushed directly, an empty OP_PUSHDATA1, 0x81 pushed directly and 2 bytes pushed
+        // with OP_PUSHDATA2
+        assert_eq!(
+            pushes("0105ac4c000181764d0200abcd"),
+            [
+                (0, vec![0x05]),
+                (3, vec![]),
+                (5, vec![0x81]),
+                (8, vec![0xab, 0xcd])
+            ]
+        );
+        assert_eq!(pushes("0105020102"), [(0, vec![0x05])]);
+    }
+
+    #[test]
+    fn test_to_p2sh32() {
+        let redeem_script = Script::from_hex("51").unwrap();
+        assert_eq!(
+            to_p2sh32(&redeem_script),
+            Script::from_hex(
+                "aa20953ccfa596a6c6d39e5980194539124fdcff116a571455a212baed811f585ee087"
+            )
+            .unwrap()
+        );
+        assert!(is_p2sh32(&to_p2sh32(&redeem_script)));
+    }
+
+    #[test]
+    fn test_custom_prefix() {
+        let p2pkh = Script::from_hex("76a914795b6a18d92f888df281f85373288a6834a7d31a88ac").unwrap();
+        let address = script_to_cash_addr(&p2pkh, "bchpriv").unwrap();
+        assert!(address.starts_with("bchpriv:q"));
+        assert_eq!(cash_addr_to_script(&address).unwrap(), p2pkh);
+    }
+
+    #[test]
+    fn test_token_addr() {
+        let p2pkh = Script::from_hex("76a914795b6a18d92f888df281f85373288a6834a7d31a88ac").unwrap();
+        let p2sh32 = Script::from_hex(&format!("aa20{}87", "11".repeat(32))).unwrap();
+        for script in [p2pkh, p2sh32] {
+            let plain = script_to_cash_addr(&script, "bitcoincash").unwrap();
+            let token = script_to_token_addr(&script, "bitcoincash").unwrap();
+            assert_ne!(plain, token);
+            assert!(!is_token_aware_addr(&plain));
+            assert!(is_token_aware_addr(&token));
+            assert!(is_token_aware_addr(
+                &AddressFormat::Bare.apply(token.clone())
+            ));
+            assert_eq!(cash_addr_to_script(&token).unwrap(), script);
+        }
+        assert!(!is_token_aware_addr("not an address"));
+    }
+
+    #[test]
+    fn test_legacy_addr() {
+        let p2pkh = Script::from_hex("76a914795b6a18d92f888df281f85373288a6834a7d31a88ac").unwrap();
+        let legacy = script_to_legacy_addr(&p2pkh, Network::Bitcoin).unwrap();
+        assert!(legacy.starts_with('1'));
+        assert_eq!(cash_addr_to_script(&legacy).unwrap(), p2pkh);
+        assert!(script_to_legacy_addr(&p2pkh, Network::Chipnet)
+            .unwrap()
+            .starts_with(['m', 'n']));
+
+        let p2sh32 = Script::from_hex(&format!("aa20{}87", "11".repeat(32))).unwrap();
+        assert!(script_to_legacy_addr(&p2sh32, Network::Bitcoin).is_err());
+        let p2pk = Script::from_hex(&format!("21{}ac", "02".repeat(33))).unwrap();
+        assert!(p2pk.is_p2pk());
+        assert!(script_to_legacy_addr(&p2pk, Network::Bitcoin).is_err());
+    }
+
+    #[test]
+    fn test_bch_amounts() {
+        assert_eq!(format_bch(0), "0");
+        assert_eq!(format_bch(100_000_000), "1");
+        assert_eq!(format_bch(12_345_000), "0.12345");
+        assert_eq!(format_bch(2_100_000_000_000_001), "21000000.00000001");
+        assert_eq!(parse_bch("0.12345").unwrap(), 12_345_000);
+        assert_eq!(parse_bch("1.").unwrap(), 100_000_000);
+        assert_eq!(parse_bch(".00000001").unwrap(), 1);
+        assert_eq!(
+            parse_bch("21000000.00000001").unwrap(),
+            2_100_000_000_000_001
+        );
+        assert!(parse_bch("0.000000001").is_err());
+        assert!(parse_bch("1e8").is_err());
+        assert!(parse_bch(".").is_err());
+        assert!(parse_bch("-1").is_err());
+        assert!(parse_bch("184467440737.09551616").is_err());
+    }
+
+    #[test]
+    fn test_u32() {
+        assert_eq!(parse_u32("4294967294").unwrap(), 0xfffffffe);
+        assert_eq!(parse_u32(" 0xfffffffe ").unwrap(), 4294967294);
+        assert_eq!(parse_u32("0XFD").unwrap(), 253);
+        assert!(parse_u32("0x").is_err());
+        assert!(parse_u32("0x100000000").is_err());
+        assert!(parse_u32("fd").is_err());
+        assert_eq!(format_u32(0xfffffffe, true), "0xfffffffe");
+        assert_eq!(format_u32(253, false), "253");
+        assert_eq!(parse_u32(&format_u32(0, true)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_amount_units() {
+        assert_eq!(AmountUnit::Sats.parse(" 1000 ").unwrap(), 1000);
+        assert_eq!(AmountUnit::Sats.parse("0.0001 BCH").unwrap(), 10_000);
+        assert_eq!(AmountUnit::Bch.parse("0.0001").unwrap(), 10_000);
+        assert_eq!(AmountUnit::Bch.parse("546sats").unwrap(), 546);
+        assert_eq!(AmountUnit::Bch.parse("1 sat").unwrap(), 1);
+        assert!(AmountUnit::Sats.parse("0.5 sats").is_err());
+        assert!(AmountUnit::Sats.parse("1 XEC").is_err());
+        assert!(AmountUnit::Bch.parse("BCH").is_err());
+    }
+
+    #[test]
+    fn test_decimal_amounts() {
+        assert_eq!(format_decimal(150, 2), "1.5");
+        assert_eq!(format_decimal(150, 0), "150");
+        assert_eq!(format_decimal(u64::MAX, 18), "18.446744073709551615");
+        assert_eq!(parse_decimal("1.5", 2).unwrap(), 150);
+        assert_eq!(parse_decimal("150", 0).unwrap(), 150);
+        assert!(parse_decimal("1.5", 0).is_err());
+        assert!(parse_decimal("1.", 0).is_ok());
+        assert!(parse_decimal("19", 18).is_err());
+    }
+}
@@ -0,0 +1,779 @@
+//! Static checks on a transaction being edited.
+//!
+//! These do not replace a full node's verification, they only catch common authoring mistakes
+//! cheaply, before the transaction gets signed or broadcast.
+
+use std::collections::{HashMap, HashSet};
+
+use bitcoincash::{
+    blockdata::{
+        opcodes::all::OP_PUSHNUM_16,
+        script::{Builder, Instruction},
+        token::{Capability, OutputData},
+    },
+    hashes::Hash,
+    Script, Sequence, TokenID, Txid,
+};
+
+use crate::cash_assembly::{opcode_name_in, InstructionSet};
+use crate::partially_signed::{MaybeUnsignedTxIn, PartiallySignedTransaction};
+use crate::util::{is_slp_op_return, non_minimal_pushes, push_minimal};
+use crate::vm_limits;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Location {
+    Transaction,
+    Input(usize),
+    Output(usize),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    pub location: Location,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(location: Location, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            location,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// Maximum number of satoshis that can ever exist, and therefore the maximum value of an output.
+pub const MAX_MONEY: u64 = 21_000_000 * 100_000_000;
+
+/// Maximum fungible token amount of an output, which is encoded as a signed 64-bit number.
+pub const MAX_FT_AMOUNT: u64 = i64::MAX as u64;
+
+/// Maximum size of a script, beyond which it fails to run.
+pub const MAX_SCRIPT_SIZE: usize = 10_000;
+
+/// Maximum size of the data a script pushes, raised from 520 bytes by the May 2025 VM limits
+/// upgrade.
+pub const MAX_PUSH_SIZE: usize = 10_000;
+
+/// Which side of a spend a script is on, for [`check_script`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ScriptRole {
+    Locking,
+    Unlocking,
+}
+
+/// Check a single script against the limits it has to stay within to run, for the field it's
+/// entered in, and for pushes not made minimally, which are nonstandard. OP_RETURN locking scripts
+/// are never run, so they are left alone.
+pub fn check_script(script: &Script, role: ScriptRole) -> Vec<String> {
+    let mut problems = vec![];
+    if role == ScriptRole::Locking && script.is_op_return() {
+        return problems;
+    }
+    if script.len() > MAX_SCRIPT_SIZE {
+        problems.push(format!(
+            "Script of {} bytes exceeds the limit of {MAX_SCRIPT_SIZE}",
+            script.len()
+        ));
+    }
+    let mut push_only = true;
+    for instruction in script.instructions() {
+        match instruction {
+            Ok(Instruction::PushBytes(bytes)) if bytes.len() > MAX_PUSH_SIZE => {
+                problems.push(format!(
+                    "Push of {} bytes exceeds the limit of {MAX_PUSH_SIZE}",
+                    bytes.len()
+                ));
+            }
+            Ok(Instruction::PushBytes(_)) => {}
+            Ok(Instruction::Op(op)) => push_only &= op.to_u8() <= OP_PUSHNUM_16.to_u8(),
+            Err(_) => {
+                problems.push("Script ends in the middle of a push".to_string());
+                break;
+            }
+        }
+    }
+    for (offset, data) in non_minimal_pushes(script) {
+        let minimal = push_minimal(Builder::new(), &data).into_script();
+        problems.push(format!(
+            "Non-minimal push at byte {offset} is nonstandard, push it with {}",
+            opcode_name_in(minimal[0], InstructionSet::Current)
+        ));
+    }
+    if role == ScriptRole::Unlocking && !push_only {
+        problems.push("Unlocking scripts may only push data".to_string());
+    }
+    problems
+}
+
+/// Run all checks on the transaction.
+pub fn check_transaction(tx: &PartiallySignedTransaction) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    check_duplicate_outpoints(tx, &mut diagnostics);
+    check_token_categories(tx, &mut diagnostics);
+    check_nft_capabilities(tx, &mut diagnostics);
+    check_nft_burns(tx, &mut diagnostics);
+    check_slp(tx, &mut diagnostics);
+    check_vm_limits(tx, &mut diagnostics);
+    check_locktime(tx, &mut diagnostics);
+    check_values(tx, &mut diagnostics);
+    diagnostics
+}
+
+/// Whether a token category can be created by this transaction, i.e. whether an input spends
+/// output 0 of the transaction whose txid is the category ID.
+pub fn is_genesis_category(tx: &PartiallySignedTransaction, category: &TokenID) -> bool {
+    tx.input.iter().any(|txin| {
+        let outpoint = txin.previous_output();
+        outpoint.vout == 0 && outpoint.txid == Txid::from_inner(category.into_inner())
+    })
+}
+
+fn check_duplicate_outpoints(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, txin) in tx.input.iter().enumerate() {
+        let outpoint = txin.previous_output();
+        if let Some(first) = tx.input[..i]
+            .iter()
+            .position(|t| t.previous_output() == outpoint)
+        {
+            diagnostics.push(Diagnostic::new(
+                Location::Input(i),
+                Severity::Error,
+                format!("Spends the same outpoint as input #{first}"),
+            ));
+        }
+    }
+}
+
+/// Token data of the outputs spent by the inputs. Returns `None` if any input does not carry the
+/// data of the output it spends, in which case the spent tokens cannot be known.
+fn input_tokens(tx: &PartiallySignedTransaction) -> Option<Vec<&OutputData>> {
+    let mut tokens = vec![];
+    for txin in &tx.input {
+        match txin {
+            MaybeUnsignedTxIn::Unsigned(txin) => tokens.extend(txin.token.as_ref()),
+            MaybeUnsignedTxIn::Signed(_) => return None,
+        }
+    }
+    Some(tokens)
+}
+
+fn check_token_categories(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    let input_categories =
+        input_tokens(tx).map(|tokens| tokens.into_iter().map(|t| t.id).collect::<HashSet<_>>());
+    for (i, txout) in tx.output.iter().enumerate() {
+        let Some(token) = &txout.token else {
+            continue;
+        };
+        if is_genesis_category(tx, &token.id) {
+            diagnostics.push(Diagnostic::new(
+                Location::Output(i),
+                Severity::Info,
+                "Token genesis",
+            ));
+        } else if input_categories
+            .as_ref()
+            .is_some_and(|c| !c.contains(&token.id))
+        {
+            diagnostics.push(Diagnostic::new(
+                Location::Output(i),
+                Severity::Error,
+                "Token category is neither spent by an input nor created by this transaction",
+            ));
+        }
+    }
+}
+
+/// Check that every NFT in the outputs is either passed through unchanged from an input, altered
+/// by spending a mutable NFT, or minted by spending a minting NFT of the same category.
+fn check_nft_capabilities(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(input_tokens) = input_tokens(tx) else {
+        return;
+    };
+    let mut available_nfts: Vec<&OutputData> = input_tokens
+        .iter()
+        .copied()
+        .filter(|t| t.has_nft())
+        .collect();
+    let mut unmatched = vec![];
+
+    // Unchanged NFTs first, so they do not use up the mutable NFTs.
+    for (i, txout) in tx.output.iter().enumerate() {
+        let Some(token) = txout.token.as_ref().filter(|t| t.has_nft()) else {
+            continue;
+        };
+        if is_genesis_category(tx, &token.id)
+            || input_tokens
+                .iter()
+                .any(|t| t.id == token.id && t.is_minting_nft())
+        {
+            continue;
+        }
+        match available_nfts.iter().position(|t| {
+            t.id == token.id
+                && t.capability() == token.capability()
+                && t.commitment == token.commitment
+        }) {
+            Some(pos) => {
+                available_nfts.swap_remove(pos);
+            }
+            None => unmatched.push((i, token)),
+        }
+    }
+
+    for (i, token) in unmatched {
+        let mutable = available_nfts
+            .iter()
+            .position(|t| t.id == token.id && t.capability() == Capability::Mutable as u8);
+        let message = match mutable {
+            Some(pos) if !token.is_minting_nft() => {
+                available_nfts.swap_remove(pos);
+                continue;
+            }
+            _ if token.is_minting_nft() => {
+                "Minting NFT requires a minting NFT of its category among the inputs"
+            }
+            _ if input_tokens.iter().any(|t| t.id == token.id && t.has_nft()) => {
+                "NFT is altered without spending a mutable NFT of its category"
+            }
+            _ => "NFT is minted without spending a minting NFT of its category",
+        };
+        diagnostics.push(Diagnostic::new(
+            Location::Output(i),
+            Severity::Warning,
+            message,
+        ));
+    }
+}
+
+/// NFTs spent by the inputs that no output carries on, with the index of their input. Outputs
+/// keeping an NFT unchanged count first, then those a mutable NFT can become, then a minting NFT
+/// for each minting NFT, which is burned when it only becomes NFTs without the minting capability.
+/// Empty if the spent tokens aren't known.
+pub fn burned_nfts(tx: &PartiallySignedTransaction) -> Vec<(usize, &OutputData)> {
+    let mut input_nfts = vec![];
+    for (i, txin) in tx.input.iter().enumerate() {
+        match txin {
+            MaybeUnsignedTxIn::Unsigned(txin) => {
+                input_nfts.extend(txin.token.as_ref().filter(|t| t.has_nft()).map(|t| (i, t)))
+            }
+            MaybeUnsignedTxIn::Signed(_) => return vec![],
+        }
+    }
+    let mut output_nfts = tx
+        .output
+        .iter()
+        .filter_map(|txout| txout.token.as_ref().filter(|t| t.has_nft()))
+        .collect::<Vec<_>>();
+    let mut carried_on = |matches: &dyn Fn(&OutputData, &OutputData) -> bool| {
+        input_nfts.retain(|(_, input)| {
+            match output_nfts.iter().position(|output| matches(input, output)) {
+                Some(pos) => {
+                    output_nfts.swap_remove(pos);
+                    false
+                }
+                None => true,
+            }
+        });
+    };
+    carried_on(&|input, output| {
+        input.id == output.id
+            && input.capability() == output.capability()
+            && input.commitment == output.commitment
+    });
+    carried_on(&|input, output| {
+        input.id == output.id
+            && input.capability() == Capability::Mutable as u8
+            && !output.is_minting_nft()
+    });
+    carried_on(&|input, output| {
+        input.id == output.id && input.is_minting_nft() && output.is_minting_nft()
+    });
+    input_nfts.sort_by_key(|(i, _)| *i);
+    input_nfts
+}
+
+fn check_nft_burns(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, nft) in burned_nfts(tx) {
+        let downgraded = tx
+            .output
+            .iter()
+            .filter_map(|txout| txout.token.as_ref())
+            .any(|token| token.id == nft.id && token.has_nft());
+        diagnostics.push(Diagnostic::new(
+            Location::Input(i),
+            Severity::Warning,
+            match nft.is_minting_nft() && downgraded {
+                true => "Minting capability is burned, no output keeps a minting NFT",
+                false => "NFT is burned, no output carries it on",
+            },
+        ));
+    }
+}
+
+/// Value of the dust outputs SLP wallets put tokens on.
+const SLP_DUST: u64 = 546;
+
+/// SLP tokens are not enforced by consensus. Their balances are tracked by indexers off the SLP
+/// OP_RETURN in output 0, and spending an SLP carrying UTXO without a valid SLP OP_RETURN burns
+/// the tokens. Which UTXOs carry them can't be told from the transaction, so inputs of the SLP
+/// dust value only get a note, most are plain coins.
+fn check_slp(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    let mut has_slp_op_return = false;
+    for (i, txout) in tx.output.iter().enumerate() {
+        if !is_slp_op_return(&txout.script_pubkey) {
+            continue;
+        }
+        has_slp_op_return = true;
+        diagnostics.push(match i {
+            0 => Diagnostic::new(Location::Output(i), Severity::Info, "SLP OP_RETURN"),
+            _ => Diagnostic::new(
+                Location::Output(i),
+                Severity::Warning,
+                "SLP OP_RETURN is only recognized as output 0",
+            ),
+        });
+    }
+    if has_slp_op_return {
+        return;
+    }
+    for (i, txin) in tx.input.iter().enumerate() {
+        if let MaybeUnsignedTxIn::Unsigned(txin) = txin {
+            if txin.value == SLP_DUST && txin.token.is_none() {
+                diagnostics.push(Diagnostic::new(
+                    Location::Input(i),
+                    Severity::Info,
+                    "If this 546 sat input carries SLP tokens, they are burned without an SLP \
+                     OP_RETURN",
+                ));
+            }
+        }
+    }
+}
+
+/// Estimate the May 2025 VM limits budgets of signed inputs. Only the unlocking bytecode and a
+/// P2SH redeem script can be seen here, so the locking script of non-P2SH inputs is not counted.
+fn check_vm_limits(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, txin) in tx.input.iter().enumerate() {
+        let Some(script_sig) = txin.script_sig() else {
+            continue;
+        };
+        let redeem_script = vm_limits::p2sh_redeem_script(script_sig);
+        let budget = vm_limits::estimate(script_sig, redeem_script.as_ref());
+        if budget.exceeds_operation_cost() {
+            diagnostics.push(Diagnostic::new(
+                Location::Input(i),
+                Severity::Error,
+                format!(
+                    "Estimated operation cost {} exceeds the limit of {}",
+                    budget.operation_cost, budget.operation_cost_limit
+                ),
+            ));
+        }
+        if budget.exceeds_hashing() {
+            diagnostics.push(Diagnostic::new(
+                Location::Input(i),
+                Severity::Error,
+                format!(
+                    "Estimated {} hash digest iterations exceed the limit of {}",
+                    budget.hash_digest_iterations, budget.hash_digest_iterations_limit
+                ),
+            ));
+        } else if budget.exceeds_hashing_standard() {
+            diagnostics.push(Diagnostic::new(
+                Location::Input(i),
+                Severity::Warning,
+                format!(
+                    "Estimated {} hash digest iterations exceed the standard limit of {}",
+                    budget.hash_digest_iterations, budget.hash_digest_iterations_limit_standard
+                ),
+            ));
+        }
+    }
+}
+
+fn check_locktime(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    if tx.lock_time.0 != 0
+        && !tx.input.is_empty()
+        && tx.input.iter().all(|t| t.sequence() == Sequence::MAX)
+    {
+        diagnostics.push(Diagnostic::new(
+            Location::Transaction,
+            Severity::Warning,
+            "Locktime is ignored because every input has sequence number 0xffffffff",
+        ));
+    }
+    if tx.version < 2 {
+        for (i, txin) in tx.input.iter().enumerate() {
+            let sequence = txin.sequence();
+            if sequence.is_relative_lock_time() && sequence.0 & 0xffff != 0 {
+                diagnostics.push(Diagnostic::new(
+                    Location::Input(i),
+                    Severity::Warning,
+                    "Relative locktime in sequence number is ignored with TX version 1",
+                ));
+            }
+        }
+    }
+}
+
+fn check_values(tx: &PartiallySignedTransaction, diagnostics: &mut Vec<Diagnostic>) {
+    let mut total = Some(0u64);
+    for (i, txout) in tx.output.iter().enumerate() {
+        if txout.value > MAX_MONEY {
+            diagnostics.push(Diagnostic::new(
+                Location::Output(i),
+                Severity::Error,
+                "Value exceeds 21 million BCH",
+            ));
+        }
+        total = total.and_then(|t| t.checked_add(txout.value));
+    }
+    if total.is_none_or(|t| t > MAX_MONEY) {
+        diagnostics.push(Diagnostic::new(
+            Location::Transaction,
+            Severity::Error,
+            "Total output value exceeds 21 million BCH",
+        ));
+    }
+
+    // The amounts of a category are summed as i64 by the nodes, reported once where it overflows
+    let mut ft_totals = HashMap::<TokenID, Option<i64>>::new();
+    for (i, txout) in tx.output.iter().enumerate() {
+        let Some(token) = &txout.token else {
+            continue;
+        };
+        let total = ft_totals.entry(token.id).or_insert(Some(0));
+        let Some(sum) = *total else {
+            continue;
+        };
+        *total = sum.checked_add(token.amount);
+        if total.is_none() {
+            diagnostics.push(Diagnostic::new(
+                Location::Output(i),
+                Severity::Error,
+                "Total fungible token amount of the category exceeds the maximum",
+            ));
+        }
+    }
+
+    let mut total = Some(0u64);
+    for (i, txin) in tx.input.iter().enumerate() {
+        let MaybeUnsignedTxIn::Unsigned(txin) = txin else {
+            continue;
+        };
+        if txin.value > MAX_MONEY {
+            diagnostics.push(Diagnostic::new(
+                Location::Input(i),
+                Severity::Error,
+                "Value exceeds 21 million BCH",
+            ));
+        }
+        total = total.and_then(|t| t.checked_add(txin.value));
+    }
+    if total.is_none_or(|t| t > MAX_MONEY) {
+        diagnostics.push(Diagnostic::new(
+            Location::Transaction,
+            Severity::Error,
+            "Total input value exceeds 21 million BCH",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{
+        blockdata::{opcodes::all::OP_RETURN, script::Builder},
+        hashes::hex::FromHex,
+        PackedLockTime, Script, Sequence, TokenID, TxOut, Txid,
+    };
+
+    use super::{
+        burned_nfts, check_script, check_transaction, Location, ScriptRole, Severity, MAX_PUSH_SIZE,
+    };
+    use crate::partially_signed::MaybeUnsignedTxIn;
+    use crate::test_util::{
+        nft_output, output, signed_input, token_output, tx, unsigned_input, TXID,
+    };
+
+    #[test]
+    fn test_token_genesis() {
+        let category = TokenID::from_hex(TXID).unwrap();
+        let diagnostics =
+            check_transaction(&tx(vec![signed_input(0)], vec![token_output(category)]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Output(0));
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+
+        let diagnostics =
+            check_transaction(&tx(vec![signed_input(1)], vec![token_output(category)]));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_token_category() {
+        let category = TokenID::from_hex(TXID).unwrap();
+        let other_txid = Txid::from_hex(&"11".repeat(32)).unwrap();
+        let diagnostics = check_transaction(&tx(
+            vec![unsigned_input(other_txid, 1, None)],
+            vec![token_output(category)],
+        ));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        let diagnostics = check_transaction(&tx(
+            vec![unsigned_input(other_txid, 1, token_output(category).token)],
+            vec![token_output(category)],
+        ));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_nft_capabilities() {
+        let category = TokenID::from_hex(TXID).unwrap();
+        let other_txid = Txid::from_hex(&"11".repeat(32)).unwrap();
+        let check = |input_capability, outputs: Vec<TxOut>| {
+            check_transaction(&tx(
+                vec![unsigned_input(
+                    other_txid,
+                    1,
+                    nft_output(category, input_capability, b"a").token,
+                )],
+                outputs,
+            ))
+        };
+
+        // Immutable NFT passed through unchanged
+        assert!(check(0, vec![nft_output(category, 0, b"a")]).is_empty());
+        // Immutable NFT altered, which also burns the original
+        let diagnostics = check(0, vec![nft_output(category, 0, b"b")]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].location, Location::Output(0));
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].location, Location::Input(0));
+        // Mutable NFT altered once, but not twice
+        assert!(check(1, vec![nft_output(category, 0, b"b")]).is_empty());
+        let diagnostics = check(
+            1,
+            vec![nft_output(category, 0, b"b"), nft_output(category, 0, b"c")],
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Output(1));
+        // Minting NFT mints anything
+        assert!(check(
+            2,
+            vec![
+                nft_output(category, 2, b""),
+                nft_output(category, 1, b"b"),
+                nft_output(category, 0, b"c")
+            ]
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_nft_burns() {
+        let category = TokenID::from_hex(TXID).unwrap();
+        let other_txid = Txid::from_hex(&"11".repeat(32)).unwrap();
+        let burns = |input_capability, outputs: Vec<TxOut>| {
+            let tx = tx(
+                vec![
+                    unsigned_input(other_txid, 1, None),
+                    unsigned_input(
+                        other_txid,
+                        1,
+                        nft_output(category, input_capability, b"a").token,
+                    ),
+                ],
+                outputs,
+            );
+            burned_nfts(&tx)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(burns(0, vec![nft_output(category, 0, b"a")]).is_empty());
+        assert_eq!(burns(0, vec![token_output(category)]), vec![1]);
+        assert_eq!(burns(0, vec![nft_output(category, 0, b"b")]), vec![1]);
+        // Mutable and minting NFTs may change
+        assert!(burns(1, vec![nft_output(category, 0, b"b")]).is_empty());
+        assert_eq!(burns(1, vec![nft_output(category, 2, b"a")]), vec![1]);
+        assert!(burns(2, vec![nft_output(category, 2, b"b")]).is_empty());
+        // A minting NFT spent only into NFTs without the capability loses it
+        assert_eq!(burns(2, vec![nft_output(category, 1, b"b")]), vec![1]);
+        assert_eq!(
+            burns(
+                2,
+                vec![nft_output(category, 0, b"b"), nft_output(category, 0, b"c")]
+            ),
+            vec![1]
+        );
+        assert!(burns(
+            2,
+            vec![nft_output(category, 0, b"b"), nft_output(category, 2, b"a")]
+        )
+        .is_empty());
+
+        let diagnostics = check_transaction(&tx(
+            vec![unsigned_input(
+                other_txid,
+                1,
+                nft_output(category, 0, b"a").token,
+            )],
+            vec![],
+        ));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Input(0));
+        let diagnostics = check_transaction(&tx(
+            vec![unsigned_input(
+                other_txid,
+                1,
+                nft_output(category, 2, b"a").token,
+            )],
+            vec![nft_output(category, 0, b"a")],
+        ));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.message.starts_with("Minting capability is burned")));
+
+        // Unknown with a signed input
+        let tx = tx(
+            vec![
+                signed_input(1),
+                unsigned_input(other_txid, 1, nft_output(category, 0, b"a").token),
+            ],
+            vec![],
+        );
+        assert!(burned_nfts(&tx).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_outpoints() {
+        let diagnostics = check_transaction(&tx(
+            vec![signed_input(1), signed_input(2), signed_input(1)],
+            vec![],
+        ));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Input(2));
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_locktime() {
+        let mut t = tx(vec![signed_input(1)], vec![]);
+        t.lock_time = PackedLockTime(800_000);
+        let diagnostics = check_transaction(&t);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Transaction);
+
+        let mut t = tx(vec![signed_input(1)], vec![]);
+        t.version = 1;
+        if let MaybeUnsignedTxIn::Signed(txin) = &mut t.input[0] {
+            txin.sequence = Sequence(10);
+        }
+        let diagnostics = check_transaction(&t);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Input(0));
+    }
+
+    #[test]
+    fn test_slp() {
+        let other_txid = Txid::from_hex(&"11".repeat(32)).unwrap();
+        let mut input = unsigned_input(other_txid, 0, None);
+        if let MaybeUnsignedTxIn::Unsigned(txin) = &mut input {
+            txin.value = 546;
+        }
+        // A plain coin of the SLP dust value only gets a note
+        let diagnostics = check_transaction(&tx(vec![input.clone()], vec![output(None)]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Input(0));
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+
+        let mut slp = output(None);
+        slp.script_pubkey = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(b"SLP\0")
+            .into_script();
+        let diagnostics = check_transaction(&tx(vec![input.clone()], vec![slp.clone()]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Output(0));
+        let diagnostics = check_transaction(&tx(vec![input], vec![output(None), slp]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, Location::Output(1));
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_values() {
+        let category = TokenID::from_hex(TXID).unwrap();
+        let mut output = token_output(category);
+        output.token = None;
+        output.value = u64::MAX / 2 + 1;
+        let diagnostics = check_transaction(&tx(vec![], vec![output.clone(), output]));
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+
+        let mut output = token_output(category);
+        output.token.as_mut().unwrap().amount = i64::MAX;
+        let other = TokenID::from_hex(&"11".repeat(32)).unwrap();
+        let diagnostics = check_transaction(&tx(
+            vec![signed_input(0)],
+            vec![output.clone(), token_output(other), output.clone(), output],
+        ));
+        let errors: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location, Location::Output(2));
+    }
+
+    #[test]
+    fn test_check_script() {
+        let script = |hex: &str| Script::from(Vec::from_hex(hex).unwrap());
+        let p2pkh = script("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac");
+        assert!(check_script(&p2pkh, ScriptRole::Locking).is_empty());
+        assert_eq!(
+            check_script(&p2pkh, ScriptRole::Unlocking),
+            ["Unlocking scripts may only push data"]
+        );
+        assert!(check_script(&script("00516001ff"), ScriptRole::Unlocking).is_empty());
+        assert_eq!(
+            check_script(&script("4c0101"), ScriptRole::Unlocking),
+            ["Non-minimal push at byte 0 is nonstandard, push it with OP_1"]
+        );
+        assert_eq!(
+            check_script(&script("4c02ab"), ScriptRole::Locking),
+            ["Script ends in the middle of a push"]
+        );
+
+        let large = Builder::new()
+            .push_slice(&[0; MAX_PUSH_SIZE + 1])
+            .into_script();
+        assert_eq!(
+            check_script(&large, ScriptRole::Unlocking),
+            [
+                "Script of 10004 bytes exceeds the limit of 10000",
+                "Push of 10001 bytes exceeds the limit of 10000"
+            ]
+        );
+        let at_limit = Builder::new().push_slice(&[0; MAX_PUSH_SIZE]).into_script();
+        assert_eq!(check_script(&at_limit, ScriptRole::Unlocking).len(), 1);
+        // Never run
+        let op_return = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(&[0; MAX_PUSH_SIZE + 1])
+            .into_script();
+        assert!(check_script(&op_return, ScriptRole::Locking).is_empty());
+    }
+}
@@ -0,0 +1,766 @@
+//! Checking that signed inputs satisfy the outputs they spend, before broadcasting.
+//!
+//! There is no script interpreter here, so only the standard templates are checked: P2PKH, P2PK
+//! and bare multisig, spent directly or through a P2SH20 or P2SH32 redeem script, with ECDSA or
+//! Schnorr signatures of any sighash type. These are checked in full, other scripts are reported
+//! as unchecked rather than guessed at.
+
+use bitcoincash::{
+    blockdata::{
+        opcodes::all::{OP_CHECKMULTISIG, OP_PUSHNUM_1, OP_PUSHNUM_16, OP_PUSHNUM_NEG1},
+        script::Instruction,
+    },
+    hashes::{hash160, sha256, sha256d, Hash, HashEngine},
+    secp256k1::{ecdsa, Message, PublicKey, Scalar, Secp256k1, SecretKey, Verification},
+    Script, Transaction, TxOut,
+};
+
+use crate::signing::{
+    digest, SIGHASH_ALL, SIGHASH_ANYONECANPAY, SIGHASH_FORKID, SIGHASH_SINGLE, SIGHASH_UTXOS,
+};
+use crate::util::is_p2sh32;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    /// The input spends its output, described by the template it matched.
+    Valid(String),
+    Invalid(String),
+    /// The input couldn't be checked, for the reason given.
+    Unchecked(String),
+}
+
+/// Check input `input_index` of `tx` against the output it spends, with `utxos` the outputs spent
+/// by every input of `tx`, where known.
+pub fn verify_input<C: Verification>(
+    secp: &Secp256k1<C>,
+    tx: &Transaction,
+    input_index: usize,
+    utxos: &[Option<TxOut>],
+) -> Verdict {
+    let Some(Some(utxo)) = utxos.get(input_index) else {
+        return Verdict::Unchecked("The output spent isn't known".to_string());
+    };
+    let Some(mut pushes) = push_data(&tx.input[input_index].script_sig) else {
+        return Verdict::Invalid("The unlocking script doesn't only push data".to_string());
+    };
+    let script_pubkey = &utxo.script_pubkey;
+    let (script_code, wrapper) = if script_pubkey.is_p2sh() || is_p2sh32(script_pubkey) {
+        let Some(redeem_script) = pushes.pop() else {
+            return Verdict::Invalid(
+                "The unlocking script doesn't push a redeem script".to_string(),
+            );
+        };
+        let (hash, wrapper) = match script_pubkey.is_p2sh() {
+            true => (hash160::Hash::hash(&redeem_script).to_vec(), "P2SH20"),
+            false => (sha256d::Hash::hash(&redeem_script).to_vec(), "P2SH32"),
+        };
+        if script_pubkey.as_bytes()[2..2 + hash.len()] != hash[..] {
+            return Verdict::Invalid(
+                "The redeem script doesn't match the hash paid to".to_string(),
+            );
+        }
+        (Script::from(redeem_script), Some(wrapper))
+    } else {
+        (script_pubkey.clone(), None)
+    };
+
+    let checker = SignatureChecker {
+        secp,
+        tx,
+        input_index,
+        script_code: &script_code,
+        utxos,
+    };
+    let result = if script_code.is_p2pkh() {
+        match &pushes[..] {
+            [signature, pubkey] if hash160::Hash::hash(pubkey)[..] == script_code[3..23] => checker
+                .check(signature, pubkey)
+                .map(|kind| format!("P2PKH, {kind} signature")),
+            [_, _] => Err("The public key doesn't match the hash paid to".to_string()),
+            _ => Err("P2PKH takes a signature and a public key".to_string()),
+        }
+    } else if script_code.is_p2pk() {
+        let pubkey = &script_code[1..script_code.len() - 1];
+        match &pushes[..] {
+            [signature] => checker
+                .check(signature, pubkey)
+                .map(|kind| format!("P2PK, {kind} signature")),
+            _ => Err("P2PK takes a signature".to_string()),
+        }
+    } else if let Some((threshold, pubkeys)) = parse_multisig(&script_code) {
+        checker
+            .check_multisig(&pushes, threshold, &pubkeys)
+            .map(|kind| {
+                format!(
+                    "{threshold}-of-{} multisig, {kind} signatures",
+                    pubkeys.len()
+                )
+            })
+    } else {
+        return Verdict::Unchecked(format!(
+            "Only P2PKH, P2PK and multisig are checked, not {}",
+            wrapper.map_or("this locking script", |_| "this redeem script")
+        ));
+    };
+    match result {
+        Ok(description) => Verdict::Valid(match wrapper {
+            Some(wrapper) => format!("{wrapper} {description}"),
+            None => description,
+        }),
+        Err(e) => Verdict::Invalid(e),
+    }
+}
+
+/// The data pushed by `script`, `None` if it has other opcodes.
+fn push_data(script: &Script) -> Option<Vec<Vec<u8>>> {
+    script
+        .instructions()
+        .map(|instruction| match instruction.ok()? {
+            Instruction::PushBytes(data) => Some(data.to_vec()),
+            Instruction::Op(op) if op == OP_PUSHNUM_NEG1 => Some(vec![0x81]),
+            Instruction::Op(op)
+                if (OP_PUSHNUM_1.to_u8()..=OP_PUSHNUM_16.to_u8()).contains(&op.to_u8()) =>
+            {
+                Some(vec![op.to_u8() - OP_PUSHNUM_1.to_u8() + 1])
+            }
+            Instruction::Op(_) => None,
+        })
+        .collect()
+}
+
+/// The threshold and public keys of a bare multisig script,
+/// `OP_m <pubkey>... OP_n OP_CHECKMULTISIG`.
+fn parse_multisig(script: &Script) -> Option<(usize, Vec<Vec<u8>>)> {
+    let number = |instruction: &Instruction<'_>| match instruction {
+        Instruction::Op(op)
+            if (OP_PUSHNUM_1.to_u8()..=OP_PUSHNUM_16.to_u8()).contains(&op.to_u8()) =>
+        {
+            Some(usize::from(op.to_u8() - OP_PUSHNUM_1.to_u8() + 1))
+        }
+        _ => None,
+    };
+    let instructions = script.instructions().collect::<Result<Vec<_>, _>>().ok()?;
+    let [first, pubkeys @ .., count, last] = &instructions[..] else {
+        return None;
+    };
+    if *last != Instruction::Op(OP_CHECKMULTISIG) {
+        return None;
+    }
+    let threshold = number(first)?;
+    let pubkeys = pubkeys
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::PushBytes(data) => Some(data.to_vec()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (number(count)? == pubkeys.len() && threshold <= pubkeys.len()).then_some((threshold, pubkeys))
+}
+
+struct SignatureChecker<'a, C: Verification> {
+    secp: &'a Secp256k1<C>,
+    tx: &'a Transaction,
+    input_index: usize,
+    script_code: &'a Script,
+    utxos: &'a [Option<TxOut>],
+}
+
+impl<C: Verification> SignatureChecker<'_, C> {
+    /// Check the transaction signature `signature`, with its sighash type, against `pubkey`,
+    /// returning whether it's ECDSA or Schnorr.
+    fn check(&self, signature: &[u8], pubkey: &[u8]) -> Result<&'static str, String> {
+        let Some((&sighash_type, signature)) = signature.split_last() else {
+            return Err("The signature is empty".to_string());
+        };
+        let message = self.message(sighash_type.into())?;
+        let pubkey = PublicKey::from_slice(pubkey).map_err(|_| "Invalid public key".to_string())?;
+        if signature.len() == 64 {
+            match verify_schnorr(self.secp, signature, &pubkey, &message) {
+                true => Ok("Schnorr"),
+                false => Err("The Schnorr signature doesn't verify".to_string()),
+            }
+        } else {
+            let signature = ecdsa::Signature::from_der(signature)
+                .map_err(|_| "The signature isn't strictly DER encoded".to_string())?;
+            match self.secp.verify_ecdsa(&message, &signature, &pubkey) {
+                Ok(()) => Ok("ECDSA"),
+                Err(_) => Err("The ECDSA signature doesn't verify".to_string()),
+            }
+        }
+    }
+
+    /// Check the pushes of a multisig unlocking script, ECDSA signatures in the order of their keys
+    /// after an empty dummy, or Schnorr signatures after a bitfield of the keys they're for.
+    fn check_multisig(
+        &self,
+        pushes: &[Vec<u8>],
+        threshold: usize,
+        pubkeys: &[Vec<u8>],
+    ) -> Result<&'static str, String> {
+        let Some((dummy, signatures)) = pushes.split_first() else {
+            return Err("Multisig takes a dummy element and signatures".to_string());
+        };
+        if signatures.len() != threshold {
+            return Err(format!(
+                "Multisig takes {threshold} signatures, there are {}",
+                signatures.len()
+            ));
+        }
+        if dummy.is_empty() {
+            let mut pubkeys = pubkeys.iter();
+            for (i, signature) in signatures.iter().enumerate() {
+                if signature.len() == 65 {
+                    return Err("Schnorr multisig needs a bitfield of the keys signed".to_string());
+                }
+                // Keys are tried in order, each signature checked against the keys after the last
+                // one that matched
+                if !pubkeys.any(|pubkey| self.check(signature, pubkey).is_ok()) {
+                    return Err(format!("Signature {} matches none of the keys left", i + 1));
+                }
+            }
+            return Ok("ECDSA");
+        }
+        if dummy.len() != pubkeys.len().div_ceil(8) {
+            return Err("The bitfield of keys signed has the wrong size".to_string());
+        }
+        let bitfield = dummy
+            .iter()
+            .rev()
+            .fold(0u32, |bits, &byte| (bits << 8) | u32::from(byte));
+        if bitfield >> pubkeys.len() != 0 || bitfield.count_ones() as usize != threshold {
+            return Err(format!(
+                "The bitfield of keys signed doesn't set {threshold} of the {} keys",
+                pubkeys.len()
+            ));
+        }
+        let signed = (0..pubkeys.len()).filter(|i| bitfield & (1 << i) != 0);
+        for (i, (signature, key)) in signatures.iter().zip(signed).enumerate() {
+            if signature.len() != 65 {
+                return Err(format!("Signature {} isn't a Schnorr signature", i + 1));
+            }
+            self.check(signature, &pubkeys[key])
+                .map_err(|e| format!("Signature {}: {e}", i + 1))?;
+        }
+        Ok("Schnorr")
+    }
+
+    /// The message signed with `sighash_type`.
+    fn message(&self, sighash_type: u32) -> Result<Message, String> {
+        let base_type = sighash_type & 0x1f;
+        if sighash_type & SIGHASH_FORKID == 0 {
+            return Err("The signature is missing SIGHASH_FORKID".to_string());
+        }
+        if !(SIGHASH_ALL..=SIGHASH_SINGLE).contains(&base_type)
+            || sighash_type & SIGHASH_UTXOS != 0 && sighash_type & SIGHASH_ANYONECANPAY != 0
+        {
+            return Err(format!("Undefined sighash type 0x{sighash_type:02x}"));
+        }
+        let utxo = self.utxos[self.input_index]
+            .as_ref()
+            .expect("checked before");
+        let utxos = match sighash_type & SIGHASH_UTXOS {
+            0 => vec![],
+            _ => self
+                .utxos
+                .iter()
+                .cloned()
+                .collect::<Option<Vec<_>>>()
+                .ok_or("SIGHASH_UTXOS signs every output spent, and some aren't known")?,
+        };
+        let sighash = digest(
+            self.tx,
+            self.input_index,
+            self.script_code,
+            utxo.value,
+            utxo.token.as_ref(),
+            &utxos,
+            sighash_type,
+        );
+        Ok(Message::from_slice(&sighash[..]).expect("sighashes are 32 bytes"))
+    }
+}
+
+/// The generator point G of secp256k1, compressed.
+const GENERATOR: [u8; 33] = [
+    0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87, 0x0b,
+    0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16, 0xf8, 0x17,
+    0x98,
+];
+
+/// Verify a Bitcoin Cash Schnorr signature `(r, s)`: whether `R = sG - eP` has `r` as its x
+/// coordinate and a y coordinate that's a quadratic residue, with `e = sha256(r || P || m)`.
+fn verify_schnorr<C: Verification>(
+    secp: &Secp256k1<C>,
+    signature: &[u8],
+    pubkey: &PublicKey,
+    message: &Message,
+) -> bool {
+    let (r, s) = signature.split_at(32);
+    let Ok(s) = SecretKey::from_slice(s) else {
+        return false;
+    };
+    let mut engine = sha256::Hash::engine();
+    engine.input(r);
+    engine.input(&pubkey.serialize());
+    engine.input(message.as_ref());
+    let e = sha256::Hash::from_engine(engine);
+    let Ok(minus_e) = SecretKey::from_slice(&e[..]).map(SecretKey::negate) else {
+        return false;
+    };
+    let Ok(minus_e_p) = pubkey.mul_tweak(secp, &Scalar::from(minus_e)) else {
+        return false;
+    };
+    let generator = PublicKey::from_slice(&GENERATOR).expect("the generator is a point");
+    let Ok(s_g) = generator.mul_tweak(secp, &Scalar::from(s)) else {
+        return false;
+    };
+    let Ok(point) = s_g.combine(&minus_e_p) else {
+        return false;
+    };
+    let point = point.serialize_uncompressed();
+    point[1..33] == *r && field::is_quadratic_residue(point[33..].try_into().unwrap())
+}
+
+/// Just enough arithmetic modulo the secp256k1 field prime for [`verify_schnorr`], on 256-bit
+/// numbers as little-endian 64-bit limbs.
+mod field {
+    const P: [u64; 4] = [
+        0xffff_fffe_ffff_fc2f,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+    ];
+    /// 2^256 - P, what 2^256 reduces to.
+    const C: u64 = 0x1_0000_03d1;
+
+    /// Whether the big-endian field element `y` is a nonzero square, by Euler's criterion.
+    pub fn is_quadratic_residue(y: [u8; 32]) -> bool {
+        let y = from_be_bytes(y);
+        // (P - 1) / 2
+        let mut exponent = P;
+        exponent[0] -= 1;
+        for i in 0..4 {
+            exponent[i] = exponent[i] >> 1 | exponent.get(i + 1).map_or(0, |next| next << 63);
+        }
+        let mut result = [1, 0, 0, 0];
+        for i in (0..256).rev() {
+            result = mul(result, result);
+            if exponent[i / 64] >> (i % 64) & 1 == 1 {
+                result = mul(result, y);
+            }
+        }
+        result == [1, 0, 0, 0]
+    }
+
+    fn from_be_bytes(bytes: [u8; 32]) -> [u64; 4] {
+        let mut limbs = [0; 4];
+        for (i, chunk) in bytes.rchunks(8).enumerate() {
+            limbs[i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        limbs
+    }
+
+    /// `a * b` modulo P, fully reduced.
+    fn mul(a: [u64; 4], b: [u64; 4]) -> [u64; 4] {
+        let mut product = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = u128::from(a[i]) * u128::from(b[j]) + u128::from(product[i + j]) + carry;
+                product[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            product[i + 4] = carry as u64;
+        }
+        // high * 2^256 + low is congruent to high * C + low
+        let mut result = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let t = u128::from(product[i]) + u128::from(product[i + 4]) * u128::from(C) + carry;
+            result[i] = t as u64;
+            carry = t >> 64;
+        }
+        while carry != 0 {
+            let mut t = carry * u128::from(C);
+            for limb in &mut result {
+                t += u128::from(*limb);
+                *limb = t as u64;
+                t >>= 64;
+                if t == 0 {
+                    break;
+                }
+            }
+            carry = t;
+        }
+        if !less_than(result, P) {
+            let mut borrow = 0;
+            for i in 0..4 {
+                let (d, b1) = result[i].overflowing_sub(P[i]);
+                let (d, b2) = d.overflowing_sub(borrow);
+                result[i] = d;
+                borrow = u64::from(b1 || b2);
+            }
+        }
+        result
+    }
+
+    fn less_than(a: [u64; 4], b: [u64; 4]) -> bool {
+        a.iter().rev().lt(b.iter().rev())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::is_quadratic_residue;
+
+        fn element(n: u64) -> [u8; 32] {
+            let mut bytes = [0; 32];
+            bytes[24..].copy_from_slice(&n.to_be_bytes());
+            bytes
+        }
+
+        #[test]
+        fn test_is_quadratic_residue() {
+            // P is 7 modulo 8, so 2 is a square and -1 isn't
+            assert!(is_quadratic_residue(element(4)));
+            assert!(is_quadratic_residue(element(2)));
+            let mut minus_one = [0xff; 32];
+            minus_one[27] = 0xfe;
+            minus_one[30] = 0xfc;
+            minus_one[31] = 0x2e;
+            assert!(!is_quadratic_residue(minus_one));
+            let mut minus_two = minus_one;
+            minus_two[31] = 0x2d;
+            assert!(!is_quadratic_residue(minus_two));
+            assert!(!is_quadratic_residue(element(0)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{
+        blockdata::{opcodes::all::OP_CHECKMULTISIG, script::Builder},
+        consensus::deserialize,
+        hashes::{hex::FromHex, sha256, Hash, HashEngine},
+        secp256k1::{Message, PublicKey, Scalar, Secp256k1, SecretKey, Signing},
+        PrivateKey, Script, Transaction, TxOut,
+    };
+
+    use super::{field, verify_input, verify_schnorr, Verdict};
+    use crate::signing::{digest, sign_p2pkh, SIGHASH_ALL_FORKID, SIGHASH_UTXOS};
+    use crate::util::to_p2sh32;
+
+    fn tx() -> Transaction {
+        deserialize(&Vec::from_hex("0100000001fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000ffffffff01202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac00000000").unwrap()).unwrap()
+    }
+
+    fn key(n: u8) -> SecretKey {
+        SecretKey::from_slice(&[n; 32]).unwrap()
+    }
+
+    /// A Bitcoin Cash Schnorr signature of `message`, with the nonce derived from the key and it.
+    fn sign_schnorr<C: Signing>(secp: &Secp256k1<C>, key: &SecretKey, message: &[u8]) -> Vec<u8> {
+        let mut nonce = SecretKey::from_slice(
+            &sha256::Hash::hash(&[&key.secret_bytes()[..], message].concat())[..],
+        )
+        .unwrap();
+        let mut point = PublicKey::from_secret_key(secp, &nonce);
+        if !field::is_quadratic_residue(point.serialize_uncompressed()[33..].try_into().unwrap()) {
+            nonce = nonce.negate();
+            point = PublicKey::from_secret_key(secp, &nonce);
+        }
+        let mut engine = sha256::Hash::engine();
+        engine.input(&point.serialize()[1..]);
+        engine.input(&PublicKey::from_secret_key(secp, key).serialize());
+        engine.input(message);
+        let e = SecretKey::from_slice(&sha256::Hash::from_engine(engine)[..]).unwrap();
+        let s = e
+            .mul_tweak(&Scalar::from(*key))
+            .unwrap()
+            .add_tweak(&Scalar::from(nonce))
+            .unwrap();
+        [&point.serialize()[1..], &s.secret_bytes()[..]].concat()
+    }
+
+    /// A signature of input 0 of `tx` spending `utxos[0]` with `script_code`, Schnorr or ECDSA.
+    fn sign<C: Signing>(
+        secp: &Secp256k1<C>,
+        tx: &Transaction,
+        script_code: &Script,
+        utxos: &[TxOut],
+        key: &SecretKey,
+        schnorr: bool,
+        sighash_type: u32,
+    ) -> Vec<u8> {
+        let sighash = digest(
+            tx,
+            0,
+            script_code,
+            utxos[0].value,
+            None,
+            utxos,
+            sighash_type,
+        );
+        let mut signature = match schnorr {
+            true => sign_schnorr(secp, key, &sighash[..]),
+            false => secp
+                .sign_ecdsa(&Message::from_slice(&sighash[..]).unwrap(), key)
+                .serialize_der()
+                .to_vec(),
+        };
+        signature.push(sighash_type as u8);
+        signature
+    }
+
+    #[test]
+    fn test_verify_p2pkh() {
+        let secp = Secp256k1::new();
+        let key = PrivateKey::new(key(1), bitcoincash::Network::Bitcoin);
+        let script_pubkey = Script::new_p2pkh(&key.public_key(&secp).pubkey_hash());
+        let utxo = TxOut {
+            value: 1000,
+            script_pubkey: script_pubkey.clone(),
+            token: None,
+        };
+        let mut tx = tx();
+        sign_p2pkh(&secp, &mut tx, 0, &key, 1000, None);
+        let utxos = [Some(utxo.clone())];
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Valid("P2PKH, ECDSA signature".to_string())
+        );
+        assert!(matches!(
+            verify_input(&secp, &tx, 0, &[None]),
+            Verdict::Unchecked(_)
+        ));
+        let wrong_value = [Some(TxOut {
+            value: 999,
+            ..utxo.clone()
+        })];
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &wrong_value),
+            Verdict::Invalid("The ECDSA signature doesn't verify".to_string())
+        );
+
+        let signature = sign(
+            &secp,
+            &tx,
+            &script_pubkey,
+            &[utxo],
+            &key.inner,
+            true,
+            SIGHASH_ALL_FORKID,
+        );
+        tx.input[0].script_sig = Builder::new()
+            .push_slice(&signature)
+            .push_key(&key.public_key(&secp))
+            .into_script();
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Valid("P2PKH, Schnorr signature".to_string())
+        );
+        tx.output[0].value += 1;
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Invalid("The Schnorr signature doesn't verify".to_string())
+        );
+
+        // The test vectors of the Bitcoin Cash Schnorr specification, as public key, message,
+        // signature and whether it's valid
+        let vectors = [
+            (
+                "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "787A848E71043D280C50470E8E1532B2DD5D20EE912A45DBDD2BD1DFBF187EF67031A98831859DC34DFFEEDDA86831842CCD0079E1F92AF177F7F22CC1DCED05",
+                true,
+            ),
+            (
+                "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "2A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D1E51A22CCEC35599B8F266912281F8365FFC2D035A230434A1A64DC59F7013FD",
+                true,
+            ),
+            (
+                "03FAC2114C2FBB091527EB7C64ECB11F8021CB45E8E7809D3C0938E4B8C0E5F84B",
+                "5E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+                "00DA9B08172A9B6F0466A2DEFD817F2D7AB437E0D253CB5395A963866B3574BE00880371D01766935B92D2AB4CD5C8A2A5837EC57FED7660773A05F0DE142380",
+                true,
+            ),
+            (
+                "03DEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+                "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+                "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6302A8DC32E64E86A333F20EF56EAC9BA30B7246D6D25E22ADB8C6BE1AEB08D49D",
+                true,
+            ),
+            (
+                "031B84C5567B126440995D3ED5AABA0565D71E1834604819FF9C17F5E9D5DD078F",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "52818579ACA59767E3291D91B76B637BEF062083284992F2D95F564CA6CB4E3530B1DA849C8E8304ADC0CFE870660334B3CFC18E825EF1DB34CFAE3DFC5D8187",
+                true,
+            ),
+            (
+                "03FAC2114C2FBB091527EB7C64ECB11F8021CB45E8E7809D3C0938E4B8C0E5F84B",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "570DD4CA83D4E6317B8EE6BAE83467A1BF419D0767122DE409394414B05080DCE9EE5F237CBD108EABAE1E37759AE47F8E4203DA3532EB28DB860F33D62D49BD",
+                true,
+            ),
+            // R.y is not a quadratic residue
+            (
+                "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "2A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1DFA16AEE06609280A19B67A24E1977E4697712B5FD2943914ECD5F730901B4AB7",
+                false,
+            ),
+            // Negated message
+            (
+                "03FAC2114C2FBB091527EB7C64ECB11F8021CB45E8E7809D3C0938E4B8C0E5F84B",
+                "5E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+                "00DA9B08172A9B6F0466A2DEFD817F2D7AB437E0D253CB5395A963866B3574BED092F9D860F1776A1F7412AD8A1EB50DACCC222BC8C0E26B2056DF2F273EFDEC",
+                false,
+            ),
+            // Negated s
+            (
+                "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "787A848E71043D280C50470E8E1532B2DD5D20EE912A45DBDD2BD1DFBF187EF68FCE5677CE7A623CB20011225797CE7A8DE1DC6CCD4F754A47DA6C600E59543C",
+                false,
+            ),
+            // Negated public key
+            (
+                "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "2A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D1E51A22CCEC35599B8F266912281F8365FFC2D035A230434A1A64DC59F7013FD",
+                false,
+            ),
+        ];
+        for (pubkey, message, signature, valid) in vectors {
+            let pubkey = PublicKey::from_slice(&Vec::from_hex(pubkey).unwrap()).unwrap();
+            let message = Message::from_slice(&Vec::from_hex(message).unwrap()).unwrap();
+            let bytes = Vec::from_hex(signature).unwrap();
+            assert_eq!(
+                verify_schnorr(&secp, &bytes, &pubkey, &message),
+                valid,
+                "{signature}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_multisig() {
+        let secp = Secp256k1::new();
+        let keys = [key(1), key(2), key(3)];
+        let redeem_script = keys
+            .iter()
+            .fold(Builder::new().push_int(2), |builder, key| {
+                builder.push_slice(&PublicKey::from_secret_key(&secp, key).serialize())
+            })
+            .push_int(3)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script();
+        let utxo = TxOut {
+            value: 1000,
+            script_pubkey: redeem_script.to_p2sh(),
+            token: None,
+        };
+        let mut tx = tx();
+        let sighash_type = SIGHASH_ALL_FORKID | SIGHASH_UTXOS;
+        let sign_p2sh20 = |key, schnorr| {
+            sign(
+                &secp,
+                &tx,
+                &redeem_script,
+                std::slice::from_ref(&utxo),
+                key,
+                schnorr,
+                sighash_type,
+            )
+        };
+        let (ecdsa, schnorr) = (
+            (sign_p2sh20(&keys[0], false), sign_p2sh20(&keys[2], false)),
+            (sign_p2sh20(&keys[0], true), sign_p2sh20(&keys[2], true)),
+        );
+        let unlock = |dummy: &[u8], signatures: [&[u8]; 2]| {
+            Builder::new()
+                .push_slice(dummy)
+                .push_slice(signatures[0])
+                .push_slice(signatures[1])
+                .push_slice(redeem_script.as_bytes())
+                .into_script()
+        };
+        let utxos = [Some(utxo.clone())];
+
+        tx.input[0].script_sig = unlock(&[], [&ecdsa.0, &ecdsa.1]);
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Valid("P2SH20 2-of-3 multisig, ECDSA signatures".to_string())
+        );
+        tx.input[0].script_sig = unlock(&[], [&ecdsa.1, &ecdsa.0]);
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Invalid("Signature 2 matches none of the keys left".to_string())
+        );
+        tx.input[0].script_sig = unlock(&[0b101], [&schnorr.0, &schnorr.1]);
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Valid("P2SH20 2-of-3 multisig, Schnorr signatures".to_string())
+        );
+        tx.input[0].script_sig = unlock(&[0b011], [&schnorr.0, &schnorr.1]);
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Invalid("Signature 2: The Schnorr signature doesn't verify".to_string())
+        );
+        // SIGHASH_UTXOS signs the other outputs spent too
+        tx.input[0].script_sig = unlock(&[0b101], [&schnorr.0, &schnorr.1]);
+        tx.input.push(tx.input[0].clone());
+        assert!(matches!(
+            verify_input(&secp, &tx, 0, &[Some(utxo.clone()), None]),
+            Verdict::Invalid(e) if e.contains("SIGHASH_UTXOS")
+        ));
+
+        let p2sh32 = TxOut {
+            script_pubkey: to_p2sh32(&redeem_script),
+            ..utxo
+        };
+        let mut tx = self::tx();
+        // Signed with SIGHASH_UTXOS for the P2SH20 output
+        tx.input[0].script_sig = unlock(&[], [&ecdsa.0, &ecdsa.1]);
+        let utxos = [Some(p2sh32.clone())];
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Invalid("Signature 1 matches none of the keys left".to_string())
+        );
+        let signatures = [&keys[1], &keys[2]].map(|key| {
+            let utxos = [p2sh32.clone()];
+            sign(
+                &secp,
+                &tx,
+                &redeem_script,
+                &utxos,
+                key,
+                false,
+                SIGHASH_ALL_FORKID,
+            )
+        });
+        tx.input[0].script_sig = unlock(&[], [&signatures[0], &signatures[1]]);
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Valid("P2SH32 2-of-3 multisig, ECDSA signatures".to_string())
+        );
+        tx.input[0].script_sig = Builder::new().push_int(1).into_script();
+        assert_eq!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Invalid("The redeem script doesn't match the hash paid to".to_string())
+        );
+        let anyone = Builder::new().push_int(1).into_script();
+        tx.input[0].script_sig = Builder::new().push_slice(anyone.as_bytes()).into_script();
+        let utxos = [Some(TxOut {
+            value: 1000,
+            script_pubkey: to_p2sh32(&anyone),
+            token: None,
+        })];
+        assert!(matches!(
+            verify_input(&secp, &tx, 0, &utxos),
+            Verdict::Unchecked(_)
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+//! Static estimates for the VM limits introduced in the May 2025 upgrade.
+//!
+//! The real accounting happens during evaluation, which we cannot do here without knowing the
+//! locking script and the stack contents. Instead every instruction is assumed to be evaluated
+//! exactly once, with hashed messages assumed to be [`ASSUMED_MESSAGE_LENGTH`] bytes unless a push
+//! immediately precedes the hashing opcode.
+//!
+//! * [CHIP-2021-05 Targeted Virtual Machine Limits](https://github.com/bitjson/bch-vm-limits)
+
+use bitcoincash::blockdata::{
+    opcodes::{all::*, All},
+    script::{Instruction, Script},
+};
+
+/// Fixed part of the density control length, added to the length of the unlocking bytecode.
+const DENSITY_CONTROL_BASE_LENGTH: u64 = 41;
+const OPERATION_COST_BUDGET_PER_BYTE: u64 = 800;
+const BASE_INSTRUCTION_COST: u64 = 100;
+const SIGNATURE_CHECK_COST: u64 = 26_000;
+const HASH_DIGEST_ITERATION_COST_STANDARD: u64 = 192;
+/// Message length assumed for hashing opcodes whose input is not a literal push.
+pub const ASSUMED_MESSAGE_LENGTH: u64 = 32;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Budget {
+    pub operation_cost: u64,
+    pub operation_cost_limit: u64,
+    pub hash_digest_iterations: u64,
+    /// Limit under standardness rules.
+    pub hash_digest_iterations_limit_standard: u64,
+    /// Limit under consensus rules.
+    pub hash_digest_iterations_limit: u64,
+}
+
+impl Budget {
+    pub fn exceeds_operation_cost(&self) -> bool {
+        self.operation_cost > self.operation_cost_limit
+    }
+
+    pub fn exceeds_hashing_standard(&self) -> bool {
+        self.hash_digest_iterations > self.hash_digest_iterations_limit_standard
+    }
+
+    pub fn exceeds_hashing(&self) -> bool {
+        self.hash_digest_iterations > self.hash_digest_iterations_limit
+    }
+}
+
+/// Number of digest iterations a hash function with 64 byte blocks needs for a message.
+fn digest_iterations(message_length: u64) -> u64 {
+    1 + (message_length + 8) / 64
+}
+
+/// Estimate the budget usage of an input, given its unlocking bytecode and the scripts that get
+/// evaluated when it is spent (the locking script and/or the P2SH redeem script).
+pub fn estimate<'a>(
+    unlocking: &'a Script,
+    evaluated: impl IntoIterator<Item = &'a Script>,
+) -> Budget {
+    let density_control_length = DENSITY_CONTROL_BASE_LENGTH + unlocking.len() as u64;
+    let mut budget = Budget {
+        operation_cost_limit: density_control_length * OPERATION_COST_BUDGET_PER_BYTE,
+        hash_digest_iterations_limit_standard: density_control_length / 2,
+        hash_digest_iterations_limit: density_control_length * 7 / 2,
+        ..Default::default()
+    };
+    for script in std::iter::once(unlocking).chain(evaluated) {
+        let mut last_push_len = None;
+        for ins in script.instructions() {
+            let Ok(ins) = ins else {
+                break;
+            };
+            budget.operation_cost += BASE_INSTRUCTION_COST;
+            let op = match ins {
+                Instruction::PushBytes(bytes) => {
+                    budget.operation_cost += bytes.len() as u64;
+                    last_push_len = Some(bytes.len() as u64);
+                    continue;
+                }
+                Instruction::Op(op) => op,
+            };
+            let message_length = last_push_len.take().unwrap_or(ASSUMED_MESSAGE_LENGTH);
+            let iterations = hash_iterations(op, message_length);
+            budget.hash_digest_iterations += iterations;
+            budget.operation_cost += iterations * HASH_DIGEST_ITERATION_COST_STANDARD;
+            budget.operation_cost += signature_checks(op) * SIGNATURE_CHECK_COST;
+        }
+    }
+    budget
+}
+
+fn hash_iterations(op: All, message_length: u64) -> u64 {
+    match op {
+        OP_SHA1 | OP_SHA256 | OP_RIPEMD160 => digest_iterations(message_length),
+        // The second round hashes the 32 byte digest of the first
+        OP_HASH160 | OP_HASH256 => digest_iterations(message_length) + digest_iterations(32),
+        _ => 0,
+    }
+}
+
+fn signature_checks(op: All) -> u64 {
+    match op {
+        // The bitcoincash crate uses BTC names, these are OP_CHECKDATASIG(VERIFY) on BCH
+        OP_CHECKSIG | OP_CHECKSIGVERIFY | OP_CHECKSIGADD | OP_RETURN_187 => 1,
+        // Without evaluating, assume the worst case of all 20 keys being checked
+        OP_CHECKMULTISIG | OP_CHECKMULTISIGVERIFY => 20,
+        _ => 0,
+    }
+}
+
+/// If the unlocking bytecode is push only and its last push parses as a script containing
+/// opcodes, assume it is a P2SH redeem script.
+pub fn p2sh_redeem_script(unlocking: &Script) -> Option<Script> {
+    let mut last = None;
+    for ins in unlocking.instructions() {
+        match ins.ok()? {
+            Instruction::PushBytes(bytes) => last = Some(bytes),
+            Instruction::Op(op) if op.to_u8() <= OP_PUSHNUM_16.to_u8() => last = None,
+            Instruction::Op(_) => return None,
+        }
+    }
+    let redeem_script = Script::from(last?.to_vec());
+    let mut has_opcodes = false;
+    for ins in redeem_script.instructions() {
+        if let Instruction::Op(op) = ins.ok()? {
+            has_opcodes |= op.to_u8() > OP_PUSHNUM_16.to_u8();
+        }
+    }
+    has_opcodes.then_some(redeem_script)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{hashes::hex::FromHex, Script};
+
+    use super::{estimate, p2sh_redeem_script};
+
+    #[test]
+    fn test_p2pkh_spend_is_within_budget() {
+        let unlocking = Script::from_hex(concat!(
+            "473044022050343561f7a42de739ed32051cf50dace181ccd2e15d41bcae2b2b676a3f553f022050566f",
+            "ea7ff2d122d0fad0b84a435927523697a0da8bd742a72fe55e3881b8f84121030a72c3eb8d023aa16385",
+            "87293e427819265fd307db1d67de8e5c4129f654bf49",
+        ))
+        .unwrap();
+        let locking =
+            Script::from_hex("76a914e22b94d8e2cb8030f6af8c09749ae10767acf0fd88ac").unwrap();
+        let budget = estimate(&unlocking, [&locking]);
+        assert_eq!(budget.operation_cost_limit, (41 + 106) * 800);
+        assert!(!budget.exceeds_operation_cost());
+        assert!(!budget.exceeds_hashing_standard());
+        assert!(p2sh_redeem_script(&unlocking).is_none());
+    }
+
+    #[test]
+    fn test_hash_heavy_script_exceeds_budget() {
+        let unlocking = Script::from_hex("51").unwrap();
+        let locking = Script::from(vec![0xaa; 100]); // OP_HASH256 x100
+        let budget = estimate(&unlocking, [&locking]);
+        assert!(budget.exceeds_hashing_standard());
+        assert!(budget.exceeds_hashing());
+    }
+}
@@ -0,0 +1,145 @@
+//! Distributing the fungible tokens of an input among a list of recipients, in proportion to
+//! their weights.
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{hashes::hex::FromHex, TokenID};
+
+use crate::{
+    components::tx_output::TxOutputSnapshot, token_summary::ft_output, util::cash_addr_to_script,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Recipient {
+    pub address: String,
+    pub weight: u64,
+}
+
+/// One recipient per line as `address` or `address, weight`, the weight defaulting to 1.
+pub fn parse_recipients(s: &str) -> Result<Vec<Recipient>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let parse_line = || -> Result<Recipient> {
+                let line = line.trim();
+                let (address, weight) = match line.rsplit_once([',', ' ', '\t']) {
+                    Some((address, weight)) => (
+                        address.trim_end_matches([',', ' ', '\t']),
+                        weight.parse().context("Invalid weight")?,
+                    ),
+                    None => (line, 1),
+                };
+                cash_addr_to_script(address)?;
+                if weight == 0 {
+                    bail!("The weight must be positive");
+                }
+                Ok(Recipient {
+                    address: address.to_string(),
+                    weight,
+                })
+            };
+            parse_line().with_context(|| format!("Line {}", i + 1))
+        })
+        .collect()
+}
+
+/// Split `amount` in proportion to `weights`, rounding down. Returns the shares and the rounding
+/// remainder.
+pub fn distribute(amount: u64, weights: &[u64]) -> (Vec<u64>, u64) {
+    let total = weights.iter().map(|&w| u128::from(w)).sum::<u128>();
+    if total == 0 {
+        return (vec![0; weights.len()], amount);
+    }
+    let shares = weights
+        .iter()
+        .map(|&w| (u128::from(amount) * u128::from(w) / total) as u64)
+        .collect::<Vec<_>>();
+    let remainder = amount - shares.iter().sum::<u64>();
+    (shares, remainder)
+}
+
+pub struct Airdrop {
+    /// The recipients' outputs, then the token change if any.
+    pub outputs: Vec<TxOutputSnapshot>,
+    /// Tokens left over from rounding down the shares, included in the token change.
+    pub remainder: u64,
+}
+
+/// Outputs sending `amount` of the `input_amount` fungible tokens of `category` to `recipients`,
+/// each with the minimum non-dust value, and the rest to `change_address`.
+pub fn build(
+    category: &str,
+    input_amount: u64,
+    amount: u64,
+    recipients: &[Recipient],
+    change_address: &str,
+) -> Result<Airdrop> {
+    let id = TokenID::from_hex(category.trim()).context("Category")?;
+    if amount > input_amount {
+        bail!("The input only has {input_amount} tokens");
+    }
+    if recipients.is_empty() {
+        bail!("Enter at least one recipient");
+    }
+    let weights = recipients.iter().map(|r| r.weight).collect::<Vec<_>>();
+    let (shares, remainder) = distribute(amount, &weights);
+    let mut outputs = vec![];
+    for (i, (recipient, share)) in recipients.iter().zip(shares).enumerate() {
+        if share == 0 {
+            bail!("Recipient {} would receive no tokens", i + 1);
+        }
+        outputs.push(ft_output(&id, &recipient.address, share)?);
+    }
+    let change = input_amount - amount + remainder;
+    if change > 0 {
+        if change_address.trim().is_empty() {
+            bail!("Enter an address for the {change} tokens of change");
+        }
+        outputs.push(TxOutputSnapshot {
+            label: "Token change".to_string(),
+            ..ft_output(&id, change_address.trim(), change)?
+        });
+    }
+    Ok(Airdrop { outputs, remainder })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, distribute, parse_recipients};
+
+    const ADDRESS: &str = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+    const CATEGORY: &str = "efc44ce628940675b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd55";
+
+    #[test]
+    fn test_distribute() {
+        assert_eq!(distribute(100, &[1, 1, 1]), (vec![33, 33, 33], 1));
+        assert_eq!(distribute(100, &[3, 1]), (vec![75, 25], 0));
+        assert_eq!(distribute(u64::MAX, &[u64::MAX, 1]).1, 1);
+    }
+
+    #[test]
+    fn test_build() {
+        let recipients = parse_recipients(&format!("{ADDRESS}\n\n{ADDRESS}, 2\n")).unwrap();
+        assert_eq!(recipients[0].weight, 1);
+        assert_eq!(recipients[1].weight, 2);
+        assert!(parse_recipients(&format!("{ADDRESS}, 0")).is_err());
+        assert!(parse_recipients("qqq").is_err());
+
+        let airdrop = build(CATEGORY, 1000, 100, &recipients, ADDRESS).unwrap();
+        assert_eq!(airdrop.remainder, 1);
+        let amounts = airdrop
+            .outputs
+            .iter()
+            .map(|o| o.token_data.ft_amount)
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec![33, 66, 901]);
+        assert!(airdrop.outputs.iter().all(|o| o.value > 546));
+
+        // Everything distributed exactly needs no change
+        let airdrop = build(CATEGORY, 99, 99, &recipients, "").unwrap();
+        assert_eq!(airdrop.outputs.len(), 2);
+        assert!(build(CATEGORY, 100, 100, &recipients, "").is_err());
+        assert!(build(CATEGORY, 100, 1, &recipients, ADDRESS).is_err());
+        assert!(build(CATEGORY, 10, 100, &recipients, ADDRESS).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! Authenticated token metadata. A category's authchain starts at the transaction whose txid is
+//! the category ID and follows the spends of output 0. Its latest BCMR announcement names the
+//! registry the identity currently vouches for, see the
+//! [BCMR specification](https://cashtokens.org/docs/bcmr/chip/).
+
+use anyhow::{anyhow, bail, Result};
+use bitcoincash::{
+    blockdata::{
+        opcodes::all::OP_RETURN,
+        script::{Builder, Instruction},
+    },
+    consensus::deserialize,
+    hashes::{
+        hex::{FromHex, ToHex},
+        sha256, Hash,
+    },
+    Script, TokenID, Transaction, Txid,
+};
+use gloo::net::http::Request;
+use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
+use serde_json::Value;
+
+use crate::backends::BCMR;
+use crate::components::token_data::MAX_FT_DECIMALS;
+use crate::electrum_client::ElectrumClient;
+
+/// Authchains longer than this are given up on, to bound the number of requests.
+const MAX_AUTHCHAIN_LENGTH: usize = 1000;
+
+/// A BCMR output: `OP_RETURN <"BCMR"> <sha256 of the registry> <URI>...`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Announcement {
+    pub hash: sha256::Hash,
+    /// Each pushed separately, HTTPS ones without the `https://` prefix.
+    pub uris: Vec<String>,
+}
+
+impl Announcement {
+    pub fn script(&self) -> Script {
+        let builder = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(b"BCMR")
+            .push_slice(&self.hash[..]);
+        self.uris
+            .iter()
+            .fold(builder, |b, uri| b.push_slice(uri.as_bytes()))
+            .into_script()
+    }
+}
+
+pub fn parse_announcement(script: &Script) -> Option<Announcement> {
+    let mut pushes = script.instructions_minimal();
+    if pushes.next()?.ok()? != Instruction::Op(OP_RETURN) {
+        return None;
+    }
+    let mut data = pushes.map(|ins| match ins {
+        Ok(Instruction::PushBytes(data)) => Some(data),
+        _ => None,
+    });
+    if data.next()?? != b"BCMR" {
+        return None;
+    }
+    let hash = sha256::Hash::from_slice(data.next()??).ok()?;
+    let uris = data
+        .map(|uri| Some(String::from_utf8_lossy(uri?).into_owned()))
+        .collect::<Option<_>>()?;
+    Some(Announcement { hash, uris })
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AuthHead {
+    pub txid: Txid,
+    /// Transactions after the authbase, the one whose txid is the category ID.
+    pub length: usize,
+    /// The latest announcement along the authchain.
+    pub announcement: Option<Announcement>,
+}
+
+pub async fn resolve_authchain<T: ClientT + SubscriptionClientT>(
+    client: &ElectrumClient<T>,
+    category: &TokenID,
+) -> Result<AuthHead> {
+    let mut txid = Txid::from_inner(category.into_inner());
+    let mut length = 0;
+    let mut announcement = None;
+    loop {
+        let hex = client.blockchain_transaction_get(&txid.to_string()).await?;
+        let tx: Transaction = deserialize(&Vec::from_hex(&hex)?)?;
+        if let Some(a) = tx
+            .output
+            .iter()
+            .find_map(|o| parse_announcement(&o.script_pubkey))
+        {
+            announcement = Some(a);
+        }
+        let status = client
+            .blockchain_outpoint_status(&txid.to_string(), 0)
+            .await?;
+        let Some(spender) = status.spender_txhash else {
+            return Ok(AuthHead {
+                txid,
+                length,
+                announcement,
+            });
+        };
+        txid = spender.parse()?;
+        length += 1;
+        if length > MAX_AUTHCHAIN_LENGTH {
+            bail!("The authchain is longer than {MAX_AUTHCHAIN_LENGTH} transactions");
+        }
+    }
+}
+
+/// What a registry says about a category.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// HTTPS URL of a registry URI, which is HTTPS without a scheme.
+pub fn registry_url(uri: &str) -> String {
+    if let Some(cid) = uri.strip_prefix("ipfs://") {
+        format!("https://ipfs.io/ipfs/{cid}")
+    } else if uri.contains("://") {
+        uri.to_string()
+    } else {
+        format!("https://{uri}")
+    }
+}
+
+/// The latest snapshot of `category` in the registry `json`.
+pub fn parse_registry(json: &str, category: &TokenID) -> Result<TokenMetadata> {
+    let json: Value = serde_json::from_str(json)?;
+    let snapshot = json["identities"][category.to_hex()]
+        .as_object()
+        .and_then(|history| history.iter().max_by(|(a, _), (b, _)| a.cmp(b)))
+        .map(|(_, snapshot)| snapshot)
+        .ok_or_else(|| anyhow!("The registry doesn't list this category"))?;
+    let decimals = snapshot["token"]["decimals"]
+        .as_u64()
+        .map(u8::try_from)
+        .transpose()?
+        .filter(|d| *d <= MAX_FT_DECIMALS);
+    Ok(TokenMetadata {
+        name: snapshot["name"].as_str().unwrap_or_default().to_string(),
+        symbol: snapshot["token"]["symbol"].as_str().map(str::to_string),
+        decimals,
+    })
+}
+
+/// Fetch the registry of `announcement` from the first URI serving content with its hash.
+pub async fn fetch_registry(
+    announcement: &Announcement,
+    category: &TokenID,
+) -> Result<TokenMetadata> {
+    let mut error = anyhow!("The announcement has no URIs");
+    crate::offline::ensure_online()?;
+    for uri in &announcement.uris {
+        let url = registry_url(uri);
+        let fetch = async {
+            let response = Request::get(&url).send().await?;
+            if !response.ok() {
+                bail!("{url} returned HTTP {}", response.status());
+            }
+            let content = response.binary().await?;
+            if sha256::Hash::hash(&content) != announcement.hash {
+                bail!("{url} doesn't serve the announced registry");
+            }
+            parse_registry(std::str::from_utf8(&content)?, category)
+        };
+        match BCMR
+            .run(fetch)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|r| r)
+        {
+            Ok(metadata) => return Ok(metadata),
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Identity {
+    pub authhead: AuthHead,
+    /// `None` without an announcement, the error if the registry couldn't be fetched.
+    pub metadata: Option<Result<TokenMetadata, String>>,
+}
+
+/// Resolve the authchain of `category` with the Electrum server at `server`, and fetch the
+/// registry it announces.
+pub async fn lookup(server: &str, category: TokenID) -> Result<Identity> {
+    // Connecting through the server's proxy, if it has one
+    let crate::chain::Connection::Electrum(client) =
+        crate::chain::Server::Electrum(server.to_string())
+            .connect()
+            .await?
+    else {
+        unreachable!("Electrum servers have Electrum connections");
+    };
+    let authhead = resolve_authchain(&client, &category).await?;
+    let metadata = match &authhead.announcement {
+        Some(announcement) => Some(
+            fetch_registry(announcement, &category)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        None => None,
+    };
+    Ok(Identity { authhead, metadata })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{
+        blockdata::{opcodes::all::OP_RETURN, script::Builder},
+        hashes::{hex::FromHex, sha256, Hash},
+        TokenID,
+    };
+
+    use super::{parse_announcement, parse_registry, registry_url};
+
+    const CATEGORY: &str = "efc44ce628940675b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd55";
+
+    #[test]
+    fn test_parse_announcement() {
+        let hash = sha256::Hash::hash(b"{}");
+        let script = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(b"BCMR")
+            .push_slice(&hash[..])
+            .push_slice(b"example.com/bcmr.json")
+            .into_script();
+        let announcement = parse_announcement(&script).unwrap();
+        assert_eq!(announcement.hash, hash);
+        assert_eq!(announcement.uris, vec!["example.com/bcmr.json"]);
+        assert_eq!(announcement.script(), script);
+
+        let other = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(b"SLP\0")
+            .push_slice(&hash[..])
+            .into_script();
+        assert!(parse_announcement(&other).is_none());
+        let short = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_slice(b"BCMR")
+            .push_slice(&[1, 2, 3])
+            .into_script();
+        assert!(parse_announcement(&short).is_none());
+    }
+
+    #[test]
+    fn test_registry_url() {
+        assert_eq!(
+            registry_url("example.com/r.json"),
+            "https://example.com/r.json"
+        );
+        assert_eq!(
+            registry_url("https://example.com/r.json"),
+            "https://example.com/r.json"
+        );
+        assert_eq!(registry_url("ipfs://bafy"), "https://ipfs.io/ipfs/bafy");
+    }
+
+    #[test]
+    fn test_parse_registry() {
+        let category = TokenID::from_hex(CATEGORY).unwrap();
+        let json = format!(
+            r#"{{"identities": {{"{CATEGORY}": {{
+                "2023-01-01T00:00:00.000Z": {{"name": "Old", "token": {{"symbol": "OLD", "decimals": 0}}}},
+                "2024-01-01T00:00:00.000Z": {{"name": "Token", "token": {{"symbol": "TOK", "decimals": 2}}}}
+            }}}}}}"#
+        );
+        let metadata = parse_registry(&json, &category).unwrap();
+        assert_eq!(metadata.name, "Token");
+        assert_eq!(metadata.symbol.as_deref(), Some("TOK"));
+        assert_eq!(metadata.decimals, Some(2));
+        assert!(parse_registry(r#"{"identities": {}}"#, &category).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Persistence of the editor state in localStorage, so it survives a refresh or a crashed tab.
+
+use anyhow::Result;
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::snapshot::EditorSnapshot;
+use crate::vault::{Sealed, VaultKey};
+
+const AUTOSAVE_KEY: &str = "bch-tx-editor.autosave";
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedTab {
+    pub name: String,
+    #[serde(with = "crate::snapshot::versioned")]
+    pub snapshot: EditorSnapshot,
+}
+
+/// The saved tabs, encrypted as JSON when saved data is encrypted.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Saved {
+    Encrypted(Sealed),
+    Plain(Vec<SavedTab>),
+}
+
+pub fn load() -> Option<Saved> {
+    LocalStorage::get(AUTOSAVE_KEY).ok()
+}
+
+pub fn save(tabs: &[SavedTab]) {
+    if let Err(e) = LocalStorage::set(AUTOSAVE_KEY, tabs) {
+        error!("Autosave failed: {e}");
+    }
+}
+
+pub async fn save_encrypted(key: &VaultKey, tabs: &[SavedTab]) {
+    let saving = async {
+        let sealed = key.seal(&serde_json::to_string(tabs)?).await?;
+        LocalStorage::set(AUTOSAVE_KEY, Saved::Encrypted(sealed))?;
+        anyhow::Ok(())
+    };
+    if let Err(e) = saving.await {
+        error!("Autosave failed: {e}");
+    }
+}
+
+/// Decrypt tabs saved with [`save_encrypted`], failing if `passphrase` is wrong.
+pub async fn unlock(passphrase: &str, sealed: &Sealed) -> Result<(VaultKey, Vec<SavedTab>)> {
+    let (key, json) = VaultKey::unlock(passphrase, sealed).await?;
+    Ok((key, serde_json::from_str(&json)?))
+}
+
+pub fn clear() {
+    LocalStorage::delete(AUTOSAVE_KEY);
+}
@@ -0,0 +1,226 @@
+//! Timeouts, concurrency and rate limits of the network backends, so slow public servers make
+//! requests wait their turn or fail instead of hanging indefinitely.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::future::{select, Either};
+use gloo::timers::future::{sleep, TimeoutFuture};
+use leptos::web_sys::js_sys::Date;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Limits {
+    /// How long a request may take before it fails.
+    pub timeout: Duration,
+    /// Requests in flight at once, more wait for one of them to finish.
+    pub max_concurrent: usize,
+    /// Least time between the starts of two requests.
+    pub min_interval: Duration,
+}
+
+/// How often requests waiting for a free slot check again.
+const SLOT_POLL: Duration = Duration::from_millis(50);
+
+/// Limits of a backend set in the preferences, each in place of its default unless `None`.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitOverrides {
+    pub timeout_secs: Option<u32>,
+    pub max_concurrent: Option<u32>,
+    pub min_interval_ms: Option<u32>,
+}
+
+/// The [`LimitOverrides`] of every backend.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendLimits {
+    pub electrum: LimitOverrides,
+    pub bcmr: LimitOverrides,
+    pub rest: LimitOverrides,
+    pub node: LimitOverrides,
+    pub fiat_rates: LimitOverrides,
+}
+
+/// Where the overrides of a backend are in [`BackendLimits`].
+pub type OverridesField = fn(&mut BackendLimits) -> &mut LimitOverrides;
+
+/// Every backend, with a label for the preferences and its overrides.
+pub fn all() -> [(&'static str, &'static Backend, OverridesField); 5] {
+    [
+        ("Electrum servers", &ELECTRUM, |l| &mut l.electrum),
+        ("Token registries", &BCMR, |l| &mut l.bcmr),
+        ("REST APIs", &REST, |l| &mut l.rest),
+        ("Node", &NODE, |l| &mut l.node),
+        ("Exchange rates", &FIAT_RATES, |l| &mut l.fiat_rates),
+    ]
+}
+
+/// Override the limits of every backend with those set in the preferences.
+pub fn set_limits(mut limits: BackendLimits) {
+    for (_, backend, overrides) in all() {
+        *backend.overrides.lock().unwrap() = *overrides(&mut limits);
+    }
+}
+
+/// The Electrum servers used for authchain lookups and fetching previous transactions.
+pub static ELECTRUM: Backend = Backend::new(
+    "The Electrum server",
+    Limits {
+        timeout: Duration::from_secs(30),
+        max_concurrent: 8,
+        min_interval: Duration::from_millis(20),
+    },
+);
+
+/// The web servers hosting token metadata registries.
+pub static BCMR: Backend = Backend::new(
+    "The registry server",
+    Limits {
+        timeout: Duration::from_secs(15),
+        max_concurrent: 2,
+        min_interval: Duration::ZERO,
+    },
+);
+
+/// The block explorer REST APIs used in place of Electrum.
+pub static REST: Backend = Backend::new(
+    "The REST server",
+    Limits {
+        timeout: Duration::from_secs(20),
+        max_concurrent: 4,
+        min_interval: Duration::from_millis(100),
+    },
+);
+
+/// The user's own node, through its JSON-RPC proxy.
+pub static NODE: Backend = Backend::new(
+    "The node",
+    Limits {
+        timeout: Duration::from_secs(30),
+        max_concurrent: 4,
+        min_interval: Duration::ZERO,
+    },
+);
+
+/// The exchange rate APIs, which rate limit clients themselves.
+pub static FIAT_RATES: Backend = Backend::new(
+    "The exchange rate source",
+    Limits {
+        timeout: Duration::from_secs(10),
+        max_concurrent: 1,
+        min_interval: Duration::from_secs(2),
+    },
+);
+
+/// A request that took longer than the timeout of its backend.
+#[derive(Debug)]
+pub struct TimedOut {
+    backend: &'static str,
+    timeout: Duration,
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} didn't respond within {} seconds",
+            self.backend,
+            self.timeout.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Counts a request in flight until dropped, also when the request is cancelled.
+struct Slot<'a>(&'a AtomicUsize);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub struct Backend {
+    name: &'static str,
+    defaults: Limits,
+    overrides: Mutex<LimitOverrides>,
+    in_flight: AtomicUsize,
+    /// Milliseconds since the epoch before which the next request can't start.
+    next_start: Mutex<f64>,
+}
+
+impl Backend {
+    const fn new(name: &'static str, limits: Limits) -> Self {
+        Self {
+            name,
+            defaults: limits,
+            overrides: Mutex::new(LimitOverrides {
+                timeout_secs: None,
+                max_concurrent: None,
+                min_interval_ms: None,
+            }),
+            in_flight: AtomicUsize::new(0),
+            next_start: Mutex::new(0.0),
+        }
+    }
+
+    /// The limits of the backend unless overridden in the preferences.
+    pub fn defaults(&self) -> Limits {
+        self.defaults
+    }
+
+    /// The limits in effect, with those set in the preferences.
+    pub fn limits(&self) -> Limits {
+        let overrides = *self.overrides.lock().unwrap();
+        Limits {
+            timeout: overrides
+                .timeout_secs
+                .map_or(self.defaults.timeout, |s| Duration::from_secs(s.into())),
+            max_concurrent: overrides
+                .max_concurrent
+                .map_or(self.defaults.max_concurrent, |n| n as usize),
+            min_interval: overrides
+                .min_interval_ms
+                .map_or(self.defaults.min_interval, |ms| {
+                    Duration::from_millis(ms.into())
+                }),
+        }
+    }
+
+    /// Run `request` once a slot is free and the rate limit allows, failing if it takes longer
+    /// than the timeout.
+    pub async fn run<F: Future>(&self, request: F) -> Result<F::Output, TimedOut> {
+        let limits = self.limits();
+        while self.in_flight.load(Ordering::Relaxed) >= limits.max_concurrent.max(1) {
+            sleep(SLOT_POLL).await;
+        }
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        let _slot = Slot(&self.in_flight);
+
+        let now = Date::now();
+        let start = {
+            let mut next_start = self.next_start.lock().unwrap();
+            let start = next_start.max(now);
+            *next_start = start + limits.min_interval.as_secs_f64() * 1000.0;
+            start
+        };
+        if start > now {
+            sleep(Duration::from_secs_f64((start - now) / 1000.0)).await;
+        }
+
+        let timeout = TimeoutFuture::new(limits.timeout.as_millis().try_into().unwrap_or(u32::MAX));
+        match select(pin!(request), pin!(timeout)).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(TimedOut {
+                backend: self.name,
+                timeout: limits.timeout,
+            }),
+        }
+    }
+}
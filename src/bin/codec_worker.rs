@@ -0,0 +1,9 @@
+//! Web worker encoding and decoding transactions for the editor, see `tx_codec`.
+
+use bch_tx_edit_core::codec::TxCodec;
+use gloo::worker::Registrable;
+
+fn main() {
+    console_error_panic_hook::set_once();
+    TxCodec::registrar().register();
+}
@@ -0,0 +1,274 @@
+//! Chain data and relay behind one trait, served by Electrum, by a block explorer's REST API where
+//! WebSocket Electrum servers are unreachable, or by the user's own node.
+
+use anyhow::{anyhow, bail, Result};
+use bitcoincash::{
+    consensus::deserialize,
+    hashes::hex::{FromHex, ToHex},
+    Transaction, Txid,
+};
+use futures::StreamExt;
+use gloo::net::http::Request;
+use jsonrpsee::core::client::{Client, ClientT, SubscriptionClientT};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::backends::{NODE, REST};
+use crate::electrum_client::ElectrumClient;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum ServerKind {
+        #[default]
+        Electrum = "electrum",
+        Rest = "rest",
+        Node = "node",
+    }
+}
+
+/// Source of chain data and relay of transactions.
+#[allow(async_fn_in_trait)]
+pub trait ChainBackend {
+    async fn transaction(&self, txid: &Txid) -> Result<Transaction>;
+
+    /// Relay `tx` to the network, returning its txid as the server reports it.
+    async fn broadcast(&self, tx: &[u8]) -> Result<Txid>;
+}
+
+impl<T: ClientT + SubscriptionClientT> ChainBackend for ElectrumClient<T> {
+    async fn transaction(&self, txid: &Txid) -> Result<Transaction> {
+        let hex = self.blockchain_transaction_get(&txid.to_string()).await?;
+        Ok(deserialize(&Vec::from_hex(&hex)?)?)
+    }
+
+    async fn broadcast(&self, tx: &[u8]) -> Result<Txid> {
+        let txid = self.blockchain_transaction_broadcast(&tx.to_hex()).await?;
+        Ok(txid.parse()?)
+    }
+}
+
+/// A server with the [Blockbook](https://github.com/trezor/blockbook) API, like most explorers
+/// built on it, at `base_url`.
+pub struct RestClient {
+    base_url: String,
+}
+
+impl RestClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// GET `path`, or POST `body` to it, within the limits of [`REST`], failing with the error
+    /// the API reports.
+    async fn call(&self, path: &str, body: Option<String>) -> Result<Value> {
+        crate::offline::ensure_online()?;
+        let url = format!("{}/api/v2/{path}", self.base_url);
+        let request = async {
+            let response = match body {
+                Some(body) => Request::post(&url).body(body)?.send().await?,
+                None => Request::get(&url).send().await?,
+            };
+            let json: Value = response.json().await?;
+            if let Some(error) = json.get("error") {
+                let message = error.get("message").unwrap_or(error);
+                bail!(
+                    "{}: {}",
+                    self.base_url,
+                    message.as_str().unwrap_or_default()
+                );
+            }
+            if !response.ok() {
+                bail!("{} returned HTTP {}", self.base_url, response.status());
+            }
+            Ok(json)
+        };
+        REST.run(request).await?
+    }
+}
+
+impl ChainBackend for RestClient {
+    async fn transaction(&self, txid: &Txid) -> Result<Transaction> {
+        let json = self.call(&format!("tx-specific/{txid}"), None).await?;
+        let hex = json["hex"]
+            .as_str()
+            .ok_or_else(|| anyhow!("{} sent no transaction hex", self.base_url))?;
+        Ok(deserialize(&Vec::from_hex(hex)?)?)
+    }
+
+    async fn broadcast(&self, tx: &[u8]) -> Result<Txid> {
+        let json = self.call("sendtx/", Some(tx.to_hex())).await?;
+        let txid = json["result"]
+            .as_str()
+            .ok_or_else(|| anyhow!("{} sent no txid", self.base_url))?;
+        Ok(txid.parse()?)
+    }
+}
+
+/// A BCHN node's JSON-RPC, reached through a proxy at `url` that allows the browser's requests
+/// and adds the RPC credentials, so they're never stored here.
+pub struct NodeClient {
+    url: String,
+}
+
+impl NodeClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+        }
+    }
+
+    /// Whether the node's mempool would accept `tx`, without relaying it: the reason it's
+    /// rejected, if it is.
+    pub async fn test_mempool_accept(&self, tx: &[u8]) -> Result<Option<String>> {
+        let results = self
+            .call("testmempoolaccept", json!([[tx.to_hex()]]))
+            .await?;
+        Ok(rejection(&results[0]))
+    }
+
+    /// Call `method` within the limits of [`NODE`], failing with the error the node reports.
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        crate::offline::ensure_online()?;
+        let body = json!({
+            "jsonrpc": "1.0",
+            "id": "bch-tx-editor",
+            "method": method,
+            "params": params,
+        });
+        let request = async {
+            let response = Request::post(&self.url).json(&body)?.send().await?;
+            // The node answers errors with HTTP 500 and a JSON body, so read it first
+            let json: Value = match response.json().await {
+                Ok(json) => json,
+                Err(_) => bail!("{} returned HTTP {}", self.url, response.status()),
+            };
+            match &json["error"] {
+                Value::Null => Ok(json["result"].clone()),
+                error => bail!(
+                    "{method}: {}",
+                    error["message"].as_str().unwrap_or_default()
+                ),
+            }
+        };
+        NODE.run(request).await?
+    }
+}
+
+impl ChainBackend for NodeClient {
+    async fn transaction(&self, txid: &Txid) -> Result<Transaction> {
+        let hex = self
+            .call("getrawtransaction", json!([txid.to_string(), false]))
+            .await?;
+        let hex = hex
+            .as_str()
+            .ok_or_else(|| anyhow!("The node sent no transaction hex"))?;
+        Ok(deserialize(&Vec::from_hex(hex)?)?)
+    }
+
+    async fn broadcast(&self, tx: &[u8]) -> Result<Txid> {
+        let txid = self
+            .call("sendrawtransaction", json!([tx.to_hex()]))
+            .await?;
+        let txid = txid
+            .as_str()
+            .ok_or_else(|| anyhow!("The node sent no txid"))?;
+        Ok(txid.parse()?)
+    }
+}
+
+/// Reason of the rejection in a result of `testmempoolaccept`, if the transaction wasn't allowed.
+fn rejection(result: &Value) -> Option<String> {
+    match result["allowed"].as_bool() {
+        Some(true) => None,
+        _ => Some(
+            result["reject-reason"]
+                .as_str()
+                .unwrap_or("rejected")
+                .to_string(),
+        ),
+    }
+}
+
+/// The server chosen for chain data, see [`ServerKind`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Server {
+    /// WebSocket URL of an Electrum server.
+    Electrum(String),
+    /// Base URL of a REST API.
+    Rest(String),
+    /// URL of the JSON-RPC proxy of a node.
+    Node(String),
+}
+
+impl Server {
+    pub async fn connect(&self) -> Result<Connection> {
+        crate::offline::ensure_online()?;
+        match self {
+            Self::Electrum(url) => Ok(Connection::Electrum(
+                crate::electrum_client::connect(&crate::proxy::route(url)).await?,
+            )),
+            Self::Rest(url) => Ok(Connection::Rest(RestClient::new(url))),
+            Self::Node(url) => Ok(Connection::Node(NodeClient::new(url))),
+        }
+    }
+}
+
+/// Follow the chain tip on the Electrum `server`, passing its height to `on_tip` once and again on
+/// every new block. Runs until the connection fails.
+pub async fn follow_tip(server: &str, on_tip: impl Fn(u32)) -> Result<()> {
+    let Connection::Electrum(client) = Server::Electrum(server.to_string()).connect().await? else {
+        unreachable!("Electrum servers connect with Electrum");
+    };
+    let (tip, mut headers) = client.blockchain_headers_subscribe().await?;
+    on_tip(tip.height.try_into()?);
+    while let Some(header) = headers.next().await {
+        on_tip(header?.height.try_into()?);
+    }
+    bail!("{server} closed the connection")
+}
+
+pub enum Connection {
+    Electrum(ElectrumClient<Client>),
+    Rest(RestClient),
+    Node(NodeClient),
+}
+
+impl ChainBackend for Connection {
+    async fn transaction(&self, txid: &Txid) -> Result<Transaction> {
+        match self {
+            Self::Electrum(client) => client.transaction(txid).await,
+            Self::Rest(client) => client.transaction(txid).await,
+            Self::Node(client) => client.transaction(txid).await,
+        }
+    }
+
+    async fn broadcast(&self, tx: &[u8]) -> Result<Txid> {
+        match self {
+            Self::Electrum(client) => client.broadcast(tx).await,
+            Self::Rest(client) => client.broadcast(tx).await,
+            Self::Node(client) => client.broadcast(tx).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::rejection;
+
+    #[test]
+    fn test_rejection() {
+        assert_eq!(rejection(&json!({"txid": "ab", "allowed": true})), None);
+        let rejected =
+            json!({"txid": "ab", "allowed": false, "reject-reason": "min relay fee not met"});
+        assert_eq!(
+            rejection(&rejected).as_deref(),
+            Some("min relay fee not met")
+        );
+        // The node returns an empty array for a transaction it can't decode
+        assert_eq!(rejection(&json!(null)).as_deref(), Some("rejected"));
+    }
+}
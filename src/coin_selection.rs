@@ -0,0 +1,756 @@
+//! Choosing UTXOs to fund a transaction, from candidates pasted by the user.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, ensure, Context as _, Result};
+use bitcoincash::{consensus::encode, hashes::Hash, OutPoint, PubkeyHash, Script, TxOut, Txid};
+use serde::Deserialize;
+
+use crate::{
+    components::{
+        token_data::{NftCapability, TokenDataSnapshot},
+        tx_input::{TxInputSnapshot, UtxoPubkeyData},
+    },
+    partially_signed::{MaybeUnsignedTxIn, PartiallySignedTransaction, SignatureType},
+    util::dust_threshold,
+};
+
+/// Size of a P2PKH change output.
+pub const P2PKH_OUTPUT_SIZE: usize = 34;
+/// Smallest P2PKH output relayed under standardness rules.
+pub const P2PKH_DUST: u64 = 546;
+/// The minimum relay fee rate, in sats per byte.
+pub const DEFAULT_FEE_RATE: f64 = 1.0;
+/// Branch and bound gives up after this many steps and falls back to largest first.
+const MAX_TRIES: usize = 100_000;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+    pub enum Strategy {
+        /// Spend the largest UTXOs first, fewest inputs but usually with change.
+        #[default]
+        LargestFirst = "largest-first",
+        /// Search for a set of UTXOs matching the target exactly enough to skip change.
+        BranchAndBound = "bnb",
+        /// Select UTXOs with the tokens the outputs need, then cover the rest with plain UTXOs.
+        TokenAware = "token-aware",
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Utxo {
+    pub outpoint: OutPoint,
+    pub value: u64,
+    /// Cash address, empty if unknown.
+    pub address: String,
+    pub token: TokenDataSnapshot,
+}
+
+impl Utxo {
+    pub fn input_snapshot(&self) -> TxInputSnapshot {
+        TxInputSnapshot {
+            txid: self.outpoint.txid.to_string(),
+            vout: self.outpoint.vout,
+            unsigned: true,
+            utxo_pubkey: UtxoPubkeyData::Addr(self.address.clone()),
+            utxo_amount: self.value,
+            token_data: self.token.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A `blockchain.address.listunspent` result entry, with the address added.
+#[derive(Deserialize)]
+pub struct ElectrumUtxo {
+    tx_hash: String,
+    tx_pos: u32,
+    value: u64,
+    #[serde(default)]
+    address: String,
+    token_data: Option<ElectrumTokenData>,
+}
+
+#[derive(Deserialize)]
+struct ElectrumTokenData {
+    category: String,
+    #[serde(default)]
+    amount: String,
+    nft: Option<ElectrumNft>,
+}
+
+#[derive(Deserialize)]
+struct ElectrumNft {
+    capability: String,
+    #[serde(default)]
+    commitment: String,
+}
+
+impl TryFrom<ElectrumUtxo> for Utxo {
+    type Error = anyhow::Error;
+
+    fn try_from(utxo: ElectrumUtxo) -> Result<Self> {
+        let token = match utxo.token_data {
+            None => TokenDataSnapshot::default(),
+            Some(token) => {
+                let ft_amount = match token.amount.as_str() {
+                    "" => 0,
+                    amount => amount.parse()?,
+                };
+                TokenDataSnapshot {
+                    cashtoken_enabled: true,
+                    category_id: token.category,
+                    has_ft_amount: ft_amount != 0,
+                    ft_amount,
+                    has_nft: token.nft.is_some(),
+                    nft_capability: match token.nft.as_ref().map(|nft| nft.capability.as_str()) {
+                        None | Some("none") => NftCapability::Immutable,
+                        Some("mutable") => NftCapability::Mutable,
+                        Some("minting") => NftCapability::Minting,
+                        Some(other) => bail!("Unknown NFT capability {other}"),
+                    },
+                    nft_commitment_hex: token.nft.map(|nft| nft.commitment).unwrap_or_default(),
+                    ..Default::default()
+                }
+            }
+        };
+        Ok(Self {
+            outpoint: OutPoint {
+                txid: utxo.tx_hash.parse()?,
+                vout: utxo.tx_pos,
+            },
+            value: utxo.value,
+            address: utxo.address,
+            token,
+        })
+    }
+}
+
+/// Parse candidates either as a JSON list of Electrum `listunspent` entries, or one per line as
+/// `txid:vout value [address]`.
+pub fn parse_candidates(s: &str) -> Result<Vec<Utxo>> {
+    if s.trim_start().starts_with('[') {
+        let utxos: Vec<ElectrumUtxo> = serde_json::from_str(s)?;
+        return utxos.into_iter().map(Utxo::try_from).collect();
+    }
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let mut parts = line.split_whitespace();
+            let mut parse = || -> Result<Utxo> {
+                let (txid, vout) = parts
+                    .next()
+                    .and_then(|outpoint| outpoint.split_once(':'))
+                    .context("Expected txid:vout")?;
+                let value = parts.next().context("Missing value")?;
+                Ok(Utxo {
+                    outpoint: OutPoint {
+                        txid: txid.parse::<Txid>()?,
+                        vout: vout.parse()?,
+                    },
+                    value: value.parse()?,
+                    address: parts.next().unwrap_or_default().to_string(),
+                    token: TokenDataSnapshot::default(),
+                })
+            };
+            parse().with_context(|| format!("Line {}", i + 1))
+        })
+        .collect()
+}
+
+/// What the selected UTXOs have to pay for.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Target {
+    /// Value of the outputs minus the inputs already in the transaction, before fees.
+    pub amount: i64,
+    /// Estimated size of the transaction before adding inputs.
+    pub size: usize,
+    /// Estimated size of each P2PKH input added, once signed.
+    pub input_size: usize,
+    /// In sats per byte.
+    pub fee_rate: f64,
+    /// Fungible token amounts the outputs need beyond the existing inputs, by category.
+    pub tokens: BTreeMap<String, u64>,
+    /// NFTs in the outputs not coming from existing inputs, as category, commitment and
+    /// capability.
+    pub nfts: Vec<(String, String, NftCapability)>,
+}
+
+impl Target {
+    pub fn from_tx(
+        tx: &PartiallySignedTransaction,
+        fee_rate: f64,
+        signatures: SignatureType,
+    ) -> Result<Self> {
+        let fee = tx
+            .fee()
+            .context("Signed inputs don't record their value, coins can't be selected for them")?;
+        let mut tokens = BTreeMap::<String, i128>::new();
+        let mut nfts = Vec::new();
+        for txout in &tx.output {
+            let token = TokenDataSnapshot::from_token_data(txout.token.as_ref());
+            *tokens.entry(token.category_id.clone()).or_default() += i128::from(token.ft_amount);
+            if token.has_nft {
+                nfts.push((
+                    token.category_id,
+                    token.nft_commitment_hex,
+                    token.nft_capability,
+                ));
+            }
+        }
+        for txin in &tx.input {
+            let MaybeUnsignedTxIn::Unsigned(txin) = txin else {
+                continue;
+            };
+            // New categories are created by spending output 0 of their txid
+            if txin.previous_output.vout == 0 {
+                let category = txin.previous_output.txid.to_string();
+                tokens.remove(&category);
+                nfts.retain(|(c, _, _)| *c != category);
+            }
+            let token = TokenDataSnapshot::from_token_data(txin.token.as_ref());
+            *tokens.entry(token.category_id.clone()).or_default() -= i128::from(token.ft_amount);
+            if token.has_nft {
+                // Minting and mutable NFTs can produce NFTs with any commitment
+                match token.nft_capability {
+                    NftCapability::Immutable => {
+                        if let Some(i) = nfts.iter().position(|(c, commitment, _)| {
+                            *c == token.category_id && *commitment == token.nft_commitment_hex
+                        }) {
+                            nfts.remove(i);
+                        }
+                    }
+                    NftCapability::Mutable | NftCapability::Minting => {
+                        nfts.retain(|(c, _, _)| *c != token.category_id)
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            amount: -fee,
+            size: tx.estimated_size(signatures),
+            input_size: signatures.p2pkh_input_size(),
+            fee_rate,
+            tokens: tokens
+                .into_iter()
+                .filter(|(category, amount)| !category.is_empty() && *amount > 0)
+                .map(|(category, amount)| (category, amount as u64))
+                .collect(),
+            nfts,
+        })
+    }
+
+    pub fn fee(&self, size: usize) -> u64 {
+        (size as f64 * self.fee_rate).ceil() as u64
+    }
+
+    /// Value the given number of selected inputs must add up to.
+    fn needed(&self, inputs: usize) -> i64 {
+        self.amount + self.fee(self.size + inputs * self.input_size) as i64
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Selection {
+    pub utxos: Vec<Utxo>,
+    pub fee: u64,
+    /// Value left over after the fee and the token change.
+    pub excess: u64,
+    /// Tokens of the selected UTXOs the outputs don't take, each to return in its own output:
+    /// the fungible tokens left of each category, and the minting NFTs kept for minting.
+    pub token_change: Vec<TokenDataSnapshot>,
+}
+
+impl Selection {
+    fn new(utxos: Vec<&Utxo>, target: &Target, token_change: Vec<TokenDataSnapshot>) -> Self {
+        let total = utxos.iter().map(|utxo| utxo.value as i64).sum::<i64>();
+        let fee = target.fee(target.size + utxos.len() * target.input_size);
+        Self {
+            excess: (total - target.amount - fee as i64) as u64,
+            fee,
+            utxos: utxos.into_iter().cloned().collect(),
+            token_change,
+        }
+    }
+
+    /// Value of a P2PKH change output taking the excess, `None` if it would be dust.
+    pub fn change_value(&self, target: &Target) -> Option<u64> {
+        self.excess
+            .checked_sub(target.fee(P2PKH_OUTPUT_SIZE))
+            .filter(|&value| value >= P2PKH_DUST)
+    }
+}
+
+/// Values for the given outputs splitting what's left of the inputs after the other outputs and
+/// the fee. `None` if the input value isn't known or doesn't cover the rest.
+pub fn max_values(
+    tx: &PartiallySignedTransaction,
+    outputs: &[usize],
+    fee_rate: f64,
+    signatures: SignatureType,
+) -> Option<Vec<u64>> {
+    let n = outputs.len() as u64;
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    let max_total = outputs
+        .iter()
+        .map(|&i| tx.output.get(i).map(|txout| txout.value))
+        .sum::<Option<u64>>()?;
+    let fee = (tx.estimated_size(signatures) as f64 * fee_rate).ceil() as i64;
+    let remaining = u64::try_from(tx.fee()? + max_total as i64 - fee).ok()?;
+    Some(
+        (0..n)
+            .map(|i| remaining / n + u64::from(i < remaining % n))
+            .collect(),
+    )
+}
+
+/// Deduct the fee from the given outputs, split between them, keeping the others exact. Fails if
+/// an output's share would leave it under the dust limit.
+pub fn subtract_fee(
+    tx: &mut PartiallySignedTransaction,
+    outputs: &[usize],
+    fee_rate: f64,
+    signatures: SignatureType,
+) -> Result<()> {
+    let n = outputs.len() as u64;
+    let fee = (tx.estimated_size(signatures) as f64 * fee_rate).ceil() as u64;
+    for (i, &output) in outputs.iter().enumerate() {
+        let share = fee / n + u64::from((i as u64) < fee % n);
+        let txout = tx.output.get_mut(output).context("No such output")?;
+        txout.value = txout
+            .value
+            .checked_sub(share)
+            .with_context(|| format!("Output {output} can't pay {share} sats of the fee"))?;
+        ensure!(
+            txout.value >= P2PKH_DUST,
+            "Output {output} would be left with {} sats after paying {share} sats of the fee, \
+             under the dust limit of {P2PKH_DUST} sats",
+            txout.value,
+        );
+    }
+    Ok(())
+}
+
+/// Add UTXOs from `pool` to `selected`, largest first, until they cover the target.
+fn largest_first<'a>(
+    mut selected: Vec<&'a Utxo>,
+    pool: &[&'a Utxo],
+    target: &Target,
+) -> Result<Vec<&'a Utxo>> {
+    let mut pool = pool.to_vec();
+    pool.sort_by_key(|utxo| std::cmp::Reverse(utxo.value));
+    let mut pool = pool.into_iter();
+    let mut total = selected.iter().map(|utxo| utxo.value as i64).sum::<i64>();
+    while total < target.needed(selected.len()) {
+        let utxo = pool
+            .next()
+            .context("The candidates don't have enough value to cover the outputs and fee")?;
+        total += utxo.value as i64;
+        selected.push(utxo);
+    }
+    Ok(selected)
+}
+
+/// Depth first search for a subset of `values`, sorted descending, summing to between `target`
+/// and `target + window`. Each step either includes or skips `values[i]`.
+#[allow(clippy::too_many_arguments)]
+fn search(
+    values: &[i64],
+    i: usize,
+    sum: i64,
+    remaining: i64,
+    target: i64,
+    window: i64,
+    selected: &mut Vec<usize>,
+    tries: &mut usize,
+) -> bool {
+    if sum > target + window {
+        return false;
+    }
+    if sum >= target {
+        return true;
+    }
+    if i == values.len() || sum + remaining < target || *tries == 0 {
+        return false;
+    }
+    *tries -= 1;
+    let remaining = remaining - values[i];
+    selected.push(i);
+    if search(
+        values,
+        i + 1,
+        sum + values[i],
+        remaining,
+        target,
+        window,
+        selected,
+        tries,
+    ) {
+        return true;
+    }
+    selected.pop();
+    search(
+        values,
+        i + 1,
+        sum,
+        remaining,
+        target,
+        window,
+        selected,
+        tries,
+    )
+}
+
+fn branch_and_bound<'a>(pool: &[&'a Utxo], target: &Target) -> Option<Vec<&'a Utxo>> {
+    let input_fee = target.fee(target.input_size) as i64;
+    let mut pool = pool
+        .iter()
+        .copied()
+        .filter(|utxo| utxo.value as i64 > input_fee)
+        .collect::<Vec<_>>();
+    pool.sort_by_key(|utxo| std::cmp::Reverse(utxo.value));
+    let values = pool
+        .iter()
+        .map(|utxo| utxo.value as i64 - input_fee)
+        .collect::<Vec<_>>();
+    // Overpaying by less than a change output would cost to create and spend is fine
+    let window = target.fee(P2PKH_OUTPUT_SIZE + target.input_size) as i64;
+    let mut selected = Vec::new();
+    let mut tries = MAX_TRIES;
+    search(
+        &values,
+        0,
+        0,
+        values.iter().sum(),
+        target.needed(0),
+        window,
+        &mut selected,
+        &mut tries,
+    )
+    .then(|| selected.into_iter().map(|i| pool[i]).collect())
+}
+
+/// UTXOs with the tokens the target needs, and the token change they leave.
+fn select_tokens<'a>(
+    candidates: &'a [Utxo],
+    target: &Target,
+) -> Result<(Vec<&'a Utxo>, Vec<TokenDataSnapshot>)> {
+    let mut selected = Vec::<&Utxo>::new();
+    // Minting NFTs selected, which can mint any NFT of their category and are kept
+    let mut minting = Vec::<&Utxo>::new();
+    for (category, commitment, capability) in &target.nfts {
+        if minting
+            .iter()
+            .any(|utxo| utxo.token.category_id == *category)
+        {
+            continue;
+        }
+        let available = || {
+            candidates.iter().filter(|utxo| {
+                utxo.token.has_nft
+                    && utxo.token.category_id == *category
+                    && !selected.iter().any(|s| s.outpoint == utxo.outpoint)
+            })
+        };
+        // An NFT of the same capability, or else a minting one, so none loses its capability.
+        // Mutable NFTs can change their commitment.
+        let spent = available().find(|utxo| {
+            utxo.token.nft_capability == *capability
+                && (utxo.token.nft_commitment_hex == *commitment
+                    || *capability == NftCapability::Mutable)
+        });
+        match spent {
+            Some(utxo) => selected.push(utxo),
+            None => {
+                let utxo = available()
+                    .find(|utxo| utxo.token.nft_capability == NftCapability::Minting)
+                    .with_context(|| {
+                        format!("No candidate has an NFT of category {category} to spend")
+                    })?;
+                selected.push(utxo);
+                minting.push(utxo);
+            }
+        }
+    }
+    for (category, &amount) in &target.tokens {
+        let mut total = selected
+            .iter()
+            .filter(|utxo| utxo.token.category_id == *category)
+            .map(|utxo| utxo.token.ft_amount)
+            .sum::<u64>();
+        let mut pool = candidates
+            .iter()
+            .filter(|utxo| utxo.token.category_id == *category && utxo.token.ft_amount > 0)
+            .filter(|utxo| !utxo.token.has_nft)
+            .filter(|utxo| !selected.iter().any(|s| s.outpoint == utxo.outpoint))
+            .collect::<Vec<_>>();
+        pool.sort_by_key(|utxo| std::cmp::Reverse(utxo.token.ft_amount));
+        let mut pool = pool.into_iter();
+        while total < amount {
+            let utxo = pool.next().with_context(|| {
+                format!("The candidates don't have {amount} tokens of category {category}")
+            })?;
+            total += utxo.token.ft_amount;
+            selected.push(utxo);
+        }
+    }
+    let mut ft_selected = BTreeMap::<&str, u64>::new();
+    for utxo in &selected {
+        *ft_selected.entry(&utxo.token.category_id).or_default() += utxo.token.ft_amount;
+    }
+    let mut change = ft_selected
+        .into_iter()
+        .filter_map(|(category, total)| {
+            let needed = target.tokens.get(category).copied().unwrap_or_default();
+            let excess = total.checked_sub(needed).filter(|&excess| excess > 0)?;
+            Some(TokenDataSnapshot {
+                cashtoken_enabled: true,
+                category_id: category.to_string(),
+                has_ft_amount: true,
+                ft_amount: excess,
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    change.extend(minting.into_iter().map(|utxo| TokenDataSnapshot {
+        has_ft_amount: false,
+        ft_amount: 0,
+        ..utxo.token.clone()
+    }));
+    Ok((selected, change))
+}
+
+/// A P2PKH output returning `token`, with the minimum non-dust value.
+fn token_change_output(token: &TokenDataSnapshot) -> Result<TxOut> {
+    let mut txout = TxOut {
+        value: 0,
+        script_pubkey: Script::new_p2pkh(&PubkeyHash::all_zeros()),
+        token: token.token_data()?,
+    };
+    txout.value = dust_threshold(&txout);
+    Ok(txout)
+}
+
+pub fn select(candidates: &[Utxo], target: &Target, strategy: Strategy) -> Result<Selection> {
+    // Spending token UTXOs where no tokens are needed would burn them
+    let plain = candidates
+        .iter()
+        .filter(|utxo| !utxo.token.cashtoken_enabled)
+        .collect::<Vec<_>>();
+    let needs_tokens = !target.tokens.is_empty() || !target.nfts.is_empty();
+    ensure!(
+        strategy == Strategy::TokenAware || !needs_tokens,
+        "The outputs need tokens, use the token-aware strategy"
+    );
+    let selected = match strategy {
+        Strategy::LargestFirst => largest_first(Vec::new(), &plain, target)?,
+        Strategy::BranchAndBound => match branch_and_bound(&plain, target) {
+            Some(selected) => selected,
+            None => largest_first(Vec::new(), &plain, target)?,
+        },
+        Strategy::TokenAware => {
+            let (selected, token_change) = select_tokens(candidates, target)?;
+            // The token change outputs are paid for like the others
+            let mut target = target.clone();
+            for token in &token_change {
+                let txout = token_change_output(token)?;
+                target.amount += txout.value as i64;
+                target.size += encode::serialize(&txout).len();
+            }
+            let selected = largest_first(selected, &plain, &target)?;
+            return Ok(Selection::new(selected, &target, token_change));
+        }
+    };
+    Ok(Selection::new(selected, target, Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bch_tx_edit_core::test_util::TXID;
+    use bitcoincash::{consensus::deserialize, hashes::hex::FromHex};
+
+    use super::{
+        max_values, parse_candidates, select, subtract_fee, Strategy, Target, Utxo, P2PKH_DUST,
+    };
+    use crate::components::token_data::{NftCapability, TokenDataSnapshot};
+    use crate::partially_signed::{PartiallySignedTransaction, SignatureType, P2PKH_INPUT_SIZE};
+    use crate::test_util::utxo;
+
+    fn target(amount: i64) -> Target {
+        Target {
+            amount,
+            size: 44,
+            input_size: P2PKH_INPUT_SIZE,
+            fee_rate: 1.0,
+            tokens: BTreeMap::new(),
+            nfts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_candidates() {
+        let lines = format!("{TXID}:1 5000 bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2\n\n{TXID}:2 700\n");
+        let utxos = parse_candidates(&lines).unwrap();
+        assert_eq!(utxos.len(), 2);
+        assert_eq!(utxos[1], utxo(2, 700));
+        assert!(parse_candidates(&format!("{TXID} 700")).is_err());
+
+        let json = format!(
+            r#"[{{"tx_hash":"{TXID}","tx_pos":3,"height":800000,"value":1000,
+                "token_data":{{"category":"{TXID}","amount":"50","nft":{{"capability":"minting","commitment":"ab"}}}}}}]"#
+        );
+        let utxos = parse_candidates(&json).unwrap();
+        assert_eq!(utxos[0].token.ft_amount, 50);
+        assert_eq!(utxos[0].token.nft_commitment_hex, "ab");
+        assert!(utxos[0].token.has_nft);
+    }
+
+    #[test]
+    fn test_select() {
+        let candidates = [
+            utxo(0, 10_000),
+            utxo(1, 3_000),
+            utxo(2, 2_000),
+            utxo(3, 1_000),
+        ];
+        let selection = select(&candidates, &target(4600), Strategy::LargestFirst).unwrap();
+        assert_eq!(selection.utxos, vec![candidates[0].clone()]);
+        assert_eq!(selection.fee, 192);
+        assert_eq!(selection.excess, 10_000 - 4600 - 192);
+        assert_eq!(selection.change_value(&target(4600)), Some(5208 - 34));
+
+        // With two inputs 4600 + 44 + 2 * 148 = 4940 is needed, just below 3000 + 2000
+        let selection = select(&candidates, &target(4600), Strategy::BranchAndBound).unwrap();
+        assert_eq!(
+            selection.utxos,
+            vec![candidates[1].clone(), candidates[2].clone()]
+        );
+        assert_eq!(selection.excess, 60);
+        assert_eq!(selection.change_value(&target(4600)), None);
+
+        assert!(select(&candidates, &target(20_000), Strategy::BranchAndBound).is_err());
+    }
+
+    #[test]
+    fn test_select_tokens() {
+        let mut token_utxo = utxo(4, 1000);
+        token_utxo.token = TokenDataSnapshot {
+            cashtoken_enabled: true,
+            category_id: TXID.to_string(),
+            has_ft_amount: true,
+            ft_amount: 100,
+            ..Default::default()
+        };
+        let candidates = [utxo(0, 10_000), token_utxo.clone()];
+        let mut target = target(1000);
+        target.tokens.insert(TXID.to_string(), 60);
+        assert!(select(&candidates, &target, Strategy::LargestFirst).is_err());
+        let selection = select(&candidates, &target, Strategy::TokenAware).unwrap();
+        assert_eq!(selection.utxos, vec![token_utxo, candidates[0].clone()]);
+        // The 40 tokens left over go back in a change output, which the excess pays for
+        assert_eq!(
+            selection.token_change,
+            [TokenDataSnapshot {
+                cashtoken_enabled: true,
+                category_id: TXID.to_string(),
+                has_ft_amount: true,
+                ft_amount: 40,
+                ..Default::default()
+            }]
+        );
+        // A 69 byte output with the minimum value of 651 sats
+        assert_eq!(selection.fee, 44 + 69 + 2 * 148);
+        assert_eq!(selection.excess, 11_000 - 1000 - 651 - selection.fee);
+
+        target.tokens.insert(TXID.to_string(), 200);
+        assert!(select(&candidates, &target, Strategy::TokenAware).is_err());
+    }
+
+    #[test]
+    fn test_select_nfts() {
+        let nft = |vout, capability, commitment: &str| {
+            let mut utxo = utxo(vout, 1000);
+            utxo.token = TokenDataSnapshot {
+                cashtoken_enabled: true,
+                category_id: TXID.to_string(),
+                has_nft: true,
+                nft_capability: capability,
+                nft_commitment_hex: commitment.to_string(),
+                ..Default::default()
+            };
+            utxo
+        };
+        let minting = nft(1, NftCapability::Minting, "");
+        let candidates = [utxo(0, 10_000), minting.clone()];
+        let mut target = target(1000);
+        target
+            .nfts
+            .push((TXID.to_string(), "ab".to_string(), NftCapability::Immutable));
+        target
+            .nfts
+            .push((TXID.to_string(), "cd".to_string(), NftCapability::Immutable));
+        // The minting NFT mints both and is kept
+        let selection = select(&candidates, &target, Strategy::TokenAware).unwrap();
+        assert_eq!(
+            selection.utxos,
+            vec![minting.clone(), candidates[0].clone()]
+        );
+        assert_eq!(selection.token_change, [minting.token.clone()]);
+
+        // An immutable NFT with the commitment is spent instead
+        let immutable = nft(2, NftCapability::Immutable, "ab");
+        let candidates = [utxo(0, 10_000), immutable.clone()];
+        target.nfts.pop();
+        let selection = select(&candidates, &target, Strategy::TokenAware).unwrap();
+        assert_eq!(selection.utxos, vec![immutable, candidates[0].clone()]);
+        assert!(selection.token_change.is_empty());
+
+        // A mutable NFT would lose its capability
+        let candidates = [utxo(0, 10_000), nft(3, NftCapability::Mutable, "ab")];
+        assert!(select(&candidates, &target, Strategy::TokenAware).is_err());
+    }
+
+    #[test]
+    fn test_max_values_and_subtract_fee() {
+        // An unsigned input paying a fee of 219 sats, 226 bytes once signed
+        let tx_bytes = Vec::<u8>::from_hex(concat!(
+            "01000000013c3b636f926cb2c5a8f971d7e06e488aa3d10f42202b293f936bafdf63d7908a1800000057",
+            "01ff4c53ff0488b21e0000000000000000005d2f27f71323296d52bf8475ad8dad79d6239fcd640629fd",
+            "dc8ef9a7229258a4023f72ac51c65717e8d44e8d86afacff3eed27ce00cea7b5a6fd1e6297fcbd4df901",
+            "00fe15feffffff20090600000000000262e80200000000001976a914c9226d620fe088b4d84a4ab0ca6b",
+            "4fe6dfb3193488ace31f0300000000001976a914795b6a18d92f888df281f85373288a6834a7d31a88ac",
+            "81cc0c00",
+        ))
+        .unwrap();
+        let tx: PartiallySignedTransaction = deserialize(&tx_bytes).unwrap();
+        let [a, b] = [tx.output[0].value, tx.output[1].value];
+        let ecdsa = SignatureType::Ecdsa;
+        assert_eq!(max_values(&tx, &[1], 1.0, ecdsa), Some(vec![b + 219 - 226]));
+        let remaining = a + b + 219 - 226;
+        assert_eq!(
+            max_values(&tx, &[0, 1], 1.0, ecdsa),
+            Some(vec![remaining / 2 + remaining % 2, remaining / 2])
+        );
+        assert_eq!(max_values(&tx, &[1], 1000.0, ecdsa), None);
+        // Schnorr signatures are 7 bytes shorter
+        assert_eq!(
+            max_values(&tx, &[1], 1.0, SignatureType::Schnorr),
+            Some(vec![b + 219 - 219])
+        );
+
+        let mut paying = tx.clone();
+        subtract_fee(&mut paying, &[0, 1], 1.0, ecdsa).unwrap();
+        assert_eq!(paying.output[0].value, a - 113);
+        assert_eq!(paying.output[1].value, b - 113);
+        assert_eq!(paying.fee(), Some(219 + 226));
+        assert!(subtract_fee(&mut paying, &[0], 10_000.0, ecdsa).is_err());
+        let mut dusty = tx.clone();
+        dusty.output[0].value = P2PKH_DUST + 100;
+        assert!(subtract_fee(&mut dusty, &[0], 1.0, ecdsa).is_err());
+    }
+}
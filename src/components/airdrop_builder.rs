@@ -0,0 +1,129 @@
+use anyhow::{Context as _, Result};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, Get, GlobalAttributes,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal, With,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    airdrop,
+    components::{token_data::TokenDataSnapshot, tx_output::TxOutputSnapshot},
+    util::short_hex,
+    Context,
+};
+
+/// Distributes the fungible tokens of an input among pasted recipients.
+#[component]
+pub fn AirdropBuilder(
+    ctx: Context,
+    /// Inputs carrying fungible tokens, with their index.
+    #[prop(into)]
+    token_inputs: Signal<Vec<(usize, TokenDataSnapshot)>>,
+    on_add: impl Fn(Vec<TxOutputSnapshot>) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let input = RwSignal::new(None::<usize>);
+    let amount = RwSignal::new(String::new());
+    let recipients = RwSignal::new(String::new());
+    let change_address = RwSignal::new(String::new());
+    let message = RwSignal::new(Ok::<_, String>(String::new()));
+
+    // The chosen input, or the first one
+    let selected = move || {
+        let token_inputs = token_inputs.get();
+        input
+            .get()
+            .and_then(|i| token_inputs.iter().find(|(index, _)| *index == i).cloned())
+            .or_else(|| token_inputs.first().cloned())
+    };
+    let build = move || -> Result<airdrop::Airdrop> {
+        let (_, token) = selected().context("No input carries fungible tokens")?;
+        let amount = match amount.get().trim() {
+            "" => token.ft_amount,
+            amount => amount.parse().context("Invalid amount")?,
+        };
+        let recipients = airdrop::parse_recipients(&recipients.get())?;
+        airdrop::build(
+            &token.category_id,
+            token.ft_amount,
+            amount,
+            &recipients,
+            &change_address.get(),
+        )
+    };
+    let add = move |_| match build() {
+        Ok(airdrop) => {
+            let count = airdrop.outputs.len();
+            on_add(airdrop.outputs);
+            recipients.set(String::new());
+            message.set(Ok(match airdrop.remainder {
+                0 => format!("{} {count}.", ctx.t("Outputs added:")),
+                r => format!(
+                    "{} {count}. {r} {}",
+                    ctx.t("Outputs added:"),
+                    ctx.t("tokens left from rounding are in the token change.")
+                ),
+            }));
+        }
+        Err(e) => message.set(Err(format!("{e:#}"))),
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex gap-1 mb-1">
+                <select
+                    class="bg-inherit border rounded p-1"
+                    on:input=move |e| input.set(event_target_value(&e).parse().ok())
+                    prop:value=move || selected().map(|(i, _)| i.to_string()).unwrap_or_default()
+                >
+                    {move || {
+                        token_inputs
+                            .get()
+                            .into_iter()
+                            .map(|(i, token)| {
+                                view! {
+                                    <option value=i.to_string()>
+                                        {ctx.t("Input")}" "{i}": "{token.ft_amount}" "{ctx.t("of")}" "{short_hex(&token.category_id)}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 w-40"
+                    placeholder=move || ctx.t("Amount, all by default")
+                    on:input=move |e| amount.set(event_target_value(&e))
+                    prop:value=amount
+                />
+            </div>
+            <textarea
+                spellcheck="false"
+                rows=4
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-stone-900 placeholder:text-stone-600 font-mono"
+                placeholder=move || ctx.t("One recipient per line as: address, or address, weight")
+                on:input=move |e| recipients.set(event_target_value(&e))
+                prop:value=recipients
+            />
+            <div class="flex gap-1">
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 font-mono grow"
+                    placeholder=move || ctx.t("Token change address")
+                    on:input=move |e| change_address.set(event_target_value(&e))
+                    prop:value=change_address
+                />
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=add>
+                    {move || ctx.t("Add outputs")}
+                </button>
+            </div>
+            <p
+                class=("text-stone-500", move || message.with(Result::is_ok))
+                class=("text-red-700", move || message.with(Result::is_err))
+            >
+                {move || message.get().unwrap_or_else(|e| e)}
+            </p>
+        </div>
+    }
+}
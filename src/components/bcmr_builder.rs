@@ -0,0 +1,133 @@
+use anyhow::{bail, Result};
+use bitcoincash::hashes::{
+    hex::{FromHex, ToHex},
+    sha256, Hash,
+};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, Get, GlobalAttributes, OnAttribute,
+        PropAttribute, RwSignal, Set,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    authchain::Announcement,
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        tx_output::TxOutputSnapshot,
+    },
+    op_return::{check_size, MAX_STANDARD_OP_RETURN_SIZE},
+    Context,
+};
+
+/// Builds the OP_RETURN output publishing a metadata registry, for issuers spending their
+/// identity output.
+#[component]
+pub fn BcmrBuilder(
+    ctx: Context,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let registry = RwSignal::new(String::new());
+    let hash = RwSignal::new(String::new());
+    let uris = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    let announcement = move || -> Result<Announcement> {
+        let uris = uris
+            .get()
+            .lines()
+            .map(str::trim)
+            .filter(|uri| !uri.is_empty())
+            .map(|uri| uri.strip_prefix("https://").unwrap_or(uri).to_string())
+            .collect::<Vec<_>>();
+        if uris.is_empty() {
+            bail!("Enter at least one URI");
+        }
+        Ok(Announcement {
+            hash: sha256::Hash::from_hex(hash.get().trim())?,
+            uris,
+        })
+    };
+    let status = move || match announcement().and_then(|a| {
+        let script = a.script();
+        check_size(&script)?;
+        Ok(script.len())
+    }) {
+        Ok(len) => (
+            false,
+            format!("{len}/{MAX_STANDARD_OP_RETURN_SIZE} {}", ctx.t("bytes")),
+        ),
+        Err(e) => (true, e.to_string()),
+    };
+    let hash_registry = move |_| {
+        hash.set(sha256::Hash::hash(registry.get().as_bytes()).to_hex());
+    };
+    let add_output = move |_| match announcement() {
+        Ok(announcement) => {
+            on_add_output(TxOutputSnapshot {
+                script_pubkey: ScriptInputValue::Hex(announcement.script().to_hex()),
+                script_display_format: ScriptDisplayFormat::Asm,
+                label: ctx.t("BCMR publication").to_string(),
+                ..Default::default()
+            });
+            error.set(String::new());
+        }
+        Err(e) => error.set(e.to_string()),
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <textarea
+                spellcheck="false"
+                rows=3
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-stone-900 placeholder:text-stone-600 font-mono"
+                placeholder=move || ctx.t("Registry JSON, exactly as published, to compute its hash")
+                on:input=move |e| registry.set(event_target_value(&e))
+                prop:value=registry
+            />
+            <div class="flex gap-1 my-1">
+                <button
+                    class="border border-solid rounded border-stone-600 px-1"
+                    title=move || ctx.t("Hash the registry JSON above")
+                    on:click=hash_registry
+                >
+                    {move || ctx.t("Hash")}
+                </button>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 font-mono grow"
+                    placeholder=move || ctx.t("SHA-256 of the registry")
+                    on:input=move |e| hash.set(event_target_value(&e))
+                    prop:value=hash
+                />
+            </div>
+            <textarea
+                spellcheck="false"
+                rows=2
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-stone-900 placeholder:text-stone-600 font-mono"
+                placeholder=move || ctx.t("Registry URIs, one per line, e.g. example.com/bcmr.json or ipfs://…")
+                on:input=move |e| uris.set(event_target_value(&e))
+                prop:value=uris
+            />
+            <div class="flex justify-between items-center">
+                <span
+                    class=("text-red-700", move || status().0)
+                    class=("text-stone-500", move || !status().0)
+                >
+                    {move || status().1}
+                </span>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1"
+                    on:click=add_output
+                >
+                    {move || ctx.t("Add output")}
+                </button>
+            </div>
+            <p class="text-red-700">{error}</p>
+            <p class="text-stone-500">
+                {move || ctx.t("The publication is authenticated when the transaction also spends the identity's authhead output 0.")}
+            </p>
+        </div>
+    }
+}
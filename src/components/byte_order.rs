@@ -0,0 +1,60 @@
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, OnAttribute, PropAttribute, RwSignal, Set,
+    },
+    view, IntoView,
+};
+
+use crate::{hashing::reverse_hash, Context};
+
+/// Converter of 32-byte hashes between the order explorers show them in and the order they're
+/// serialized in, either side editable.
+#[component]
+pub fn ByteOrderConverter(ctx: Context) -> impl IntoView {
+    let displayed = RwSignal::new(String::new());
+    let serialized = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    // Set `from` to what was typed and `to` to its reverse
+    let convert = move |value: String, from: RwSignal<String>, to: RwSignal<String>| {
+        match reverse_hash(&value) {
+            Ok(reversed) => {
+                to.set(reversed);
+                error.set(String::new());
+            }
+            Err(_) if value.trim().is_empty() => {
+                to.set(String::new());
+                error.set(String::new());
+            }
+            Err(e) => error.set(format!("{e:#}")),
+        }
+        from.set(value);
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <label class="block text-stone-500">
+                {move || ctx.t("Displayed: txids, block hashes and token categories as explorers and wallets show them")}
+            </label>
+            <input
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono w-full mb-1 placeholder:text-stone-600"
+                placeholder=move || ctx.t("Hash in display order")
+                spellcheck="false"
+                on:input=move |e| convert(event_target_value(&e), displayed, serialized)
+                prop:value=displayed
+            />
+            <label class="block text-stone-500">
+                {move || ctx.t("Serialized: as in raw transactions, and as OP_OUTPOINTTXHASH and OP_UTXOTOKENCATEGORY push them")}
+            </label>
+            <input
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono w-full placeholder:text-stone-600"
+                placeholder=move || ctx.t("Hash in serialized order")
+                spellcheck="false"
+                on:input=move |e| convert(event_target_value(&e), serialized, displayed)
+                prop:value=serialized
+            />
+            <p class="text-red-700">{error}</p>
+        </div>
+    }
+}
@@ -0,0 +1,165 @@
+use bitcoincash::hashes::hex::ToHex;
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, Get, GlobalAttributes, IntoAny,
+        OnAttribute, PropAttribute, RwSignal, Set, Show, Signal,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        tx_input::TxInputSnapshot,
+        tx_output::TxOutputSnapshot,
+    },
+    timelock::{parse_locktime, Cltv, Recipient},
+    util::script_to_cash_addr,
+    Context,
+};
+
+/// Lock coins until a block height or date with OP_CHECKLOCKTIMEVERIFY, producing the locked
+/// output and, once it's mined, the input spending it.
+#[component]
+pub fn CltvWizard(
+    ctx: Context,
+    #[prop(into)] cash_addr_prefix: Signal<String>,
+    /// Called with the spending input and the transaction locktime it needs.
+    on_add_input: impl Fn(TxInputSnapshot, u32) + Copy + Send + Sync + 'static,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let by_date = RwSignal::new(false);
+    let locktime = RwSignal::new(String::new());
+    let recipient = RwSignal::new(String::new());
+
+    let cltv = move || {
+        let locktime = parse_locktime(by_date.get(), &locktime.get())?;
+        Cltv::new(locktime, recipient.get().parse::<Recipient>()?)
+    };
+    let details = move || {
+        cltv().and_then(|cltv| {
+            let redeem_script = cltv.redeem_script();
+            let address = script_to_cash_addr(&redeem_script.to_p2sh(), &cash_addr_prefix.get())?;
+            Ok((redeem_script.to_hex(), address))
+        })
+    };
+
+    let add_output = move |_| {
+        if let Ok((_, address)) = details() {
+            on_add_output(TxOutputSnapshot {
+                script_pubkey: ScriptInputValue::Addr(address),
+                script_display_format: ScriptDisplayFormat::Addr,
+                label: ctx.t("CLTV timelock").to_string(),
+                ..Default::default()
+            });
+        }
+    };
+    let add_input = move |_| {
+        if let Ok(cltv) = cltv() {
+            // The default sequence of 0xfffffffe keeps the locktime enforced
+            on_add_input(
+                TxInputSnapshot {
+                    script_sig: ScriptInputValue::Asm(cltv.spending_script_sig_asm()),
+                    script_sig_format: ScriptDisplayFormat::Asm,
+                    label: ctx
+                        .t("CLTV locked UTXO: fill in txid, vout and the signature")
+                        .to_string(),
+                    ..Default::default()
+                },
+                cltv.locktime,
+            );
+        }
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex gap-1 mb-1">
+                <label class="w-24">{move || ctx.t("Locked until:")}</label>
+                <LocktimeInput ctx by_date text=locktime/>
+            </div>
+            <div class="flex gap-1 mb-1">
+                <label class="w-24">{move || ctx.t("Recipient:")}</label>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow font-mono placeholder:text-stone-600"
+                    placeholder=move || ctx.t("Public key or P2PKH address")
+                    on:input=move |e| recipient.set(event_target_value(&e))
+                    prop:value=recipient
+                />
+            </div>
+            {move || match details() {
+                Ok((redeem_script, address)) => {
+                    view! {
+                        <div class="break-all">
+                            <div>{ctx.t("Redeem script:")}" " <span class="font-mono">{redeem_script}</span></div>
+                            <div>{ctx.t("Address:")}" " <span class="font-mono">{address}</span></div>
+                        </div>
+                    }
+                        .into_any()
+                }
+                Err(_) if recipient.get().trim().is_empty() => ().into_any(),
+                Err(e) => view! { <div class="text-red-700">{e.to_string()}</div> }.into_any(),
+            }}
+            <div class="flex gap-1 mt-1">
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an output paying to the timelocked address")
+                    disabled=move || details().is_err()
+                    on:click=add_output
+                >
+                    {move || ctx.t("Add locked output")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an input spending the locked output, and set the transaction locktime it needs")
+                    disabled=move || details().is_err()
+                    on:click=add_input
+                >
+                    {move || ctx.t("Add spending input")}
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Block height or date input, parsed with [`parse_locktime`].
+#[component]
+pub fn LocktimeInput(
+    ctx: Context,
+    by_date: RwSignal<bool>,
+    text: RwSignal<String>,
+) -> impl IntoView {
+    view! {
+        <select
+            class="bg-inherit border rounded px-1"
+            on:change=move |e| {
+                by_date.set(event_target_value(&e) == "date");
+                text.set(String::new());
+            }
+            prop:value=move || if by_date.get() { "date" } else { "height" }
+        >
+            <option value="height">{move || ctx.t("Block height")}</option>
+            <option value="date">{move || ctx.t("Date (UTC)")}</option>
+        </select>
+        <Show
+            when=move || by_date.get()
+            fallback=move || {
+                view! {
+                    <input
+                        class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow placeholder:text-stone-600"
+                        placeholder=move || ctx.t("Block height")
+                        on:input=move |e| text.set(event_target_value(&e))
+                        prop:value=text
+                    />
+                }
+            }
+        >
+            <input
+                type="datetime-local"
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow"
+                on:input=move |e| text.set(event_target_value(&e))
+                prop:value=text
+            />
+        </Show>
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{bail, Result};
+use bitcoincash::{hashes::hex::FromHex, TokenID};
+use leptos::{
+    component,
+    prelude::{
+        event_target_checked, event_target_value, ClassAttribute, ElementChild, Get, GetUntracked,
+        GlobalAttributes, OnAttribute, PropAttribute, Read, RwSignal, Set, Signal, With,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    coin_selection::{parse_candidates, select, Strategy, Target, Utxo},
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        token_data::TokenDataSnapshot,
+        tx_input::TxInputSnapshot,
+        tx_output::TxOutputSnapshot,
+    },
+    macros::StrEnum,
+    minting::nft_output,
+    partially_signed::{PartiallySignedTransaction, SignatureType},
+    token_summary::ft_output,
+    Context,
+};
+
+/// Pick inputs from pasted candidate UTXOs, and those of the watch-only wallets, to pay for the
+/// outputs and the fee.
+#[component]
+pub fn CoinSelector(
+    ctx: Context,
+    #[prop(into)] watched: Signal<Vec<Utxo>>,
+    build_tx: impl Fn() -> Result<PartiallySignedTransaction> + Copy + Send + Sync + 'static,
+    /// How the inputs will be signed, for their size.
+    signatures: Signal<SignatureType>,
+    /// Called with the inputs to add, and the change outputs: the token change, and a BCH change
+    /// output if one is worth adding.
+    on_select: impl Fn(Vec<TxInputSnapshot>, Vec<TxOutputSnapshot>) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let candidates = RwSignal::new(String::new());
+    let strategy = RwSignal::new(Strategy::default());
+    let fee_rate = RwSignal::new(ctx.preferences.get_untracked().fee_rate.to_string());
+    let change_address = RwSignal::new(String::new());
+    let include_watched = RwSignal::new(true);
+    let message = RwSignal::new(Ok(String::new()));
+
+    let run = move || -> Result<String> {
+        let tx = build_tx()?;
+        let fee_rate = fee_rate
+            .get()
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|rate| *rate >= 0.0)
+            .ok_or_else(|| anyhow::anyhow!(ctx.t("Invalid fee rate")))?;
+        let mut candidates = parse_candidates(&candidates.get())?;
+        if include_watched.get() {
+            for utxo in watched.get() {
+                if !candidates.iter().any(|c| c.outpoint == utxo.outpoint) {
+                    candidates.push(utxo);
+                }
+            }
+        }
+        // Candidates already spent by the transaction can't be selected again
+        let candidates = candidates
+            .into_iter()
+            .filter(|utxo| {
+                !tx.input
+                    .iter()
+                    .any(|txin| *txin.previous_output() == utxo.outpoint)
+            })
+            .collect::<Vec<_>>();
+        let target = Target::from_tx(&tx, fee_rate, signatures.get())?;
+        let selection = select(&candidates, &target, strategy.get())?;
+        let change_address = change_address.get().trim().to_string();
+        if !selection.token_change.is_empty() && change_address.is_empty() {
+            bail!("The selected coins have tokens left over, enter a change address to return them to");
+        }
+        let mut outputs = selection
+            .token_change
+            .iter()
+            .map(|token| token_change(token, &change_address, ctx.t("Token change")))
+            .collect::<Result<Vec<_>>>()?;
+        let change = selection
+            .change_value(&target)
+            .filter(|_| !change_address.is_empty())
+            .map(|value| TxOutputSnapshot {
+                value,
+                script_pubkey: ScriptInputValue::Addr(change_address),
+                script_display_format: ScriptDisplayFormat::Addr,
+                label: ctx.t("Change").to_string(),
+                ..Default::default()
+            });
+        let mut message = format!(
+            "{} {}, {} {} sats",
+            ctx.t("Inputs selected:"),
+            selection.utxos.len(),
+            ctx.t("fee"),
+            selection.fee
+        );
+        match &change {
+            Some(change) => message += &format!(", {} {} sats", ctx.t("change"), change.value),
+            None => message += &format!(", {} {}", selection.excess, ctx.t("sats over the fee")),
+        }
+        if !outputs.is_empty() {
+            message += &format!(", {} {}", outputs.len(), ctx.t("token change outputs"));
+        }
+        outputs.extend(change);
+        on_select(
+            selection
+                .utxos
+                .iter()
+                .map(|utxo| utxo.input_snapshot())
+                .collect(),
+            outputs,
+        );
+        Ok(message)
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <textarea
+                spellcheck="false"
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-stone-900 placeholder:text-stone-600 font-mono"
+                placeholder=move || ctx.t("Candidate UTXOs, one per line as txid:vout value address, or Electrum listunspent JSON")
+                on:input=move |e| candidates.set(event_target_value(&e))
+                prop:value=candidates
+            />
+            <label class:hidden=move || watched.with(Vec::is_empty)>
+                <input
+                    type="checkbox"
+                    class="mr-1"
+                    on:change=move |e| include_watched.set(event_target_checked(&e))
+                    prop:checked=include_watched
+                />
+                {move || format!("{} ({})", ctx.t("Include the coins of the watch-only wallets"), watched.with(Vec::len))}
+            </label>
+            <div class="flex flex-wrap items-center gap-1">
+                <label>{move || ctx.t("Strategy:")}</label>
+                <select
+                    class="bg-inherit border rounded px-1"
+                    on:input=move |e| strategy.set(Strategy::from_str(&event_target_value(&e)).unwrap())
+                    prop:value=move || strategy.get().to_str()
+                >
+                    <option value=Strategy::LargestFirst.to_str()>{move || ctx.t("Largest first")}</option>
+                    <option value=Strategy::BranchAndBound.to_str()>{move || ctx.t("Branch and bound")}</option>
+                    <option value=Strategy::TokenAware.to_str()>{move || ctx.t("Token-aware")}</option>
+                </select>
+                <label class="ml-2">{move || ctx.t("Fee rate (sat/byte):")}</label>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 w-16"
+                    on:input=move |e| fee_rate.set(event_target_value(&e))
+                    prop:value=fee_rate
+                />
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow font-mono placeholder:text-stone-600 ml-2"
+                    placeholder=move || ctx.t("Change address (optional)")
+                    on:input=move |e| change_address.set(event_target_value(&e))
+                    prop:value=change_address
+                />
+                <button
+                    class="border border-solid rounded border-stone-600 px-2"
+                    title=move || ctx.t("Add inputs covering the outputs and the fee")
+                    on:click=move |_| message.set(run().map_err(|e| e.to_string()))
+                >
+                    {move || ctx.t("Select coins")}
+                </button>
+            </div>
+            <div class=("text-red-700", move || message.read().is_err())>
+                {move || message.get().unwrap_or_else(|e| e)}
+            </div>
+        </div>
+    }
+}
+
+/// Output labelled `label` returning the fungible tokens or the NFT in `token` to `address`.
+fn token_change(token: &TokenDataSnapshot, address: &str, label: &str) -> Result<TxOutputSnapshot> {
+    let category = TokenID::from_hex(&token.category_id)?;
+    let output = match token.has_nft {
+        true => nft_output(
+            &category,
+            address,
+            token.nft_capability,
+            &Vec::from_hex(&token.nft_commitment_hex)?,
+        )?,
+        false => ft_output(&category, address, token.ft_amount)?,
+    };
+    Ok(TxOutputSnapshot {
+        label: label.to_string(),
+        ..output
+    })
+}
@@ -0,0 +1,29 @@
+use leptos::{
+    component,
+    prelude::{ClassAttribute, CollectView, ElementChild, Get, Signal},
+    view, IntoView,
+};
+
+use crate::validation::{Diagnostic, Severity};
+
+#[component]
+pub fn DiagnosticList(#[prop(into)] diagnostics: Signal<Vec<Diagnostic>>) -> impl IntoView {
+    view! {
+        <ul class="text-sm">
+            {move || {
+                diagnostics
+                    .get()
+                    .into_iter()
+                    .map(|d| {
+                        let class = match d.severity {
+                            Severity::Info => "text-sky-600",
+                            Severity::Warning => "text-yellow-700",
+                            Severity::Error => "text-red-700",
+                        };
+                        view! { <li class=class>{d.message}</li> }
+                    })
+                    .collect_view()
+            }}
+        </ul>
+    }
+}
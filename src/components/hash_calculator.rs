@@ -0,0 +1,71 @@
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, Get, IntoAny, OnAttribute,
+        PropAttribute, RwSignal, Set,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    hashing::{digests, input_data, HashInputFormat},
+    macros::StrEnum,
+    Context,
+};
+
+/// Hashes of hex or UTF-8 data by each hashing opcode, to paste into scripts.
+#[component]
+pub fn HashCalculator(ctx: Context) -> impl IntoView {
+    let input = RwSignal::new(String::new());
+    let format = RwSignal::new(HashInputFormat::default());
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex">
+                <textarea
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono grow placeholder:text-stone-600"
+                    rows=2
+                    spellcheck="false"
+                    placeholder=move || match format.get() {
+                        HashInputFormat::Hex => ctx.t("Data hex"),
+                        HashInputFormat::Utf8 => ctx.t("Text"),
+                    }
+                    on:input=move |e| input.set(event_target_value(&e))
+                    prop:value=input
+                />
+                <div>
+                    <select
+                        class="bg-inherit border rounded ml-1 p-1"
+                        on:input=move |e| {
+                            format.set(HashInputFormat::from_str(&event_target_value(&e)).unwrap())
+                        }
+                        prop:value=move || format.get().to_str()
+                    >
+                        <option value=HashInputFormat::Hex.to_str()>"Hex"</option>
+                        <option value=HashInputFormat::Utf8.to_str()>"UTF-8"</option>
+                    </select>
+                </div>
+            </div>
+            {move || match input_data(&input.get(), format.get()) {
+                Ok(data) => {
+                    view! {
+                        <p class="text-stone-500">{format!("{} {}", data.len(), ctx.t("bytes"))}</p>
+                        {digests(&data)
+                            .into_iter()
+                            .map(|(opcode, digest)| {
+                                view! {
+                                    <div class="flex flex-wrap gap-1">
+                                        <span class="w-32">{opcode}</span>
+                                        <span class="font-mono break-all select-all">{digest}</span>
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    }
+                        .into_any()
+                }
+                Err(e) => view! { <p class="text-red-700">{format!("{e:#}")}</p> }.into_any(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,190 @@
+use bitcoincash::hashes::{
+    hex::{FromHex, ToHex},
+    sha256,
+};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, For, Get, GlobalAttributes, IntoAny,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{
+        cltv_wizard::LocktimeInput,
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        tx_input::TxInputSnapshot,
+        tx_output::TxOutputSnapshot,
+    },
+    timelock::{parse_locktime, Htlc},
+    util::script_to_cash_addr,
+    Context,
+};
+
+/// Build a hash time locked contract output, and the inputs claiming it with the preimage or
+/// refunding it after the timeout.
+#[component]
+pub fn HtlcWizard(
+    ctx: Context,
+    #[prop(into)] cash_addr_prefix: Signal<String>,
+    /// Called with the spending input and the transaction locktime it needs, if any.
+    on_add_input: impl Fn(TxInputSnapshot, Option<u32>) + Copy + Send + Sync + 'static,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let hash = RwSignal::new(String::new());
+    let preimage = RwSignal::new(String::new());
+    let recipient = RwSignal::new(String::new());
+    let refund = RwSignal::new(String::new());
+    let by_date = RwSignal::new(false);
+    let timeout = RwSignal::new(String::new());
+
+    let htlc = move || -> anyhow::Result<Htlc> {
+        Ok(Htlc {
+            hash: sha256::Hash::from_hex(hash.get().trim())
+                .map_err(|e| anyhow::anyhow!("{} {e}", ctx.t("Hash:")))?,
+            recipient: recipient
+                .get()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{} {e}", ctx.t("Recipient:")))?,
+            refund: refund
+                .get()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{} {e}", ctx.t("Refund to:")))?,
+            timeout: parse_locktime(by_date.get(), &timeout.get())
+                .map_err(|e| anyhow::anyhow!("{} {e}", ctx.t("Timeout:")))?,
+        })
+    };
+    let details = move || {
+        htlc().and_then(|htlc| {
+            let redeem_script = htlc.redeem_script();
+            let address = script_to_cash_addr(&redeem_script.to_p2sh(), &cash_addr_prefix.get())?;
+            Ok((redeem_script.to_hex(), address))
+        })
+    };
+    // The preimage is optional, it can be filled in when claiming
+    let claim_script_sig = move || {
+        let preimage = preimage.get();
+        let preimage = match preimage.trim() {
+            "" => None,
+            hex => Some(
+                Vec::from_hex(hex).map_err(|e| anyhow::anyhow!("{} {e}", ctx.t("Preimage:")))?,
+            ),
+        };
+        htlc()?.claim_script_sig_asm(preimage.as_deref())
+    };
+
+    let add_output = move |_| {
+        if let Ok((_, address)) = details() {
+            on_add_output(TxOutputSnapshot {
+                script_pubkey: ScriptInputValue::Addr(address),
+                script_display_format: ScriptDisplayFormat::Addr,
+                label: "HTLC".to_string(),
+                ..Default::default()
+            });
+        }
+    };
+    let add_claim_input = move |_| {
+        if let Ok(script_sig) = claim_script_sig() {
+            on_add_input(
+                TxInputSnapshot {
+                    script_sig: ScriptInputValue::Asm(script_sig),
+                    script_sig_format: ScriptDisplayFormat::Asm,
+                    label: ctx
+                        .t("HTLC claim: fill in txid, vout and the signature")
+                        .to_string(),
+                    ..Default::default()
+                },
+                None,
+            );
+        }
+    };
+    let add_refund_input = move |_| {
+        if let Ok(htlc) = htlc() {
+            on_add_input(
+                TxInputSnapshot {
+                    script_sig: ScriptInputValue::Asm(htlc.refund_script_sig_asm()),
+                    script_sig_format: ScriptDisplayFormat::Asm,
+                    label: ctx
+                        .t("HTLC refund: fill in txid, vout and the signature")
+                        .to_string(),
+                    ..Default::default()
+                },
+                Some(htlc.timeout),
+            );
+        }
+    };
+
+    let text_fields = [
+        ("Hash:", "SHA-256 hash of the secret, hex", hash),
+        ("Preimage:", "Secret, hex, only needed to claim", preimage),
+        ("Recipient:", "Public key or P2PKH address", recipient),
+        ("Refund to:", "Public key or P2PKH address", refund),
+    ];
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <For each=move || text_fields key=|(label, _, _)| *label let:field>
+                <div class="flex gap-1 mb-1">
+                    <label class="w-24">{move || ctx.t(field.0)}</label>
+                    <input
+                        class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow font-mono placeholder:text-stone-600"
+                        placeholder=move || ctx.t(field.1)
+                        on:input=move |e| field.2.set(event_target_value(&e))
+                        prop:value=field.2
+                    />
+                </div>
+            </For>
+            <div class="flex gap-1 mb-1">
+                <label class="w-24">{move || ctx.t("Timeout:")}</label>
+                <LocktimeInput ctx by_date text=timeout/>
+            </div>
+            {move || match details() {
+                Ok((redeem_script, address)) => {
+                    view! {
+                        <div class="break-all">
+                            <div>{ctx.t("Redeem script:")}" " <span class="font-mono">{redeem_script}</span></div>
+                            <div>{ctx.t("Address:")}" " <span class="font-mono">{address}</span></div>
+                        </div>
+                    }
+                        .into_any()
+                }
+                Err(_) if hash.get().trim().is_empty() => ().into_any(),
+                Err(e) => view! { <div class="text-red-700">{e.to_string()}</div> }.into_any(),
+            }}
+            {move || {
+                claim_script_sig()
+                    .err()
+                    .filter(|_| details().is_ok())
+                    .map(|e| view! { <div class="text-red-700">{e.to_string()}</div> })
+            }}
+            <div class="flex gap-1 mt-1">
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an output paying to the HTLC address")
+                    disabled=move || details().is_err()
+                    on:click=add_output
+                >
+                    {move || ctx.t("Add HTLC output")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an input claiming the HTLC with the preimage")
+                    disabled=move || claim_script_sig().is_err()
+                    on:click=add_claim_input
+                >
+                    {move || ctx.t("Add claim input")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an input refunding the HTLC, and set the transaction locktime it needs")
+                    disabled=move || details().is_err()
+                    on:click=add_refund_input
+                >
+                    {move || ctx.t("Add refund input")}
+                </button>
+            </div>
+        </div>
+    }
+}
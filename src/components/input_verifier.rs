@@ -0,0 +1,111 @@
+use bitcoincash::secp256k1::{All, Secp256k1};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, Get, GetUntracked, Memo,
+        OnAttribute, PropAttribute, RwSignal, Set, StoredValue, WithValue,
+    },
+    task::spawn_local,
+    view, IntoView,
+};
+
+use crate::{
+    partially_signed::PartiallySignedTransaction,
+    verification::Verdict,
+    verify::{fetch_missing, parse_transactions, verify},
+    Context,
+};
+
+/// Whether the signatures of each signed input in a standard template are valid for the output it
+/// spends, found in pasted previous transactions or fetched from the server. Other scripts aren't
+/// run, there is no script interpreter.
+#[component]
+pub fn InputVerifier(
+    ctx: Context,
+    tx: Memo<Option<PartiallySignedTransaction>>,
+    secp: StoredValue<Secp256k1<All>>,
+) -> impl IntoView {
+    let previous = RwSignal::new(String::new());
+    let verdicts = RwSignal::new(None::<Vec<Option<Verdict>>>);
+    let error = RwSignal::new(String::new());
+
+    let check = move |_| {
+        verdicts.set(None);
+        error.set(String::new());
+        let Some(tx) = tx.get_untracked() else {
+            error.set(ctx.t("The transaction isn't valid").to_string());
+            return;
+        };
+        let mut previous = match parse_transactions(&previous.get_untracked()) {
+            Ok(previous) => previous,
+            Err(e) => {
+                error.set(format!("{e:#}"));
+                return;
+            }
+        };
+        let server = (!ctx.offline()).then(|| ctx.chain_server()).flatten();
+        spawn_local(async move {
+            // Inputs whose outputs can't be fetched are reported as unchecked
+            if let Some(server) = server {
+                if let Err(e) = fetch_missing(&server, &tx, &mut previous).await {
+                    error.set(format!(
+                        "{} {e:#}",
+                        ctx.t("Fetching the outputs spent failed:")
+                    ));
+                }
+            }
+            verdicts.set(Some(secp.with_value(|secp| verify(secp, &tx, &previous))));
+        });
+    };
+
+    let results = move || {
+        verdicts.get().map(|verdicts| {
+            verdicts
+                .into_iter()
+                .enumerate()
+                .map(|(i, verdict)| {
+                    let (class, text) = match verdict {
+                        Some(Verdict::Valid(how)) => {
+                            ("text-green-600", format!("{} {how}", ctx.t("Valid:")))
+                        }
+                        Some(Verdict::Invalid(why)) => {
+                            ("text-red-700", format!("{} {why}", ctx.t("Invalid:")))
+                        }
+                        Some(Verdict::Unchecked(why)) => (
+                            "text-yellow-700",
+                            format!("{} {why}", ctx.t("Not checked:")),
+                        ),
+                        None => ("text-stone-500", ctx.t("Unsigned").to_string()),
+                    };
+                    view! {
+                        <p class=class>{format!("{} #{i}: {text}", ctx.t("Input"))}</p>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <p class="text-stone-500">
+                {move || ctx.t("Checks the signatures of P2PKH, P2PK and multisig inputs, also through P2SH, with the outputs they spend taken from the transactions pasted here or fetched from the server. Inputs with other scripts are left unchecked, as scripts aren't run.")}
+            </p>
+            <textarea
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono w-full placeholder:text-stone-600"
+                rows=2
+                spellcheck="false"
+                placeholder=move || ctx.t("Previous transactions hex, one per line")
+                on:input=move |e| previous.set(event_target_value(&e))
+                prop:value=previous
+            />
+            <button
+                class="border border-solid rounded border-stone-600 px-1"
+                on:click=check
+            >
+                {move || ctx.t("Check signatures")}
+            </button>
+            {results}
+            <p class="text-red-700">{error}</p>
+        </div>
+    }
+}
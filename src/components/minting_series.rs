@@ -0,0 +1,182 @@
+use anyhow::{Context as _, Result};
+use bitcoincash::{hashes::hex::FromHex, TokenID};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, Get, GlobalAttributes,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal, With,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{
+        token_data::{NftCapability, TokenDataSnapshot},
+        tx_output::TxOutputSnapshot,
+    },
+    macros::StrEnum,
+    minting::{self, IndexEncoding, Series},
+    util::short_hex,
+    Context,
+};
+
+/// Mints a collection of NFTs from an input's minting NFT, their commitments numbered in turn.
+#[component]
+pub fn MintingSeries(
+    ctx: Context,
+    /// Inputs carrying minting NFTs, with their index.
+    #[prop(into)]
+    minting_inputs: Signal<Vec<(usize, TokenDataSnapshot)>>,
+    on_add: impl Fn(Vec<TxOutputSnapshot>) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let input = RwSignal::new(None::<usize>);
+    let prefix = RwSignal::new(String::new());
+    let start = RwSignal::new("0".to_string());
+    let count = RwSignal::new(String::new());
+    let encoding = RwSignal::new(IndexEncoding::default());
+    let width = RwSignal::new("0".to_string());
+    let capability = RwSignal::new(NftCapability::Immutable);
+    let address = RwSignal::new(String::new());
+    let message = RwSignal::new(Ok::<_, String>(String::new()));
+
+    // The chosen input, or the first one
+    let selected = move || {
+        let minting_inputs = minting_inputs.get();
+        input
+            .get()
+            .and_then(|i| {
+                minting_inputs
+                    .iter()
+                    .find(|(index, _)| *index == i)
+                    .cloned()
+            })
+            .or_else(|| minting_inputs.first().cloned())
+    };
+    let build = move || -> Result<Vec<TxOutputSnapshot>> {
+        let (_, token) = selected().context("No input carries a minting NFT")?;
+        let category = TokenID::from_hex(token.category_id.trim()).context("Category")?;
+        let series = Series {
+            prefix: Vec::from_hex(prefix.get().trim()).context("Invalid prefix")?,
+            start: start.get().trim().parse().context("Invalid first index")?,
+            count: count.get().trim().parse().context("Invalid count")?,
+            encoding: encoding.get(),
+            width: width.get().trim().parse().context("Invalid width")?,
+        };
+        minting::build(&category, &series, capability.get(), &address.get())
+    };
+    let add = move |_| match build() {
+        Ok(outputs) => {
+            let count = outputs.len();
+            on_add(outputs);
+            message.set(Ok(format!("{} {count}.", ctx.t("Outputs added:"))));
+        }
+        Err(e) => message.set(Err(format!("{e:#}"))),
+    };
+    let field =
+        "border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600";
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex flex-wrap gap-1 mb-1">
+                <select
+                    class="bg-inherit border rounded p-1"
+                    on:input=move |e| input.set(event_target_value(&e).parse().ok())
+                    prop:value=move || selected().map(|(i, _)| i.to_string()).unwrap_or_default()
+                >
+                    {move || {
+                        minting_inputs
+                            .get()
+                            .into_iter()
+                            .map(|(i, token)| {
+                                view! {
+                                    <option value=i.to_string()>
+                                        {ctx.t("Input")}" "{i}": "{short_hex(&token.category_id)}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+                <select
+                    class="bg-inherit border rounded p-1"
+                    title=move || ctx.t("Capability of the minted NFTs")
+                    on:input=move |e| {
+                        if let Some(c) = NftCapability::from_str(&event_target_value(&e)) {
+                            capability.set(c);
+                        }
+                    }
+                    prop:value=move || capability.get().to_str()
+                >
+                    <option value="immutable">{move || ctx.t("Immutable")}</option>
+                    <option value="mutable">{move || ctx.t("Mutable")}</option>
+                    <option value="minting">{move || ctx.t("Minting")}</option>
+                </select>
+            </div>
+            <div class="flex flex-wrap gap-1 mb-1">
+                <input
+                    class=format!("{field} font-mono grow")
+                    placeholder=move || ctx.t("Commitment prefix, hex")
+                    on:input=move |e| prefix.set(event_target_value(&e))
+                    prop:value=prefix
+                />
+                <select
+                    class="bg-inherit border rounded p-1"
+                    title=move || ctx.t("How the index follows the prefix")
+                    on:input=move |e| {
+                        if let Some(e) = IndexEncoding::from_str(&event_target_value(&e)) {
+                            encoding.set(e);
+                        }
+                    }
+                    prop:value=move || encoding.get().to_str()
+                >
+                    <option value="number">{move || ctx.t("Script number")}</option>
+                    <option value="big-endian">{move || ctx.t("Big-endian")}</option>
+                    <option value="decimal">{move || ctx.t("Decimal digits")}</option>
+                </select>
+                <label title=move || ctx.t("Bytes the index is padded to, 0 for none")>
+                    {move || ctx.t("Width")}" "
+                    <input
+                        class=format!("{field} w-12")
+                        disabled=move || encoding.with(|e| *e == IndexEncoding::Number)
+                        on:input=move |e| width.set(event_target_value(&e))
+                        prop:value=width
+                    />
+                </label>
+            </div>
+            <div class="flex flex-wrap gap-1">
+                <label>
+                    {move || ctx.t("From")}" "
+                    <input
+                        class=format!("{field} w-20")
+                        on:input=move |e| start.set(event_target_value(&e))
+                        prop:value=start
+                    />
+                </label>
+                <input
+                    class=format!("{field} w-20")
+                    placeholder=move || ctx.t("Count")
+                    on:input=move |e| count.set(event_target_value(&e))
+                    prop:value=count
+                />
+                <input
+                    class=format!("{field} font-mono grow")
+                    placeholder=move || ctx.t("Recipient address")
+                    on:input=move |e| address.set(event_target_value(&e))
+                    prop:value=address
+                />
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=add>
+                    {move || ctx.t("Add outputs")}
+                </button>
+            </div>
+            <p
+                class=("text-stone-500", move || message.with(Result::is_ok))
+                class=("text-red-700", move || message.with(Result::is_err))
+            >
+                {move || message.get().unwrap_or_else(|e| e)}
+            </p>
+            <p class="text-stone-500">
+                {move || ctx.t("Keep the minting NFT in an output to mint more later, or it is burned.")}
+            </p>
+        </div>
+    }
+}
@@ -1,46 +1,241 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use leptos::{
     component,
     prelude::{
-        event_target_value, ClassAttribute, OnAttribute, PropAttribute, ReadSignal, RwSignal, Set,
+        event_target_value, Callable, Callback, ClassAttribute, CollectView, ElementChild, Get,
+        GetUntracked, GlobalAttributes, IntoAny, Memo, OnAttribute, PropAttribute, ReadSignal,
+        RwSignal, Set, Signal, Update, With,
     },
-    tachys::html::property::IntoProperty,
     view, IntoView,
 };
 
+use crate::fiat::FiatRate;
+use crate::opcode_reference::tooltip;
+use crate::util::{format_decimal, format_u32, parse_decimal, parse_u32, AmountUnit};
+use crate::validation::MAX_MONEY;
+use crate::Context;
+
+pub mod airdrop_builder;
+pub mod bcmr_builder;
+pub mod byte_order;
+pub mod cltv_wizard;
+pub mod coin_selector;
+pub mod diagnostics;
+pub mod hash_calculator;
+pub mod htlc_wizard;
+pub mod input_verifier;
+pub mod minting_series;
+pub mod multisig_wizard;
+pub mod network_editor;
+pub mod number_converter;
+pub mod op_return_builder;
+pub mod opcode_palette;
+pub mod p2sh_builder;
+pub mod pay_to_many;
+pub mod preferences;
+pub mod prevout_viewer;
 pub mod script_input;
+pub mod shortcut_help;
+pub mod sweep_tool;
+pub mod template_picker;
+pub mod template_variables;
 pub mod token_data;
+pub mod token_identity;
+pub mod token_summary;
 pub mod tracker;
+pub mod tx_editor;
 pub mod tx_input;
 pub mod tx_output;
+pub mod vault_prompt;
+pub mod watch_only;
+
+/// Set `signal` to `value`, notifying its subscribers only if the value changed, so restoring a
+/// whole card doesn't rerun everything that depends on the fields it leaves alone.
+pub fn set_changed<T: PartialEq + Send + Sync + 'static>(signal: RwSignal<T>, value: T) {
+    signal.maybe_update(|old| {
+        let changed = *old != value;
+        if changed {
+            *old = value;
+        }
+        changed
+    });
+}
 
+/// Input for a value parsed from its text, with [`FromStr`] and [`ToString`] unless `parse` and
+/// `format` are given. Text that doesn't parse or falls outside `min..=max` leaves the value alone
+/// and outlines the field in red, with the reason as its tooltip.
 #[component]
-pub fn ParsedInput<T: FromStr + Clone + Send + Sync + 'static>(value: RwSignal<T>) -> impl IntoView
+pub fn ParsedInput<T>(
+    ctx: Context,
+    value: RwSignal<T>,
+    #[prop(optional)] min: Option<T>,
+    #[prop(optional)] max: Option<T>,
+    /// Parses the text in place of [`FromStr`], or returns the message to show.
+    #[prop(optional)]
+    parse: Option<Callback<String, Result<T, String>>>,
+    #[prop(optional)] format: Option<Callback<T, String>>,
+) -> impl IntoView
 where
-    ReadSignal<T>: IntoProperty,
+    T: FromStr + ToString + PartialOrd + Clone + Send + Sync + 'static,
+    T::Err: Display,
 {
-    let parse_success = RwSignal::new(true);
-    let (thevalue, set_value) = value.split();
+    let error = RwSignal::new(None::<String>);
+    let format = move |v: T| match format {
+        Some(format) => format.run(v),
+        None => v.to_string(),
+    };
+    // Only rewrite the field when the value actually changes, so partial entries can be typed
+    let display = Memo::new(move |_| format(value.get()));
+    let parse = move |text: String| {
+        let v = match parse {
+            Some(parse) => parse.run(text)?,
+            None => text.trim().parse().map_err(|e: T::Err| e.to_string())?,
+        };
+        if let Some(min) = min.clone().filter(|min| v < *min) {
+            return Err(format!("{} {}", ctx.t("Must be at least"), format(min)));
+        }
+        if let Some(max) = max.clone().filter(|max| v > *max) {
+            return Err(format!("{} {}", ctx.t("Must be at most"), format(max)));
+        }
+        Ok(v)
+    };
 
     view! {
         <input
-            on:input=move |e| {
-                let new_value = event_target_value(&e);
-                match new_value.parse() {
-                    Ok(v) => {
-                        set_value(v);
-                        parse_success.set(true);
-                    }
-                    Err(_) => {
-                        parse_success.set(false);
-                    }
+            on:input=move |e| match parse(event_target_value(&e)) {
+                Ok(v) => {
+                    value.set(v);
+                    error.set(None);
                 }
+                Err(e) => error.set(Some(e)),
             }
-            prop:value=thevalue
+            prop:value=display
+            title=move || error.get()
             class="border border-solid rounded px-1 bg-stone-900 placeholder:text-stone-600"
-            class=("border-stone-600", parse_success)
-            class=("border-red-700", move || !parse_success())
+            class=("border-stone-600", move || error.with(Option::is_none))
+            class=("border-red-700", move || error.with(Option::is_some))
+        />
+    }
+}
+
+/// Input for an amount of sats, entered and displayed in `unit`, though an amount suffixed with
+/// its unit, like "0.0001 BCH", is converted exactly whichever unit is selected.
+#[component]
+pub fn AmountInput(
+    ctx: Context,
+    value: RwSignal<u64>,
+    #[prop(into)] unit: Signal<AmountUnit>,
+) -> impl IntoView {
+    view! {
+        <ParsedInput
+            ctx
+            value=value
+            max=MAX_MONEY
+            parse=Callback::new(move |text: String| {
+                unit.get_untracked().parse(&text).map_err(|e| e.to_string())
+            })
+            format=Callback::new(move |v| unit.get().format(v))
+        />
+    }
+}
+
+/// Input for an amount in base units, entered and displayed with `decimals` decimal places.
+#[component]
+pub fn DecimalInput(
+    ctx: Context,
+    value: RwSignal<u64>,
+    #[prop(into)] decimals: Signal<u8>,
+    max: u64,
+) -> impl IntoView {
+    view! {
+        <ParsedInput
+            ctx
+            value=value
+            max=max
+            parse=Callback::new(move |text: String| {
+                parse_decimal(&text, decimals.get_untracked()).map_err(|e| e.to_string())
+            })
+            format=Callback::new(move |v| format_decimal(v, decimals.get()))
+        />
+    }
+}
+
+/// Input for a u32 such as a sequence number or locktime, in decimal or `0x`-prefixed hex. `hex`
+/// toggles the display and follows whichever form was last typed.
+#[component]
+pub fn U32Input(ctx: Context, value: RwSignal<u32>, hex: RwSignal<bool>) -> impl IntoView {
+    view! {
+        <ParsedInput
+            ctx
+            value=value
+            parse=Callback::new(move |text: String| {
+                let v = parse_u32(&text).map_err(|e| e.to_string())?;
+                hex.set(text.trim().to_ascii_lowercase().starts_with("0x"));
+                Ok(v)
+            })
+            format=Callback::new(move |v| format_u32(v, hex.get()))
         />
     }
 }
+
+/// Link opening `href` on the block explorer in a new tab, shown only when there is one.
+#[component]
+pub fn ExplorerLink(ctx: Context, #[prop(into)] href: Signal<Option<String>>) -> impl IntoView {
+    move || {
+        href.get().map(|href| {
+            view! {
+                <a
+                    href=href
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    class="px-1 text-stone-500 hover:text-stone-300"
+                    title=move || ctx.t("Open in the block explorer")
+                >
+                    "↗"
+                </a>
+            }
+        })
+    }
+}
+
+/// Disassembled `asm`, documenting each opcode in its tooltip.
+#[component]
+pub fn AsmText(asm: String) -> impl IntoView {
+    let words = asm
+        .split(' ')
+        .enumerate()
+        .map(|(i, word)| {
+            let separator = (i > 0).then_some(" ");
+            match tooltip(word) {
+                Some(doc) => view! {
+                    {separator}
+                    <span class="cursor-help" title=doc>
+                        {word.to_string()}
+                    </span>
+                }
+                .into_any(),
+                None => view! { {separator}{word.to_string()} }.into_any(),
+            }
+        })
+        .collect_view();
+    view! { <span class="font-mono break-all">{words}</span> }
+}
+
+/// Approximate fiat equivalent of `sats`, shown only when an exchange rate is available.
+#[component]
+pub fn FiatValue(
+    ctx: Context,
+    #[prop(into)] sats: Signal<u64>,
+    rate: ReadSignal<Option<FiatRate>>,
+) -> impl IntoView {
+    move || {
+        rate.get().map(|rate| {
+            view! {
+                <span class="text-sm text-stone-500 ml-2" title=move || ctx.t("Approximate, for information only")>
+                    {move || rate.format(sats.get())}
+                </span>
+            }
+        })
+    }
+}
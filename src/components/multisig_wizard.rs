@@ -0,0 +1,132 @@
+use bitcoincash::{
+    hashes::hex::ToHex,
+    secp256k1::{All, Secp256k1},
+};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, For, Get, GlobalAttributes, IntoAny,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal, StoredValue, WithValue,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        tx_input::{TxInputSnapshot, UtxoPubkeyData},
+        tx_output::TxOutputSnapshot,
+    },
+    multisig::{Cosigner, Multisig},
+    util::script_to_cash_addr,
+    Context,
+};
+
+/// Set up a 2-of-3 escrow from the keys of the three parties, producing the funding output and the
+/// unsigned input spending it.
+#[component]
+pub fn MultisigWizard(
+    ctx: Context,
+    secp: StoredValue<Secp256k1<All>>,
+    #[prop(into)] cash_addr_prefix: Signal<String>,
+    on_add_input: impl Fn(TxInputSnapshot) + Copy + Send + Sync + 'static,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let cosigners = [(); 3].map(|_| RwSignal::new(String::new()));
+
+    let multisig = move || {
+        let cosigners = cosigners
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                c.get()
+                    .parse::<Cosigner>()
+                    .map_err(|e| anyhow::anyhow!("{} {}: {e}", ctx.t("Cosigner"), i + 1))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        secp.with_value(|secp| Multisig::new(secp, 2, cosigners))
+    };
+    let details = move || {
+        multisig().and_then(|multisig| {
+            let redeem_script = multisig.redeem_script();
+            let address = script_to_cash_addr(&redeem_script.to_p2sh(), &cash_addr_prefix.get())?;
+            Ok((redeem_script.to_hex(), address))
+        })
+    };
+
+    let add_input = move |_| {
+        if let Ok(multisig) = multisig() {
+            on_add_input(TxInputSnapshot {
+                unsigned: true,
+                utxo_pubkey: UtxoPubkeyData::Hex(
+                    multisig.unsigned_script_sig().raw_script().to_hex(),
+                ),
+                label: ctx
+                    .t("2-of-3 escrow: fill in txid, vout and amount")
+                    .to_string(),
+                ..Default::default()
+            });
+        }
+    };
+    let add_output = move |_| {
+        if let Ok((_, address)) = details() {
+            on_add_output(TxOutputSnapshot {
+                script_pubkey: ScriptInputValue::Addr(address),
+                script_display_format: ScriptDisplayFormat::Addr,
+                label: ctx.t("2-of-3 escrow").to_string(),
+                ..Default::default()
+            });
+        }
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="mb-1">
+                {move || ctx.t("2-of-3 escrow: enter a public key or an xpub of each party. An xpub may be followed by a derivation path like")}" "
+                <span class="font-mono">"/0/5"</span> {move || ctx.t(", by default the first receiving address.")}
+            </div>
+            <For each=move || cosigners.into_iter().enumerate() key=|(i, _)| *i let:cosigner>
+                <div class="flex gap-1 mb-1">
+                    <label class="w-24">{move || format!("{} {}:", ctx.t("Cosigner"), cosigner.0 + 1)}</label>
+                    <input
+                        class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow font-mono placeholder:text-stone-600"
+                        placeholder=move || ctx.t("Public key or xpub")
+                        on:input=move |e| cosigner.1.set(event_target_value(&e))
+                        prop:value=cosigner.1
+                    />
+                </div>
+            </For>
+            {move || match details() {
+                Ok((redeem_script, address)) => {
+                    view! {
+                        <div class="break-all">
+                            <div>{ctx.t("Redeem script:")}" " <span class="font-mono">{redeem_script}</span></div>
+                            <div>{ctx.t("Address:")}" " <span class="font-mono">{address}</span></div>
+                        </div>
+                    }
+                        .into_any()
+                }
+                Err(_) if cosigners.iter().all(|c| c.get().trim().is_empty()) => ().into_any(),
+                Err(e) => view! { <div class="text-red-700">{e.to_string()}</div> }.into_any(),
+            }}
+            <div class="flex gap-1 mt-1">
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an output paying to the escrow address")
+                    disabled=move || details().is_err()
+                    on:click=add_output
+                >
+                    {move || ctx.t("Add funding output")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-2 disabled:opacity-30"
+                    title=move || ctx.t("Add an unsigned input spending from the escrow, for the cosigners to sign in Electron Cash")
+                    disabled=move || details().is_err()
+                    on:click=add_input
+                >
+                    {move || ctx.t("Add spending input")}
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,110 @@
+use bitcoincash::Network;
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, For, Get, GetUntracked,
+        GlobalAttributes, OnAttribute, PropAttribute, RwSignal, Set, Update,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    macros::StrEnum,
+    networks::{self, CustomNetwork},
+    Context, NETWORKS,
+};
+
+/// Definitions of custom networks, saving them on every change.
+#[component]
+pub fn NetworkEditor(networks: RwSignal<Vec<CustomNetwork>>, ctx: Context) -> impl IntoView {
+    let name = RwSignal::new(String::new());
+    let prefix = RwSignal::new(String::new());
+    let base = RwSignal::new(Network::Regtest);
+    // One URL per line
+    let servers = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    let add = move |_| {
+        let network = CustomNetwork {
+            name: name.get_untracked().trim().to_string(),
+            cash_addr_prefix: prefix.get_untracked().trim().to_string(),
+            base: base.get_untracked(),
+            electrum_servers: servers
+                .get_untracked()
+                .lines()
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        if let Err(e) = network.validate(&networks.get_untracked()) {
+            error.set(e.to_string());
+            return;
+        }
+        networks.update(|n| n.push(network));
+        networks::save(&networks.get_untracked());
+        name.set(String::new());
+        prefix.set(String::new());
+        servers.set(String::new());
+        error.set(String::new());
+    };
+    let remove = move |name: String| {
+        networks.update(|n| n.retain(|n| n.name != name));
+        networks::save(&networks.get_untracked());
+    };
+    let input_class =
+        "border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600";
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="table">
+                <For each=move || networks.get() key=|n| n.name.clone() let:network>
+                    <div class="table-row">
+                        <div class="table-cell pr-2">{network.name.clone()}</div>
+                        <div class="table-cell pr-2 font-mono">{network.cash_addr_prefix.clone()}</div>
+                        <div class="table-cell pr-2">{network.base.to_str()}</div>
+                        <div class="table-cell pr-2 font-mono">{network.electrum_servers.join(" ")}</div>
+                        <button class="table-cell text-red-700" on:click=move |_| remove(network.name.clone())>
+                            {move || ctx.t("Delete")}
+                        </button>
+                    </div>
+                </For>
+            </div>
+            <div class="flex flex-wrap items-start gap-1 mt-1">
+                <input
+                    class=input_class
+                    placeholder=move || ctx.t("Name")
+                    on:input=move |e| name.set(event_target_value(&e))
+                    prop:value=name
+                />
+                <input
+                    class=input_class
+                    placeholder=move || ctx.t("CashAddr prefix")
+                    on:input=move |e| prefix.set(event_target_value(&e))
+                    prop:value=prefix
+                />
+                <select
+                    class="bg-inherit border rounded p-1"
+                    title=move || ctx.t("Built-in network whose rules it follows")
+                    on:input=move |e| base.set(Network::from_str(&event_target_value(&e)).unwrap())
+                    prop:value=move || base.get().to_str()
+                >
+                    {NETWORKS
+                        .map(|n| view! { <option value=n.to_str()>{n.to_str()}</option> })
+                        .collect_view()}
+                </select>
+                <textarea
+                    class=input_class
+                    rows=1
+                    placeholder=move || ctx.t("Electrum servers, one per line")
+                    on:input=move |e| servers.set(event_target_value(&e))
+                    prop:value=servers
+                />
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=add>
+                    {move || ctx.t("Add network")}
+                </button>
+            </div>
+            <p class="text-red-700">{error}</p>
+        </div>
+    }
+}
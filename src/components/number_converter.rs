@@ -0,0 +1,102 @@
+use bitcoincash::{blockdata::script::Builder, hashes::hex::ToHex};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, IntoAny, OnAttribute, PropAttribute,
+        Read, RwSignal, Set,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    cash_assembly::{disassemble_in, InstructionSet},
+    hex_format,
+    util::{push_minimal, script_num},
+    vm_number::{self, is_minimal},
+    Context,
+};
+
+/// Converter between integers and their script number encoding, either side editable.
+#[component]
+pub fn NumberConverter(ctx: Context) -> impl IntoView {
+    let number = RwSignal::new(String::new());
+    let hex = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    let on_number = move |e| {
+        let value = event_target_value(&e);
+        match vm_number::encode(&value) {
+            Ok(data) => {
+                hex.set(data.to_hex());
+                error.set(String::new());
+            }
+            Err(e) => error.set(format!("{e:#}")),
+        }
+        number.set(value);
+    };
+    let on_hex = move |e| {
+        let value = event_target_value(&e);
+        match hex_format::parse(&value).and_then(|data| vm_number::decode(&data)) {
+            Ok(n) => {
+                number.set(n.to_string());
+                error.set(String::new());
+            }
+            Err(e) => error.set(format!("{e:#}")),
+        }
+        hex.set(value);
+    };
+    // How the current encoding is pushed, and whether the VM would read it as a number
+    let details = move || {
+        let data = hex_format::parse(&hex.read()).ok()?;
+        let n = vm_number::decode(&data).ok()?;
+        // Push opcodes are the same in every instruction set
+        let push = disassemble_in(
+            &push_minimal(Builder::new(), &data).into_script(),
+            InstructionSet::Current,
+        );
+        Some(match is_minimal(&data) {
+            true => view! {
+                <p class="text-stone-500">
+                    {format!("{} {} ", data.len(), ctx.t("bytes, pushed with"))}
+                    <span class="font-mono">{push}</span>
+                </p>
+            }
+            .into_any(),
+            false => view! {
+                <p class="text-yellow-700">
+                    {format!(
+                        "{} {n} {} {}",
+                        ctx.t("Not minimally encoded, numeric opcodes fail on it."),
+                        ctx.t("is minimally"),
+                        match script_num(n).to_hex() {
+                            hex if hex.is_empty() => ctx.t("empty").to_string(),
+                            hex => hex,
+                        },
+                    )}
+                </p>
+            }
+            .into_any(),
+        })
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex flex-wrap gap-1">
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono grow placeholder:text-stone-600"
+                    placeholder=move || ctx.t("Integer")
+                    on:input=on_number
+                    prop:value=number
+                />
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono grow placeholder:text-stone-600"
+                    placeholder=move || ctx.t("Script number hex")
+                    on:input=on_hex
+                    prop:value=hex
+                />
+            </div>
+            {details}
+            <p class="text-red-700">{error}</p>
+        </div>
+    }
+}
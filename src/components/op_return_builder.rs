@@ -0,0 +1,118 @@
+use bitcoincash::{hashes::hex::ToHex, Script};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, For, Get, GetUntracked, GetValue,
+        OnAttribute, PropAttribute, Read, RwSignal, Set, SetValue, StoredValue, Update, Write,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::script_input::ScriptInputValue,
+    macros::StrEnum,
+    op_return::{
+        build_script, check_size, parse_script, PushField, PushFormat, MAX_STANDARD_OP_RETURN_SIZE,
+    },
+    Context,
+};
+
+/// Structured editor for an OP_RETURN output, writing the locking script on every change.
+#[component]
+pub fn OpReturnBuilder(ctx: Context, script_pubkey: RwSignal<ScriptInputValue>) -> impl IntoView {
+    let initial = Script::try_from(script_pubkey.get_untracked())
+        .ok()
+        .and_then(|s| parse_script(&s))
+        .filter(|fields| !fields.is_empty())
+        .unwrap_or_else(|| vec![PushField::default()]);
+    let next_key = StoredValue::new(initial.len());
+    let fields = RwSignal::new(
+        initial
+            .into_iter()
+            .enumerate()
+            .map(|(key, field)| (key, RwSignal::new(field)))
+            .collect::<Vec<_>>(),
+    );
+    let built = move || {
+        let fields = fields
+            .read()
+            .iter()
+            .map(|(_, f)| f.get())
+            .collect::<Vec<_>>();
+        build_script(&fields)
+    };
+    // Only write the script on edits, opening the builder shouldn't change the output
+    let sync = move || {
+        if let Ok(script) = built() {
+            script_pubkey.set(ScriptInputValue::Hex(script.to_hex()));
+        }
+    };
+    let add_field = move |_| {
+        let key = next_key.get_value();
+        next_key.set_value(key + 1);
+        fields
+            .write()
+            .push((key, RwSignal::new(PushField::default())));
+        sync();
+    };
+    let remove_field = move |key: usize| {
+        fields.update(|fields| fields.retain(|(k, _)| *k != key));
+        sync();
+    };
+    let status = move || match built().and_then(|script| {
+        check_size(&script)?;
+        Ok(script.len())
+    }) {
+        Ok(len) => (
+            false,
+            format!("{len}/{MAX_STANDARD_OP_RETURN_SIZE} {}", ctx.t("bytes")),
+        ),
+        Err(e) => (true, e.to_string()),
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 my-1 text-sm">
+            <For each=move || fields.get() key=|(key, _)| *key let:field>
+                {
+                    let (key, field) = field;
+                    view! {
+                        <div class="flex gap-1 mb-1">
+                            <select
+                                class="bg-inherit border rounded p-1"
+                                on:input=move |e| {
+                                    field.write().format = PushFormat::from_str(&event_target_value(&e)).unwrap();
+                                    sync();
+                                }
+                                prop:value=move || field.read().format.to_str()
+                            >
+                                <option value=PushFormat::Utf8.to_str()>UTF-8</option>
+                                <option value=PushFormat::Hex.to_str()>Hex</option>
+                                <option value=PushFormat::Number.to_str()>{move || ctx.t("Number")}</option>
+                            </select>
+                            <input
+                                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 grow font-mono"
+                                on:input=move |e| {
+                                    field.write().value = event_target_value(&e);
+                                    sync();
+                                }
+                                prop:value=move || field.read().value.clone()
+                            />
+                            <button
+                                class="border border-solid rounded border-stone-600 px-2 bg-red-950"
+                                on:click=move |_| remove_field(key)
+                            >
+                                "−"
+                            </button>
+                        </div>
+                    }
+                }
+            </For>
+            <div class="flex justify-between">
+                <button class="border border-solid rounded border-stone-600 px-2" on:click=add_field>
+                    {move || ctx.t("Add push")}
+                </button>
+                <span class=("text-red-700", move || status().0)>{move || status().1}</span>
+            </div>
+        </div>
+    }
+}
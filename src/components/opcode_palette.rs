@@ -0,0 +1,165 @@
+use anyhow::Result;
+use bitcoincash::{blockdata::script::Builder, hashes::hex::ToHex, Script};
+use leptos::{
+    component,
+    html::Textarea,
+    prelude::{
+        event_target_value, request_animation_frame, ClassAttribute, CollectView, ElementChild,
+        GetUntracked, GlobalAttributes, NodeRef, OnAttribute, PropAttribute, Read, RwSignal, Set,
+        Update, With,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    cash_assembly::{disassemble_in, opcode_name_in, InstructionSet},
+    components::script_input::{ScriptDisplayFormat, ScriptInputValue},
+    macros::StrEnum,
+    op_return::{PushField, PushFormat},
+    opcode_reference::tooltip,
+    opcodes::{insert_token, push_asm, CATEGORIES},
+    util::push_minimal,
+    Context,
+};
+
+/// The script input last focused, which the palette inserts into.
+#[derive(Copy, Clone)]
+pub struct PaletteTarget {
+    pub value: RwSignal<ScriptInputValue>,
+    pub format: RwSignal<ScriptDisplayFormat>,
+    pub textarea: NodeRef<Textarea>,
+}
+
+impl PaletteTarget {
+    /// Insert `token` at the caret, switching the input to ASM with the opcodes of `set`. Returns
+    /// false if the input is gone.
+    fn insert(self, token: &str, set: InstructionSet) -> Result<bool> {
+        let (Some(value), Some(format)) = (
+            self.value.try_get_untracked(),
+            self.format.try_get_untracked(),
+        ) else {
+            return Ok(false);
+        };
+        let textarea = self.textarea.get_untracked();
+        let (asm, caret) = match value {
+            ScriptInputValue::Asm(asm) if format == ScriptDisplayFormat::Asm => {
+                let caret = textarea
+                    .as_ref()
+                    .and_then(|t| t.selection_start().ok().flatten());
+                (asm, caret.map_or(usize::MAX, |c| c as usize))
+            }
+            ScriptInputValue::Asm(asm) => (asm, usize::MAX),
+            value if value.is_empty() => (String::new(), 0),
+            value => (disassemble_in(&Script::try_from(value)?, set), usize::MAX),
+        };
+        let (asm, caret) = insert_token(&asm, caret, token);
+        self.format.set(ScriptDisplayFormat::Asm);
+        self.value.set(ScriptInputValue::Asm(asm));
+        if let Some(textarea) = textarea {
+            // After the new value is rendered, which would move the caret to the end
+            request_animation_frame(move || {
+                let _ = textarea.focus();
+                let _ = textarea.set_selection_range(caret as u32, caret as u32);
+            });
+        }
+        Ok(true)
+    }
+}
+
+/// Buttons inserting opcodes and pushes into the last focused script input.
+#[component]
+pub fn OpcodePalette(target: RwSignal<Option<PaletteTarget>>, ctx: Context) -> impl IntoView {
+    let push = RwSignal::new(PushField::default());
+    let error = RwSignal::new(String::new());
+
+    let insert = move |token: &str| {
+        let Some(palette_target) = target.get_untracked() else {
+            return;
+        };
+        let set = InstructionSet::for_network(ctx.network.get_untracked());
+        match palette_target.insert(token, set) {
+            Ok(true) => error.set(String::new()),
+            // The card was deleted
+            Ok(false) => target.set(None),
+            Err(e) => error.set(format!("{} {e}", ctx.t("The script isn't valid:"))),
+        }
+    };
+    let insert_push = move |_| match push.with(push_asm) {
+        Ok(token) => insert(&token),
+        Err(e) => error.set(format!("{} {e}", ctx.t("Invalid push:"))),
+    };
+    // The bytes of the push, with the smallest opcode that pushes the data
+    let encoded = move || {
+        let data = push
+            .with(|p| (!p.value.is_empty()).then(|| p.data()))?
+            .ok()?;
+        let script = push_minimal(Builder::new(), &data).into_script();
+        // Push opcodes are the same in every instruction set
+        let opcode = opcode_name_in(script[0], InstructionSet::Current);
+        Some(format!("{opcode} {}", script.to_hex()))
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm bg-stone-800 sticky top-0 z-10">
+            {CATEGORIES
+                .iter()
+                .map(|category| {
+                    view! {
+                        <div class="flex flex-wrap items-center gap-1 mb-1">
+                            <span class="w-28 text-stone-500">{move || ctx.t(category.name)}</span>
+                            {category
+                                .opcodes
+                                .iter()
+                                .map(|&opcode| {
+                                    view! {
+                                        <button
+                                            class="border border-solid rounded border-stone-600 px-1 font-mono text-xs"
+                                            title=tooltip(opcode)
+                                            on:mousedown=|e| e.prevent_default()
+                                            on:click=move |_| insert(opcode)
+                                        >
+                                            {opcode.trim_start_matches("OP_")}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    }
+                })
+                .collect_view()}
+            <div class="flex flex-wrap items-center gap-1">
+                <span class="w-28 text-stone-500">{move || ctx.t("Push data")}</span>
+                <select
+                    class="bg-inherit border rounded p-1"
+                    on:input=move |e| {
+                        let format = PushFormat::from_str(&event_target_value(&e)).unwrap();
+                        push.update(|p| p.format = format);
+                    }
+                    prop:value=move || push.read().format.to_str()
+                >
+                    <option value=PushFormat::Utf8.to_str()>{move || ctx.t("Text")}</option>
+                    <option value=PushFormat::Hex.to_str()>"Hex"</option>
+                    <option value=PushFormat::Number.to_str()>{move || ctx.t("Number")}</option>
+                </select>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono grow"
+                    on:input=move |e| push.update(|p| p.value = event_target_value(&e))
+                    prop:value=move || push.read().value.clone()
+                />
+                <button
+                    class="border border-solid rounded border-stone-600 px-1"
+                    title=move || ctx.t("Insert a push of this data")
+                    on:mousedown=|e| e.prevent_default()
+                    on:click=insert_push
+                >
+                    {move || ctx.t("Push")}
+                </button>
+            </div>
+            <p class="font-mono break-all text-stone-500">{encoded}</p>
+            <p class="text-red-700">{error}</p>
+            <p class="text-stone-500" class:hidden=move || target.with(Option::is_some)>
+                {move || ctx.t("Click into a script field, then on an opcode to insert it there.")}
+            </p>
+        </div>
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use bitcoincash::{hashes::hex::ToHex, Script};
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, Effect, ElementChild, Get, GetUntracked, IntoAny,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{
+        opcode_palette::PaletteTarget,
+        script_input::{ScriptDisplayFormat, ScriptInput, ScriptInputValue},
+    },
+    macros::StrEnum,
+    util::{script_to_cash_addr, to_p2sh32},
+    validation::ScriptRole,
+    Context,
+};
+
+/// Editor of the redeem script an output pays to through P2SH20 or P2SH32, writing the locking
+/// script on every change. The redeem script is kept with the output to edit it again.
+#[component]
+pub fn P2shBuilder(
+    ctx: Context,
+    script_pubkey: RwSignal<ScriptInputValue>,
+    redeem_script: RwSignal<ScriptInputValue>,
+    p2sh32: RwSignal<bool>,
+    palette: RwSignal<Option<PaletteTarget>>,
+) -> impl IntoView {
+    let format = RwSignal::new(match redeem_script.get_untracked() {
+        value if value.is_empty() => ScriptDisplayFormat::Asm,
+        value => value.format(),
+    });
+    let locking_script = move || -> Result<Option<Script>> {
+        let value = redeem_script.get();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let redeem_script = Script::try_from(value)?;
+        Ok(Some(match p2sh32.get() {
+            true => to_p2sh32(&redeem_script),
+            false => redeem_script.to_p2sh(),
+        }))
+    };
+    let write = move |script: Script| script_pubkey.set(ScriptInputValue::Hex(script.to_hex()));
+    // Only write the locking script on edits, opening the builder shouldn't change the output
+    Effect::new(move |first_run: Option<()>| {
+        let script = locking_script();
+        if first_run.is_some() {
+            if let Ok(Some(script)) = script {
+                write(script);
+            }
+        }
+    });
+    // The locking script edited since, or written before the redeem script was
+    let mismatch = move || {
+        let script = locking_script().ok().flatten()?;
+        let current = Script::try_from(script_pubkey.get()).ok();
+        (current.as_ref() != Some(&script)).then_some(script)
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-1 text-sm">
+            <div class="flex">
+                <ScriptInput
+                    value=redeem_script
+                    format
+                    ctx
+                    palette
+                    rows=2
+                    role=ScriptRole::Locking
+                    placeholder=Signal::derive(move || match format.get() {
+                        ScriptDisplayFormat::Hex => ctx.t("Redeem script hex"),
+                        _ => ctx.t("Redeem script ASM"),
+                    })
+                />
+                <div>
+                    <select
+                        class="bg-inherit border rounded ml-1 p-1"
+                        on:input=move |e| {
+                            format.set(ScriptDisplayFormat::from_str(&event_target_value(&e)).unwrap())
+                        }
+                        prop:value=move || format.get().to_str()
+                    >
+                        <option value=ScriptDisplayFormat::Asm.to_str()>"Asm"</option>
+                        <option value=ScriptDisplayFormat::Hex.to_str()>"Hex"</option>
+                    </select>
+                    <select
+                        class="bg-inherit border rounded ml-1 p-1"
+                        title=move || ctx.t("P2SH32 pays to a 32-byte hash, which can't be found colliding with another script")
+                        on:input=move |e| p2sh32.set(event_target_value(&e) == "p2sh32")
+                        prop:value=move || if p2sh32.get() { "p2sh32" } else { "p2sh20" }
+                    >
+                        <option value="p2sh20">"P2SH20"</option>
+                        <option value="p2sh32">"P2SH32"</option>
+                    </select>
+                </div>
+            </div>
+            {move || {
+                locking_script()
+                    .ok()
+                    .flatten()
+                    .and_then(|script| script_to_cash_addr(&script, &ctx.cash_addr_prefix()).ok())
+                    .map(|address| view! { <p class="font-mono break-all">{address}</p> })
+            }}
+            {move || match mismatch() {
+                Some(script) => view! {
+                    <p class="text-yellow-700">
+                        {ctx.t("The locking script doesn't pay to this redeem script.")}
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1 ml-2"
+                            on:click=move |_| write(script.clone())
+                        >
+                            {ctx.t("Pay to it")}
+                        </button>
+                    </p>
+                }
+                .into_any(),
+                None => ().into_any(),
+            }}
+        </div>
+    }
+}
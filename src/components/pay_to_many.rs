@@ -0,0 +1,65 @@
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, Get, GlobalAttributes, OnAttribute,
+        PropAttribute, Read, RwSignal, Set, Signal,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::tx_output::TxOutputSnapshot, macros::StrEnum, pay_to_many, util::AmountUnit,
+    Context,
+};
+
+/// Paste area turning `address, amount` lines into outputs.
+#[component]
+pub fn PayToMany(
+    ctx: Context,
+    #[prop(into)] unit: Signal<AmountUnit>,
+    on_add: impl Fn(Vec<TxOutputSnapshot>) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let text = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    let add = move |_| match pay_to_many::parse(&text.get(), unit.get()) {
+        Ok(outputs) => {
+            on_add(outputs);
+            text.set(String::new());
+            error.set(None);
+        }
+        Err(e) => error.set(Some(format!("{e:#}"))),
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <textarea
+                spellcheck="false"
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-stone-900 placeholder:text-stone-600 font-mono"
+                placeholder=move || {
+                    format!(
+                        "{} {}. {}",
+                        ctx.t("One payment per line as: address, amount in"),
+                        unit.get().to_str(),
+                        ctx.t("Use ! as the amount to send the rest."),
+                    )
+                }
+                on:input=move |e| {
+                    text.set(event_target_value(&e));
+                    error.set(None);
+                }
+                prop:value=text
+            />
+            <div class="flex items-center gap-2">
+                <button
+                    class="border border-solid rounded border-stone-600 px-2"
+                    title=move || ctx.t("Add an output for each line")
+                    on:click=add
+                >
+                    {move || ctx.t("Add outputs")}
+                </button>
+                <span class="text-red-700">{move || error.read().clone()}</span>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,243 @@
+use bitcoincash::Network;
+use leptos::{
+    component,
+    prelude::{
+        event_target_checked, event_target_value, ClassAttribute, CollectView, ElementChild,
+        GetUntracked, OnAttribute, PropAttribute, Read, RwSignal, Update,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    backends::{self, LimitOverrides, OverridesField},
+    components::script_input::ScriptDisplayFormat,
+    macros::StrEnum,
+    partially_signed::SignatureType,
+    preferences::Preferences,
+    util::AddressFormat,
+    Context, NETWORKS,
+};
+
+/// Editor of the [`Preferences`], saving them on every change.
+#[component]
+pub fn PreferencesPanel(preferences: RwSignal<Preferences>, ctx: Context) -> impl IntoView {
+    let set = move |f: &dyn Fn(&mut Preferences)| {
+        preferences.update(|p| f(p));
+        preferences.get_untracked().save();
+    };
+    // One limit of a backend, its default as the placeholder
+    let limit_input = move |field: OverridesField,
+                            limit: fn(&mut LimitOverrides) -> &mut Option<u32>,
+                            default: u64| {
+        view! {
+            <input
+                class="table-cell border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 w-24"
+                placeholder=default.to_string()
+                on:change=move |e| {
+                    let value = match event_target_value(&e).trim() {
+                        "" => Ok(None),
+                        text => text.parse().map(Some),
+                    };
+                    if let Ok(value) = value {
+                        set(&|p| *limit(field(&mut p.backend_limits)) = value);
+                    }
+                }
+                prop:value=move || {
+                    let mut limits = preferences.read().backend_limits;
+                    limit(field(&mut limits)).map_or(String::new(), |v| v.to_string())
+                }
+            />
+        }
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="table">
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Default network:")}</label>
+                    <select
+                        class="table-cell bg-inherit border rounded p-1"
+                        on:input=move |e| {
+                            let network = Network::from_str(&event_target_value(&e)).unwrap();
+                            set(&|p| p.network = network);
+                        }
+                        prop:value=move || preferences.read().network.to_str()
+                    >
+                        {NETWORKS
+                            .map(|n| view! { <option value=n.to_str()>{n.to_str()}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("TX version:")}</label>
+                    <input
+                        class="table-cell border border-solid rounded border-stone-600 px-1 bg-stone-900"
+                        on:change=move |e| {
+                            if let Ok(version) = event_target_value(&e).trim().parse() {
+                                set(&|p| p.tx_version = version);
+                            }
+                        }
+                        prop:value=move || preferences.read().tx_version.to_string()
+                    />
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Sequence Number:")}</label>
+                    <input
+                        class="table-cell border border-solid rounded border-stone-600 px-1 bg-stone-900"
+                        on:change=move |e| {
+                            if let Ok(sequence) = event_target_value(&e).trim().parse() {
+                                set(&|p| p.sequence = sequence);
+                            }
+                        }
+                        prop:value=move || preferences.read().sequence.to_string()
+                    />
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Unlocking scripts in:")}</label>
+                    <select
+                        class="table-cell bg-inherit border rounded p-1"
+                        on:input=move |e| {
+                            let format = ScriptDisplayFormat::from_str(&event_target_value(&e)).unwrap();
+                            set(&|p| p.input_script_format = format);
+                        }
+                        prop:value=move || preferences.read().input_script_format.to_str()
+                    >
+                        <option value=ScriptDisplayFormat::Asm.to_str()>Asm</option>
+                        <option value=ScriptDisplayFormat::Hex.to_str()>Hex</option>
+                    </select>
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Locking scripts in:")}</label>
+                    <select
+                        class="table-cell bg-inherit border rounded p-1"
+                        on:input=move |e| {
+                            let format = ScriptDisplayFormat::from_str(&event_target_value(&e)).unwrap();
+                            set(&|p| p.output_script_format = format);
+                        }
+                        prop:value=move || preferences.read().output_script_format.to_str()
+                    >
+                        <option value=ScriptDisplayFormat::Addr.to_str()>{move || ctx.t("Address")}</option>
+                        <option value=ScriptDisplayFormat::Legacy.to_str()>{move || ctx.t("Legacy")}</option>
+                        <option value=ScriptDisplayFormat::Asm.to_str()>Asm</option>
+                        <option value=ScriptDisplayFormat::Hex.to_str()>Hex</option>
+                    </select>
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Addresses:")}</label>
+                    <select
+                        class="table-cell bg-inherit border rounded p-1"
+                        on:input=move |e| {
+                            let format = AddressFormat::from_str(&event_target_value(&e)).unwrap();
+                            set(&|p| p.address_format = format);
+                        }
+                        prop:value=move || preferences.read().address_format.to_str()
+                    >
+                        <option value=AddressFormat::Prefixed.to_str()>{move || ctx.t("with prefix")}</option>
+                        <option value=AddressFormat::Bare.to_str()>{move || ctx.t("without prefix")}</option>
+                    </select>
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Estimate fees for:")}</label>
+                    <select
+                        class="table-cell bg-inherit border rounded p-1"
+                        title=move || ctx.t("Signature type unsigned inputs are sized for, Schnorr signatures are 7 bytes shorter")
+                        on:input=move |e| {
+                            let signatures = SignatureType::from_str(&event_target_value(&e)).unwrap();
+                            set(&|p| p.signature_type = signatures);
+                        }
+                        prop:value=move || preferences.read().signature_type.to_str()
+                    >
+                        <option value=SignatureType::Ecdsa.to_str()>{move || ctx.t("ECDSA signatures")}</option>
+                        <option value=SignatureType::Schnorr.to_str()>{move || ctx.t("Schnorr signatures")}</option>
+                    </select>
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Fee rate (sat/byte):")}</label>
+                    <input
+                        class="table-cell border border-solid rounded border-stone-600 px-1 bg-stone-900"
+                        title=move || ctx.t("Paid by outputs sending the max or paying the fee, and the starting rate of coin selection and sweeping")
+                        on:change=move |e| {
+                            if let Ok(rate) = event_target_value(&e).trim().parse::<f64>() {
+                                if rate.is_finite() && rate >= 0.0 {
+                                    set(&|p| p.fee_rate = rate);
+                                }
+                            }
+                        }
+                        prop:value=move || preferences.read().fee_rate.to_string()
+                    />
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Auto-lock after (minutes):")}</label>
+                    <input
+                        class="table-cell border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600"
+                        placeholder=move || ctx.t("never")
+                        on:change=move |e| {
+                            let minutes = match event_target_value(&e).trim() {
+                                "" => Ok(0),
+                                text => text.parse(),
+                            };
+                            if let Ok(minutes) = minutes {
+                                set(&|p| p.auto_lock_minutes = minutes);
+                            }
+                        }
+                        prop:value=move || match preferences.read().auto_lock_minutes {
+                            0 => String::new(),
+                            minutes => minutes.to_string(),
+                        }
+                    />
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Encrypt saved tabs and wallets:")}</label>
+                    <input
+                        type="checkbox"
+                        class="table-cell"
+                        title=move || ctx.t("Ask for a passphrase on load, labels and scripts stay unreadable without it")
+                        on:change=move |e| {
+                            let enabled = event_target_checked(&e);
+                            set(&|p| p.encrypt_saved = enabled);
+                        }
+                        prop:checked=move || preferences.read().encrypt_saved
+                    />
+                </div>
+                <div class="table-row">
+                    <label class="table-cell pr-1">{move || ctx.t("Debug panel:")}</label>
+                    <input
+                        type="checkbox"
+                        class="table-cell"
+                        on:change=move |e| {
+                            let enabled = event_target_checked(&e);
+                            set(&|p| p.debug_panel = enabled);
+                        }
+                        prop:checked=move || preferences.read().debug_panel
+                    />
+                </div>
+            </div>
+            <p class="text-stone-500">
+                {move || ctx.t("Applied to new transactions, inputs and outputs.")}
+            </p>
+            <p class="mt-2">{move || ctx.t("Network limits, empty for the defaults shown:")}</p>
+            <div class="table">
+                <div class="table-row text-stone-500">
+                    <span class="table-cell pr-1"></span>
+                    <span class="table-cell pr-1">{move || ctx.t("Timeout (seconds)")}</span>
+                    <span class="table-cell pr-1">{move || ctx.t("Requests at once")}</span>
+                    <span class="table-cell pr-1">{move || ctx.t("Interval (ms)")}</span>
+                </div>
+                {backends::all()
+                    .into_iter()
+                    .map(|(label, backend, field)| {
+                        let defaults = backend.defaults();
+                        view! {
+                            <div class="table-row">
+                                <label class="table-cell pr-1">{move || ctx.t(label)}</label>
+                                {limit_input(field, |l| &mut l.timeout_secs, defaults.timeout.as_secs())}
+                                {limit_input(field, |l| &mut l.max_concurrent, defaults.max_concurrent as u64)}
+                                {limit_input(field, |l| &mut l.min_interval_ms, defaults.min_interval.as_millis() as u64)}
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
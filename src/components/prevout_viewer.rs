@@ -0,0 +1,101 @@
+use bitcoincash::consensus::serialize;
+use bitcoincash::{Transaction, Txid};
+use leptos::{
+    component,
+    prelude::{
+        ClassAttribute, CollectView, ElementChild, Get, GlobalAttributes, IntoAny, RwSignal, Set,
+        Signal,
+    },
+    task::spawn_local,
+    view, IntoView,
+};
+
+use crate::cash_assembly::{disassemble_in, InstructionSet};
+use crate::chain::ChainBackend;
+use crate::components::{AsmText, ExplorerLink};
+use crate::util::{script_to_cash_addr, short_hex};
+use crate::Context;
+
+#[derive(Clone)]
+enum Fetch {
+    Pending,
+    Done(Result<Transaction, String>),
+}
+
+/// Read-only summary of the transaction `txid`, fetched from the chosen server, with output `vout`
+/// marked as the one spent.
+#[component]
+pub fn PrevoutViewer(ctx: Context, txid: Txid, #[prop(into)] vout: Signal<u32>) -> impl IntoView {
+    let fetch = RwSignal::new(Fetch::Pending);
+    match ctx.chain_server() {
+        Some(server) => spawn_local(async move {
+            let tx = async { server.connect().await?.transaction(&txid).await }.await;
+            fetch.set(Fetch::Done(tx.map_err(|e| e.to_string())));
+        }),
+        None => fetch.set(Fetch::Done(Err(ctx
+            .t("No server is set for this network")
+            .to_string()))),
+    }
+
+    let summary = move |tx: Transaction| {
+        let fetched_txid = tx.txid();
+        let prefix = ctx.cash_addr_prefix();
+        let set = InstructionSet::for_network(ctx.network.get());
+        let outputs = tx
+            .output
+            .iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let script = match script_to_cash_addr(&output.script_pubkey, &prefix) {
+                    Ok(address) => address.into_any(),
+                    Err(_) => {
+                        let asm = disassemble_in(&output.script_pubkey, set);
+                        view! { <AsmText asm/> }.into_any()
+                    }
+                };
+                let value = output.value;
+                let spent = move || vout.get() as usize == i;
+                view! {
+                    <tr class=("text-sky-600", spent)>
+                        <td class="pr-3 text-right">{i}</td>
+                        <td class="pr-3 text-right">{move || ctx.amount_unit.get().format(value)}</td>
+                        <td class="pr-3 font-mono break-all">{script}</td>
+                        <td class="pr-3">{output.token.as_ref().map(|_| "CashToken")}</td>
+                        <td>{move || spent().then(|| ctx.t("spent here"))}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        let output_count = tx.output.len();
+        let out_of_range = move || vout.get() as usize >= output_count;
+        view! {
+            // A server returning another transaction than asked for can't be trusted with this one
+            {(fetched_txid != txid).then(|| view! {
+                <p class="text-red-700">{ctx.t("The server returned a different transaction:")}" "{fetched_txid.to_string()}</p>
+            })}
+            <p>
+                {serialize(&tx).len()}" "{ctx.t("bytes")}", "{ctx.t("version")}" "{tx.version}", "
+                {ctx.t("locktime")}" "{tx.lock_time.0}", "{tx.input.len()}" "{ctx.t("inputs")}
+                <ExplorerLink ctx href=Signal::derive(move || ctx.tx_link(&txid.to_string()))/>
+            </p>
+            <table>
+                <tbody>{outputs}</tbody>
+            </table>
+            {move || out_of_range().then(|| view! {
+                <p class="text-red-700">{ctx.t("The transaction has no output at this index")}</p>
+            })}
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-1 text-sm" title=txid.to_string()>
+            <p class="text-stone-500">{ctx.t("Previous transaction")}" "{short_hex(&txid.to_string())}</p>
+            {move || match fetch.get() {
+                Fetch::Pending => view! { <p class="text-stone-500">{ctx.t("Fetching…")}</p> }.into_any(),
+                Fetch::Done(Ok(tx)) => summary(tx),
+                Fetch::Done(Err(e)) => view! { <p class="text-red-700">{e}</p> }.into_any(),
+            }}
+        </div>
+    }
+}
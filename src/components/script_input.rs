@@ -1,19 +1,28 @@
-use bitcoincash::{hashes::hex::ToHex, Network, Script};
+use bitcoincash::{hashes::hex::ToHex, Script};
 use leptos::{
     component,
+    html::Textarea,
     prelude::{
-        event_target_value, ClassAttribute, Get, GlobalAttributes, MaybeProp, OnAttribute,
-        PropAttribute, ReadSignal, RwSignal, Set,
+        event_target_value, request_animation_frame, ClassAttribute, CollectView, ElementChild,
+        Get, GetUntracked, GlobalAttributes, MaybeProp, Memo, NodeRef, NodeRefAttribute,
+        OnAttribute, PropAttribute, RwSignal, Set, Signal, With, WithUntracked,
     },
     view, IntoView,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    js_reexport::{bin_to_cash_assembly, cash_assembly_to_bin},
-    util::{cash_addr_to_script, script_to_cash_addr},
+    cash_assembly::{self, InstructionSet},
+    components::opcode_palette::PaletteTarget,
+    hex_format,
+    opcode_reference::tooltip,
+    opcodes::{complete_word, marked_lines, word_at, word_before},
+    util::{cash_addr_to_script, script_to_cash_addr, script_to_legacy_addr, script_to_token_addr},
+    validation::{check_script, ScriptRole},
+    Context,
 };
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ScriptInputValue {
     Hex(String),
     Addr(String),
@@ -54,12 +63,13 @@ impl TryFrom<ScriptInputValue> for Script {
     type Error = anyhow::Error;
     fn try_from(s: ScriptInputValue) -> Result<Self, Self::Error> {
         match s {
-            ScriptInputValue::Hex(mut s) => {
-                s.retain(|c| !c.is_ascii_whitespace());
-                Ok(s.parse::<Script>()?)
-            }
+            ScriptInputValue::Hex(s) => Ok(Script::from(hex_format::parse(&s)?)),
             ScriptInputValue::Addr(s) => cash_addr_to_script(&s),
-            ScriptInputValue::Asm(s) => Ok(Script::from(cash_assembly_to_bin(&s)?.into_vec())),
+            // With every opcode known on any network, those the selected one doesn't know are
+            // pointed out by the script input
+            ScriptInputValue::Asm(s) => {
+                Ok(cash_assembly::assemble_in(&s, InstructionSet::Upcoming)?)
+            }
         }
     }
 }
@@ -71,95 +81,261 @@ impl Default for ScriptInputValue {
 }
 
 str_enum! {
-    #[derive(Copy, Clone, PartialEq, Eq)]
+    #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
     pub enum ScriptDisplayFormat {
         Addr = "addr",
+        /// Base58 address, shown for [`ScriptInputValue::Addr`] values.
+        Legacy = "legacy",
+        /// Token-aware CashAddr, shown for [`ScriptInputValue::Addr`] values.
+        TokenAddr = "token-addr",
         Asm = "asm",
         Hex = "hex",
     }
 }
 
+/// Completions offered at once while typing ASM.
+const MAX_COMPLETIONS: usize = 8;
+
 #[component]
 pub fn ScriptInput(
     value: RwSignal<ScriptInputValue>,
     format: RwSignal<ScriptDisplayFormat>,
-    network: ReadSignal<Network>,
+    ctx: Context,
     #[prop(into, default=Default::default())] disabled: MaybeProp<bool>,
+    placeholder: Signal<&'static str>,
+    #[prop(optional)] rows: Option<u32>,
+    /// Which side of a spend the script is on, to warn of the limits it breaks.
+    #[prop(optional)]
+    role: Option<ScriptRole>,
+    /// Set to this input when it's focused, for the opcode palette to insert into.
+    #[prop(optional)]
+    palette: Option<RwSignal<Option<PaletteTarget>>>,
 ) -> impl IntoView {
-    let error = RwSignal::new(false);
+    let textarea = NodeRef::<Textarea>::new();
     let disabled = move || disabled().unwrap_or(false);
 
-    let render_value = move || {
-        let value = value();
-        let format = format();
+    let address_format = Memo::new(move |_| ctx.preferences.with(|p| p.address_format));
+    // Converting large scripts is slow, so this only reruns when the value, the format, the network
+    // or the address format change
+    let rendered = Memo::new(move |_| -> Result<String, String> {
+        let value = value.get();
+        let format = format.get();
         if value.format() == format || value.is_empty() {
-            error.set(false);
-            return value.inner().into();
+            return Ok(value.inner().clone());
         }
-        match format {
-            ScriptDisplayFormat::Hex => match Script::try_from(value) {
-                Ok(s) => {
-                    error.set(false);
-                    s.to_hex()
-                }
-                Err(e) => {
-                    error.set(true);
-                    e.to_string()
-                }
-            },
-            ScriptDisplayFormat::Asm => match Script::try_from(value) {
-                Ok(s) => {
-                    error.set(false);
-                    bin_to_cash_assembly(s.as_bytes().into())
-                }
-                Err(e) => {
-                    error.set(true);
-                    e.to_string()
-                }
+        let script = Script::try_from(value).map_err(|e| e.to_string())?;
+        let rendered = match format {
+            ScriptDisplayFormat::Hex => Ok(script.to_hex()),
+            ScriptDisplayFormat::Asm => Ok(cash_assembly::disassemble_in(
+                &script,
+                InstructionSet::for_network(ctx.network.get()),
+            )),
+            ScriptDisplayFormat::Legacy => script_to_legacy_addr(&script, ctx.network.get()),
+            ScriptDisplayFormat::TokenAddr => {
+                script_to_token_addr(&script, &ctx.cash_addr_prefix())
+                    .map(|a| address_format.get().apply(a))
+            }
+            ScriptDisplayFormat::Addr => script_to_cash_addr(&script, &ctx.cash_addr_prefix())
+                .map(|a| address_format.get().apply(a)),
+        };
+        rendered.map_err(|e| e.to_string())
+    });
+    let error = move || rendered.with(Result::is_err);
+    let render_value = move || match rendered.get() {
+        Ok(s) | Err(s) => s,
+    };
+
+    // While typing ASM, the text not committed yet and the opcodes completing the word at the caret
+    let draft = RwSignal::new(None::<String>);
+    let completions = RwSignal::new(Vec::<String>::new());
+    let caret = move || {
+        textarea
+            .get_untracked()
+            .and_then(|t| t.selection_start().ok().flatten())
+            .map_or(usize::MAX, |c| c as usize)
+    };
+    let on_input = move |text: String| {
+        if format.get_untracked() != ScriptDisplayFormat::Asm {
+            return;
+        }
+        let word = word_before(&text, caret());
+        let set = InstructionSet::for_network(ctx.network.get_untracked());
+        let mut found = cash_assembly::opcode_completions(word, set);
+        // Nothing to offer for a word typed out already
+        if found.len() == 1 && found[0] == word {
+            found.clear();
+        }
+        found.truncate(MAX_COMPLETIONS);
+        completions.set(found);
+        draft.set(Some(text));
+    };
+    // Documentation of the opcode the caret is in
+    let caret_doc = RwSignal::new(None::<String>);
+    let document_caret = move || {
+        let doc = textarea
+            .get_untracked()
+            .filter(|_| format.get_untracked() == ScriptDisplayFormat::Asm)
+            .and_then(|t| tooltip(word_at(&t.value(), caret())));
+        caret_doc.set(doc);
+    };
+    let complete = move |opcode: &str| {
+        let Some(textarea) = textarea.get_untracked() else {
+            return;
+        };
+        let (asm, caret) = complete_word(&textarea.value(), caret(), opcode);
+        value.set(ScriptInputValue::Asm(asm));
+        draft.set(None);
+        completions.set(vec![]);
+        // After the new value is rendered, which would move the caret to the end
+        request_animation_frame(move || {
+            let _ = textarea.focus();
+            let _ = textarea.set_selection_range(caret as u32, caret as u32);
+        });
+    };
+    // Where the ASM typed or committed fails to assemble, with every unknown opcode in it
+    let diagnostics = Memo::new(move |_| {
+        if format.get() != ScriptDisplayFormat::Asm {
+            return None;
+        }
+        let asm = match draft.get() {
+            Some(asm) => asm,
+            None => match value.get() {
+                ScriptInputValue::Asm(asm) => asm,
+                _ => return None,
             },
-            ScriptDisplayFormat::Addr => {
-                let script: Script = match value.try_into() {
-                    Ok(s) => s,
-                    Err(e) => {
-                        error.set(true);
-                        return e.to_string();
-                    }
-                };
-                match script_to_cash_addr(&script, network.get()) {
-                    Ok(a) => {
-                        error.set(false);
-                        a
+        };
+        let set = InstructionSet::for_network(ctx.network.get());
+        let error = cash_assembly::assemble_in(&asm, set).err()?;
+        let unknown = cash_assembly::unknown_opcodes(&asm, set);
+        Some((asm, error, unknown))
+    });
+    // Limits the committed script breaks, which would only be found out at relay
+    let problems = Memo::new(move |_| {
+        let role = role?;
+        let script = Script::try_from(value.get()).ok()?;
+        Some(check_script(&script, role)).filter(|problems| !problems.is_empty())
+    });
+    let diagnostics_view = move || {
+        diagnostics.get().map(|(asm, error, unknown)| {
+            let lines = marked_lines(&asm, &unknown, error.line)
+                .into_iter()
+                .map(|(number, segments)| {
+                    let segments = segments
+                        .into_iter()
+                        .map(|(text, marked)| {
+                            view! {
+                                <span class=("underline decoration-wavy decoration-red-700", marked)>
+                                    {text.to_string()}
+                                </span>
+                            }
+                        })
+                        .collect_view();
+                    view! {
+                        <div class="whitespace-pre-wrap break-all">
+                            <span class="text-stone-500 select-none">{number}": "</span>
+                            {segments}
+                        </div>
                     }
-                    Err(e) => {
-                        error.set(true);
-                        e.to_string()
-                    }
-                }
+                })
+                .collect_view();
+            view! {
+                <div class="font-mono text-xs">
+                    {lines}
+                    <p class="text-red-700">{error.to_string()}</p>
+                </div>
             }
-        }
+        })
     };
 
     view! {
-        <textarea
-            spellcheck="false"
-            on:change=move |e| {
-                match format() {
-                    ScriptDisplayFormat::Hex => {
-                        value.set(ScriptInputValue::Hex(event_target_value(&e)));
+        <div class="grow">
+            <textarea
+                node_ref=textarea
+                spellcheck="false"
+                placeholder=move || placeholder.get()
+                rows=rows
+                on:focus=move |_| {
+                    if let Some(palette) = palette {
+                        palette.set(Some(PaletteTarget { value, format, textarea }));
                     }
-                    ScriptDisplayFormat::Addr => {
-                        value.set(ScriptInputValue::Addr(event_target_value(&e)));
+                }
+                on:input=move |e| on_input(event_target_value(&e))
+                on:keyup=move |_| document_caret()
+                on:click=move |_| document_caret()
+                on:blur=move |_| caret_doc.set(None)
+                on:keydown=move |e| {
+                    let first = completions.with_untracked(|c| c.first().cloned());
+                    match (e.key().as_str(), first) {
+                        ("Tab", Some(opcode)) => {
+                            e.prevent_default();
+                            complete(&opcode);
+                        }
+                        ("Escape", _) => completions.set(vec![]),
+                        _ => {}
                     }
-                    ScriptDisplayFormat::Asm => {
-                        value.set(ScriptInputValue::Asm(event_target_value(&e)));
+                }
+                on:change=move |e| {
+                    draft.set(None);
+                    completions.set(vec![]);
+                    match format() {
+                        ScriptDisplayFormat::Hex => {
+                            value.set(ScriptInputValue::Hex(event_target_value(&e)));
+                        }
+                        ScriptDisplayFormat::Addr
+                        | ScriptDisplayFormat::Legacy
+                        | ScriptDisplayFormat::TokenAddr => {
+                            value.set(ScriptInputValue::Addr(event_target_value(&e)));
+                        }
+                        ScriptDisplayFormat::Asm => {
+                            value.set(ScriptInputValue::Asm(event_target_value(&e)));
+                        }
                     }
                 }
-            }
-            class="border border-solid rounded border-stone-600 px-1 w-full bg-inherit placeholder:text-stone-600 font-mono grow bg-stone-900"
-            prop:value=render_value
-            disabled=move || error() || disabled()
-            class=("text-red-700", error)
-            class=("opacity-30", disabled)
-        />
+                class="border border-solid rounded border-stone-600 px-1 w-full bg-inherit placeholder:text-stone-600 font-mono grow bg-stone-900"
+                prop:value=render_value
+                disabled=move || error() || disabled()
+                class=("text-red-700", error)
+                class=("opacity-30", disabled)
+            />
+            <div class="flex flex-wrap gap-1 text-xs">
+                {move || {
+                    completions
+                        .get()
+                        .into_iter()
+                        .map(|opcode| {
+                            let label = opcode.clone();
+                            let doc = tooltip(&opcode).unwrap_or_default();
+                            view! {
+                                <button
+                                    class="border border-solid rounded border-stone-600 px-1 font-mono"
+                                    title=move || {
+                                        format!("{doc}\n{}", ctx.t("Complete with this opcode, Tab for the first"))
+                                    }
+                                    // Before the textarea loses focus and commits its text
+                                    on:mousedown=move |e| {
+                                        e.prevent_default();
+                                        complete(&opcode);
+                                    }
+                                >
+                                    {label}
+                                </button>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+            <p class="text-stone-500 text-xs">{caret_doc}</p>
+            {diagnostics_view}
+            {move || {
+                problems
+                    .get()
+                    .map(|problems| {
+                        problems
+                            .into_iter()
+                            .map(|problem| view! { <p class="text-yellow-700 text-xs">{problem}</p> })
+                            .collect_view()
+                    })
+            }}
+        </div>
     }
 }
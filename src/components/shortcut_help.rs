@@ -0,0 +1,44 @@
+use leptos::{
+    component,
+    prelude::{ClassAttribute, CollectView, ElementChild, OnAttribute},
+    view, IntoView,
+};
+
+use crate::{shortcuts::SHORTCUTS, Context};
+
+/// Cheat sheet of all keyboard shortcuts, shown over the editor.
+#[component]
+pub fn ShortcutHelp(
+    ctx: Context,
+    on_close: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <div
+            class="fixed inset-0 bg-black/60 flex items-center justify-center z-10"
+            on:click=move |_| on_close()
+        >
+            <div
+                class="border border-solid rounded-md border-stone-600 bg-stone-800 p-3"
+                on:click=|e| e.stop_propagation()
+            >
+                <p class="mb-2">{move || ctx.t("Keyboard shortcuts")}</p>
+                <table class="text-sm">
+                    {SHORTCUTS
+                        .iter()
+                        .map(|s| {
+                            view! {
+                                <tr>
+                                    <td class="pr-3 font-mono">{s.label()}</td>
+                                    <td>{move || ctx.t(s.description)}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </table>
+                <p class="text-sm text-stone-500 mt-2">
+                    {move || ctx.t("Shortcuts with Alt also work while typing. Esc closes this list.")}
+                </p>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use bitcoincash::{secp256k1::Secp256k1, Address, Script};
+use leptos::{
+    component,
+    prelude::{
+        event_target_checked, event_target_value, ClassAttribute, ElementChild, Get, GetUntracked,
+        GlobalAttributes, IntoAny, OnAttribute, PropAttribute, RwSignal, Set, With,
+    },
+    task::spawn_local,
+    view, IntoView,
+};
+
+use crate::{
+    coin_selection::Utxo,
+    partially_signed::PartiallySignedTransaction,
+    sensitive,
+    sweep::{self, Sweep},
+    util::{cash_addr_to_script, script_to_cash_addr},
+    Context,
+};
+
+/// Paper wallet recovery: the address of a pasted WIF, its UTXOs fetched from the Electrum server,
+/// and a transaction sending them all to a destination address.
+#[component]
+pub fn SweepTool(
+    ctx: Context,
+    on_build: impl Fn(PartiallySignedTransaction) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let wif = RwSignal::new(String::new());
+    sensitive::register(wif);
+    let destination = RwSignal::new(String::new());
+    let fee_rate = RwSignal::new(ctx.preferences.get_untracked().fee_rate.to_string());
+    let sign = RwSignal::new(true);
+    let utxos = RwSignal::new(None::<Vec<Utxo>>);
+    let message = RwSignal::new(Ok(String::new()));
+
+    // The key with its P2PKH script and CashAddr, and its legacy address
+    let key = move || -> Result<_> {
+        let secp = Secp256k1::signing_only();
+        let key = sweep::parse_key(&wif.get(), ctx.network.get())?;
+        let pubkey = key.public_key(&secp);
+        let script = Script::new_p2pkh(&pubkey.pubkey_hash());
+        let cash_addr = script_to_cash_addr(&script, &ctx.cash_addr_prefix())?;
+        let legacy = Address::p2pkh(&pubkey, key.network).to_string();
+        Ok((key, script, cash_addr, legacy))
+    };
+
+    let fetch = move |_| {
+        let (_, script, address, _) = match key() {
+            Ok(key) => key,
+            Err(e) => {
+                message.set(Err(e.to_string()));
+                return;
+            }
+        };
+        let Some(server) = ctx.electrum_server() else {
+            message.set(Err(ctx.t("No server is set for this network").to_string()));
+            return;
+        };
+        message.set(Ok(ctx.t("Fetching…").to_string()));
+        utxos.set(None);
+        spawn_local(async move {
+            match sweep::fetch_utxos(&server, &script, &address).await {
+                Ok(fetched) => {
+                    message.set(Ok(String::new()));
+                    utxos.set(Some(fetched));
+                }
+                Err(e) => message.set(Err(e.to_string())),
+            }
+        });
+    };
+
+    let build = move |_| {
+        let run = || -> Result<String> {
+            let (key, ..) = key()?;
+            let destination = cash_addr_to_script(destination.get_untracked().trim())?;
+            let fee_rate = fee_rate
+                .get_untracked()
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| *rate >= 0.0)
+                .ok_or_else(|| anyhow::anyhow!(ctx.t("Invalid fee rate")))?;
+            let utxos = utxos.get_untracked().unwrap_or_default();
+            let secp = Secp256k1::signing_only();
+            let Sweep {
+                tx,
+                fee,
+                skipped_tokens,
+            } = sweep::build(
+                &secp,
+                &key,
+                &utxos,
+                destination,
+                fee_rate,
+                sign.get_untracked(),
+            )?;
+            let mut message = format!("{} {fee} sats", ctx.t("Built the sweep, fee"));
+            if skipped_tokens > 0 {
+                message += &format!(", {skipped_tokens} {}", ctx.t("UTXOs with tokens left out"));
+            }
+            on_build(tx);
+            Ok(message)
+        };
+        message.set(run().map_err(|e| format!("{e:#}")));
+    };
+
+    let total = move || {
+        utxos.with(|utxos| {
+            utxos.as_ref().map(|utxos| {
+                let value = utxos.iter().map(|utxo| utxo.value).sum::<u64>();
+                format!(
+                    "{} UTXOs, {}",
+                    utxos.len(),
+                    ctx.amount_unit.get().format(value)
+                )
+            })
+        })
+    };
+    let input_class =
+        "border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600";
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="flex flex-wrap items-center gap-1">
+                <input
+                    type="password"
+                    autocomplete="off"
+                    class=format!("{input_class} font-mono grow")
+                    placeholder=move || ctx.t("WIF private key")
+                    on:input=move |e| {
+                        wif.set(event_target_value(&e));
+                        utxos.set(None);
+                    }
+                    prop:value=wif
+                />
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=fetch>
+                    {move || ctx.t("Fetch UTXOs")}
+                </button>
+            </div>
+            {move || match key() {
+                Ok((_, _, cash_addr, legacy)) => view! {
+                    <p class="font-mono break-all">{cash_addr}</p>
+                    <p class="font-mono break-all text-stone-500">{legacy}</p>
+                }
+                .into_any(),
+                Err(e) if !wif.with(String::is_empty) => {
+                    view! { <p class="text-red-700">{e.to_string()}</p> }.into_any()
+                }
+                Err(_) => ().into_any(),
+            }}
+            <p>{total}</p>
+            <div class="flex flex-wrap items-center gap-1">
+                <input
+                    class=format!("{input_class} font-mono grow")
+                    placeholder=move || ctx.t("Destination address")
+                    on:input=move |e| destination.set(event_target_value(&e))
+                    prop:value=destination
+                />
+                <label>{move || ctx.t("Fee rate:")}</label>
+                <input
+                    class=format!("{input_class} w-12")
+                    on:input=move |e| fee_rate.set(event_target_value(&e))
+                    prop:value=fee_rate
+                />
+                <label title=move || ctx.t("Leave the inputs unsigned to sign them in a wallet")>
+                    <input
+                        type="checkbox"
+                        on:change=move |e| sign.set(event_target_checked(&e))
+                        prop:checked=sign
+                    />
+                    " "{move || ctx.t("Sign")}
+                </label>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1"
+                    title=move || ctx.t("Replace the transaction with one spending every coin of the key")
+                    on:click=build
+                >
+                    {move || ctx.t("Build sweep")}
+                </button>
+            </div>
+            {move || match message.get() {
+                Ok(message) => view! { <p class="text-stone-500">{message}</p> }.into_any(),
+                Err(e) => view! { <p class="text-red-700">{e}</p> }.into_any(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,138 @@
+use leptos::{
+    component,
+    prelude::{
+        event_target, event_target_value, ClassAttribute, CollectView, ElementChild, Get,
+        GlobalAttributes, OnAttribute, PropAttribute, Read, RwSignal, Set, StoredValue, Update,
+        WithValue,
+    },
+    view, web_sys, IntoView,
+};
+
+use crate::{
+    snapshot::EditorSnapshot,
+    templates::{self, Template},
+    Context,
+};
+
+/// Select a built-in template or saved preset to replace the editor contents, and save the current
+/// contents as a preset.
+#[component]
+pub fn TemplatePicker(
+    ctx: Context,
+    on_load: impl Fn(EditorSnapshot) + Copy + Send + Sync + 'static,
+    current: impl Fn() -> EditorSnapshot + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let builtin = StoredValue::new(templates::builtin());
+    let presets = RwSignal::new(templates::load_presets());
+    let preset_name = RwSignal::new(String::new());
+
+    let load = move |value: String| {
+        let snapshot = match value.split_once(':') {
+            Some(("builtin", i)) => i
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| builtin.with_value(|b| b.get(i).map(|t| t.snapshot.clone()))),
+            Some(("preset", i)) => i
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| presets.read().get(i).map(|t| t.snapshot.clone())),
+            _ => None,
+        };
+        if let Some(snapshot) = snapshot {
+            on_load(snapshot);
+        }
+    };
+    let save_preset = move |_| {
+        let name = preset_name.get().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        presets.update(|presets| {
+            let snapshot = current();
+            match presets.iter_mut().find(|t| t.name == name) {
+                Some(t) => t.snapshot = snapshot,
+                None => presets.push(Template { name, snapshot }),
+            }
+            templates::save_presets(presets);
+        });
+        preset_name.set(String::new());
+    };
+    let delete_preset = move |i: usize| {
+        presets.update(|presets| {
+            presets.remove(i);
+            templates::save_presets(presets);
+        });
+    };
+
+    view! {
+        <div class="flex flex-wrap items-center gap-1 mb-3 text-sm">
+            <select
+                class="bg-inherit border rounded p-1"
+                on:change=move |e| {
+                    load(event_target_value(&e));
+                    // Back to the placeholder, so the same template can be picked again
+                    event_target::<web_sys::HtmlSelectElement>(&e).set_value("");
+                }
+            >
+                <option value="">{move || ctx.t("Start from a template…")}</option>
+                <optgroup label=move || ctx.t("Templates")>
+                    {builtin
+                        .with_value(|b| {
+                            b.iter()
+                                .enumerate()
+                                .map(|(i, t)| {
+                                    view! { <option value=format!("builtin:{i}")>{t.name.clone()}</option> }
+                                })
+                                .collect_view()
+                        })}
+                </optgroup>
+                <optgroup label=move || ctx.t("My presets")>
+                    {move || {
+                        presets
+                            .read()
+                            .iter()
+                            .enumerate()
+                            .map(|(i, t)| {
+                                view! { <option value=format!("preset:{i}")>{t.name.clone()}</option> }
+                            })
+                            .collect_view()
+                    }}
+                </optgroup>
+            </select>
+            <input
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 ml-3"
+                placeholder=move || ctx.t("Preset name")
+                on:input=move |e| preset_name.set(event_target_value(&e))
+                prop:value=preset_name
+            />
+            <button
+                class="border border-solid rounded border-stone-600 px-1"
+                title=move || ctx.t("Save the current transaction as a preset, replacing one with the same name")
+                on:click=save_preset
+            >
+                {move || ctx.t("Save preset")}
+            </button>
+            {move || {
+                presets
+                    .read()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| {
+                        view! {
+                            <span class="border border-solid rounded border-stone-600 px-1">
+                                {t.name.clone()}
+                                <button
+                                    class="ml-1"
+                                    title=ctx.t("Delete preset")
+                                    on:click=move |_| delete_preset(i)
+                                >
+                                    "×"
+                                </button>
+                            </span>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context as _, Result};
+use bitcoincash::hashes::hex::FromHex;
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, Get, GetUntracked,
+        GlobalAttributes, IntoAny, OnAttribute, PropAttribute, RwSignal, Set, Update, With,
+        WithUntracked,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    cash_assembly::{substitute, variables, InstructionSet},
+    components::{opcode_palette::PaletteTarget, script_input::ScriptInputValue},
+    Context,
+};
+
+/// The variables of a template pasted into the last focused script input, like
+/// `<owner.public_key>`, each with a field for its data, and a button substituting them.
+#[component]
+pub fn TemplateVariables(target: RwSignal<Option<PaletteTarget>>, ctx: Context) -> impl IntoView {
+    // Hex data by variable name, kept across inputs so templates sharing a variable fill it once
+    let values = RwSignal::new(BTreeMap::<String, String>::new());
+    let error = RwSignal::new(String::new());
+
+    // None when there is no input or it isn't ASM
+    let names = move || {
+        let value = target.get()?.value.try_get()?;
+        match value {
+            ScriptInputValue::Asm(asm) => Some(variables(
+                &asm,
+                InstructionSet::for_network(ctx.network.get()),
+            )),
+            _ => None,
+        }
+    };
+
+    let instantiate = move |_| {
+        let run = || -> Result<()> {
+            let Some(target) = target.get_untracked() else {
+                return Ok(());
+            };
+            let Some(ScriptInputValue::Asm(asm)) = target.value.try_get_untracked() else {
+                return Ok(());
+            };
+            let set = InstructionSet::for_network(ctx.network.get_untracked());
+            let mut data = BTreeMap::new();
+            for name in variables(&asm, set) {
+                let hex = values.with_untracked(|v| v.get(&name).cloned().unwrap_or_default());
+                let hex = hex.trim();
+                if hex.is_empty() {
+                    continue;
+                }
+                let bytes = Vec::from_hex(hex.strip_prefix("0x").unwrap_or(hex))
+                    .with_context(|| format!("Invalid hex for {name}"))?;
+                data.insert(name, bytes);
+            }
+            target
+                .value
+                .set(ScriptInputValue::Asm(substitute(&asm, &data, set)));
+            Ok(())
+        };
+        error.set(run().map_or_else(|e| format!("{e:#}"), |()| String::new()));
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            {move || match names() {
+                None => {
+                    view! {
+                        <p class="text-stone-500">
+                            {ctx.t("Paste a template into a script field in ASM, then click into it to fill in its variables.")}
+                        </p>
+                    }
+                        .into_any()
+                }
+                Some(names) if names.is_empty() => {
+                    view! { <p class="text-stone-500">{ctx.t("The script has no variables.")}</p> }
+                        .into_any()
+                }
+                Some(names) => {
+                    view! {
+                        {names
+                            .into_iter()
+                            .map(|name| {
+                                let key = name.clone();
+                                view! {
+                                    <div class="flex flex-wrap items-center gap-1 mb-1">
+                                        <span class="w-48 font-mono break-all">{name.clone()}</span>
+                                        <input
+                                            class="border border-solid rounded border-stone-600 px-1 bg-stone-900 font-mono grow placeholder:text-stone-600"
+                                            placeholder=move || ctx.t("Hex data")
+                                            on:input=move |e| {
+                                                let hex = event_target_value(&e);
+                                                values.update(|v| {
+                                                    v.insert(key.clone(), hex);
+                                                });
+                                            }
+                                            prop:value=move || {
+                                                values.with(|v| v.get(&name).cloned().unwrap_or_default())
+                                            }
+                                        />
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1"
+                            title=move || ctx.t("Replace the variables that have data with it")
+                            on:mousedown=|e| e.prevent_default()
+                            on:click=instantiate
+                        >
+                            {move || ctx.t("Instantiate")}
+                        </button>
+                    }
+                        .into_any()
+                }
+            }}
+            <p class="text-red-700">{error}</p>
+        </div>
+    }
+}
@@ -1,8 +1,9 @@
 use std::borrow::Cow;
 
 use anyhow::Result;
+use base64::prelude::{Engine as _, BASE64_STANDARD};
 use bitcoincash::{
-    blockdata::token::{Capability, OutputData, Structure},
+    blockdata::token::{Capability, OutputData, Structure, MAX_CONSENSUS_COMMITMENT_LENGTH},
     hashes::hex::{FromHex, ToHex},
     TokenID,
 };
@@ -10,16 +11,24 @@ use leptos::{
     component,
     prelude::{
         event_target_checked, event_target_value, AddAnyAttr, ClassAttribute, Dispose,
-        ElementChild, Get, GlobalAttributes, OnAttribute, PropAttribute, Read, RwSignal, Set, Show,
-        Write,
+        ElementChild, For, Get, GetUntracked, GlobalAttributes, IsDisposed, OnAttribute,
+        PropAttribute, Read, RwSignal, Set, Show, Signal, Track,
     },
     view, IntoView,
 };
+use serde::{Deserialize, Serialize};
 
-use crate::{components::ParsedInput, macros::StrEnum};
+use crate::{
+    components::{set_changed, token_identity::TokenIdentity, DecimalInput},
+    macros::StrEnum,
+    util::{format_decimal, short_hex},
+    validation::MAX_FT_AMOUNT,
+    vm_number::{self, increment_script_num, parse_script_num},
+    Context,
+};
 
 str_enum! {
-    #[derive(Copy, Clone, Default)]
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub enum NftCapability {
         #[default]
         Immutable = "immutable",
@@ -28,6 +37,19 @@ str_enum! {
     }
 }
 
+impl NftCapability {
+    /// Capability of the NFT in `token_data`, which must have one.
+    pub fn of(token_data: &OutputData) -> Self {
+        if (token_data.capability() & Capability::Mutable as u8) != 0 {
+            NftCapability::Mutable
+        } else if (token_data.capability() & Capability::Minting as u8) != 0 {
+            NftCapability::Minting
+        } else {
+            NftCapability::Immutable
+        }
+    }
+}
+
 impl From<NftCapability> for Capability {
     fn from(t: NftCapability) -> Self {
         use bitcoincash::blockdata::token::Capability as C;
@@ -40,20 +62,150 @@ impl From<NftCapability> for Capability {
 }
 
 str_enum! {
-    #[derive(Copy, Clone, Default, PartialEq, Eq)]
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub enum NftCommitmentFormat {
         #[default]
         Hex = "hex",
         Plaintext = "plaintext",
+        Base64 = "base64",
+        /// A script number, as read by covenants with `OP_BIN2NUM`.
+        Number = "number",
+    }
+}
+
+impl NftCommitmentFormat {
+    /// Bytes of a commitment entered in this format. Hex is stored as typed and checked later.
+    fn parse(self, s: &str) -> Result<Vec<u8>> {
+        Ok(match self {
+            NftCommitmentFormat::Hex => {
+                let mut hex = s.to_string();
+                hex.retain(|c| !c.is_ascii_whitespace());
+                Vec::from_hex(&hex)?
+            }
+            NftCommitmentFormat::Plaintext => s.as_bytes().to_vec(),
+            NftCommitmentFormat::Base64 => BASE64_STANDARD.decode(s.trim())?,
+            NftCommitmentFormat::Number => vm_number::encode(s)?,
+        })
+    }
+}
+
+/// Most decimals a fungible token can have, as in the BCMR specification.
+pub const MAX_FT_DECIMALS: u8 = 18;
+
+/// Plain copy of the values in a [`TokenDataState`].
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TokenDataSnapshot {
+    pub cashtoken_enabled: bool,
+    pub category_id: String,
+    pub has_ft_amount: bool,
+    /// Raw amount, in the token's smallest unit.
+    pub ft_amount: u64,
+    /// Decimals the amount is entered and shown with. Not part of the token data on chain.
+    #[serde(default)]
+    pub ft_decimals: u8,
+    pub has_nft: bool,
+    pub nft_capability: NftCapability,
+    pub nft_commitment_hex: String,
+    pub nft_commitment_format: NftCommitmentFormat,
+}
+
+impl TokenDataSnapshot {
+    pub fn from_token_data(token_data: Option<&OutputData>) -> Self {
+        let Some(token_data) = token_data else {
+            return Self::default();
+        };
+        let has_nft = token_data.has_nft();
+        let nft_capability = match has_nft {
+            true => NftCapability::of(token_data),
+            false => NftCapability::default(),
+        };
+        Self {
+            cashtoken_enabled: true,
+            category_id: token_data.id.to_hex(),
+            has_ft_amount: token_data.amount != 0,
+            ft_amount: u64::try_from(token_data.amount).unwrap(),
+            ft_decimals: 0,
+            has_nft,
+            nft_capability,
+            nft_commitment_hex: match has_nft && token_data.has_commitment_length() {
+                true => token_data.commitment.to_hex(),
+                false => String::new(),
+            },
+            nft_commitment_format: NftCommitmentFormat::default(),
+        }
+    }
+
+    pub fn token_data(&self) -> Result<Option<OutputData>> {
+        Ok(match self.cashtoken_enabled {
+            false => None,
+            true => {
+                let ft_amount = if self.has_ft_amount {
+                    if self.ft_amount == 0 {
+                        anyhow::bail!("FT amount must be nonzero");
+                    }
+                    i64::try_from(self.ft_amount)?
+                } else {
+                    0
+                };
+                let has_nft = self.has_nft;
+                let capability = match has_nft {
+                    true => self.nft_capability.into(),
+                    false => Capability::None,
+                };
+                let commitment = match has_nft {
+                    true => Vec::from_hex(&self.nft_commitment_hex)?,
+                    false => vec![],
+                };
+                if commitment.len() > usize::from(MAX_CONSENSUS_COMMITMENT_LENGTH) {
+                    anyhow::bail!(
+                        "NFT commitment is {} bytes, the maximum is {} bytes",
+                        commitment.len(),
+                        MAX_CONSENSUS_COMMITMENT_LENGTH
+                    );
+                }
+                let mut structure = 0;
+                if ft_amount != 0 {
+                    structure |= Structure::HasAmount as u8;
+                }
+                if has_nft {
+                    structure |= Structure::HasNFT as u8;
+                }
+                if !commitment.is_empty() {
+                    structure |= Structure::HasCommitmentLength as u8;
+                }
+                Some(OutputData {
+                    id: TokenID::from_hex(&self.category_id)?,
+                    bitfield: structure | capability as u8,
+                    amount: ft_amount,
+                    commitment,
+                })
+            }
+        })
     }
 }
 
+/// A category ID offered for filling in an output's category.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CategoryChoice {
+    pub label: String,
+    pub category_id: String,
+}
+
+/// An input's NFT whose commitment, read as a counter, can be carried on incremented.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CounterChoice {
+    pub label: String,
+    pub category_id: String,
+    pub commitment_hex: String,
+}
+
 #[derive(Copy, Clone)]
 pub struct TokenDataState {
     pub cashtoken_enabled: RwSignal<bool>,
     pub category_id: RwSignal<String>,
     pub has_ft_amount: RwSignal<bool>,
     pub ft_amount: RwSignal<u64>,
+    pub ft_decimals: RwSignal<u8>,
     pub has_nft: RwSignal<bool>,
     pub nft_capability: RwSignal<NftCapability>,
     pub nft_commitment_hex: RwSignal<String>,
@@ -62,16 +214,17 @@ pub struct TokenDataState {
 }
 
 impl TokenDataState {
-    pub fn new(key: usize) -> Self {
+    pub fn from_snapshot(key: usize, snapshot: &TokenDataSnapshot) -> Self {
         Self {
-            cashtoken_enabled: RwSignal::new(false),
-            category_id: RwSignal::default(),
-            has_ft_amount: RwSignal::new(false),
-            ft_amount: RwSignal::new(0),
-            has_nft: RwSignal::new(false),
-            nft_capability: RwSignal::default(),
-            nft_commitment_hex: RwSignal::default(),
-            nft_commitment_format: RwSignal::default(),
+            cashtoken_enabled: RwSignal::new(snapshot.cashtoken_enabled),
+            category_id: RwSignal::new(snapshot.category_id.clone()),
+            has_ft_amount: RwSignal::new(snapshot.has_ft_amount),
+            ft_amount: RwSignal::new(snapshot.ft_amount),
+            ft_decimals: RwSignal::new(snapshot.ft_decimals),
+            has_nft: RwSignal::new(snapshot.has_nft),
+            nft_capability: RwSignal::new(snapshot.nft_capability),
+            nft_commitment_hex: RwSignal::new(snapshot.nft_commitment_hex.clone()),
+            nft_commitment_format: RwSignal::new(snapshot.nft_commitment_format),
             key,
         }
     }
@@ -82,6 +235,7 @@ impl TokenDataState {
             category_id,
             has_ft_amount,
             ft_amount,
+            ft_decimals,
             has_nft,
             nft_capability,
             nft_commitment_hex,
@@ -92,106 +246,118 @@ impl TokenDataState {
         category_id.dispose();
         has_ft_amount.dispose();
         ft_amount.dispose();
+        ft_decimals.dispose();
         has_nft.dispose();
         nft_capability.dispose();
         nft_commitment_hex.dispose();
         nft_commitment_format.dispose();
     }
 
+    /// The signals to check for leaks, as part of the card holding this token data.
+    pub fn signals(self) -> Vec<Box<dyn IsDisposed>> {
+        let Self {
+            cashtoken_enabled,
+            category_id,
+            has_ft_amount,
+            ft_amount,
+            ft_decimals,
+            has_nft,
+            nft_capability,
+            nft_commitment_hex,
+            nft_commitment_format,
+            key: _,
+        } = self;
+        vec![
+            Box::new(cashtoken_enabled),
+            Box::new(category_id),
+            Box::new(has_ft_amount),
+            Box::new(ft_amount),
+            Box::new(ft_decimals),
+            Box::new(has_nft),
+            Box::new(nft_capability),
+            Box::new(nft_commitment_hex),
+            Box::new(nft_commitment_format),
+        ]
+    }
+
+    pub fn snapshot(self) -> TokenDataSnapshot {
+        TokenDataSnapshot {
+            cashtoken_enabled: self.cashtoken_enabled.get(),
+            category_id: self.category_id.get(),
+            has_ft_amount: self.has_ft_amount.get(),
+            ft_amount: self.ft_amount.get(),
+            ft_decimals: self.ft_decimals.get(),
+            has_nft: self.has_nft.get(),
+            nft_capability: self.nft_capability.get(),
+            nft_commitment_hex: self.nft_commitment_hex.get(),
+            nft_commitment_format: self.nft_commitment_format.get(),
+        }
+    }
+
+    pub fn restore(self, snapshot: &TokenDataSnapshot) {
+        set_changed(self.cashtoken_enabled, snapshot.cashtoken_enabled);
+        set_changed(self.category_id, snapshot.category_id.clone());
+        set_changed(self.has_ft_amount, snapshot.has_ft_amount);
+        set_changed(self.ft_amount, snapshot.ft_amount);
+        set_changed(self.ft_decimals, snapshot.ft_decimals);
+        set_changed(self.has_nft, snapshot.has_nft);
+        set_changed(self.nft_capability, snapshot.nft_capability);
+        set_changed(self.nft_commitment_hex, snapshot.nft_commitment_hex.clone());
+        set_changed(self.nft_commitment_format, snapshot.nft_commitment_format);
+    }
+
+    /// Short description of the token for card summaries, `None` without a token.
+    pub fn summary(self, ctx: Context) -> Option<String> {
+        if !self.cashtoken_enabled.get() {
+            return None;
+        }
+        let mut summary = format!("{} {}", ctx.t("token"), short_hex(&self.category_id.read()));
+        if self.has_ft_amount.get() {
+            summary += &format!(
+                " {} {}",
+                ctx.t("FT"),
+                format_decimal(self.ft_amount.get(), self.ft_decimals.get())
+            );
+        }
+        if self.has_nft.get() {
+            summary += &format!(" {} {}", ctx.t("NFT"), self.nft_capability.get().to_str());
+        }
+        Some(summary)
+    }
+
     pub fn token_data(self) -> Result<Option<OutputData>> {
-        Ok(match self.cashtoken_enabled.get() {
-            false => None,
-            true => {
-                let ft_amount = if self.has_ft_amount.get() {
-                    if self.ft_amount.get() == 0 {
-                        anyhow::bail!("FT amount must be nonzero");
-                    }
-                    i64::try_from(self.ft_amount.get())?
-                } else {
-                    0
-                };
-                let has_nft = self.has_nft.get();
-                let capability = match has_nft {
-                    true => self.nft_capability.get().into(),
-                    false => Capability::None,
-                };
-                let commitment = match has_nft {
-                    true => Vec::from_hex(&self.nft_commitment_hex.read())?,
-                    false => vec![],
-                };
-                let mut structure = 0;
-                if ft_amount != 0 {
-                    structure |= Structure::HasAmount as u8;
-                }
-                if has_nft {
-                    structure |= Structure::HasNFT as u8;
-                }
-                if !commitment.is_empty() {
-                    structure |= Structure::HasCommitmentLength as u8;
-                }
-                Some(OutputData {
-                    id: TokenID::from_hex(&self.category_id.read())?,
-                    bitfield: structure | capability as u8,
-                    amount: ft_amount,
-                    commitment,
-                })
-            }
-        })
+        self.snapshot().token_data()
     }
 
-    pub fn update_from_token_data(self, token_data: Option<&OutputData>) {
-        match token_data {
-            None => {
-                self.cashtoken_enabled.set(false);
-                self.category_id.write().clear();
-                self.has_ft_amount.set(false);
-                self.ft_amount.set(0);
-                self.has_nft.set(false);
-                self.nft_capability.set(NftCapability::default());
-                self.nft_commitment_hex.write().clear();
-                self.nft_commitment_format
-                    .set(NftCommitmentFormat::default());
-            }
-            Some(token_data) => {
-                self.cashtoken_enabled.set(true);
-                self.category_id.set(token_data.id.to_hex());
-                self.has_ft_amount.set(token_data.amount != 0);
-                self.ft_amount
-                    .set(u64::try_from(token_data.amount).unwrap());
-                let has_nft = token_data.has_nft();
-                self.has_nft.set(has_nft);
-                if has_nft {
-                    self.nft_capability.set(
-                        if (token_data.capability() & Capability::Mutable as u8) != 0 {
-                            NftCapability::Mutable
-                        } else if (token_data.capability() & Capability::Minting as u8) != 0 {
-                            NftCapability::Minting
-                        } else {
-                            NftCapability::Immutable
-                        },
-                    );
-                    if token_data.has_commitment_length() {
-                        self.nft_commitment_hex.set(token_data.commitment.to_hex());
-                    } else {
-                        self.nft_commitment_hex.write().clear();
-                    }
-                    self.nft_commitment_format
-                        .set(NftCommitmentFormat::default());
-                } else {
-                    self.nft_capability.set(NftCapability::default());
-                    self.nft_commitment_hex.write().clear();
-                    self.nft_commitment_format
-                        .set(NftCommitmentFormat::default());
-                }
-            }
+    /// Snapshot of `token_data`, keeping the decimals, which aren't part of it.
+    pub fn snapshot_from_token_data(self, token_data: Option<&OutputData>) -> TokenDataSnapshot {
+        TokenDataSnapshot {
+            ft_decimals: self.ft_decimals.get_untracked(),
+            ..TokenDataSnapshot::from_token_data(token_data)
         }
     }
 }
 
 #[component]
-pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
+pub fn TokenData(
+    ctx: Context,
+    token_data: TokenDataState,
+    /// Electrum server of the selected network, for the metadata lookup.
+    #[prop(into)]
+    electrum_server: Signal<Option<String>>,
+    /// Whether offline mode is on, disabling the metadata lookup.
+    #[prop(into)]
+    offline: Signal<bool>,
+    /// Categories offered in a dropdown next to the category ID.
+    #[prop(optional, into)]
+    categories: Option<Signal<Vec<CategoryChoice>>>,
+    /// NFTs offered for setting the commitment to theirs plus one.
+    #[prop(optional, into)]
+    counters: Option<Signal<Vec<CounterChoice>>>,
+) -> impl IntoView {
     let cashtoken_enabled = token_data.cashtoken_enabled;
     let has_ft_amount = token_data.has_ft_amount;
+    let ft_decimals = token_data.ft_decimals;
     let has_nft = token_data.has_nft;
     let nft_capability = token_data.nft_capability;
     let nft_commitment_hex = token_data.nft_commitment_hex;
@@ -199,6 +365,40 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
 
     let nft_commitment_error = RwSignal::new(false);
     let nft_commitment_lossy = RwSignal::new(false);
+    // The typed commitment isn't valid in its format, and wasn't stored.
+    let nft_commitment_parse_error = RwSignal::new(false);
+    // Byte length of the commitment being typed, before it is committed on change.
+    let nft_commitment_draft_len = RwSignal::new(None::<usize>);
+    let nft_commitment_len = move || {
+        nft_commitment_draft_len().or_else(|| {
+            let hex = nft_commitment_hex.read();
+            hex.chars()
+                .all(|c| c.is_ascii_hexdigit())
+                .then(|| hex.len() / 2)
+        })
+    };
+    let nft_commitment_too_long = move || {
+        nft_commitment_len().is_some_and(|l| l > usize::from(MAX_CONSENSUS_COMMITMENT_LENGTH))
+    };
+
+    let ft_decimals_error = RwSignal::new(false);
+    let counter_error = RwSignal::new(None::<String>);
+    let increment_counter = move |counter: &CounterChoice| {
+        let incremented = Vec::from_hex(&counter.commitment_hex)
+            .map_err(anyhow::Error::from)
+            .and_then(|commitment| increment_script_num(&commitment));
+        match incremented {
+            Ok(commitment) => {
+                token_data.category_id.set(counter.category_id.clone());
+                has_nft.set(true);
+                nft_commitment_hex.set(commitment.to_hex());
+                nft_commitment_format.set(NftCommitmentFormat::Number);
+                nft_commitment_parse_error.set(false);
+                counter_error.set(None);
+            }
+            Err(e) => counter_error.set(Some(format!("{}: {e}", counter.label))),
+        }
+    };
 
     let parsed_input_ft_id = move || format!("tx-output-ft-{}", token_data.key);
     let input_category_id = move || format!("tx-output-cat-{}", token_data.key);
@@ -207,7 +407,7 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
         <Show when=cashtoken_enabled>
             // CashToken category
             <div class="mt-3 mb-1 flex">
-                <label for=input_category_id class="mr-1">Category:</label>
+                <label for=input_category_id class="mr-1">{move || ctx.t("Category:")}</label>
                 <input
                     id=input_category_id
                     on:change=move |e| token_data.category_id.set(event_target_value(&e))
@@ -216,10 +416,48 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                         "font-mono grow placeholder:text-stone-600",
                     )
                     prop:value=token_data.category_id
-                    placeholder="Category ID"
+                    placeholder=move || ctx.t("Category ID")
                 />
+                {categories
+                    .map(|categories| {
+                        view! {
+                            <select
+                                class="bg-inherit border rounded ml-1 p-1 w-32"
+                                title=move || ctx.t("Fill in the category of a token in the inputs")
+                                on:input=move |e| {
+                                    let category_id = event_target_value(&e);
+                                    if !category_id.is_empty() {
+                                        token_data.category_id.set(category_id);
+                                    }
+                                }
+                                prop:value=move || {
+                                    token_data.category_id.track();
+                                    ""
+                                }
+                            >
+                                <option value="">{move || ctx.t("From input…")}</option>
+                                <For
+                                    each=move || categories.get()
+                                    key=|c| c.clone()
+                                    let:c
+                                >
+                                    <option value=c.category_id.clone()>
+                                        {c.label}" ("{short_hex(&c.category_id)}")"
+                                    </option>
+                                </For>
+                            </select>
+                        }
+                    })}
             </div>
 
+            <TokenIdentity
+                ctx
+                category_id=token_data.category_id
+                decimals=token_data.ft_decimals
+                electrum_server
+                offline
+            />
+
             // CashToken fungible amount
             <div class="my-1 ml-1">
                 <label>
@@ -235,16 +473,44 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                     class=("opacity-30", move || !has_ft_amount())
                     for=parsed_input_ft_id
                 >
-                    Amount:
+                    {move || ctx.t("Amount:")}
                 </label>
-                <ParsedInput
+                <DecimalInput
+                    ctx
+                    decimals=ft_decimals
                     value=token_data.ft_amount
+                    max=MAX_FT_AMOUNT
                     {..}
                     id=parsed_input_ft_id
                     disabled={move || !has_ft_amount()}
                     class=("w-52", true)
                     class=("disabled:opacity-30", true)
                 />
+                <label class="ml-2 mr-1" class=("opacity-30", move || !has_ft_amount())>
+                    {move || ctx.t("Decimals:")}
+                    <input
+                        class="border border-solid rounded px-1 bg-stone-900 w-12 ml-1 disabled:opacity-30"
+                        class=("border-stone-600", move || !ft_decimals_error())
+                        class=("border-red-700", ft_decimals_error)
+                        title=move || ctx.t("Only changes how the amount is entered and shown")
+                        disabled=move || !has_ft_amount()
+                        on:input=move |e| {
+                            match event_target_value(&e)
+                                .trim()
+                                .parse()
+                                .ok()
+                                .filter(|d| *d <= MAX_FT_DECIMALS)
+                            {
+                                Some(d) => {
+                                    ft_decimals.set(d);
+                                    ft_decimals_error.set(false);
+                                }
+                                None => ft_decimals_error.set(true),
+                            }
+                        }
+                        prop:value=move || ft_decimals().to_string()
+                    />
+                </label>
             </div>
 
             // CashToken NFT
@@ -270,9 +536,9 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                         }
                         prop:value={move || nft_capability().to_str()}
                     >
-                        <option value={|| NftCapability::Immutable.to_str()}>Immutable</option>
-                        <option value={|| NftCapability::Mutable.to_str()}>Mutable</option>
-                        <option value={|| NftCapability::Minting.to_str()}>Minting</option>
+                        <option value={|| NftCapability::Immutable.to_str()}>{move || ctx.t("Immutable")}</option>
+                        <option value={|| NftCapability::Mutable.to_str()}>{move || ctx.t("Mutable")}</option>
+                        <option value={|| NftCapability::Minting.to_str()}>{move || ctx.t("Minting")}</option>
                     </select>
 
                     // NFT commitment
@@ -280,21 +546,41 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                         <textarea
                             spellcheck="false"
                             rows=1
+                            on:input=move |e| {
+                                let value = event_target_value(&e);
+                                let format = nft_commitment_format();
+                                let parsed = format.parse(&value);
+                                nft_commitment_parse_error
+                                    .set(format != NftCommitmentFormat::Hex && parsed.is_err());
+                                nft_commitment_draft_len.set(Some(match format {
+                                    NftCommitmentFormat::Hex => {
+                                        value.chars().filter(|c| !c.is_ascii_whitespace()).count() / 2
+                                    }
+                                    _ => parsed.map_or(0, |b| b.len()),
+                                }));
+                            }
                             on:change=move |e| {
+                                nft_commitment_draft_len.set(None);
+                                let value = event_target_value(&e);
                                 match nft_commitment_format() {
                                     NftCommitmentFormat::Hex => {
-                                        nft_commitment_hex.set(event_target_value(&e));
-                                    }
-                                    NftCommitmentFormat::Plaintext => {
-                                        nft_commitment_hex.set(event_target_value(&e).as_bytes().to_hex());
+                                        nft_commitment_parse_error.set(false);
+                                        nft_commitment_hex.set(value);
                                     }
+                                    format => match format.parse(&value) {
+                                        Ok(bytes) => {
+                                            nft_commitment_parse_error.set(false);
+                                            nft_commitment_hex.set(bytes.to_hex());
+                                        }
+                                        Err(_) => nft_commitment_parse_error.set(true),
+                                    },
                                 }
                             }
                             class=concat!(
                                 "border border-solid rounded border-stone-600 px-1 w-full bg-inherit ",
                                 "placeholder:text-stone-600 font-mono grow bg-stone-900 ",
                             )
-                            placeholder="Commitment"
+                            placeholder=move || ctx.t("Commitment")
                             prop:value=move || {
                                 match nft_commitment_format() {
                                     NftCommitmentFormat::Hex => {
@@ -302,7 +588,7 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                                         nft_commitment_lossy.set(false);
                                         nft_commitment_hex()
                                     }
-                                    NftCommitmentFormat::Plaintext => 'a: {
+                                    format => 'a: {
                                         let bytes = match Vec::from_hex(&nft_commitment_hex.read()) {
                                             Ok(b) => b,
                                             Err(e) => {
@@ -312,6 +598,22 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                                             }
                                         };
                                         nft_commitment_error.set(false);
+                                        if format == NftCommitmentFormat::Base64 {
+                                            nft_commitment_lossy.set(false);
+                                            break 'a BASE64_STANDARD.encode(bytes);
+                                        }
+                                        if format == NftCommitmentFormat::Number {
+                                            break 'a match parse_script_num(&bytes) {
+                                                Ok(n) => {
+                                                    nft_commitment_lossy.set(false);
+                                                    n.to_string()
+                                                }
+                                                Err(e) => {
+                                                    nft_commitment_lossy.set(true);
+                                                    e.to_string()
+                                                }
+                                            };
+                                        }
                                         let text = String::from_utf8_lossy(&bytes);
                                         match text {
                                             Cow::Borrowed(s) => {
@@ -329,7 +631,7 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                             disabled=move || !has_nft()
                                 || nft_commitment_error()
                                 || nft_commitment_lossy()
-                            class=("text-red-700", nft_commitment_error)
+                            class=("text-red-700", move || nft_commitment_error() || nft_commitment_parse_error())
                             class=("text-yellow-700", nft_commitment_lossy)
                             class=("opacity-30", move || !has_nft())
                         />
@@ -338,6 +640,7 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                                 class="bg-inherit border rounded ml-1 p-1 disabled:opacity-30"
                                 disabled=move || !has_nft()
                                 on:input=move |e| {
+                                    nft_commitment_parse_error.set(false);
                                     nft_commitment_format.set(
                                         NftCommitmentFormat::from_str(&event_target_value(&e)).unwrap()
                                     )
@@ -345,10 +648,53 @@ pub fn TokenData(token_data: TokenDataState) -> impl IntoView {
                                 prop:value={move || nft_commitment_format().to_str()}
                             >
                                 <option value={|| NftCommitmentFormat::Hex.to_str()}>Hex</option>
-                                <option value={|| NftCommitmentFormat::Plaintext.to_str()}>Plaintext</option>
+                                <option value={|| NftCommitmentFormat::Plaintext.to_str()}>{move || ctx.t("Plaintext")}</option>
+                                <option value={|| NftCommitmentFormat::Base64.to_str()}>Base64</option>
+                                <option value={|| NftCommitmentFormat::Number.to_str()}>{move || ctx.t("Number")}</option>
                             </select>
                         </div>
+                        {counters
+                            .map(|counters| {
+                                view! {
+                                    <select
+                                        class="bg-inherit border rounded ml-1 p-1 w-32 disabled:opacity-30"
+                                        title=move || ctx.t("Set the commitment to an input's commitment plus one, read as a number")
+                                        disabled=move || !has_nft()
+                                        on:input=move |e| {
+                                            let label = event_target_value(&e);
+                                            if let Some(counter) = counters.read().iter().find(|c| c.label == label) {
+                                                increment_counter(counter);
+                                            }
+                                        }
+                                        prop:value=move || {
+                                            nft_commitment_hex.track();
+                                            ""
+                                        }
+                                    >
+                                        <option value="">{move || ctx.t("+1 from input…")}</option>
+                                        <For
+                                            each=move || counters.get()
+                                            key=|c| c.clone()
+                                            let:c
+                                        >
+                                            <option value=c.label.clone()>{c.label.clone()}</option>
+                                        </For>
+                                    </select>
+                                }
+                            })}
+                    </div>
+                    <div
+                        class="text-sm"
+                        class=("opacity-30", move || !has_nft())
+                        class=("text-red-700", nft_commitment_too_long)
+                    >
+                        {move || nft_commitment_len().unwrap_or(0)}
+                        "/"
+                        {MAX_CONSENSUS_COMMITMENT_LENGTH}
+                        " "
+                        {move || ctx.t("bytes")}
                     </div>
+                    {move || counter_error.get().map(|e| view! { <p class="text-sm text-red-700">{e}</p> })}
                 </div>
             </div>
         </Show>
@@ -0,0 +1,125 @@
+use bitcoincash::{hashes::hex::FromHex, TokenID};
+use leptos::{
+    component,
+    prelude::{
+        ClassAttribute, ElementChild, Get, GetUntracked, GlobalAttributes, IntoAny, OnAttribute,
+        Read, RwSignal, Set, Signal,
+    },
+    task::spawn_local,
+    view, IntoView,
+};
+
+use crate::{
+    authchain::{self, Identity},
+    util::short_hex,
+    Context,
+};
+
+#[derive(Clone)]
+enum Lookup {
+    Idle,
+    Pending,
+    Done(String, Result<Identity, String>),
+}
+
+/// Looks up the authenticated metadata of a category, offering its decimals for the amount.
+#[component]
+pub fn TokenIdentity(
+    ctx: Context,
+    category_id: RwSignal<String>,
+    decimals: RwSignal<u8>,
+    #[prop(into)] electrum_server: Signal<Option<String>>,
+    #[prop(into)] offline: Signal<bool>,
+) -> impl IntoView {
+    let lookup = RwSignal::new(Lookup::Idle);
+
+    let start = move |_| {
+        let category_id = category_id.get_untracked();
+        let category = match TokenID::from_hex(category_id.trim()) {
+            Ok(c) => c,
+            Err(e) => {
+                lookup.set(Lookup::Done(
+                    category_id,
+                    Err(format!("{} {e}", ctx.t("Invalid category:"))),
+                ));
+                return;
+            }
+        };
+        let Some(server) = electrum_server.get_untracked() else {
+            let error = ctx
+                .t("No known Electrum server for this network")
+                .to_string();
+            lookup.set(Lookup::Done(category_id, Err(error)));
+            return;
+        };
+        lookup.set(Lookup::Pending);
+        spawn_local(async move {
+            let identity = authchain::lookup(&server, category).await;
+            lookup.set(Lookup::Done(
+                category_id,
+                identity.map_err(|e| e.to_string()),
+            ));
+        });
+    };
+
+    let result = move || match lookup.get() {
+        Lookup::Idle => None,
+        Lookup::Pending => Some(
+            view! { <p class="text-stone-500">{ctx.t("Following the authchain…")}</p> }.into_any(),
+        ),
+        // Looked up before the category was changed
+        Lookup::Done(looked_up, _) if looked_up != *category_id.read() => None,
+        Lookup::Done(_, Err(e)) => Some(view! { <p class="text-red-700">{e}</p> }.into_any()),
+        Lookup::Done(_, Ok(identity)) => {
+            let authhead = identity.authhead.txid.to_string();
+            let metadata = match identity.metadata {
+                None => {
+                    view! { <p class="text-stone-500">{ctx.t("The authchain announces no metadata.")}</p> }
+                        .into_any()
+                }
+                Some(Err(e)) => view! { <p class="text-red-700">{e}</p> }.into_any(),
+                Some(Ok(metadata)) => view! {
+                    <p>
+                        {metadata.name}
+                        {metadata.symbol.map(|s| format!(" ({s})"))}
+                        {metadata.decimals.map(|d| view! {
+                            <button
+                                class="border border-solid rounded border-stone-600 px-1 ml-2"
+                                title=ctx.t("Enter and show the amount with the registry's decimals")
+                                on:click=move |_| decimals.set(d)
+                            >
+                                {ctx.t("Use")}" "{d}" "{ctx.t("decimals")}
+                            </button>
+                        })}
+                    </p>
+                }
+                .into_any(),
+            };
+            Some(view! {
+                <p class="text-stone-500" title=authhead.clone()>
+                    {ctx.t("Authhead")}" "{short_hex(&authhead)}", "{identity.authhead.length}" "
+                    {ctx.t("transactions after the authbase")}
+                </p>
+                {metadata}
+            }
+            .into_any())
+        }
+    };
+
+    view! {
+        <div class="ml-1 text-sm">
+            <button
+                class="border border-solid rounded border-stone-600 px-1 disabled:opacity-30"
+                title=move || match offline.get() {
+                    true => ctx.t("Unavailable in offline mode"),
+                    false => ctx.t("Find the metadata the category's authchain vouches for, using an Electrum server"),
+                }
+                disabled=offline
+                on:click=start
+            >
+                {move || ctx.t("Identity")}
+            </button>
+            {result}
+        </div>
+    }
+}
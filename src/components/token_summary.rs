@@ -0,0 +1,147 @@
+use bitcoincash::hashes::hex::ToHex;
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, CollectView, ElementChild, GetUntracked,
+        GlobalAttributes, IntoAny, Memo, OnAttribute, PropAttribute, RwSignal, Set, Show, With,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    components::{token_data::NftCapability, tx_output::TxOutputSnapshot},
+    macros::StrEnum,
+    partially_signed::PartiallySignedTransaction,
+    token_summary::{summarize, CategorySummary},
+    util::short_hex,
+    Context,
+};
+
+fn nfts(capabilities: &[NftCapability]) -> String {
+    if capabilities.is_empty() {
+        return "0".into();
+    }
+    let names: Vec<_> = capabilities.iter().map(|c| c.to_str()).collect();
+    format!("{} ({})", capabilities.len(), names.join(", "))
+}
+
+fn signed(delta: impl Into<i128>) -> String {
+    match delta.into() {
+        d if d > 0 => format!("+{d}"),
+        d => d.to_string(),
+    }
+}
+
+fn category_row(
+    c: CategorySummary,
+    change_address: RwSignal<String>,
+    error: RwSignal<String>,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+    ctx: Context,
+) -> impl IntoView {
+    let category = c.category.to_hex();
+    // The tokens of a genesis aren't spent from the inputs
+    let add_change = (c.ft_delta() < 0 && !c.genesis).then(|| {
+        let c = c.clone();
+        let add = move |_| match c.token_change(change_address.get_untracked().trim()) {
+            Some(Ok(output)) => {
+                on_add_output(output);
+                error.set(String::new());
+            }
+            Some(Err(e)) => error.set(format!("{}: {e}", ctx.t("Token change address"))),
+            None => {}
+        };
+        view! {
+            <button
+                class="border border-solid rounded border-stone-600 px-1 ml-2"
+                title=ctx.t("Add an output returning the tokens the outputs don't spend to the token change address")
+                on:click=add
+            >
+                {ctx.t("Add change")}
+            </button>
+        }
+    });
+    view! {
+        <tr>
+            <td class="pr-3 font-mono" title=category.clone()>
+                {short_hex(&category)}
+                {c.genesis.then(|| view! { <span class="text-sky-600 ml-1">{ctx.t("genesis")}</span> })}
+            </td>
+            <td class="pr-3 text-right">{c.ft_in.to_string()}</td>
+            <td class="pr-3 text-right">{c.ft_out.to_string()}</td>
+            <td class="pr-3 text-right" class=("text-red-700", c.ft_delta() < 0)>{signed(c.ft_delta())}</td>
+            <td class="pr-3">{nfts(&c.nfts_in)}</td>
+            <td class="pr-3">{nfts(&c.nfts_out)}</td>
+            <td class="text-right" class=("text-red-700", c.nft_delta() < 0)>{signed(c.nft_delta())}</td>
+            <td>{add_change}</td>
+        </tr>
+    }
+}
+
+/// Tokens spent and created by the transaction, per category.
+#[component]
+pub fn TokenSummaryPanel(
+    tx: Memo<Option<PartiallySignedTransaction>>,
+    on_add_output: impl Fn(TxOutputSnapshot) + Copy + Send + Sync + 'static,
+    ctx: Context,
+) -> impl IntoView {
+    let summary = move || tx.with(|tx| tx.as_ref().map(summarize));
+    let change_address = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+    let burns_ft = move || {
+        summary().is_some_and(|s| s.categories.iter().any(|c| c.ft_delta() < 0 && !c.genesis))
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            {move || match summary() {
+                None => view! {
+                    <p class="text-stone-500">{ctx.t("The transaction isn't complete yet.")}</p>
+                }
+                .into_any(),
+                Some(summary) if summary.categories.is_empty() => view! {
+                    <p class="text-stone-500">{ctx.t("No tokens in this transaction.")}</p>
+                }
+                .into_any(),
+                Some(summary) => view! {
+                    <table>
+                        <thead class="text-stone-500 text-left">
+                            <tr>
+                                <th class="pr-3">{ctx.t("Category")}</th>
+                                <th class="pr-3">{ctx.t("FT in")}</th>
+                                <th class="pr-3">{ctx.t("FT out")}</th>
+                                <th class="pr-3">{ctx.t("FT net")}</th>
+                                <th class="pr-3">{ctx.t("NFTs in")}</th>
+                                <th class="pr-3">{ctx.t("NFTs out")}</th>
+                                <th>{ctx.t("NFT net")}</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {summary
+                                .categories
+                                .into_iter()
+                                .map(|c| category_row(c, change_address, error, on_add_output, ctx))
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                    {(!summary.inputs_known).then(|| view! {
+                        <p class="text-yellow-700">
+                            {ctx.t("Signed inputs are left out, the tokens they spend are unknown.")}
+                        </p>
+                    })}
+                }
+                .into_any(),
+            }}
+            <Show when=burns_ft>
+                <input
+                    class="border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600 font-mono w-full mt-1"
+                    placeholder=ctx.t("Token change address")
+                    on:input=move |e| change_address.set(event_target_value(&e))
+                    prop:value=change_address
+                />
+                <p class="text-red-700">{error}</p>
+            </Show>
+        </div>
+    }
+}
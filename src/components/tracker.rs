@@ -1,19 +1,182 @@
-#![allow(unused)]
-use leptos::logging::log;
+//! Opt-in debug panel that tracks the signals of every input and output card by its key, so
+//! signals left alive after a card is deleted show up in the UI instead of as growing memory.
 
-pub struct Tracker {
-    id: usize,
+use std::cell::RefCell;
+
+use leptos::{
+    component,
+    logging::log,
+    prelude::{
+        ArcTrigger, ClassAttribute, CollectView, ElementChild, IsDisposed, Notify, OnAttribute,
+        Track,
+    },
+    view, IntoView,
+};
+
+use crate::Context;
+
+/// Events kept for the panel, older ones are dropped.
+const MAX_EVENTS: usize = 100;
+
+/// Card state made of signals, each of which must be disposed with the card.
+pub trait Tracked {
+    const KIND: &'static str;
+
+    fn key(&self) -> usize;
+
+    /// Every signal owned by the card, including those of nested state.
+    fn signals(&self) -> Vec<Box<dyn IsDisposed>>;
+}
+
+struct Entry {
+    key: usize,
+    kind: &'static str,
+    signals: Vec<Box<dyn IsDisposed>>,
+    deleted: bool,
+}
+
+impl Entry {
+    fn alive(&self) -> usize {
+        self.signals.iter().filter(|s| !s.is_disposed()).count()
+    }
 }
 
-impl Tracker {
-    pub fn new(id: usize) -> Self {
-        log!("Tracker {} created", id);
-        Self { id }
+#[derive(Default)]
+struct Registry {
+    enabled: bool,
+    entries: Vec<Entry>,
+    events: Vec<String>,
+    changed: ArcTrigger,
+}
+
+impl Registry {
+    fn event(&mut self, event: String) {
+        log!("{event}");
+        if self.events.len() == MAX_EVENTS {
+            self.events.remove(0);
+        }
+        self.events.push(event);
+        self.changed.notify();
     }
 }
 
-impl Drop for Tracker {
-    fn drop(&mut self) {
-        log!("Tracker {} dropped", self.id);
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+/// Start or stop tracking. Cards created while disabled are never tracked.
+pub fn set_enabled(enabled: bool) {
+    REGISTRY.with_borrow_mut(|r| {
+        r.enabled = enabled;
+        if !enabled {
+            r.entries.clear();
+            r.events.clear();
+            r.changed.notify();
+        }
+    });
+}
+
+/// Record the signals of a newly created card.
+pub fn created<T: Tracked>(state: &T) {
+    REGISTRY.with_borrow_mut(|r| {
+        if !r.enabled {
+            return;
+        }
+        let signals = state.signals();
+        let event = format!(
+            "{} {} created with {} signals",
+            T::KIND,
+            state.key(),
+            signals.len()
+        );
+        r.entries.push(Entry {
+            key: state.key(),
+            kind: T::KIND,
+            signals,
+            deleted: false,
+        });
+        r.event(event);
+    });
+}
+
+/// Record that a card was deleted, after its signals were disposed. It stays listed only if some
+/// of them are still alive.
+pub fn disposed<T: Tracked>(state: &T) {
+    REGISTRY.with_borrow_mut(|r| {
+        let Some(i) = r.entries.iter().position(|e| e.key == state.key()) else {
+            return;
+        };
+        let event = match r.entries[i].alive() {
+            0 => {
+                r.entries.remove(i);
+                format!("{} {} disposed", T::KIND, state.key())
+            }
+            alive => {
+                r.entries[i].deleted = true;
+                format!(
+                    "{} {} disposed, leaking {alive} signals",
+                    T::KIND,
+                    state.key()
+                )
+            }
+        };
+        r.event(event);
+    });
+}
+
+#[component]
+pub fn TrackerPanel(ctx: Context) -> impl IntoView {
+    let changed = REGISTRY.with_borrow(|r| r.changed.clone());
+    let rows = {
+        let changed = changed.clone();
+        move || {
+            changed.track();
+            REGISTRY.with_borrow(|r| {
+                r.entries
+                    .iter()
+                    .map(|e| {
+                        let (alive, total) = (e.alive(), e.signals.len());
+                        view! {
+                            <div class="table-row" class=("text-red-700", e.deleted)>
+                                <div class="table-cell pr-2">{e.kind}</div>
+                                <div class="table-cell pr-2">{e.key}</div>
+                                <div class="table-cell">{format!("{alive}/{total}")}</div>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            })
+        }
+    };
+    let events = move || {
+        changed.track();
+        REGISTRY.with_borrow(|r| {
+            r.events
+                .iter()
+                .rev()
+                .map(|e| view! { <div>{e.clone()}</div> })
+                .collect_view()
+        })
+    };
+    // Forget the leaks seen so far, so new ones stand out
+    let clear = move |_| {
+        REGISTRY.with_borrow_mut(|r| {
+            r.entries.retain(|e| !e.deleted);
+            r.events.clear();
+            r.changed.notify();
+        })
+    };
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm font-mono">
+            <div class="flex justify-between mb-1">
+                <span>{move || ctx.t("Live signals per card, leaks of deleted cards in red")}</span>
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=clear>
+                    {move || ctx.t("Clear")}
+                </button>
+            </div>
+            <div class="table mb-1">{rows}</div>
+            <div class="max-h-40 overflow-y-auto text-stone-500">{events}</div>
+        </div>
     }
 }
@@ -0,0 +1,1879 @@
+use anyhow::{bail, Result};
+use bitcoincash::blockdata::token::OutputData;
+use bitcoincash::hashes::hex::ToHex;
+use bitcoincash::hashes::Hash;
+use bitcoincash::psbt::serialize::Serialize;
+use bitcoincash::secp256k1::{All, Secp256k1};
+use bitcoincash::{PackedLockTime, TxOut, Txid};
+use futures::future::{abortable, AbortHandle};
+use gloo::timers::callback::Timeout;
+use leptos::prelude::window;
+use leptos::prelude::{
+    document, event_target_checked, event_target_value, on_cleanup, window_event_listener,
+    AddAnyAttr, ClassAttribute, CollectView, CustomAttribute, Dispose, Effect, ElementChild, For,
+    Get, GetUntracked, GetValue, GlobalAttributes, IntoAny, Memo, OnAttribute, PropAttribute, Read,
+    ReadUntracked, RwSignal, Set, SetValue, Show, Signal, StoredValue, Update, UpdateValue, With,
+    WithUntracked, WithValue, Write,
+};
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use leptos::{component, logging::log, view, IntoView};
+use leptos::{ev, web_sys};
+
+use crate::chain::{ChainBackend, NodeClient, Server};
+use crate::coin_selection::{max_values, subtract_fee};
+use crate::components::airdrop_builder::AirdropBuilder;
+use crate::components::bcmr_builder::BcmrBuilder;
+use crate::components::byte_order::ByteOrderConverter;
+use crate::components::cltv_wizard::CltvWizard;
+use crate::components::coin_selector::CoinSelector;
+use crate::components::diagnostics::DiagnosticList;
+use crate::components::hash_calculator::HashCalculator;
+use crate::components::htlc_wizard::HtlcWizard;
+use crate::components::input_verifier::InputVerifier;
+use crate::components::minting_series::MintingSeries;
+use crate::components::multisig_wizard::MultisigWizard;
+use crate::components::number_converter::NumberConverter;
+use crate::components::opcode_palette::{OpcodePalette, PaletteTarget};
+use crate::components::pay_to_many::PayToMany;
+use crate::components::shortcut_help::ShortcutHelp;
+use crate::components::sweep_tool::SweepTool;
+use crate::components::template_picker::TemplatePicker;
+use crate::components::template_variables::TemplateVariables;
+use crate::components::token_data::{CategoryChoice, CounterChoice, NftCapability};
+use crate::components::token_summary::TokenSummaryPanel;
+use crate::components::tx_input::{TxInput, TxInputSnapshot, TxInputState};
+use crate::components::tx_output::{TxOutput, TxOutputSnapshot, TxOutputState};
+use crate::components::{set_changed, FiatValue, ParsedInput, U32Input};
+use crate::hex_format::{self, HexFormat};
+use crate::history::History;
+use crate::js_reexport::{clipboard_read_text, clipboard_write_text, download_text};
+use crate::macros::StrEnum;
+use crate::partially_signed::{MaybeUnsignedTxIn, PartiallySignedTransaction, SignatureType};
+use crate::shortcuts::{self, Action};
+use crate::snapshot::{ClipboardEntry, EditorSnapshot};
+use crate::tx_codec;
+use crate::util::short_hex;
+use crate::validation::{burned_nfts, check_transaction, Diagnostic, Location, Severity};
+use crate::Context;
+
+/// Transactions with more inputs and outputs than this only update their size after editing
+/// pauses for [`DEBOUNCE_DELAY_MS`].
+const DEBOUNCE_ENTRIES: usize = 100;
+const DEBOUNCE_DELAY_MS: u32 = 300;
+
+/// Size of the transaction being edited, shown below the fee.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct SizeEstimate {
+    bytes: usize,
+    /// Whether all inputs are signed, so the size won't change.
+    exact: bool,
+}
+
+impl SizeEstimate {
+    fn new(tx: &PartiallySignedTransaction, signatures: SignatureType) -> Self {
+        Self {
+            bytes: tx.estimated_size(signatures),
+            exact: tx.is_signed(),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct TabState {
+    pub key: usize,
+    pub name: RwSignal<String>,
+    /// Latest state of the tab's editor, kept up to date for autosaving. The editor starts from
+    /// this state when it is created.
+    pub snapshot: RwSignal<Option<EditorSnapshot>>,
+}
+
+impl TabState {
+    pub fn new(key: usize, name: String, snapshot: Option<EditorSnapshot>) -> Self {
+        Self {
+            key,
+            name: RwSignal::new(name),
+            snapshot: RwSignal::new(snapshot),
+        }
+    }
+
+    pub fn dispose(self) {
+        self.name.dispose();
+        self.snapshot.dispose();
+    }
+}
+
+/// The transaction edited in a tab, as the signals its cards are made of, with the operations the
+/// editor and its tools perform on it.
+#[derive(Copy, Clone)]
+pub struct TxEditorState {
+    pub inputs: RwSignal<Vec<TxInputState>>,
+    pub outputs: RwSignal<Vec<TxOutputState>>,
+    pub version: RwSignal<i32>,
+    pub locktime: RwSignal<u32>,
+    ctx: Context,
+}
+
+impl TxEditorState {
+    /// An editor with no inputs or outputs, ready for [`restore`](Self::restore).
+    pub fn new(ctx: Context) -> Self {
+        Self {
+            inputs: RwSignal::new(vec![]),
+            outputs: RwSignal::new(vec![]),
+            version: RwSignal::new(2),
+            locktime: RwSignal::new(0),
+            ctx,
+        }
+    }
+
+    pub fn snapshot(self) -> EditorSnapshot {
+        EditorSnapshot {
+            version: self.version.get(),
+            locktime: self.locktime.get(),
+            inputs: self.inputs.read().iter().map(|t| t.snapshot()).collect(),
+            outputs: self.outputs.read().iter().map(|t| t.snapshot()).collect(),
+        }
+    }
+
+    pub fn restore(self, snapshot: &EditorSnapshot) {
+        self.resize_inputs(snapshot.inputs.len());
+        self.resize_outputs(snapshot.outputs.len());
+        set_changed(self.version, snapshot.version);
+        set_changed(self.locktime, snapshot.locktime);
+        for (tx_input, s) in self.inputs.read_untracked().iter().zip(&snapshot.inputs) {
+            tx_input.restore(s);
+        }
+        for (tx_output, s) in self.outputs.read_untracked().iter().zip(&snapshot.outputs) {
+            tx_output.restore(s);
+        }
+    }
+
+    /// Replace the transaction with `tx`, keeping the labels and the amount options of the cards.
+    pub fn load_pst(self, tx: &PartiallySignedTransaction) {
+        self.resize_inputs(tx.input.len());
+        self.resize_outputs(tx.output.len());
+        set_changed(self.version, tx.version);
+        set_changed(self.locktime, tx.lock_time.0);
+        // One restore per card, which only notifies the fields that changed
+        for (tx_input, input) in self.inputs.read_untracked().iter().zip(&tx.input) {
+            tx_input.update_from_txin(input);
+        }
+        for (tx_output, output) in self.outputs.read_untracked().iter().zip(&tx.output) {
+            tx_output.update_from_txout(output);
+        }
+    }
+
+    /// Replace the transaction with the one in `hex`, decoding it on this thread.
+    pub fn load_hex(self, hex: &str) -> Result<()> {
+        let bytes = hex_format::parse(hex)?;
+        self.load_pst(&PartiallySignedTransaction::deserialize_any(&bytes)?);
+        Ok(())
+    }
+
+    /// A new transaction with one blank input and output.
+    pub fn reset(self) {
+        let preferences = self.ctx.preferences.get_untracked();
+        for tx_input in self.inputs.write().drain(..) {
+            tx_input.dispose();
+        }
+        for tx_output in self.outputs.write().drain(..) {
+            tx_output.dispose();
+        }
+        self.restore(&preferences.editor_snapshot());
+    }
+
+    pub fn to_pst(self) -> Result<PartiallySignedTransaction> {
+        self.to_pst_skipping_blank_inputs(false)
+    }
+
+    /// The transaction, leaving out the blank inputs if `skip_blank` so coin selection can tell
+    /// how much the other inputs are short.
+    pub fn to_pst_skipping_blank_inputs(
+        self,
+        skip_blank: bool,
+    ) -> Result<PartiallySignedTransaction> {
+        let input = self
+            .inputs
+            .read()
+            .iter()
+            .filter(|tx_input| !(skip_blank && self.is_blank_input(tx_input)))
+            .map(|&tx_input| tx_input.try_into())
+            .collect::<Result<_, _>>()?;
+        let output = self
+            .outputs
+            .read()
+            .iter()
+            .map(|&tx_output| tx_output.try_into())
+            .collect::<Result<_, _>>()?;
+        let mut tx = PartiallySignedTransaction {
+            version: self.version.get(),
+            lock_time: PackedLockTime(self.locktime.get()),
+            input,
+            output,
+        };
+        let fee_paying = self
+            .outputs
+            .read()
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.subtract_fee.get())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if !fee_paying.is_empty() {
+            let (fee_rate, signatures) = self
+                .ctx
+                .preferences
+                .with(|p| (p.fee_rate, p.signature_type));
+            subtract_fee(&mut tx, &fee_paying, fee_rate, signatures)?;
+        }
+        Ok(tx)
+    }
+
+    /// Whether the input is left as in a new transaction, which coin selection replaces.
+    pub fn is_blank_input(self, tx_input: &TxInputState) -> bool {
+        tx_input.snapshot() == self.ctx.preferences.get_untracked().input_snapshot()
+    }
+
+    pub fn is_blank_output(self, tx_output: &TxOutputState) -> bool {
+        tx_output.snapshot() == self.ctx.preferences.get_untracked().output_snapshot()
+    }
+
+    /// Append an input with the default values from the preferences.
+    pub fn add_blank_input(self) {
+        self.add_input(&self.ctx.preferences.get_untracked().input_snapshot());
+    }
+
+    pub fn add_blank_output(self) {
+        self.add_output(&self.ctx.preferences.get_untracked().output_snapshot());
+    }
+
+    pub fn add_input(self, snapshot: &TxInputSnapshot) {
+        let len = self.inputs.with_untracked(Vec::len);
+        self.insert_input(len, snapshot);
+    }
+
+    pub fn add_output(self, snapshot: &TxOutputSnapshot) {
+        let len = self.outputs.with_untracked(Vec::len);
+        self.insert_output(len, snapshot);
+    }
+
+    pub fn insert_input(self, index: usize, snapshot: &TxInputSnapshot) {
+        let mut tx_inputs = self.inputs.write();
+        tx_inputs.insert(
+            index,
+            TxInputState::from_snapshot(self.ctx.next_key(), index, snapshot),
+        );
+        for (i, tx) in tx_inputs.iter().enumerate().skip(index + 1) {
+            tx.index.set(i);
+        }
+    }
+
+    pub fn insert_output(self, index: usize, snapshot: &TxOutputSnapshot) {
+        let mut tx_outputs = self.outputs.write();
+        tx_outputs.insert(
+            index,
+            TxOutputState::from_snapshot(self.ctx.next_key(), index, snapshot),
+        );
+        for (i, tx) in tx_outputs.iter().enumerate().skip(index + 1) {
+            tx.index.set(i);
+        }
+    }
+
+    /// Insert a blank input next to the one with the given key, offset 0 for above and 1 for below.
+    pub fn insert_input_near(self, key: usize, offset: usize) {
+        let index = self.input_index(key);
+        self.insert_input(
+            index + offset,
+            &self.ctx.preferences.get_untracked().input_snapshot(),
+        );
+    }
+
+    pub fn insert_output_near(self, key: usize, offset: usize) {
+        let index = self.output_index(key);
+        self.insert_output(
+            index + offset,
+            &self.ctx.preferences.get_untracked().output_snapshot(),
+        );
+    }
+
+    /// Insert a copy of the input with the given key below it.
+    pub fn duplicate_input(self, key: usize) {
+        let index = self.input_index(key);
+        let snapshot = self.inputs.read_untracked()[index].snapshot();
+        self.insert_input(index + 1, &snapshot);
+    }
+
+    pub fn duplicate_output(self, key: usize) {
+        let index = self.output_index(key);
+        let snapshot = self.outputs.read_untracked()[index].snapshot();
+        self.insert_output(index + 1, &snapshot);
+    }
+
+    pub fn delete_input(self, key: usize) {
+        self.retain_inputs(|t| t.key != key);
+    }
+
+    pub fn delete_output(self, key: usize) {
+        self.retain_outputs(|t| t.key != key);
+    }
+
+    pub fn delete_selected_inputs(self) {
+        self.retain_inputs(|t| !t.selected.get_untracked());
+    }
+
+    pub fn delete_selected_outputs(self) {
+        self.retain_outputs(|t| !t.selected.get_untracked());
+    }
+
+    pub fn delete_blank_inputs(self) {
+        self.retain_inputs(|t| !self.is_blank_input(t));
+    }
+
+    pub fn delete_blank_outputs(self) {
+        self.retain_outputs(|t| !self.is_blank_output(t));
+    }
+
+    pub fn duplicate_selected_inputs(self) {
+        let selected = self.inputs.with_untracked(|t| {
+            t.iter()
+                .filter(|t| t.selected.get_untracked())
+                .map(|t| t.key)
+                .collect::<Vec<_>>()
+        });
+        for key in selected {
+            self.duplicate_input(key);
+        }
+    }
+
+    pub fn duplicate_selected_outputs(self) {
+        let selected = self.outputs.with_untracked(|t| {
+            t.iter()
+                .filter(|t| t.selected.get_untracked())
+                .map(|t| t.key)
+                .collect::<Vec<_>>()
+        });
+        for key in selected {
+            self.duplicate_output(key);
+        }
+    }
+
+    fn input_index(self, key: usize) -> usize {
+        self.inputs
+            .with_untracked(|t| t.iter().position(|t| t.key == key).unwrap())
+    }
+
+    fn output_index(self, key: usize) -> usize {
+        self.outputs
+            .with_untracked(|t| t.iter().position(|t| t.key == key).unwrap())
+    }
+
+    /// Dispose of the inputs `keep` returns false for, and renumber the rest.
+    fn retain_inputs(self, keep: impl Fn(&TxInputState) -> bool) {
+        let mut tx_inputs = self.inputs.write();
+        tx_inputs.retain(|t| {
+            let keep = keep(t);
+            if !keep {
+                t.dispose();
+            }
+            keep
+        });
+        for (i, tx) in tx_inputs.iter().enumerate() {
+            tx.index.set(i);
+        }
+    }
+
+    fn retain_outputs(self, keep: impl Fn(&TxOutputState) -> bool) {
+        let mut tx_outputs = self.outputs.write();
+        tx_outputs.retain(|t| {
+            let keep = keep(t);
+            if !keep {
+                t.dispose();
+            }
+            keep
+        });
+        for (i, tx) in tx_outputs.iter().enumerate() {
+            tx.index.set(i);
+        }
+    }
+
+    // The lists are only written when their length changes, rerendering every card otherwise
+    fn resize_inputs(self, len: usize) {
+        if self.inputs.with_untracked(Vec::len) == len {
+            return;
+        }
+        let snapshot = self.ctx.preferences.get_untracked().input_snapshot();
+        let mut t = self.inputs.write();
+        for tx_input in t.drain(len.min(t.len())..) {
+            tx_input.dispose();
+        }
+        while t.len() < len {
+            let index = t.len();
+            t.push(TxInputState::from_snapshot(
+                self.ctx.next_key(),
+                index,
+                &snapshot,
+            ));
+        }
+    }
+
+    fn resize_outputs(self, len: usize) {
+        if self.outputs.with_untracked(Vec::len) == len {
+            return;
+        }
+        let snapshot = self.ctx.preferences.get_untracked().output_snapshot();
+        let mut t = self.outputs.write();
+        for tx_output in t.drain(len.min(t.len())..) {
+            tx_output.dispose();
+        }
+        while t.len() < len {
+            let index = t.len();
+            t.push(TxOutputState::from_snapshot(
+                self.ctx.next_key(),
+                index,
+                &snapshot,
+            ));
+        }
+    }
+}
+
+/// Editor of a single transaction, shown in its own tab.
+#[component]
+pub fn TxEditor(
+    tab: TabState,
+    secp: StoredValue<Secp256k1<All>>,
+    ctx: Context,
+    /// Whether this editor is in the active tab, and should respond to keyboard shortcuts.
+    #[prop(into)]
+    active: Signal<bool>,
+) -> impl IntoView {
+    let state = TxEditorState::new(ctx);
+    let TxEditorState {
+        inputs: tx_inputs,
+        outputs: tx_outputs,
+        version: tx_version,
+        locktime: tx_locktime,
+        ..
+    } = state;
+    let tx_hex = RwSignal::new(String::new());
+    let tx_hex_errored = RwSignal::new(false);
+    // Shows only the size and txid in place of the hex, which is slow to lay out for huge
+    // transactions, and builds the hex only when revealed, copied or downloaded
+    let size_only = RwSignal::new(false);
+    let serialize_message = RwSignal::new(String::new());
+    // The NFT burns confirmed as intentional, serializing fails while others are burned
+    let acknowledged_burns = RwSignal::new(Vec::<(usize, OutputData)>::new());
+    let history = RwSignal::new(History::<EditorSnapshot>::default());
+
+    let build_tx = move || state.to_pst();
+    let owned_burns = |tx: &PartiallySignedTransaction| {
+        burned_nfts(tx)
+            .into_iter()
+            .map(|(i, token)| (i, token.clone()))
+            .collect::<Vec<_>>()
+    };
+    let serialize_tx = move || -> Result<Vec<Vec<u8>>> {
+        let tx = build_tx()?;
+        if owned_burns(&tx) != acknowledged_burns.get_untracked() {
+            bail!("Confirm the NFT burns first");
+        }
+        let tx_serialized = tx.serialize();
+        let mut sm = serialize_message.write();
+        if sm.is_empty() || sm.ends_with('.') {
+            *sm = format!("{} {}", tx_serialized.len(), ctx.t("bytes"));
+        } else {
+            *sm = format!("{} {}.", tx_serialized.len(), ctx.t("bytes"));
+        }
+        Ok(tx.fields())
+    };
+    // Built once per edit and shared by everything showing live information about the transaction
+    let live_tx = Memo::new(move |_| build_tx().ok());
+    let fee = Memo::new(move |_| live_tx.read().as_ref().map(|tx| tx.fee()));
+    let burns = Memo::new(move |_| live_tx.read().as_ref().map(owned_burns).unwrap_or_default());
+    let burns_acknowledged = move || burns.with(|b| *b == *acknowledged_burns.read());
+    // Offered for outputs' token categories: a genesis from input 0, and the inputs' tokens
+    let input_categories = Memo::new(move |_| {
+        let mut categories = Vec::<CategoryChoice>::new();
+        for tx_input in tx_inputs.get() {
+            let index = tx_input.index.get();
+            if index == 0 && tx_input.vout.get() == 0 && !tx_input.txid.read().is_empty() {
+                categories.push(CategoryChoice {
+                    label: ctx.t("Genesis from input 0").to_string(),
+                    category_id: tx_input.txid.get(),
+                });
+            }
+            let token_data = tx_input.token_data_state;
+            let category_id = token_data.category_id.get();
+            if token_data.cashtoken_enabled.get()
+                && !category_id.is_empty()
+                && !categories.iter().any(|c| c.category_id == category_id)
+            {
+                categories.push(CategoryChoice {
+                    label: format!("{} {index}", ctx.t("Input")),
+                    category_id,
+                });
+            }
+        }
+        categories
+    });
+    let input_counters = Memo::new(move |_| {
+        tx_inputs
+            .get()
+            .into_iter()
+            .filter_map(|tx_input| {
+                let token_data = tx_input.token_data_state;
+                (token_data.cashtoken_enabled.get() && token_data.has_nft.get()).then(|| {
+                    CounterChoice {
+                        label: format!("{} {}", ctx.t("Input"), tx_input.index.get()),
+                        category_id: token_data.category_id.get(),
+                        commitment_hex: token_data.nft_commitment_hex.get(),
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    let ft_inputs = Memo::new(move |_| {
+        tx_inputs
+            .get()
+            .into_iter()
+            .map(|tx_input| (tx_input.index.get(), tx_input.token_data_state.snapshot()))
+            .filter(|(_, token)| token.cashtoken_enabled && token.has_ft_amount)
+            .collect::<Vec<_>>()
+    });
+    let minting_inputs = Memo::new(move |_| {
+        tx_inputs
+            .get()
+            .into_iter()
+            .map(|tx_input| (tx_input.index.get(), tx_input.token_data_state.snapshot()))
+            .filter(|(_, token)| {
+                token.cashtoken_enabled
+                    && token.has_nft
+                    && token.nft_capability == NftCapability::Minting
+            })
+            .collect::<Vec<_>>()
+    });
+    let size = RwSignal::new(None::<SizeEstimate>);
+    let pending_size = StoredValue::new_local(None::<Timeout>);
+    Effect::new(move |_| {
+        let entries = live_tx.with(|tx| tx.as_ref().map(|tx| tx.input.len() + tx.output.len()));
+        let signatures = ctx.preferences.with(|p| p.signature_type);
+        let update_size = move || {
+            size.set(
+                live_tx
+                    .with_untracked(|tx| tx.as_ref().map(|tx| SizeEstimate::new(tx, signatures))),
+            )
+        };
+        // Replacing the pending timeout cancels it
+        if entries.unwrap_or_default() > DEBOUNCE_ENTRIES {
+            pending_size.set_value(Some(Timeout::new(DEBOUNCE_DELAY_MS, update_size)));
+        } else {
+            pending_size.set_value(None);
+            update_size();
+        }
+    });
+    // Outputs sending the max take what's left after the other outputs and the fee
+    Effect::new(move |_| {
+        let max_outputs = tx_outputs
+            .read()
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.send_max.get())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if max_outputs.is_empty() {
+            return;
+        }
+        let (fee_rate, signatures) = ctx.preferences.with(|p| (p.fee_rate, p.signature_type));
+        let Some(values) = live_tx.with(|tx| {
+            tx.as_ref()
+                .and_then(|tx| max_values(tx, &max_outputs, fee_rate, signatures))
+        }) else {
+            return;
+        };
+        for (i, value) in max_outputs.into_iter().zip(values) {
+            let output_value = tx_outputs.read_untracked()[i].value;
+            if output_value.get_untracked() != value {
+                output_value.set(value);
+            }
+        }
+    });
+    // Every field that can't be converted, so they can all be fixed in one go
+    let build_errors = Memo::new(move |_| {
+        if live_tx.with(Option::is_some) {
+            return vec![];
+        }
+        let error = |location, e: anyhow::Error| {
+            Diagnostic::new(location, Severity::Error, format!("{e:#}"))
+        };
+        let mut errors = tx_inputs
+            .read()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &tx_input)| {
+                let e = MaybeUnsignedTxIn::try_from(tx_input).err()?;
+                Some(error(Location::Input(i), e))
+            })
+            .chain(
+                tx_outputs
+                    .read()
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, &tx_output)| {
+                        let e = TxOut::try_from(tx_output).err()?;
+                        Some(error(Location::Output(i), e))
+                    }),
+            )
+            .collect::<Vec<_>>();
+        // Fields convert but the transaction still can't be built, e.g. when the fee can't be paid
+        if errors.is_empty() {
+            if let Err(e) = build_tx() {
+                errors.push(error(Location::Transaction, e));
+            }
+        }
+        errors
+    });
+    let error_count = move |location: Location| {
+        build_errors.with(|errors| errors.iter().filter(|d| d.location == location).count())
+    };
+    let diagnostics = Memo::new(move |_| {
+        live_tx
+            .read()
+            .as_ref()
+            .map(check_transaction)
+            .unwrap_or_else(|| build_errors.get())
+    });
+    let diagnostics_at = move |location: Location| -> Vec<Diagnostic> {
+        diagnostics
+            .read()
+            .iter()
+            .filter(|d| d.location == location)
+            .cloned()
+            .collect()
+    };
+    // Bumped by every conversion sent to the codec worker, so only the latest one is applied
+    let codec_generation = StoredValue::new(0u64);
+    let next_codec_generation = move || {
+        codec_generation.update_value(|g| *g += 1);
+        codec_generation.get_value()
+    };
+    let is_latest = move |generation| codec_generation.try_get_value() == Some(generation);
+    // Characters done and total of the hex being decoded, once it takes more than one chunk
+    let decode_progress = RwSignal::new(None::<(usize, usize)>);
+    let decode_abort = StoredValue::new(None::<AbortHandle>);
+    let cancel_decode = move || {
+        if let Some(abort) = decode_abort.try_update_value(Option::take).flatten() {
+            abort.abort();
+        }
+        decode_progress.set(None);
+    };
+    let deserialize_tx = move || async move {
+        cancel_decode();
+        let generation = next_codec_generation();
+        serialize_message.set(String::new());
+        let on_progress = move |done, total| {
+            if is_latest(generation) {
+                decode_progress.set(Some((done, total)));
+            }
+        };
+        let (decoding, abort) = abortable(tx_codec::decode(tx_hex.get_untracked(), on_progress));
+        decode_abort.set_value(Some(abort));
+        let tx = decoding.await;
+        if !is_latest(generation) {
+            return Ok(());
+        }
+        decode_progress.set(None);
+        decode_abort.set_value(None);
+        let Ok(tx) = tx else {
+            // Cancelled
+            return Ok(());
+        };
+        state.load_pst(&tx?);
+        anyhow::Ok(())
+    };
+    let take_snapshot = move || state.snapshot();
+    let restore_snapshot = move |snapshot: &EditorSnapshot| state.restore(snapshot);
+    restore_snapshot(
+        &tab.snapshot
+            .get_untracked()
+            .unwrap_or_else(|| ctx.preferences.get_untracked().editor_snapshot()),
+    );
+    Effect::new(move |_| {
+        let snapshot = take_snapshot();
+        history.update(|h| h.record(snapshot.clone()));
+        tab.snapshot.set(Some(snapshot));
+    });
+    let undo = move || {
+        if let Some(snapshot) = history.write().undo() {
+            restore_snapshot(&snapshot);
+        }
+    };
+    let redo = move || {
+        if let Some(snapshot) = history.write().redo() {
+            restore_snapshot(&snapshot);
+        }
+    };
+    let tx_hex_id = format!("tx-hex-{}", tab.key);
+    let serialize = move || match serialize_tx() {
+        Ok(fields) => {
+            tx_hex_errored.set(false);
+            if size_only.get_untracked() {
+                return;
+            }
+            let generation = next_codec_generation();
+            spawn_local(async move {
+                let hex = tx_codec::encode(fields, ctx.hex_format.get_untracked()).await;
+                if !is_latest(generation) {
+                    return;
+                }
+                match hex {
+                    Ok(hex) => tx_hex.set(hex),
+                    Err(e) => log!("Serialization error: {e}"),
+                }
+            });
+        }
+        // The errors are shown on the cards and listed above the hex
+        Err(_) => {
+            tx_hex_errored.set(true);
+            serialize_message.set(String::new());
+        }
+    };
+    let deserialize = move || {
+        spawn_local(async move {
+            if let Err(e) = deserialize_tx().await {
+                log!("Deserialization error: {e}");
+                tx_hex_errored.set(true);
+            }
+        })
+    };
+    // Keeps the hex in step with the form while the form is valid, and the form in step with the
+    // hex while it is being edited, see the textarea's input handler
+    let auto_sync = RwSignal::new(false);
+    Effect::new(move |prev_format: Option<HexFormat>| {
+        let format = ctx.hex_format.get();
+        if !auto_sync.get() || size_only.get() {
+            return format;
+        }
+        let Some((bytes, fields)) =
+            live_tx.with(|tx| tx.as_ref().map(|tx| (tx.serialize(), tx.fields())))
+        else {
+            return format;
+        };
+        if !burns_acknowledged() {
+            return format;
+        }
+        // Leave the hex as typed if it's the same transaction, to not move the cursor
+        let same_tx = hex_format::parse(&tx_hex.read_untracked()).is_ok_and(|hex| hex == bytes);
+        if !same_tx || prev_format != Some(format) {
+            tx_hex.set(format.format(&fields));
+            tx_hex_errored.set(false);
+        }
+        format
+    });
+    let hex_summary = Memo::new(move |_| {
+        if !size_only.get() {
+            return None;
+        }
+        live_tx.with(|tx| {
+            tx.as_ref().map(|tx| {
+                let bytes = tx.serialize();
+                (bytes.len(), tx.is_signed().then(|| Txid::hash(&bytes)))
+            })
+        })
+    });
+    let hex_action_error = RwSignal::new(String::new());
+    let materialize_hex = move || {
+        live_tx.with_untracked(|tx| {
+            tx.as_ref()
+                .map(|tx| ctx.hex_format.get_untracked().format(&tx.fields()))
+        })
+    };
+    let reveal_hex = move || {
+        size_only.set(false);
+        serialize();
+    };
+    let copy_hex = move || {
+        let Some(hex) = materialize_hex() else {
+            return;
+        };
+        spawn_local(async move {
+            match clipboard_write_text(&hex).await {
+                Ok(()) => hex_action_error.set(String::new()),
+                Err(e) => hex_action_error.set(e.to_string()),
+            }
+        });
+    };
+    let download_hex = move || {
+        let Some(hex) = materialize_hex() else {
+            return;
+        };
+        let name = match hex_summary.get_untracked() {
+            Some((_, Some(txid))) => format!("{txid}.hex"),
+            _ => "transaction.hex".to_string(),
+        };
+        match download_text(&name, &hex) {
+            Ok(()) => hex_action_error.set(String::new()),
+            Err(e) => hex_action_error.set(e.to_string()),
+        }
+    };
+    let broadcasting = RwSignal::new(false);
+    // Check with the node's testmempoolaccept first, when broadcasting through a node
+    let preflight = RwSignal::new(true);
+    let node_chosen = move || matches!(ctx.chain_server(), Some(Server::Node(_)));
+    let broadcast_message = RwSignal::new(String::new());
+    let broadcast = move || {
+        let Some(bytes) = live_tx.with_untracked(|tx| {
+            tx.as_ref()
+                .filter(|tx| tx.is_signed())
+                .map(|tx| tx.serialize())
+        }) else {
+            broadcast_message.set(
+                ctx.t("Only a fully signed transaction can be broadcast")
+                    .to_string(),
+            );
+            return;
+        };
+        let Some(server) = ctx.chain_server() else {
+            broadcast_message.set(ctx.t("No server is set for this network").to_string());
+            return;
+        };
+        let preflight = preflight.get_untracked();
+        broadcasting.set(true);
+        broadcast_message.set(String::new());
+        spawn_local(async move {
+            // The node's verdict before anything is relayed, so a rejected transaction can still
+            // be fixed
+            if let (true, Server::Node(url)) = (preflight, &server) {
+                let rejection = match NodeClient::new(url).test_mempool_accept(&bytes).await {
+                    Ok(None) => None,
+                    Ok(Some(reason)) => {
+                        Some(format!("{} {reason}", ctx.t("Rejected by the node:")))
+                    }
+                    Err(e) => Some(e.to_string()),
+                };
+                if let Some(rejection) = rejection {
+                    broadcasting.set(false);
+                    broadcast_message.set(rejection);
+                    return;
+                }
+            }
+            let confirmed = window()
+                .confirm_with_message(ctx.t("Broadcast this transaction? It can't be taken back."))
+                .unwrap_or(false);
+            if !confirmed {
+                broadcasting.set(false);
+                return;
+            }
+            let result = async { server.connect().await?.broadcast(&bytes).await }.await;
+            broadcasting.set(false);
+            match result {
+                Ok(txid) => broadcast_message.set(format!("{} {txid}", ctx.t("Broadcast:"))),
+                Err(e) => broadcast_message.set(e.to_string()),
+            }
+        });
+    };
+    let show_shortcuts = RwSignal::new(false);
+    let show_multisig_wizard = RwSignal::new(false);
+    let show_cltv_wizard = RwSignal::new(false);
+    let show_htlc_wizard = RwSignal::new(false);
+    let show_coin_selector = RwSignal::new(false);
+    let show_sweep_tool = RwSignal::new(false);
+    let show_pay_to_many = RwSignal::new(false);
+    let show_opcode_palette = RwSignal::new(false);
+    let show_template_variables = RwSignal::new(false);
+    let show_hash_calculator = RwSignal::new(false);
+    let show_number_converter = RwSignal::new(false);
+    let show_byte_order = RwSignal::new(false);
+    let show_input_verifier = RwSignal::new(false);
+    let show_token_summary = RwSignal::new(false);
+    let show_bcmr_builder = RwSignal::new(false);
+    let show_airdrop_builder = RwSignal::new(false);
+    let show_minting_series = RwSignal::new(false);
+    let palette_target = RwSignal::new(None::<PaletteTarget>);
+    let focus_first_field = move |key: usize| {
+        let field = document()
+            .get_element_by_id(&format!("card-{key}"))
+            .and_then(|card| {
+                card.query_selector("input:not([type=checkbox]), textarea, select")
+                    .ok()
+                    .flatten()
+            })
+            .and_then(|field| field.dyn_into::<web_sys::HtmlElement>().ok());
+        if let Some(field) = field {
+            let _ = field.focus();
+        }
+    };
+    // Move focus to the first field of the card `offset` places away from the focused one
+    let focus_card = move |offset: isize| {
+        let keys = tx_inputs
+            .read_untracked()
+            .iter()
+            .map(|t| t.key)
+            .chain(tx_outputs.read_untracked().iter().map(|t| t.key))
+            .collect::<Vec<_>>();
+        let document = document();
+        let current = document
+            .active_element()
+            .and_then(|e| e.closest("[data-card]").ok().flatten())
+            .and_then(|e| e.get_attribute("data-card"))
+            .and_then(|key| key.parse::<usize>().ok())
+            .and_then(|key| keys.iter().position(|k| *k == key));
+        let target = match current {
+            Some(i) => i.checked_add_signed(offset).filter(|i| *i < keys.len()),
+            None if offset > 0 => Some(0),
+            None => keys.len().checked_sub(1),
+        };
+        let Some(target) = target else {
+            return;
+        };
+        focus_first_field(keys[target]);
+    };
+    let tx_hex_id_attr = tx_hex_id.clone();
+    let focus_hex = {
+        let tx_hex_id = tx_hex_id.clone();
+        move || {
+            let textarea = document()
+                .get_element_by_id(&tx_hex_id)
+                .and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok());
+            if let Some(textarea) = textarea {
+                let _ = textarea.focus();
+            }
+        }
+    };
+    let keydown_handle = window_event_listener(ev::keydown, move |e| {
+        if !active.get_untracked() {
+            return;
+        }
+        if e.key() == "Escape" && show_shortcuts.get_untracked() {
+            show_shortcuts.set(false);
+            return;
+        }
+        let target = e
+            .target()
+            .and_then(|t| t.dyn_into::<web_sys::Element>().ok());
+        let in_text_field = target
+            .as_ref()
+            .is_some_and(|t| matches!(t.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"));
+        let Some(shortcut) = shortcuts::find(
+            &e.key(),
+            &e.code(),
+            e.ctrl_key() || e.meta_key(),
+            e.alt_key(),
+            e.shift_key(),
+            in_text_field,
+        ) else {
+            return;
+        };
+        // Text fields only update their signal on change, which hasn't fired yet
+        if let Some(textarea) =
+            target.and_then(|t| t.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+        {
+            if textarea.id() == tx_hex_id {
+                tx_hex.set(textarea.value());
+            }
+        }
+        match shortcut.action {
+            Action::AddInput => state.add_blank_input(),
+            Action::AddOutput => state.add_blank_output(),
+            Action::Serialize => serialize(),
+            Action::Deserialize => deserialize(),
+            Action::Undo => undo(),
+            Action::Redo => redo(),
+            Action::FocusHex => focus_hex(),
+            Action::NextCard => focus_card(1),
+            Action::PrevCard => focus_card(-1),
+            Action::ShowHelp => show_shortcuts.set(true),
+        }
+        e.prevent_default();
+    });
+    on_cleanup(move || keydown_handle.remove());
+
+    let tx_version_id = format!("tx-version-{}", tab.key);
+    let tx_locktime_id = format!("tx-locktime-{}", tab.key);
+    let locktime_hex = RwSignal::new(false);
+    // Blocks past the chain tip for the locktime preset
+    let tip_offset = RwSignal::new(0u32);
+
+    view! {
+        <TemplatePicker ctx on_load=move |snapshot| restore_snapshot(&snapshot) current=take_snapshot/>
+        <Show when=show_multisig_wizard>
+            <MultisigWizard
+                ctx
+                secp
+                cash_addr_prefix=Signal::derive(move || ctx.cash_addr_prefix())
+                on_add_input=move |snapshot| state.add_input(&snapshot)
+                on_add_output=move |snapshot| state.add_output(&snapshot)
+            />
+        </Show>
+        <Show when=show_cltv_wizard>
+            <CltvWizard
+                ctx
+                cash_addr_prefix=Signal::derive(move || ctx.cash_addr_prefix())
+                on_add_input=move |snapshot, locktime| {
+                    state.add_input(&snapshot);
+                    tx_locktime.set(locktime);
+                }
+                on_add_output=move |snapshot| state.add_output(&snapshot)
+            />
+        </Show>
+        <Show when=show_htlc_wizard>
+            <HtlcWizard
+                ctx
+                cash_addr_prefix=Signal::derive(move || ctx.cash_addr_prefix())
+                on_add_input=move |snapshot, locktime| {
+                    state.add_input(&snapshot);
+                    if let Some(locktime) = locktime {
+                        tx_locktime.set(locktime);
+                    }
+                }
+                on_add_output=move |snapshot| state.add_output(&snapshot)
+            />
+        </Show>
+        <Show when=show_coin_selector>
+            <CoinSelector
+                ctx
+                watched=Signal::derive(move || {
+                    ctx.watched_utxos.with(|w| w.values().flatten().cloned().collect::<Vec<_>>())
+                })
+                build_tx=move || state.to_pst_skipping_blank_inputs(true)
+                signatures=Signal::derive(move || ctx.preferences.with(|p| p.signature_type))
+                on_select=move |inputs, change| {
+                    state.delete_blank_inputs();
+                    for input in &inputs {
+                        state.add_input(input);
+                    }
+                    for output in &change {
+                        state.add_output(output);
+                    }
+                }
+            />
+        </Show>
+        <Show when=show_sweep_tool>
+            <SweepTool ctx on_build=move |tx| state.load_pst(&tx)/>
+        </Show>
+        <Show when=show_opcode_palette>
+            <OpcodePalette target=palette_target ctx/>
+        </Show>
+        <Show when=show_template_variables>
+            <TemplateVariables target=palette_target ctx/>
+        </Show>
+        <Show when=show_hash_calculator>
+            <HashCalculator ctx/>
+        </Show>
+        <Show when=show_number_converter>
+            <NumberConverter ctx/>
+        </Show>
+        <Show when=show_byte_order>
+            <ByteOrderConverter ctx/>
+        </Show>
+        <Show when=show_input_verifier>
+            <InputVerifier ctx tx=live_tx secp/>
+        </Show>
+        <Show when=show_token_summary>
+            <TokenSummaryPanel tx=live_tx on_add_output=move |output| state.add_output(&output) ctx/>
+        </Show>
+        <Show when=show_airdrop_builder>
+            <AirdropBuilder
+                ctx
+                token_inputs=ft_inputs
+                on_add=move |outputs| {
+                    for output in &outputs {
+                        state.add_output(output);
+                    }
+                }
+            />
+        </Show>
+        <Show when=show_minting_series>
+            <MintingSeries
+                ctx
+                minting_inputs
+                on_add=move |outputs| {
+                    for output in &outputs {
+                        state.add_output(output);
+                    }
+                }
+            />
+        </Show>
+        <Show when=show_bcmr_builder>
+            <BcmrBuilder ctx on_add_output=move |snapshot| state.add_output(&snapshot)/>
+        </Show>
+        <Show when=show_pay_to_many>
+            <PayToMany
+                ctx
+                unit=ctx.amount_unit
+                on_add=move |outputs| {
+                    state.delete_blank_outputs();
+                    for output in &outputs {
+                        state.add_output(output);
+                    }
+                }
+            />
+        </Show>
+        <div class="flex gap-3 justify-between">
+            <div class="table">
+                <div class="table-row">
+                    <div class="table-cell pr-1 pb-1">
+                        <label for=tx_version_id.clone()>{move || ctx.t("TX version:")}</label>
+                    </div>
+                    <div class="table-cell pb-1">
+                        <ParsedInput ctx value={tx_version} min=1 max=2 {..} id=tx_version_id placeholder="2"/>
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for=tx_locktime_id.clone()>{move || ctx.t("Locktime:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <U32Input ctx value={tx_locktime} hex=locktime_hex {..} id=tx_locktime_id placeholder="0"/>
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                            class=("bg-stone-900", locktime_hex)
+                            title=move || ctx.t("Show in hexadecimal")
+                            on:click=move |_| locktime_hex.update(|h| *h = !*h)
+                        >
+                            "0x"
+                        </button>
+                        {move || ctx.tip_height.get().map(|tip| view! {
+                            <span class="ml-2 text-sm" title=move || ctx.t("The locktime N blocks past the chain tip. At the tip itself, it's final in the next block.")>
+                                {move || ctx.t("Tip")}" "{tip}" + "
+                                <ParsedInput ctx value=tip_offset min=0 {..} class=("w-12", true)/>
+                                <button
+                                    class="border border-solid rounded border-stone-600 px-1 ml-1"
+                                    on:click=move |_| tx_locktime.set(tip.saturating_add(tip_offset.get_untracked()))
+                                >
+                                    {move || ctx.t("Set")}
+                                </button>
+                            </span>
+                        })}
+                    </div>
+                </div>
+            </div>
+            <div>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 text-sm"
+                    class=("bg-stone-900", show_multisig_wizard)
+                    title=move || ctx.t("Set up a 2-of-3 multisig escrow")
+                    on:click=move |_| show_multisig_wizard.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Multisig escrow")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_cltv_wizard)
+                    title=move || ctx.t("Lock coins until a block height or date")
+                    on:click=move |_| show_cltv_wizard.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("CLTV timelock")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_htlc_wizard)
+                    title=move || ctx.t("Build a hash time locked contract, as used in atomic swaps")
+                    on:click=move |_| show_htlc_wizard.update(|s| *s = !*s)
+                >
+                    "HTLC"
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_coin_selector)
+                    title=move || ctx.t("Select inputs from a list of UTXOs to pay for the outputs")
+                    on:click=move |_| show_coin_selector.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Coin selection")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_sweep_tool)
+                    title=move || ctx.t("Send every coin of a private key, such as a paper wallet's, to an address")
+                    on:click=move |_| show_sweep_tool.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Sweep key")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_pay_to_many)
+                    title=move || ctx.t("Add outputs from a list of addresses and amounts")
+                    on:click=move |_| show_pay_to_many.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Pay to many")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_opcode_palette)
+                    title=move || ctx.t("Insert opcodes into the last focused script")
+                    on:click=move |_| show_opcode_palette.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Opcodes")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_template_variables)
+                    title=move || ctx.t("Fill in the variables of a contract template in the last focused script")
+                    on:click=move |_| show_template_variables.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Variables")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_hash_calculator)
+                    title=move || ctx.t("Hash data with the hashing opcodes of scripts")
+                    on:click=move |_| show_hash_calculator.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Hashes")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_number_converter)
+                    title=move || ctx.t("Convert between integers and script number encodings")
+                    on:click=move |_| show_number_converter.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Numbers")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_byte_order)
+                    title=move || ctx.t("Reverse the bytes of txids and token categories between explorer and serialized order")
+                    on:click=move |_| show_byte_order.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Byte order")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_input_verifier)
+                    title=move || ctx.t("Check the signatures of signed P2PKH, P2PK and multisig inputs, other scripts aren't run")
+                    on:click=move |_| show_input_verifier.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Signatures")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_token_summary)
+                    title=move || ctx.t("Total the tokens spent and created, per category")
+                    on:click=move |_| show_token_summary.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Tokens")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_airdrop_builder)
+                    title=move || ctx.t("Distribute the fungible tokens of an input among many addresses")
+                    on:click=move |_| show_airdrop_builder.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Airdrop")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_minting_series)
+                    title=move || ctx.t("Mint a numbered series of NFTs from an input's minting NFT")
+                    on:click=move |_| show_minting_series.update(|s| *s = !*s)
+                >
+                    {move || ctx.t("Mint series")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", show_bcmr_builder)
+                    title=move || ctx.t("Publish a token metadata registry in an OP_RETURN output")
+                    on:click=move |_| show_bcmr_builder.update(|s| *s = !*s)
+                >
+                    "BCMR"
+                </button>
+            </div>
+        </div>
+        <div class="flex flex-wrap gap-3 mt-3">
+            <div class="basis-[32rem] grow">
+                <div class="mb-1 flex justify-between">
+                    <p>{move || ctx.t("Inputs")}</p>
+                    <BulkActions
+                        ctx
+                        any_selected=Signal::derive(move || {
+                            tx_inputs.read().iter().any(|t| t.selected.get())
+                        })
+                        on_delete=move || state.delete_selected_inputs()
+                        on_duplicate=move || state.duplicate_selected_inputs()
+                    />
+                    <CollapseAll ctx on_set=move |collapsed| {
+                        tx_inputs.read().iter().for_each(|t| t.collapsed.set(collapsed))
+                    }/>
+                </div>
+                <ol start="0">
+                    <For
+                        each=move || 0..tx_inputs.read().len()
+                        key=move |i| tx_inputs.read()[*i].key
+                        let:i
+                    >
+                        {
+                            let tx_input = tx_inputs.read()[i];
+                            view! {
+                                <li
+                                    id=format!("card-{}", tx_input.key)
+                                    data-card=tx_input.key
+                                    class="border border-solid rounded-md border-stone-600 p-1 mb-2 bg-stone-800"
+                                >
+                                    <CardHeader ctx collapsed=tx_input.collapsed selected=tx_input.selected label=tx_input.label errors=Signal::derive(move || error_count(Location::Input(tx_input.index.get()))) summary=move || {
+                                        secp.with_value(|secp| tx_input.summary(secp, ctx))
+                                    }/>
+                                    <div class:hidden=tx_input.collapsed>
+                                        <TxInput tx_input secp ctx palette=palette_target/>
+                                    </div>
+                                    <DiagnosticList diagnostics=Signal::derive(move || {
+                                        diagnostics_at(Location::Input(tx_input.index.get()))
+                                    })/>
+                                    <div class="flex justify-between">
+                                        <div>
+                                            <button
+                                                on:click=move |_| state.delete_input(tx_input.key)
+                                                class="border border-solid rounded border-stone-600 px-2 bg-red-950"
+                                            >
+                                                "−"
+                                            </button>
+                                            <button
+                                                on:click=move |_| state.duplicate_input(tx_input.key)
+                                                class="border border-solid rounded border-stone-600 px-2 ml-1"
+                                                title=move || ctx.t("Duplicate this input below")
+                                            >
+                                                {move || ctx.t("Duplicate")}
+                                            </button>
+                                            <InsertButtons ctx on_insert=move |offset| {
+                                                state.insert_input_near(tx_input.key, offset)
+                                            }/>
+                                            <ClipboardButtons
+                                                ctx
+                                                copy=move || ClipboardEntry::Input(tx_input.snapshot()).to_json()
+                                                paste=move |json| {
+                                                    tx_input.restore(&ClipboardEntry::input_from_json(json)?);
+                                                    Ok(())
+                                                }
+                                            />
+                                        </div>
+                                        <span class="text-sm mr-4">"#"{tx_input.index}</span>
+                                    </div>
+                                </li>
+                            }
+                        }
+                    </For>
+                </ol>
+                <button
+                    on:click=move |_| state.add_blank_input()
+                    class="border border-solid rounded border-stone-600 px-2"
+                >
+                    "+"
+                </button>
+            </div>
+            <div class="basis-[32rem] grow">
+                <div class="mb-1 flex justify-between">
+                    <p>{move || ctx.t("Outputs")}</p>
+                    <BulkActions
+                        ctx
+                        any_selected=Signal::derive(move || {
+                            tx_outputs.read().iter().any(|t| t.selected.get())
+                        })
+                        on_delete=move || state.delete_selected_outputs()
+                        on_duplicate=move || state.duplicate_selected_outputs()
+                    />
+                    <CollapseAll ctx on_set=move |collapsed| {
+                        tx_outputs.read().iter().for_each(|t| t.collapsed.set(collapsed))
+                    }/>
+                </div>
+                <ol start="0">
+                    <For
+                        each=move || 0..tx_outputs.read().len()
+                        key=move |i| tx_outputs.read()[*i].key
+                        let:i
+                    >
+                        {
+                            let tx_output = tx_outputs.read()[i];
+                            view! {
+                                <li
+                                    id=format!("card-{}", tx_output.key)
+                                    data-card=tx_output.key
+                                    class="border border-solid rounded border-stone-600 p-1 bg-stone-800 mb-2"
+                                >
+                                    <CardHeader ctx collapsed=tx_output.collapsed selected=tx_output.selected label=tx_output.label errors=Signal::derive(move || error_count(Location::Output(tx_output.index.get()))) summary=move || tx_output.summary(ctx)/>
+                                    <div class:hidden=tx_output.collapsed>
+                                        <TxOutput
+                                            tx_output
+                                            ctx
+                                            palette=palette_target
+                                            categories=input_categories
+                                            counters=input_counters
+                                        />
+                                    </div>
+                                    <DiagnosticList diagnostics=Signal::derive(move || {
+                                        diagnostics_at(Location::Output(tx_output.index.get()))
+                                    })/>
+                                    <div class="flex justify-between">
+                                        <div>
+                                            <button
+                                                on:click=move |_| state.delete_output(tx_output.key)
+                                                class="border border-solid rounded border-stone-600 px-2 bg-red-950"
+                                            >"−"</button>
+                                            <button
+                                                on:click=move |_| state.duplicate_output(tx_output.key)
+                                                class="border border-solid rounded border-stone-600 px-2 ml-1"
+                                                title=move || ctx.t("Duplicate this output below")
+                                            >{move || ctx.t("Duplicate")}</button>
+                                            <InsertButtons ctx on_insert=move |offset| {
+                                                state.insert_output_near(tx_output.key, offset)
+                                            }/>
+                                            <ClipboardButtons
+                                                ctx
+                                                copy=move || ClipboardEntry::Output(tx_output.snapshot()).to_json()
+                                                paste=move |json| {
+                                                    tx_output.restore(&ClipboardEntry::output_from_json(json)?);
+                                                    Ok(())
+                                                }
+                                            />
+                                        </div>
+                                        <span class="text-sm mr-4">"#"{tx_output.index}</span>
+                                    </div>
+                                </li>
+                            }
+                        }
+                    </For>
+                </ol>
+                <button
+                    on:click=move |_| state.add_blank_output()
+                    class="border border-solid rounded border-stone-600 px-2"
+                >
+                    "+"
+                </button>
+            </div>
+        </div>
+        {move || {
+            let unit = ctx.amount_unit.get();
+            match fee.get()? {
+                Some(fee) if fee >= 0 => {
+                    let fee = fee as u64;
+                    Some(view! {
+                        <p>
+                            {ctx.t("Fee:")}" "{unit.format(fee)}" "{unit.to_str()}
+                            <FiatValue ctx sats=fee rate=ctx.fiat_rate/>
+                        </p>
+                    }.into_any())
+                }
+                Some(fee) => Some(view! {
+                    <p class="text-red-700">
+                        {ctx.t("Outputs exceed inputs by")}" "{unit.format(fee.unsigned_abs())}" "{unit.to_str()}
+                    </p>
+                }.into_any()),
+                None => Some(view! {
+                    <p class="text-stone-500">{ctx.t("Fee: unknown, signed inputs don't include their value")}</p>
+                }.into_any()),
+            }
+        }}
+        {move || {
+            let size = size.get()?;
+            let fee_rate = fee
+                .get()
+                .flatten()
+                .filter(|fee| *fee >= 0)
+                .map(|fee| format!(", {:.2} {}", fee as f64 / size.bytes as f64, ctx.t("sat/byte")));
+            Some(view! {
+                <p class="text-sm text-stone-500">
+                    {ctx.t("Size:")}" "{if size.exact { "" } else { "~" }}{size.bytes}" "{ctx.t("bytes")}{fee_rate}
+                </p>
+            })
+        }}
+        <div class="mt-3">
+            <button
+                class="border border-solid rounded border-stone-600 px-1"
+                title=move || ctx.t("Serialize (Alt+S)")
+                on:click=move |_| serialize()
+            >
+                {move || ctx.t("Serialize")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1"
+                title=move || ctx.t("Deserialize (Alt+D)")
+                on:click=move |_| deserialize()
+            >
+                {move || ctx.t("Deserialize")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1 ml-3 bg-red-950"
+                on:click=move |_| state.reset()
+            >
+                {move || ctx.t("Reset")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1 ml-3 disabled:opacity-30"
+                title=move || ctx.t("Undo (Ctrl+Z)")
+                disabled=move || !history.with(|h| h.can_undo())
+                on:click=move |_| undo()
+            >
+                {move || ctx.t("Undo")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1 disabled:opacity-30"
+                title=move || ctx.t("Redo (Ctrl+Y)")
+                disabled=move || !history.with(|h| h.can_redo())
+                on:click=move |_| redo()
+            >
+                {move || ctx.t("Redo")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1"
+                title=move || ctx.t("Keyboard shortcuts (?)")
+                on:click=move |_| show_shortcuts.set(true)
+            >
+                {move || ctx.t("Shortcuts")}
+            </button>
+            <ClipboardButtons
+                ctx
+                copy=move || {
+                    serde_json::to_string_pretty(&state.snapshot().to_versioned_json())
+                        .expect("JSON values always serialize")
+                }
+                paste=move |json| {
+                    state.restore(&EditorSnapshot::from_versioned_json(serde_json::from_str(json)?)?);
+                    Ok(())
+                }
+            />
+            <label class="mx-1" title=move || ctx.t("Update the hex on every change to the form, and the form on every valid change to the hex")>
+                <input
+                    type="checkbox"
+                    class="mr-1"
+                    on:change=move |e| auto_sync.set(event_target_checked(&e))
+                    prop:checked=auto_sync
+                />
+                {move || ctx.t("Auto-sync")}
+            </label>
+            <label class="mx-1" title=move || ctx.t("Show only the size and ID of the transaction in place of its hex, which is slow to display for huge transactions")>
+                <input
+                    type="checkbox"
+                    class="mr-1"
+                    on:change=move |e| size_only.set(event_target_checked(&e))
+                    prop:checked=size_only
+                />
+                {move || ctx.t("Size only")}
+            </label>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 mx-1 disabled:opacity-30"
+                title=move || ctx.t("Send the signed transaction to the network through the chosen server")
+                disabled=move || broadcasting.get() || ctx.offline()
+                on:click=move |_| broadcast()
+            >
+                {move || ctx.t("Broadcast")}
+            </button>
+            <label class="mx-1" class:hidden=move || !node_chosen() title=move || ctx.t("Have the node test whether its mempool accepts the transaction before broadcasting it, to show why it would be rejected")>
+                <input
+                    type="checkbox"
+                    class="mr-1"
+                    on:change=move |e| preflight.set(event_target_checked(&e))
+                    prop:checked=preflight
+                />
+                {move || ctx.t("Test first")}
+            </label>
+            <span class="text-sm">{broadcast_message}</span>
+            <span>{serialize_message}</span>
+            <Show when=move || build_errors.with(|errors| !errors.is_empty())>
+                <div class="border border-solid rounded border-red-700 p-1 my-1 text-sm">
+                    <p>{move || ctx.t("The transaction can't be serialized until these are fixed:")}</p>
+                    <ul>
+                        {move || {
+                            build_errors
+                                .get()
+                                .into_iter()
+                                .map(|d| {
+                                    let card = match d.location {
+                                        Location::Input(i) => tx_inputs.read_untracked().get(i).map(|t| (t.key, format!("{} #{i}", ctx.t("Input")))),
+                                        Location::Output(i) => tx_outputs.read_untracked().get(i).map(|t| (t.key, format!("{} #{i}", ctx.t("Output")))),
+                                        Location::Transaction => None,
+                                    };
+                                    view! {
+                                        <li class="text-red-700">
+                                            {card.map(|(key, name)| view! {
+                                                <button class="underline mr-1" on:click=move |_| focus_first_field(key)>
+                                                    {name}":"
+                                                </button>
+                                            })}
+                                            {d.message}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </div>
+            </Show>
+            <Show when=move || burns.with(|b| !b.is_empty())>
+                <div class="border border-solid rounded border-yellow-700 p-1 my-1 text-sm">
+                    <p>{move || ctx.t("These NFTs are spent but no output carries them on, or keeps their minting capability, they will be destroyed:")}</p>
+                    <ul>
+                        {move || {
+                            burns
+                                .get()
+                                .into_iter()
+                                .map(|(i, token)| {
+                                    let key = tx_inputs.read_untracked().get(i).map(|t| t.key);
+                                    let category = token.id.to_hex();
+                                    view! {
+                                        <li class="text-yellow-700">
+                                            <button class="underline mr-1" on:click=move |_| key.into_iter().for_each(focus_first_field)>
+                                                {format!("{} #{i}", ctx.t("Input"))}":"
+                                            </button>
+                                            <span class="font-mono" title=category.clone()>{short_hex(&category)}</span>
+                                            {(!token.commitment.is_empty()).then(|| format!(" {}", token.commitment.to_hex()))}
+                                            {token.is_minting_nft().then(|| format!(" ({})", ctx.t("minting capability")))}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                    <label>
+                        <input
+                            type="checkbox"
+                            class="mr-1"
+                            on:change=move |e| {
+                                let burns = if event_target_checked(&e) { burns.get() } else { vec![] };
+                                acknowledged_burns.set(burns);
+                            }
+                            prop:checked=burns_acknowledged
+                        />
+                        {move || ctx.t("Intentional burn")}
+                    </label>
+                </div>
+            </Show>
+            <DiagnosticList diagnostics=Signal::derive(move || diagnostics_at(Location::Transaction))/>
+            <Show when=move || decode_progress.read().is_some()>
+                <div class="flex items-center gap-1 text-sm">
+                    {move || ctx.t("Decoding hex")}
+                    <progress
+                        class="grow"
+                        max=move || decode_progress.get().map_or(1, |(_, total)| total)
+                        value=move || decode_progress.get().map_or(0, |(done, _)| done)
+                    />
+                    <button
+                        class="border border-solid rounded border-stone-600 px-1"
+                        on:click=move |_| cancel_decode()
+                    >
+                        {move || ctx.t("Cancel")}
+                    </button>
+                </div>
+            </Show>
+            <Show when=size_only>
+                <div class="border border-solid rounded border-stone-600 p-1 my-1">
+                    {move || match hex_summary.get() {
+                        Some((bytes, txid)) => view! {
+                            <span>{bytes}" "{ctx.t("bytes")}</span>
+                            {txid.map(|txid| view! { <span class="font-mono ml-2">{txid.to_string()}</span> })}
+                        }
+                            .into_any(),
+                        None => view! {
+                            <span class="text-stone-500">{ctx.t("The transaction can't be serialized")}</span>
+                        }
+                            .into_any(),
+                    }}
+                    <div class="mt-1">
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1"
+                            on:click=move |_| reveal_hex()
+                        >
+                            {move || ctx.t("Reveal hex")}
+                        </button>
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1 ml-1 disabled:opacity-30"
+                            disabled=move || hex_summary.with(Option::is_none)
+                            on:click=move |_| copy_hex()
+                        >
+                            {move || ctx.t("Copy hex")}
+                        </button>
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1 ml-1 disabled:opacity-30"
+                            disabled=move || hex_summary.with(Option::is_none)
+                            on:click=move |_| download_hex()
+                        >
+                            {move || ctx.t("Download")}
+                        </button>
+                        <span class="text-sm text-red-700 ml-1">{hex_action_error}</span>
+                    </div>
+                </div>
+            </Show>
+            <Show when=move || !size_only.get()>
+                <textarea
+                    id=tx_hex_id_attr.clone()
+                    spellcheck="false"
+                    class="border border-solid rounded border-stone-600 px-1 w-full placeholder:text-stone-600 font-mono grow my-1"
+                    class=("bg-stone-900", move || !tx_hex_errored())
+                    class=("bg-red-950", tx_hex_errored)
+                    on:input=move |e| {
+                        tx_hex_errored.set(false);
+                        if auto_sync.get_untracked() {
+                            tx_hex.set(event_target_value(&e));
+                            // Partially typed hex is expected, so only flag it
+                            spawn_local(async move {
+                                tx_hex_errored.set(deserialize_tx().await.is_err());
+                            });
+                        }
+                    }
+                    on:change=move |e| tx_hex.set(event_target_value(&e))
+                    prop:value={tx_hex}
+                />
+            </Show>
+        </div>
+        <Show when=show_shortcuts>
+            <ShortcutHelp ctx on_close=move || show_shortcuts.set(false)/>
+        </Show>
+    }
+}
+
+/// Header of an input or output card, with its label, a summary of its values and a button to
+/// collapse the card down to the header.
+#[component]
+fn CardHeader(
+    ctx: Context,
+    collapsed: RwSignal<bool>,
+    selected: RwSignal<bool>,
+    label: RwSignal<String>,
+    /// Number of fields with errors, shown as a badge.
+    errors: Signal<usize>,
+    summary: impl Fn() -> String + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="flex text-sm mb-1">
+            <button
+                class="px-1 w-6"
+                title=move || ctx.t(if collapsed() { "Expand" } else { "Collapse" })
+                on:click=move |_| collapsed.update(|c| *c = !*c)
+            >
+                {move || if collapsed() { "▸" } else { "▾" }}
+            </button>
+            <input
+                type="checkbox"
+                class="mr-1"
+                title=move || ctx.t("Select for bulk actions")
+                on:change=move |e| selected.set(event_target_checked(&e))
+                prop:checked=selected
+            />
+            <input
+                class="bg-inherit border border-stone-600 rounded px-1 w-40 shrink-0"
+                placeholder=move || ctx.t("Label")
+                title=move || ctx.t("Note for this entry, not included in the transaction")
+                on:input=move |e| label.set(event_target_value(&e))
+                prop:value=label
+            />
+            <span
+                class="rounded bg-red-950 text-red-300 px-1 ml-2 shrink-0"
+                class:hidden=move || errors.get() == 0
+            >
+                {move || match errors.get() {
+                    1 => ctx.t("1 error").to_string(),
+                    n => format!("{n} {}", ctx.t("errors")),
+                }}
+            </span>
+            <span
+                class="truncate font-mono ml-2"
+                class=("text-stone-500", move || !collapsed())
+                title=summary
+            >
+                {summary}
+            </span>
+        </div>
+    }
+}
+
+/// Buttons inserting a new entry above (offset 0) or below (offset 1) a card.
+#[component]
+fn InsertButtons(
+    ctx: Context,
+    on_insert: impl Fn(usize) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <button
+            on:click=move |_| on_insert(0)
+            class="border border-solid rounded border-stone-600 px-2 ml-1"
+            title=move || ctx.t("Insert a new entry above")
+        >
+            "+↑"
+        </button>
+        <button
+            on:click=move |_| on_insert(1)
+            class="border border-solid rounded border-stone-600 px-2 ml-1"
+            title=move || ctx.t("Insert a new entry below")
+        >
+            "+↓"
+        </button>
+    }
+}
+
+/// Copy a card as JSON, or replace its values with JSON from the clipboard.
+#[component]
+fn ClipboardButtons(
+    ctx: Context,
+    copy: impl Fn() -> String + Copy + Send + Sync + 'static,
+    paste: impl Fn(&str) -> Result<()> + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let error = RwSignal::new(String::new());
+    let on_copy = move |_| {
+        let json = copy();
+        spawn_local(async move {
+            match clipboard_write_text(&json).await {
+                Ok(()) => error.set(String::new()),
+                Err(e) => error.set(e.to_string()),
+            }
+        });
+    };
+    let on_paste = move |_| {
+        spawn_local(async move {
+            match clipboard_read_text().await.and_then(|json| paste(&json)) {
+                Ok(()) => error.set(String::new()),
+                Err(e) => error.set(e.to_string()),
+            }
+        });
+    };
+    view! {
+        <button
+            on:click=on_copy
+            class="border border-solid rounded border-stone-600 px-2 ml-1"
+            title=move || ctx.t("Copy as JSON")
+        >
+            {move || ctx.t("Copy")}
+        </button>
+        <button
+            on:click=on_paste
+            class="border border-solid rounded border-stone-600 px-2 ml-1"
+            title=move || ctx.t("Replace with JSON from the clipboard")
+        >
+            {move || ctx.t("Paste")}
+        </button>
+        <span class="text-sm text-red-700 ml-1">{error}</span>
+    }
+}
+
+/// Actions applied to all selected cards of a list, shown while any are selected.
+#[component]
+fn BulkActions(
+    ctx: Context,
+    any_selected: Signal<bool>,
+    on_delete: impl Fn() + Copy + Send + Sync + 'static,
+    on_duplicate: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <Show when=any_selected>
+            <div class="text-sm">
+                <button class="px-1 text-red-700" on:click=move |_| on_delete()>
+                    {move || ctx.t("Delete selected")}
+                </button>
+                <button class="px-1" on:click=move |_| on_duplicate()>
+                    {move || ctx.t("Duplicate selected")}
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn CollapseAll(
+    ctx: Context,
+    on_set: impl Fn(bool) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="text-sm">
+            <button class="px-1" on:click=move |_| on_set(false)>
+                {move || ctx.t("Expand all")}
+            </button>
+            <button class="px-1" on:click=move |_| on_set(true)>
+                {move || ctx.t("Collapse all")}
+            </button>
+        </div>
+    }
+}
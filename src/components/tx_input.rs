@@ -1,24 +1,33 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use bitcoincash::hashes::hex::ToHex;
 use bitcoincash::secp256k1::{Secp256k1, Verification};
-use bitcoincash::{OutPoint, Script, Sequence, TxIn};
+use bitcoincash::{OutPoint, Script, Sequence, TxIn, Txid};
 use leptos::prelude::{
     event_target_checked, event_target_value, AddAnyAttr, ClassAttribute, Dispose, ElementChild,
-    Get, GlobalAttributes, OnAttribute, PropAttribute, ReadValue, RwSignal, Set, Show, StoredValue,
-    Write,
+    Get, GetUntracked, GlobalAttributes, IsDisposed, OnAttribute, PropAttribute, ReadValue,
+    RwSignal, Set, Show, Signal, StoredValue, Update,
 };
 use leptos::{component, view, IntoView};
+use serde::{Deserialize, Serialize};
 
 use super::script_input::ScriptInputValue;
+use crate::cash_assembly::{disassemble_in, InstructionSet};
+use crate::components::opcode_palette::PaletteTarget;
+use crate::components::prevout_viewer::PrevoutViewer;
 use crate::components::script_input::{ScriptDisplayFormat, ScriptInput};
 use crate::components::{
-    token_data::{TokenData, TokenDataState},
-    ParsedInput,
+    set_changed,
+    token_data::{TokenData, TokenDataSnapshot, TokenDataState},
+    tracker::{self, Tracked},
+    AmountInput, ExplorerLink, FiatValue, ParsedInput, U32Input,
 };
-use crate::js_reexport::bin_to_cash_assembly;
+use crate::hex_format;
 use crate::macros::StrEnum;
 use crate::partially_signed::{MaybeUnsignedTxIn, UnsignedScriptSig, UnsignedTxIn};
-use crate::util::{cash_addr_to_script, script_to_cash_addr};
+use crate::util::{
+    cash_addr_to_script, format_bch, script_to_cash_addr, script_to_legacy_addr, short_hex,
+};
+use crate::validation::ScriptRole;
 use crate::Context;
 
 str_enum! {
@@ -26,12 +35,14 @@ str_enum! {
     pub enum PubkeyDisplayFormat {
         #[default]
         Addr = "addr",
+        /// Base58 address, stored as [`UtxoPubkeyData::Addr`].
+        Legacy = "legacy",
         Asm = "asm",
         Hex = "hex",
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UtxoPubkeyData {
     Hex(String),
     Addr(String),
@@ -60,21 +71,35 @@ impl UtxoPubkeyData {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Addr(s) | Self::Hex(s) => s.is_empty(),
+        }
+    }
+
     pub fn inner(self) -> String {
         match self {
             Self::Addr(s) | Self::Hex(s) => s,
         }
     }
+
+    /// Address of the spent UTXO, if it can be determined.
+    pub fn address<C: Verification>(self, secp: &Secp256k1<C>, prefix: &str) -> Option<String> {
+        if self.empty_or_addr() {
+            return Some(self.inner()).filter(|a| !a.is_empty());
+        }
+        let script: UnsignedScriptSig = self.try_into().ok()?;
+        script_to_cash_addr(&script.script_pubkey(secp)?, prefix).ok()
+    }
 }
 
 impl TryFrom<UtxoPubkeyData> for UnsignedScriptSig {
     type Error = anyhow::Error;
     fn try_from(s: UtxoPubkeyData) -> Result<Self, Self::Error> {
         match s {
-            UtxoPubkeyData::Hex(mut s) => {
-                s.retain(|c| !c.is_ascii_whitespace());
-                Ok(UnsignedScriptSig::from_raw_script(s.parse::<Script>()?))
-            }
+            UtxoPubkeyData::Hex(s) => Ok(UnsignedScriptSig::from_raw_script(Script::from(
+                hex_format::parse(&s)?,
+            ))),
             UtxoPubkeyData::Addr(s) => Ok(UnsignedScriptSig::from_script_pubkey(
                 cash_addr_to_script(&s)?,
             )),
@@ -82,6 +107,40 @@ impl TryFrom<UtxoPubkeyData> for UnsignedScriptSig {
     }
 }
 
+/// Plain copy of the values in a [`TxInputState`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxInputSnapshot {
+    pub txid: String,
+    pub vout: u32,
+    pub sequence: u32,
+    pub script_sig: ScriptInputValue,
+    pub script_sig_format: ScriptDisplayFormat,
+    pub unsigned: bool,
+    pub utxo_pubkey: UtxoPubkeyData,
+    pub utxo_amount: u64,
+    pub token_data: TokenDataSnapshot,
+    /// Free-text note, kept out of the serialized transaction.
+    #[serde(default)]
+    pub label: String,
+}
+
+impl Default for TxInputSnapshot {
+    fn default() -> Self {
+        Self {
+            txid: String::new(),
+            vout: 0,
+            sequence: 4294967294,
+            script_sig: ScriptInputValue::default(),
+            script_sig_format: ScriptDisplayFormat::Hex,
+            unsigned: false,
+            utxo_pubkey: UtxoPubkeyData::default(),
+            utxo_amount: 0,
+            token_data: TokenDataSnapshot::default(),
+            label: String::new(),
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct TxInputState {
     pub txid: RwSignal<String>,
@@ -95,25 +154,39 @@ pub struct TxInputState {
     pub utxo_pubkey: RwSignal<UtxoPubkeyData>,
     pub utxo_amount: RwSignal<u64>,
     pub token_data_state: TokenDataState,
+    pub label: RwSignal<String>,
     pub index: RwSignal<usize>,
+    /// Whether the card only shows a one-line summary. Not part of the snapshot.
+    pub collapsed: RwSignal<bool>,
+    /// Whether the card is selected for bulk actions. Not part of the snapshot.
+    pub selected: RwSignal<bool>,
     pub key: usize,
 }
 
 impl TxInputState {
     pub fn new(key: usize, index: usize) -> Self {
-        Self {
-            txid: RwSignal::default(),
-            vout: RwSignal::new(0),
-            sequence: RwSignal::new(4294967294),
-            script_sig: RwSignal::default(),
-            script_sig_format: RwSignal::new(ScriptDisplayFormat::Hex),
-            unsigned: RwSignal::new(false),
-            utxo_pubkey: RwSignal::default(),
-            utxo_amount: RwSignal::new(0),
-            token_data_state: TokenDataState::new(key),
+        Self::from_snapshot(key, index, &TxInputSnapshot::default())
+    }
+
+    pub fn from_snapshot(key: usize, index: usize, snapshot: &TxInputSnapshot) -> Self {
+        let state = Self {
+            txid: RwSignal::new(snapshot.txid.clone()),
+            vout: RwSignal::new(snapshot.vout),
+            sequence: RwSignal::new(snapshot.sequence),
+            script_sig: RwSignal::new(snapshot.script_sig.clone()),
+            script_sig_format: RwSignal::new(snapshot.script_sig_format),
+            unsigned: RwSignal::new(snapshot.unsigned),
+            utxo_pubkey: RwSignal::new(snapshot.utxo_pubkey.clone()),
+            utxo_amount: RwSignal::new(snapshot.utxo_amount),
+            token_data_state: TokenDataState::from_snapshot(key, &snapshot.token_data),
+            label: RwSignal::new(snapshot.label.clone()),
             index: RwSignal::new(index),
+            collapsed: RwSignal::new(false),
+            selected: RwSignal::new(false),
             key,
-        }
+        };
+        tracker::created(&state);
+        state
     }
 
     pub fn dispose(&self) {
@@ -127,7 +200,10 @@ impl TxInputState {
             utxo_pubkey,
             utxo_amount,
             token_data_state,
+            label,
             index,
+            collapsed,
+            selected,
             key: _,
         } = self;
         txid.dispose();
@@ -139,36 +215,134 @@ impl TxInputState {
         utxo_pubkey.dispose();
         utxo_amount.dispose();
         token_data_state.dispose();
+        label.dispose();
         index.dispose();
+        collapsed.dispose();
+        selected.dispose();
+        tracker::disposed(self);
     }
 
-    pub fn update_from_txin(&self, input: &MaybeUnsignedTxIn) {
-        self.txid.set(input.previous_output().txid.to_string());
-        self.vout.set(input.previous_output().vout);
-        self.sequence.set(input.sequence().0);
-
-        match input {
-            MaybeUnsignedTxIn::Signed(txin) => {
-                self.script_sig
-                    .set(ScriptInputValue::Hex(txin.script_sig.to_hex()));
-                self.script_sig_format.set(ScriptDisplayFormat::Hex);
-                self.unsigned.set(false);
-                self.utxo_pubkey.set(Default::default());
-                self.utxo_amount.set(0);
-                self.token_data_state.update_from_token_data(None);
-            }
-            MaybeUnsignedTxIn::Unsigned(txin) => {
-                self.script_sig.write().clear();
-                self.script_sig_format.set(ScriptDisplayFormat::Hex);
-                self.unsigned.set(true);
-                self.utxo_pubkey.set(UtxoPubkeyData::Hex(
-                    txin.unsigned_script_sig.raw_script().to_hex(),
-                ));
-                self.utxo_amount.set(txin.value);
-                self.token_data_state
-                    .update_from_token_data(txin.token.as_ref());
-            }
+    pub fn snapshot(&self) -> TxInputSnapshot {
+        TxInputSnapshot {
+            txid: self.txid.get(),
+            vout: self.vout.get(),
+            sequence: self.sequence.get(),
+            script_sig: self.script_sig.get(),
+            script_sig_format: self.script_sig_format.get(),
+            unsigned: self.unsigned.get(),
+            utxo_pubkey: self.utxo_pubkey.get(),
+            utxo_amount: self.utxo_amount.get(),
+            token_data: self.token_data_state.snapshot(),
+            label: self.label.get(),
+        }
+    }
+
+    pub fn restore(&self, snapshot: &TxInputSnapshot) {
+        set_changed(self.txid, snapshot.txid.clone());
+        set_changed(self.vout, snapshot.vout);
+        set_changed(self.sequence, snapshot.sequence);
+        set_changed(self.script_sig, snapshot.script_sig.clone());
+        set_changed(self.script_sig_format, snapshot.script_sig_format);
+        set_changed(self.unsigned, snapshot.unsigned);
+        set_changed(self.utxo_pubkey, snapshot.utxo_pubkey.clone());
+        set_changed(self.utxo_amount, snapshot.utxo_amount);
+        self.token_data_state.restore(&snapshot.token_data);
+        set_changed(self.label, snapshot.label.clone());
+    }
+
+    /// One-line description for the card header.
+    pub fn summary<C: Verification>(&self, secp: &Secp256k1<C>, ctx: Context) -> String {
+        let txid = self.txid.get();
+        let mut parts = vec![if txid.is_empty() {
+            ctx.t("(no outpoint)").to_string()
+        } else {
+            format!("{}:{}", short_hex(&txid), self.vout.get())
+        }];
+        if self.unsigned.get() {
+            parts.push(ctx.t("unsigned").to_string());
+            parts.extend(
+                self.utxo_pubkey
+                    .get()
+                    .address(secp, &ctx.cash_addr_prefix()),
+            );
+            parts.push(format!("{} BCH", format_bch(self.utxo_amount.get())));
+            parts.extend(self.token_data_state.summary(ctx));
+        } else {
+            parts.push(ctx.t("signed").to_string());
         }
+        parts.join(" · ")
+    }
+
+    pub fn update_from_txin(&self, input: &MaybeUnsignedTxIn) {
+        let (script_sig, utxo_pubkey, utxo_amount, token) = match input {
+            MaybeUnsignedTxIn::Signed(txin) => (
+                ScriptInputValue::Hex(txin.script_sig.to_hex()),
+                UtxoPubkeyData::default(),
+                0,
+                None,
+            ),
+            MaybeUnsignedTxIn::Unsigned(txin) => (
+                ScriptInputValue::default(),
+                UtxoPubkeyData::Hex(txin.unsigned_script_sig.raw_script().to_hex()),
+                txin.value,
+                txin.token.as_ref(),
+            ),
+        };
+        self.restore(&TxInputSnapshot {
+            txid: input.previous_output().txid.to_string(),
+            vout: input.previous_output().vout,
+            sequence: input.sequence().0,
+            script_sig,
+            script_sig_format: ScriptDisplayFormat::Hex,
+            unsigned: matches!(input, MaybeUnsignedTxIn::Unsigned(_)),
+            utxo_pubkey,
+            utxo_amount,
+            token_data: self.token_data_state.snapshot_from_token_data(token),
+            label: self.label.get_untracked(),
+        });
+    }
+}
+
+impl Tracked for TxInputState {
+    const KIND: &'static str = "Input";
+
+    fn key(&self) -> usize {
+        self.key
+    }
+
+    fn signals(&self) -> Vec<Box<dyn IsDisposed>> {
+        let Self {
+            txid,
+            vout,
+            sequence,
+            script_sig,
+            script_sig_format,
+            unsigned,
+            utxo_pubkey,
+            utxo_amount,
+            token_data_state,
+            label,
+            index,
+            collapsed,
+            selected,
+            key: _,
+        } = *self;
+        let mut signals: Vec<Box<dyn IsDisposed>> = vec![
+            Box::new(txid),
+            Box::new(vout),
+            Box::new(sequence),
+            Box::new(script_sig),
+            Box::new(script_sig_format),
+            Box::new(unsigned),
+            Box::new(utxo_pubkey),
+            Box::new(utxo_amount),
+            Box::new(label),
+            Box::new(index),
+            Box::new(collapsed),
+            Box::new(selected),
+        ];
+        signals.extend(token_data_state.signals());
+        signals
     }
 }
 
@@ -177,10 +351,14 @@ impl TryFrom<TxInputState> for TxIn {
     fn try_from(tx_input: TxInputState) -> Result<Self, Self::Error> {
         Ok(TxIn {
             previous_output: OutPoint {
-                txid: tx_input.txid.get().parse()?,
+                txid: tx_input.txid.get().parse().context("Txid")?,
                 vout: tx_input.vout.get(),
             },
-            script_sig: tx_input.script_sig.get().try_into()?,
+            script_sig: tx_input
+                .script_sig
+                .get()
+                .try_into()
+                .context("Unlocking script")?,
             sequence: Sequence(tx_input.sequence.get()),
             witness: Default::default(),
         })
@@ -192,13 +370,20 @@ impl TryFrom<TxInputState> for UnsignedTxIn {
     fn try_from(tx_input: TxInputState) -> Result<Self, Self::Error> {
         Ok(UnsignedTxIn {
             previous_output: OutPoint {
-                txid: tx_input.txid.get().parse()?,
+                txid: tx_input.txid.get().parse().context("Txid")?,
                 vout: tx_input.vout.get(),
             },
             sequence: Sequence(tx_input.sequence.get()),
-            unsigned_script_sig: tx_input.utxo_pubkey.get().try_into()?,
+            unsigned_script_sig: tx_input
+                .utxo_pubkey
+                .get()
+                .try_into()
+                .context("Previous output")?,
             value: tx_input.utxo_amount.get(),
-            token: tx_input.token_data_state.token_data()?,
+            token: tx_input
+                .token_data_state
+                .token_data()
+                .context("Token data")?,
         })
     }
 }
@@ -219,6 +404,7 @@ pub fn TxInput<C: Verification + 'static>(
     tx_input: TxInputState,
     secp: StoredValue<Secp256k1<C>>,
     ctx: Context,
+    palette: RwSignal<Option<PaletteTarget>>,
 ) -> impl IntoView {
     let txid = tx_input.txid;
     let script_sig = tx_input.script_sig;
@@ -229,7 +415,10 @@ pub fn TxInput<C: Verification + 'static>(
 
     let pubkey_format = RwSignal::new(PubkeyDisplayFormat::default());
     let utxo_pubkey_enabled = RwSignal::new(true);
+    let sequence_hex = RwSignal::new(false);
     let utxo_pubkey_error = RwSignal::new(false);
+    let show_prevout = RwSignal::new(false);
+    let parsed_txid = move || txid.get().parse::<Txid>().ok();
 
     let parsed_input_seq_id = move || format!("tx-input-sn-{}", tx_input.key);
     let parsed_input_val_id = move || format!("tx-input-val-{}", tx_input.key);
@@ -262,7 +451,10 @@ pub fn TxInput<C: Verification + 'static>(
                 match script {
                     Ok(s) => {
                         utxo_pubkey_error.set(false);
-                        bin_to_cash_assembly(s.raw_script().as_bytes().into())
+                        disassemble_in(
+                            s.raw_script(),
+                            InstructionSet::for_network(ctx.network.get()),
+                        )
                     }
                     Err(e) => {
                         utxo_pubkey_error.set(true);
@@ -270,8 +462,10 @@ pub fn TxInput<C: Verification + 'static>(
                     }
                 }
             }
-            PubkeyDisplayFormat::Addr => {
-                if utxo_pubkey.empty_or_addr() {
+            format @ (PubkeyDisplayFormat::Addr | PubkeyDisplayFormat::Legacy) => {
+                if utxo_pubkey.empty_or_addr()
+                    && (matches!(format, PubkeyDisplayFormat::Addr) || utxo_pubkey.is_empty())
+                {
                     utxo_pubkey_error.set(false);
                     utxo_pubkey_enabled.set(true);
                     return utxo_pubkey.inner();
@@ -287,9 +481,16 @@ pub fn TxInput<C: Verification + 'static>(
                 let Some(script) = script.script_pubkey(&secp.read_value()) else {
                     utxo_pubkey_enabled.set(false);
                     utxo_pubkey_error.set(true);
-                    return "Unknown address".into();
+                    return ctx.t("Unknown address").into();
                 };
-                match script_to_cash_addr(&script, ctx.network.get()) {
+                let address = match format {
+                    PubkeyDisplayFormat::Legacy => {
+                        script_to_legacy_addr(&script, ctx.network.get())
+                    }
+                    _ => script_to_cash_addr(&script, &ctx.cash_addr_prefix())
+                        .map(|a| ctx.preferences.get().address_format.apply(a)),
+                };
+                match address {
                     Ok(a) => {
                         utxo_pubkey_enabled.set(true);
                         utxo_pubkey_error.set(false);
@@ -314,24 +515,49 @@ pub fn TxInput<C: Verification + 'static>(
                     "placeholder:text-stone-600 font-mono grow",
                 )
                 prop:value=txid
-                placeholder="Transaction ID"
+                placeholder=move || ctx.t("Transaction ID")
+            />
+            <ExplorerLink
+                ctx
+                href=Signal::derive(move || {
+                    let txid = txid.get();
+                    txid.parse::<Txid>().ok().and_then(|_| ctx.tx_link(&txid))
+                })
             />
             <span>:</span>
-            <ParsedInput value=tx_input.vout {..} placeholder="Index" class=("w-16", true) id=""/>
+            <ParsedInput ctx value=tx_input.vout {..} placeholder=move || ctx.t("Index") class=("w-16", true) id=""/>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm disabled:opacity-30"
+                class=("bg-stone-900", show_prevout)
+                title=move || ctx.t("Fetch the previous transaction to check the output spent")
+                disabled=move || parsed_txid().is_none() || ctx.offline()
+                on:click=move |_| show_prevout.update(|s| *s = !*s)
+            >
+                {move || ctx.t("View")}
+            </button>
         </div>
+        <Show when=move || show_prevout.get() && !ctx.offline()>
+            {move || parsed_txid().map(|txid| view! { <PrevoutViewer ctx txid vout=tx_input.vout/> })}
+        </Show>
         <div class="mb-1 flex">
             <ScriptInput
                 value=script_sig
                 format=script_sig_format
-                network=ctx.network
+                ctx
+                palette
+                role=ScriptRole::Unlocking
                 disabled=unsigned
-                attr:placeholder=move || {
+                placeholder=Signal::derive(move || {
                     match script_sig_format() {
-                        ScriptDisplayFormat::Addr => "How did you make this happen?",
-                        ScriptDisplayFormat::Hex => "Unlocking Script Hex",
-                        ScriptDisplayFormat::Asm => "Unlocking Script Asm",
+                        ScriptDisplayFormat::Addr
+                        | ScriptDisplayFormat::Legacy
+                        | ScriptDisplayFormat::TokenAddr => {
+                            ctx.t("How did you make this happen?")
+                        }
+                        ScriptDisplayFormat::Hex => ctx.t("Unlocking Script Hex"),
+                        ScriptDisplayFormat::Asm => ctx.t("Unlocking Script Asm"),
                     }
-                }
+                })
             />
             <div>
                 <select
@@ -348,8 +574,16 @@ pub fn TxInput<C: Verification + 'static>(
             </div>
         </div>
         <div class="my-1">
-            <label class="mr-1" for=parsed_input_seq_id>Sequence Number:</label>
-            <ParsedInput value=tx_input.sequence {..} id=parsed_input_seq_id placeholder="Sequence"/>
+            <label class="mr-1" for=parsed_input_seq_id>{move || ctx.t("Sequence Number:")}</label>
+            <U32Input ctx value=tx_input.sequence hex=sequence_hex {..} id=parsed_input_seq_id placeholder=move || ctx.t("Sequence")/>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                class=("bg-stone-900", sequence_hex)
+                title=move || ctx.t("Show in hexadecimal")
+                on:click=move |_| sequence_hex.update(|h| *h = !*h)
+            >
+                "0x"
+            </button>
             <label>
                 <input
                     type="checkbox"
@@ -363,7 +597,7 @@ pub fn TxInput<C: Verification + 'static>(
                     }
                     prop:checked=unsigned
                 />
-                Unsigned
+                {move || ctx.t("Unsigned")}
             </label>
         </div>
 
@@ -378,7 +612,7 @@ pub fn TxInput<C: Verification + 'static>(
                             PubkeyDisplayFormat::Hex => {
                                 utxo_pubkey.set(UtxoPubkeyData::Hex(event_target_value(&e)));
                             }
-                            PubkeyDisplayFormat::Addr => {
+                            PubkeyDisplayFormat::Addr | PubkeyDisplayFormat::Legacy => {
                                 utxo_pubkey.set(UtxoPubkeyData::Addr(event_target_value(&e)));
                             }
                             _ => unreachable!(),
@@ -387,14 +621,21 @@ pub fn TxInput<C: Verification + 'static>(
                     class="border border-solid rounded border-stone-600 px-1 w-full bg-inherit placeholder:text-stone-600 font-mono grow bg-stone-900"
                     placeholder=move || {
                         match pubkey_format() {
-                            PubkeyDisplayFormat::Addr => "Previous Address",
-                            PubkeyDisplayFormat::Hex | PubkeyDisplayFormat::Asm => "Serialized Data",
+                            PubkeyDisplayFormat::Addr | PubkeyDisplayFormat::Legacy => ctx.t("Previous Address"),
+                            PubkeyDisplayFormat::Hex | PubkeyDisplayFormat::Asm => ctx.t("Serialized Data"),
                         }
                     }
                     prop:value=render_utxo_pubkey
                     disabled=move || !utxo_pubkey_enabled()
                     class=("text-red-700", utxo_pubkey_error)
                 />
+                <ExplorerLink
+                    ctx
+                    href=Signal::derive(move || {
+                        let address = utxo_pubkey.get().address(&secp.read_value(), &ctx.cash_addr_prefix())?;
+                        ctx.address_link(&address)
+                    })
+                />
                 <div>
                     <select
                         class="bg-inherit border rounded ml-1 p-1"
@@ -403,7 +644,8 @@ pub fn TxInput<C: Verification + 'static>(
                         }
                         prop:value={move || pubkey_format().to_str()}
                     >
-                        <option value={|| PubkeyDisplayFormat::Addr.to_str()}>Address</option>
+                        <option value={|| PubkeyDisplayFormat::Addr.to_str()}>{move || ctx.t("Address")}</option>
+                        <option value={|| PubkeyDisplayFormat::Legacy.to_str()}>{move || ctx.t("Legacy")}</option>
                         <option value={|| PubkeyDisplayFormat::Asm.to_str()}>Asm</option>
                         <option value={|| PubkeyDisplayFormat::Hex.to_str()}>Hex</option>
                     </select>
@@ -412,8 +654,9 @@ pub fn TxInput<C: Verification + 'static>(
 
             // Amount
             <div class="my-1">
-                <label class="mr-1" for=parsed_input_val_id>Sats:</label>
-                <ParsedInput value=tx_input.utxo_amount {..} placeholder="Sats" id=parsed_input_val_id class=("w-52", true)/>
+                <label class="mr-1" for=parsed_input_val_id>{move || ctx.amount_unit.get().to_str()}":"</label>
+                <AmountInput ctx value=tx_input.utxo_amount unit=ctx.amount_unit {..} placeholder=move || ctx.t("Amount") id=parsed_input_val_id class=("w-52", true)/>
+                <FiatValue ctx sats=tx_input.utxo_amount rate=ctx.fiat_rate/>
                 <label>
                     <input
                         type="checkbox"
@@ -426,6 +669,6 @@ pub fn TxInput<C: Verification + 'static>(
             </div>
         </Show>
 
-        <TokenData token_data=tx_input.token_data_state />
+        <TokenData ctx token_data=tx_input.token_data_state electrum_server=Signal::derive(move || ctx.electrum_server()) offline=Signal::derive(move || ctx.offline()) />
     }
 }
@@ -1,41 +1,140 @@
-use anyhow::Result;
-use bitcoincash::TxOut;
+use anyhow::{Context as _, Result};
+use bitcoincash::hashes::hex::ToHex;
+use bitcoincash::{Script, TxOut};
 use leptos::prelude::{
-    event_target_checked, event_target_value, AddAnyAttr, ClassAttribute, Dispose, ElementChild,
-    Get, OnAttribute, PropAttribute, RwSignal, Set,
+    event_target_checked, event_target_value, untrack, AddAnyAttr, ClassAttribute, Dispose,
+    ElementChild, Get, GetUntracked, GlobalAttributes, IsDisposed, OnAttribute, PropAttribute,
+    Read, RwSignal, Set, Show, Signal, Update,
 };
 use leptos::{component, view, IntoView};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
+        op_return_builder::OpReturnBuilder,
+        opcode_palette::PaletteTarget,
+        p2sh_builder::P2shBuilder,
         script_input::{ScriptDisplayFormat, ScriptInput, ScriptInputValue},
-        token_data::{TokenData, TokenDataState},
-        ParsedInput,
+        set_changed,
+        token_data::{CategoryChoice, CounterChoice, TokenData, TokenDataSnapshot, TokenDataState},
+        tracker::{self, Tracked},
+        AmountInput, ExplorerLink, FiatValue,
     },
     macros::StrEnum,
+    util::{
+        classify_script, dust_threshold, format_bch, is_token_aware_addr, script_to_cash_addr,
+        script_to_token_addr, ScriptType,
+    },
+    validation::ScriptRole,
     Context,
 };
 
+/// Plain copy of the values in a [`TxOutputState`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxOutputSnapshot {
+    pub value: u64,
+    pub script_pubkey: ScriptInputValue,
+    pub script_display_format: ScriptDisplayFormat,
+    pub token_data: TokenDataSnapshot,
+    /// Free-text note, kept out of the serialized transaction.
+    #[serde(default)]
+    pub label: String,
+    /// Whether the value is set to everything left after the other outputs and the fee.
+    #[serde(default)]
+    pub send_max: bool,
+    /// Whether the fee is deducted from the value when building the transaction.
+    #[serde(default)]
+    pub subtract_fee: bool,
+    /// Script the locking script pays to through P2SH, kept to edit it again. Not part of the
+    /// output.
+    #[serde(default)]
+    pub redeem_script: ScriptInputValue,
+    /// Whether the redeem script is paid to with P2SH32 rather than P2SH20.
+    #[serde(default)]
+    pub p2sh32: bool,
+}
+
+impl Default for TxOutputSnapshot {
+    fn default() -> Self {
+        Self {
+            value: 0,
+            script_pubkey: ScriptInputValue::default(),
+            script_display_format: ScriptDisplayFormat::Addr,
+            token_data: TokenDataSnapshot::default(),
+            label: String::new(),
+            send_max: false,
+            subtract_fee: false,
+            redeem_script: ScriptInputValue::default(),
+            p2sh32: false,
+        }
+    }
+}
+
+impl TxOutputSnapshot {
+    /// This output with the value, locking script and token of `txout`, keeping the label, the
+    /// redeem script and the decimals, which aren't part of it. The amount options are cleared, as
+    /// the fee was already taken off the value of a built transaction.
+    pub fn with_txout(&self, txout: &TxOut) -> Self {
+        Self {
+            value: txout.value,
+            script_pubkey: ScriptInputValue::Hex(txout.script_pubkey.to_hex()),
+            script_display_format: match classify_script(&txout.script_pubkey) {
+                ScriptType::OpReturn => ScriptDisplayFormat::Asm,
+                _ => ScriptDisplayFormat::Addr,
+            },
+            token_data: TokenDataSnapshot {
+                ft_decimals: self.token_data.ft_decimals,
+                ..TokenDataSnapshot::from_token_data(txout.token.as_ref())
+            },
+            send_max: false,
+            subtract_fee: false,
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct TxOutputState {
     pub value: RwSignal<u64>,
     pub script_pubkey: RwSignal<ScriptInputValue>,
     pub script_display_format: RwSignal<ScriptDisplayFormat>,
     pub token_data_state: TokenDataState,
+    pub label: RwSignal<String>,
+    pub send_max: RwSignal<bool>,
+    pub subtract_fee: RwSignal<bool>,
+    pub redeem_script: RwSignal<ScriptInputValue>,
+    pub p2sh32: RwSignal<bool>,
     pub index: RwSignal<usize>,
+    /// Whether the card only shows a one-line summary. Not part of the snapshot.
+    pub collapsed: RwSignal<bool>,
+    /// Whether the card is selected for bulk actions. Not part of the snapshot.
+    pub selected: RwSignal<bool>,
     pub key: usize,
 }
 
 impl TxOutputState {
     pub fn new(key: usize, index: usize) -> Self {
-        Self {
-            value: RwSignal::new(0),
-            script_pubkey: RwSignal::default(),
-            script_display_format: RwSignal::new(ScriptDisplayFormat::Addr),
-            token_data_state: TokenDataState::new(key),
+        Self::from_snapshot(key, index, &TxOutputSnapshot::default())
+    }
+
+    pub fn from_snapshot(key: usize, index: usize, snapshot: &TxOutputSnapshot) -> Self {
+        let state = Self {
+            value: RwSignal::new(snapshot.value),
+            script_pubkey: RwSignal::new(snapshot.script_pubkey.clone()),
+            script_display_format: RwSignal::new(snapshot.script_display_format),
+            token_data_state: TokenDataState::from_snapshot(key, &snapshot.token_data),
+            label: RwSignal::new(snapshot.label.clone()),
+            send_max: RwSignal::new(snapshot.send_max),
+            subtract_fee: RwSignal::new(snapshot.subtract_fee),
+            redeem_script: RwSignal::new(snapshot.redeem_script.clone()),
+            p2sh32: RwSignal::new(snapshot.p2sh32),
             index: RwSignal::new(index),
+            collapsed: RwSignal::new(false),
+            selected: RwSignal::new(false),
             key,
-        }
+        };
+        tracker::created(&state);
+        state
     }
 
     pub fn dispose(self) {
@@ -44,54 +143,221 @@ impl TxOutputState {
             script_pubkey,
             script_display_format,
             token_data_state,
+            label,
+            send_max,
+            subtract_fee,
+            redeem_script,
+            p2sh32,
             index,
+            collapsed,
+            selected,
             key: _,
         } = self;
         value.dispose();
         script_pubkey.dispose();
         script_display_format.dispose();
         token_data_state.dispose();
+        label.dispose();
+        send_max.dispose();
+        subtract_fee.dispose();
+        redeem_script.dispose();
+        p2sh32.dispose();
         index.dispose();
+        collapsed.dispose();
+        selected.dispose();
+        tracker::disposed(&self);
+    }
+
+    /// One-line description for the card header.
+    pub fn summary(self, ctx: Context) -> String {
+        let script = Script::try_from(self.script_pubkey.get()).ok();
+        let mut parts = vec![match script {
+            Some(s) if s.is_empty() => ctx.t("(no locking script)").to_string(),
+            Some(s) => script_to_cash_addr(&s, &ctx.cash_addr_prefix())
+                .unwrap_or_else(|_| classify_script(&s).to_str().to_string()),
+            None => ctx.t("(invalid locking script)").to_string(),
+        }];
+        parts.push(format!("{} BCH", format_bch(self.value.get())));
+        parts.extend(self.token_data_state.summary(ctx));
+        parts.join(" · ")
+    }
+
+    pub fn snapshot(self) -> TxOutputSnapshot {
+        TxOutputSnapshot {
+            value: self.value.get(),
+            script_pubkey: self.script_pubkey.get(),
+            script_display_format: self.script_display_format.get(),
+            token_data: self.token_data_state.snapshot(),
+            label: self.label.get(),
+            send_max: self.send_max.get(),
+            subtract_fee: self.subtract_fee.get(),
+            redeem_script: self.redeem_script.get(),
+            p2sh32: self.p2sh32.get(),
+        }
+    }
+
+    /// Restore from `txout`, see [`TxOutputSnapshot::with_txout`].
+    pub fn update_from_txout(self, txout: &TxOut) {
+        self.restore(&untrack(|| self.snapshot()).with_txout(txout));
+    }
+
+    pub fn restore(self, snapshot: &TxOutputSnapshot) {
+        set_changed(self.value, snapshot.value);
+        set_changed(self.script_pubkey, snapshot.script_pubkey.clone());
+        set_changed(self.script_display_format, snapshot.script_display_format);
+        self.token_data_state.restore(&snapshot.token_data);
+        set_changed(self.label, snapshot.label.clone());
+        set_changed(self.send_max, snapshot.send_max);
+        set_changed(self.subtract_fee, snapshot.subtract_fee);
+        set_changed(self.redeem_script, snapshot.redeem_script.clone());
+        set_changed(self.p2sh32, snapshot.p2sh32);
     }
 }
 
-impl TryFrom<TxOutputState> for TxOut {
+impl Tracked for TxOutputState {
+    const KIND: &'static str = "Output";
+
+    fn key(&self) -> usize {
+        self.key
+    }
+
+    fn signals(&self) -> Vec<Box<dyn IsDisposed>> {
+        let Self {
+            value,
+            script_pubkey,
+            script_display_format,
+            token_data_state,
+            label,
+            send_max,
+            subtract_fee,
+            redeem_script,
+            p2sh32,
+            index,
+            collapsed,
+            selected,
+            key: _,
+        } = *self;
+        let mut signals: Vec<Box<dyn IsDisposed>> = vec![
+            Box::new(value),
+            Box::new(script_pubkey),
+            Box::new(script_display_format),
+            Box::new(label),
+            Box::new(send_max),
+            Box::new(subtract_fee),
+            Box::new(redeem_script),
+            Box::new(p2sh32),
+            Box::new(index),
+            Box::new(collapsed),
+            Box::new(selected),
+        ];
+        signals.extend(token_data_state.signals());
+        signals
+    }
+}
+
+impl TryFrom<&TxOutputSnapshot> for TxOut {
     type Error = anyhow::Error;
-    fn try_from(tx_output: TxOutputState) -> Result<Self, Self::Error> {
-        let script_pubkey = tx_output.script_pubkey.get().try_into()?;
-        let token = tx_output.token_data_state.token_data()?;
+    fn try_from(snapshot: &TxOutputSnapshot) -> Result<Self, Self::Error> {
+        let script_pubkey = snapshot
+            .script_pubkey
+            .clone()
+            .try_into()
+            .context("Locking script")?;
+        let token = snapshot.token_data.token_data().context("Token data")?;
         Ok(TxOut {
-            value: tx_output.value.get(),
+            value: snapshot.value,
             script_pubkey,
             token,
         })
     }
 }
 
+impl TryFrom<TxOutputState> for TxOut {
+    type Error = anyhow::Error;
+    fn try_from(tx_output: TxOutputState) -> Result<Self, Self::Error> {
+        TxOut::try_from(&tx_output.snapshot())
+    }
+}
+
 #[component]
-pub fn TxOutput(tx_output: TxOutputState, ctx: Context) -> impl IntoView {
+pub fn TxOutput(
+    tx_output: TxOutputState,
+    ctx: Context,
+    palette: RwSignal<Option<PaletteTarget>>,
+    /// Categories of the tokens in the inputs, offered for the output's token.
+    #[prop(into)]
+    categories: Signal<Vec<CategoryChoice>>,
+    /// NFTs of the inputs whose commitment can be carried on incremented.
+    #[prop(into)]
+    counters: Signal<Vec<CounterChoice>>,
+) -> impl IntoView {
     let script_pubkey = tx_output.script_pubkey;
     let script_format = tx_output.script_display_format;
     let cashtoken_enabled = tx_output.token_data_state.cashtoken_enabled;
 
     let parsed_input_val_id = format!("tx-output-val-{}", tx_output.key);
 
+    let script_type = move || {
+        Script::try_from(script_pubkey.get())
+            .ok()
+            .filter(|s| !s.is_empty())
+            .map(|s| classify_script(&s).to_str())
+    };
+    let min_value = move || TxOut::try_from(tx_output).ok().map(|o| dust_threshold(&o));
+    let op_return_builder = RwSignal::new(false);
+    let p2sh_builder = RwSignal::new(!tx_output.redeem_script.get_untracked().is_empty());
+    let plain_token_addr = move || {
+        cashtoken_enabled.get()
+            && matches!(
+                &*script_pubkey.read(),
+                ScriptInputValue::Addr(a) if !a.trim().is_empty() && !is_token_aware_addr(a)
+            )
+    };
+    let to_token_addr = move |_| {
+        let Ok(script) = Script::try_from(script_pubkey.get_untracked()) else {
+            return;
+        };
+        if let Ok(address) = script_to_token_addr(&script, &untrack(|| ctx.cash_addr_prefix())) {
+            let address = ctx
+                .preferences
+                .get_untracked()
+                .address_format
+                .apply(address);
+            script_pubkey.set(ScriptInputValue::Addr(address));
+            script_format.set(ScriptDisplayFormat::TokenAddr);
+        }
+    };
+
     view! {
         // Address
         <div class="mb-1 flex">
             <ScriptInput
                 value=script_pubkey
                 format=script_format
-                network=ctx.network
-                {..}
+                ctx
+                palette
                 rows=1
-                placeholder=move || {
+                role=ScriptRole::Locking
+                placeholder=Signal::derive(move || {
                     match script_format() {
-                        ScriptDisplayFormat::Addr => "Address",
-                        ScriptDisplayFormat::Hex => "Locking Script Hex",
-                        ScriptDisplayFormat::Asm => "Locking Script Asm",
+                        ScriptDisplayFormat::Addr => ctx.t("Address"),
+                        ScriptDisplayFormat::Legacy => ctx.t("Legacy address"),
+                        ScriptDisplayFormat::TokenAddr => ctx.t("Token-aware address"),
+                        ScriptDisplayFormat::Hex => ctx.t("Locking Script Hex"),
+                        ScriptDisplayFormat::Asm => ctx.t("Locking Script Asm"),
                     }
-                }
+                })
+            />
+            <ExplorerLink
+                ctx
+                href=Signal::derive(move || {
+                    let script = Script::try_from(script_pubkey.get()).ok()?;
+                    // Bare multisig is described rather than given an address
+                    let address = script_to_cash_addr(&script, &ctx.cash_addr_prefix())
+                        .ok()
+                        .filter(|address| address.contains(':'))?;
+                    ctx.address_link(&address)
+                })
             />
             <div>
                 <select
@@ -101,28 +367,129 @@ pub fn TxOutput(tx_output: TxOutputState, ctx: Context) -> impl IntoView {
                     }
                     prop:value={move || script_format().to_str()}
                 >
-                    <option value={ScriptDisplayFormat::Addr.to_str()}>Address</option>
+                    <option value={ScriptDisplayFormat::Addr.to_str()}>{move || ctx.t("Address")}</option>
+                    <option value={ScriptDisplayFormat::Legacy.to_str()}>{move || ctx.t("Legacy")}</option>
+                    <Show when=cashtoken_enabled>
+                        <option value={ScriptDisplayFormat::TokenAddr.to_str()}>{move || ctx.t("Token-aware")}</option>
+                    </Show>
                     <option value={ScriptDisplayFormat::Asm.to_str()}>Asm</option>
                     <option value={ScriptDisplayFormat::Hex.to_str()}>Hex</option>
                 </select>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", op_return_builder)
+                    title=move || ctx.t("Build an OP_RETURN script from a list of pushes")
+                    on:click=move |_| {
+                        op_return_builder.update(|b| *b = !*b);
+                        if op_return_builder() {
+                            script_format.set(ScriptDisplayFormat::Asm);
+                        }
+                    }
+                >
+                    "OP_RETURN"
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                    class=("bg-stone-900", p2sh_builder)
+                    title=move || ctx.t("Pay to the P2SH hash of a redeem script")
+                    on:click=move |_| p2sh_builder.update(|b| *b = !*b)
+                >
+                    "P2SH"
+                </button>
             </div>
         </div>
+        <Show when=plain_token_addr>
+            <p class="text-yellow-700 text-sm mb-1">
+                {move || ctx.t("This address isn't token-aware, many wallets will not see the tokens sent to it.")}
+                <button
+                    class="border border-solid rounded border-stone-600 px-1 ml-2"
+                    title=move || ctx.t("Replace with the token-aware address of the same locking script")
+                    on:click=to_token_addr
+                >
+                    {move || ctx.t("Convert")}
+                </button>
+            </p>
+        </Show>
+        <Show when=op_return_builder>
+            <OpReturnBuilder ctx script_pubkey/>
+        </Show>
+        <Show when=p2sh_builder>
+            <P2shBuilder
+                ctx
+                script_pubkey
+                redeem_script=tx_output.redeem_script
+                p2sh32=tx_output.p2sh32
+                palette
+            />
+        </Show>
 
         // Amount
         <div class="my-1">
-            <label class="mr-1" for=parsed_input_val_id.clone()>Sats:</label>
-            <ParsedInput value=tx_output.value {..} id=parsed_input_val_id placeholder="Sats" class=("w-52", true)/>
+            {move || script_type().map(|script_type| view! {
+                <span class="border border-solid rounded border-stone-600 px-1 mr-2 text-sm bg-stone-900">
+                    {script_type}
+                </span>
+            })}
+            <label class="mr-1" for=parsed_input_val_id.clone()>{move || ctx.amount_unit.get().to_str()}":"</label>
+            <AmountInput ctx value=tx_output.value unit=ctx.amount_unit {..} id=parsed_input_val_id placeholder=move || ctx.t("Amount") class=("w-52", true) prop:disabled=tx_output.send_max/>
+            <FiatValue ctx sats=tx_output.value rate=ctx.fiat_rate/>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 ml-2 text-sm"
+                class=("bg-stone-900", tx_output.send_max)
+                title=move || ctx.t("Send everything left after the other outputs and the fee, at the fee rate of the preferences")
+                on:click=move |_| {
+                    tx_output.send_max.update(|m| *m = !*m);
+                    tx_output.subtract_fee.set(false);
+                }
+            >
+                {move || ctx.t("Max")}
+            </button>
+            <button
+                class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                class=("bg-stone-900", tx_output.subtract_fee)
+                title=move || ctx.t("Deduct the fee, at the fee rate of the preferences, from this output when building the transaction")
+                on:click=move |_| {
+                    tx_output.subtract_fee.update(|s| *s = !*s);
+                    tx_output.send_max.set(false);
+                }
+            >
+                {move || ctx.t("Pays fee")}
+            </button>
             <label>
                 <input
                     type="checkbox"
                     class="ml-5"
-                    on:change=move |e| cashtoken_enabled.set(event_target_checked(&e))
+                    on:change=move |e| {
+                        let enabled = event_target_checked(&e);
+                        cashtoken_enabled.set(enabled);
+                        // Show addresses in the form wallets expect for the output
+                        match (enabled, script_format.get_untracked()) {
+                            (true, ScriptDisplayFormat::Addr) => {
+                                script_format.set(ScriptDisplayFormat::TokenAddr);
+                            }
+                            (false, ScriptDisplayFormat::TokenAddr) => {
+                                script_format.set(ScriptDisplayFormat::Addr);
+                            }
+                            _ => {}
+                        }
+                    }
                     prop:checked=cashtoken_enabled
                 />
                 CashToken
             </label>
+            <Show when=cashtoken_enabled>
+                {move || min_value().map(|min_value| view! {
+                    <button
+                        class="border border-solid rounded border-stone-600 px-1 ml-3 text-sm"
+                        title=move || ctx.t("Set to the minimum non-dust amount")
+                        on:click=move |_| tx_output.value.set(min_value)
+                    >
+                        {move || ctx.t("Min:")}" "{min_value}
+                    </button>
+                })}
+            </Show>
         </div>
 
-        <TokenData token_data=tx_output.token_data_state />
+        <TokenData ctx token_data=tx_output.token_data_state electrum_server=Signal::derive(move || ctx.electrum_server()) offline=Signal::derive(move || ctx.offline()) categories counters />
     }
 }
@@ -0,0 +1,121 @@
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, Callable, Callback, ClassAttribute, ElementChild, Get, GetUntracked,
+        GlobalAttributes, LocalStorage, OnAttribute, PropAttribute, RwSignal, Set, Update,
+    },
+    task::spawn_local,
+    view, IntoView,
+};
+
+use crate::autosave::{self, Saved, SavedTab};
+use crate::sensitive;
+use crate::vault::VaultKey;
+use crate::watch_only;
+use crate::Context;
+
+/// Whether saved tabs or watch-only wallets are encrypted, and need their passphrase.
+fn encrypted_saved() -> bool {
+    matches!(autosave::load(), Some(Saved::Encrypted(_)))
+        || matches!(watch_only::load(), Some(watch_only::Saved::Encrypted(_)))
+}
+
+/// Prompt for the passphrase of saved tabs and watch-only wallets: the one they're encrypted with,
+/// or a new one when encryption was just turned on. `on_unlock` receives the tabs decrypted with
+/// it, if any.
+#[component]
+pub fn VaultPrompt(
+    ctx: Context,
+    key: RwSignal<Option<VaultKey>, LocalStorage>,
+    on_unlock: Callback<Vec<SavedTab>>,
+    /// Called after the encrypted tabs and wallets are deleted, for a forgotten passphrase.
+    on_discard: Callback<()>,
+) -> impl IntoView {
+    let passphrase = RwSignal::new(String::new());
+    sensitive::register(passphrase);
+    let encrypted = RwSignal::new(encrypted_saved());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(String::new());
+    let submit = move || {
+        if passphrase.get_untracked().is_empty() {
+            error.set(ctx.t("Enter a passphrase").to_string());
+            return;
+        }
+        busy.set(true);
+        spawn_local(async move {
+            let mut entered = passphrase.get_untracked();
+            let result = match (autosave::load(), watch_only::load()) {
+                (Some(Saved::Encrypted(sealed)), _) => autosave::unlock(&entered, &sealed)
+                    .await
+                    .map(|(key, tabs)| (key, Some(tabs))),
+                // The wallets are opened with the key once it's set
+                (_, Some(watch_only::Saved::Encrypted(sealed))) => {
+                    VaultKey::unlock(&entered, &sealed)
+                        .await
+                        .map(|(key, _)| (key, None))
+                }
+                _ => VaultKey::new(&entered).await.map(|key| (key, None)),
+            };
+            sensitive::zeroize(&mut entered);
+            busy.set(false);
+            match result {
+                Ok((unlocked, tabs)) => {
+                    passphrase.update(sensitive::zeroize);
+                    error.set(String::new());
+                    key.set(Some(unlocked));
+                    if let Some(tabs) = tabs {
+                        on_unlock.run(tabs);
+                    }
+                }
+                Err(e) => error.set(e.to_string()),
+            }
+        });
+    };
+    let discard = move |_| {
+        autosave::clear();
+        watch_only::clear();
+        encrypted.set(false);
+        on_discard.run(());
+    };
+
+    view! {
+        <div class="border border-yellow-700 rounded-md p-2 mb-3 flex flex-wrap items-center gap-3">
+            <span>
+                {move || {
+                    if encrypted.get() {
+                        ctx.t("Saved tabs and wallets are encrypted, enter the passphrase to restore them and keep saving:")
+                    } else {
+                        ctx.t("Choose a passphrase to encrypt saved tabs and wallets with:")
+                    }
+                }}
+            </span>
+            <input
+                type="password"
+                class="border border-solid rounded border-stone-600 px-1 bg-stone-900"
+                on:input=move |e| passphrase.set(event_target_value(&e))
+                on:keydown=move |e| {
+                    if e.key() == "Enter" {
+                        submit();
+                    }
+                }
+                prop:value=passphrase
+            />
+            <button
+                class="border border-solid rounded-md border-stone-600 px-2 py-1 disabled:opacity-30"
+                disabled=busy
+                on:click=move |_| submit()
+            >
+                {move || if encrypted.get() { ctx.t("Unlock") } else { ctx.t("Encrypt") }}
+            </button>
+            <button
+                class="border border-solid rounded-md border-stone-600 px-2 py-1"
+                class:hidden=move || !encrypted.get()
+                title=move || ctx.t("For a forgotten passphrase, the saved tabs and wallets can't be recovered")
+                on:click=discard
+            >
+                {move || ctx.t("Discard")}
+            </button>
+            <span class="text-red-700">{error}</span>
+        </div>
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use leptos::{
+    component,
+    prelude::{
+        event_target_value, ClassAttribute, ElementChild, For, Get, GetUntracked, GlobalAttributes,
+        Notify, OnAttribute, PropAttribute, ReadSignal, RwSignal, Set, Trigger, Update, With,
+        WithUntracked,
+    },
+    view, IntoView,
+};
+
+use crate::{
+    coin_selection::Utxo,
+    components::ParsedInput,
+    util::short_hex,
+    watch_only::{WatchOnly, WatchSource, DEFAULT_GAP_LIMIT},
+    Context,
+};
+
+/// Watch-only wallets with the balance and UTXO count of each.
+#[component]
+pub fn WatchOnlyPanel(
+    ctx: Context,
+    wallets: RwSignal<Vec<WatchOnly>>,
+    /// Addresses of each wallet, by name, once scanned.
+    addresses: ReadSignal<BTreeMap<String, Vec<String>>>,
+    /// UTXOs of the watched addresses, by address.
+    utxos: ReadSignal<BTreeMap<String, Vec<Utxo>>>,
+    /// Why following the wallets failed, if it did.
+    status: ReadSignal<String>,
+    /// Scan the wallets again, for addresses used since.
+    rescan: Trigger,
+) -> impl IntoView {
+    let name = RwSignal::new(String::new());
+    let source = RwSignal::new(String::new());
+    let gap_limit = RwSignal::new(DEFAULT_GAP_LIMIT);
+    let error = RwSignal::new(String::new());
+
+    let add = move |_| {
+        let wallet = match WatchOnly::parse(
+            &name.get_untracked(),
+            &source.get_untracked(),
+            gap_limit.get_untracked(),
+        ) {
+            Ok(wallet) => wallet,
+            Err(e) => {
+                error.set(e.to_string());
+                return;
+            }
+        };
+        if wallets.with_untracked(|w| w.iter().any(|w| w.name == wallet.name)) {
+            error.set(format!(
+                "{} {}",
+                ctx.t("There already is a wallet named"),
+                wallet.name
+            ));
+            return;
+        }
+        wallets.update(|w| w.push(wallet));
+        name.set(String::new());
+        source.set(String::new());
+        error.set(String::new());
+    };
+    let remove = move |name: String| {
+        wallets.update(|w| w.retain(|w| w.name != name));
+    };
+    // Balance and UTXO count of the wallet `name`, as far as its addresses were fetched
+    let totals = move |name: &str| {
+        addresses.with(|addresses| {
+            utxos.with(|utxos| {
+                addresses
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|address| utxos.get(address))
+                    .flatten()
+                    .fold((0, 0), |(balance, count), utxo| {
+                        (balance + utxo.value, count + 1)
+                    })
+            })
+        })
+    };
+    let input_class =
+        "border border-solid rounded border-stone-600 px-1 bg-stone-900 placeholder:text-stone-600";
+
+    view! {
+        <div class="border border-solid rounded border-stone-600 p-1 mb-3 text-sm">
+            <div class="table">
+                <For each=move || wallets.get() key=|w| w.name.clone() let:wallet>
+                    <div class="table-row">
+                        <div class="table-cell pr-2">{wallet.name.clone()}</div>
+                        <div class="table-cell pr-2 font-mono">
+                            {match &wallet.source {
+                                WatchSource::Addresses(_) => String::new(),
+                                WatchSource::Xpub(xpub) => format!("{} ({} {})", short_hex(xpub), ctx.t("gap limit"), wallet.gap_limit),
+                            }}
+                        </div>
+                        <div class="table-cell pr-2">
+                            {
+                                let name = wallet.name.clone();
+                                move || match addresses.with(|a| a.get(&name).map(Vec::len)) {
+                                    Some(count) => format!("{count} {}", ctx.t("addresses")),
+                                    None => ctx.t("Scanning…").to_string(),
+                                }
+                            }
+                        </div>
+                        <div class="table-cell pr-2 text-right">
+                            {
+                                let name = wallet.name.clone();
+                                move || ctx.amount_unit.get().format(totals(&name).0)
+                            }
+                        </div>
+                        <div class="table-cell pr-2">
+                            {
+                                let name = wallet.name.clone();
+                                move || format!("{} UTXOs", totals(&name).1)
+                            }
+                        </div>
+                        <button class="table-cell text-red-700" on:click=move |_| remove(wallet.name.clone())>
+                            {move || ctx.t("Delete")}
+                        </button>
+                    </div>
+                </For>
+            </div>
+            <div class="flex flex-wrap items-start gap-1 mt-1">
+                <input
+                    class=input_class
+                    placeholder=move || ctx.t("Name")
+                    on:input=move |e| name.set(event_target_value(&e))
+                    prop:value=name
+                />
+                <textarea
+                    class=format!("{input_class} font-mono grow")
+                    rows=1
+                    spellcheck="false"
+                    placeholder=move || ctx.t("Addresses one per line, or an xpub")
+                    on:input=move |e| source.set(event_target_value(&e))
+                    prop:value=source
+                />
+                <label class="self-center">{move || ctx.t("Gap limit:")}</label>
+                <ParsedInput
+                    ctx
+                    value=gap_limit
+                    min=1
+                    {..}
+                    class=("w-12", true)
+                    title=move || ctx.t("For an xpub, the unused addresses in a row after which scanning stops")
+                />
+                <button class="border border-solid rounded border-stone-600 px-1" on:click=add>
+                    {move || ctx.t("Add wallet")}
+                </button>
+                <button
+                    class="border border-solid rounded border-stone-600 px-1"
+                    title=move || ctx.t("Scan the wallets again, for addresses used since")
+                    on:click=move |_| rescan.notify()
+                >
+                    {move || ctx.t("Rescan")}
+                </button>
+            </div>
+            <p class="text-stone-500">
+                {move || ctx.t("Their coins are followed on the Electrum server and offered to the coin selector.")}
+            </p>
+            <p class="text-red-700">{error}</p>
+            <p class="text-red-700">{status}</p>
+        </div>
+    }
+}
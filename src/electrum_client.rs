@@ -1,25 +1,66 @@
 #![allow(unused)]
+use std::fmt::{self, Display};
+use std::str::FromStr;
 use std::time::Duration;
 
 use futures::{Stream, StreamExt};
 use jsonrpsee::core::{
     client::{ClientT, SubscriptionClientT},
     params::ArrayParams,
+    traits::ToRpcParams,
     ClientError as Error,
 };
+use serde::de::DeserializeOwned;
+
+use crate::backends::ELECTRUM;
+use crate::coin_selection::ElectrumUtxo;
 
 /// Wrapper that adds convenience methods for interacting with the [Electrum Cash
 /// Protocol](https://electrum-cash-protocol.readthedocs.io/en/latest/index.html).
 #[derive(Debug)]
 pub struct ElectrumClient<T> {
     pub client: T,
+    /// Negotiated with `server.version`, [`ProtocolVersion::MIN`] until then.
+    pub protocol: ProtocolVersion,
 }
 
 #[derive(Debug)]
 pub struct ServerVersionResponse {
     pub server_software_version: String,
     /// The ElectrumX protocol version that will be used
-    pub protocol_version: String,
+    pub protocol_version: ProtocolVersion,
+}
+
+/// Version of the protocol as major and minor number, the patch number making no difference to
+/// the methods.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct ProtocolVersion(pub u32, pub u32);
+
+impl ProtocolVersion {
+    /// Oldest version spoken, which servers that don't negotiate are assumed to speak.
+    pub const MIN: Self = Self(1, 4);
+    /// Newest version spoken, adding the token filter of `listunspent`.
+    pub const MAX: Self = Self(1, 5);
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = s.split('.').map(str::parse::<u32>);
+        match (numbers.next(), numbers.next()) {
+            (Some(Ok(major)), Some(Ok(minor))) if numbers.all(|n| n.is_ok()) => {
+                Ok(Self(major, minor))
+            }
+            _ => Err(format!("Invalid protocol version {s}")),
+        }
+    }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -28,16 +69,74 @@ pub struct BlockHeaders {
     pub hex: String,
 }
 
+/// A transaction of a scripthash, from `blockchain.scripthash.get_history`.
+#[derive(serde::Deserialize, Debug)]
+pub struct HistoryEntry {
+    pub tx_hash: String,
+    /// Height of the block including the transaction, 0 or -1 if unconfirmed.
+    pub height: i64,
+}
+
+/// Status of an outpoint, from `blockchain.outpoint.subscribe`.
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct OutpointStatus {
+    /// Height of the transaction creating the outpoint, 0 if unconfirmed. Missing if unknown.
+    pub height: Option<i64>,
+    /// Transaction spending the outpoint, if spent.
+    pub spender_txhash: Option<String>,
+    pub spender_height: Option<i64>,
+}
+
+/// Connect to the Electrum server at the WebSocket `url`. Only available in the browser.
+#[cfg(target_arch = "wasm32")]
+pub async fn connect(url: &str) -> Result<ElectrumClient<jsonrpsee::core::client::Client>, Error> {
+    let builder =
+        jsonrpsee::wasm_client::WasmClientBuilder::new().request_timeout(ELECTRUM.limits().timeout);
+    let client = ELECTRUM
+        .run(builder.build(url))
+        .await
+        .map_err(|_| Error::RequestTimeout)??;
+    let mut client = ElectrumClient::new(client);
+    client.protocol = client
+        .server_version("bch-tx-editor")
+        .await?
+        .protocol_version;
+    Ok(client)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn connect(url: &str) -> Result<ElectrumClient<jsonrpsee::core::client::Client>, Error> {
+    Err(Error::Custom(format!(
+        "Can't connect to {url} outside of the browser"
+    )))
+}
+
 impl<T: ClientT + SubscriptionClientT> ElectrumClient<T> {
-    /// The `server.version` method.
+    /// Call `method`, within the limits of [`ELECTRUM`].
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl ToRpcParams + Send,
+    ) -> Result<R, Error> {
+        ELECTRUM
+            .run(self.client.request(method, params))
+            .await
+            .map_err(|_| Error::RequestTimeout)?
+    }
+
+    /// The `server.version` method, negotiating the newest version both sides speak between
+    /// [`ProtocolVersion::MIN`] and [`ProtocolVersion::MAX`]. Servers only answer it once per
+    /// connection.
     pub async fn server_version(&self, client_name: &str) -> Result<ServerVersionResponse, Error> {
-        let [server_software_version, protocol_version]: [String; 2] = self
-            .client
-            .request("server.version", (client_name, ["1.2", "1.4"]))
-            .await?;
+        let range = [
+            ProtocolVersion::MIN.to_string(),
+            ProtocolVersion::MAX.to_string(),
+        ];
+        let [server_software_version, protocol_version]: [String; 2] =
+            self.request("server.version", (client_name, range)).await?;
         Ok(ServerVersionResponse {
             server_software_version,
-            protocol_version,
+            protocol_version: protocol_version.parse().map_err(Error::Custom)?,
         })
     }
 
@@ -57,26 +156,94 @@ impl<T: ClientT + SubscriptionClientT> ElectrumClient<T> {
         let subscription = self
             .client
             .subscribe_to_method::<(BlockHeaders,)>("blockchain.headers.subscribe")
-            .await
-            .unwrap();
+            .await?;
         let result: BlockHeaders = self
-            .client
             .request("blockchain.headers.subscribe", ArrayParams::new())
             .await?;
         Ok((result, subscription.map(|x| Ok(x.map(|(y,)| y)?))))
     }
 
-    /// The `server.ping` method.
-    pub async fn server_ping(&self) -> Result<(), Error> {
-        let _: Option<()> = self
+    /// The `blockchain.transaction.get` method, returning the raw transaction hex.
+    pub async fn blockchain_transaction_get(&self, txid: &str) -> Result<String, Error> {
+        self.request("blockchain.transaction.get", (txid,)).await
+    }
+
+    /// The `blockchain.transaction.broadcast` method, returning the txid.
+    pub async fn blockchain_transaction_broadcast(&self, raw_tx: &str) -> Result<String, Error> {
+        self.request("blockchain.transaction.broadcast", (raw_tx,))
+            .await
+    }
+
+    /// The `blockchain.scripthash.subscribe` method, returning the status of the scripthash. Its
+    /// changes arrive on [`Self::scripthash_notifications`].
+    pub async fn blockchain_scripthash_subscribe(
+        &self,
+        scripthash: &str,
+    ) -> Result<Option<String>, Error> {
+        self.request("blockchain.scripthash.subscribe", (scripthash,))
+            .await
+    }
+
+    /// Notifications of `blockchain.scripthash.subscribe`, as the scripthash whose status changed,
+    /// for every scripthash subscribed to afterwards.
+    pub async fn scripthash_notifications(
+        &self,
+    ) -> Result<impl Stream<Item = Result<String, Error>>, Error> {
+        let subscription = self
             .client
-            .request("server.ping", ArrayParams::new())
+            .subscribe_to_method::<(String, Option<String>)>("blockchain.scripthash.subscribe")
             .await?;
+        Ok(subscription.map(|x| Ok(x.map(|(scripthash, _)| scripthash)?)))
+    }
+
+    /// The `blockchain.scripthash.get_history` method.
+    pub async fn blockchain_scripthash_get_history(
+        &self,
+        scripthash: &str,
+    ) -> Result<Vec<HistoryEntry>, Error> {
+        self.request("blockchain.scripthash.get_history", (scripthash,))
+            .await
+    }
+
+    /// The `blockchain.scripthash.listunspent` method, asking for UTXOs with and without tokens
+    /// alike where the server takes a token filter, as of 1.5.
+    pub async fn blockchain_scripthash_listunspent(
+        &self,
+        scripthash: &str,
+    ) -> Result<Vec<ElectrumUtxo>, Error> {
+        let method = "blockchain.scripthash.listunspent";
+        match self.protocol >= ProtocolVersion(1, 5) {
+            true => self.request(method, (scripthash, "include_tokens")).await,
+            false => self.request(method, (scripthash,)).await,
+        }
+    }
+
+    /// The `blockchain.outpoint.subscribe` method, unsubscribing right after the status is known.
+    pub async fn blockchain_outpoint_status(
+        &self,
+        txid: &str,
+        vout: u32,
+    ) -> Result<OutpointStatus, Error> {
+        let status: OutpointStatus = self
+            .request("blockchain.outpoint.subscribe", (txid, vout))
+            .await?;
+        let _: bool = self
+            .request("blockchain.outpoint.unsubscribe", (txid, vout))
+            .await?;
+        Ok(status)
+    }
+
+    /// The `server.ping` method.
+    pub async fn server_ping(&self) -> Result<(), Error> {
+        let _: Option<()> = self.request("server.ping", ArrayParams::new()).await?;
         Ok(())
     }
 
     pub fn new(client: T) -> Self {
-        Self { client }
+        Self {
+            client,
+            protocol: ProtocolVersion::MIN,
+        }
     }
 
     pub async fn ping_loop(&self) {
@@ -89,3 +256,19 @@ impl<T: ClientT + SubscriptionClientT> ElectrumClient<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ProtocolVersion;
+
+    #[test]
+    fn test_protocol_version() {
+        assert_eq!("1.5".parse(), Ok(ProtocolVersion(1, 5)));
+        assert_eq!("1.4.6".parse(), Ok(ProtocolVersion(1, 4)));
+        assert!("1".parse::<ProtocolVersion>().is_err());
+        assert!("1.5.x".parse::<ProtocolVersion>().is_err());
+        assert!(ProtocolVersion(1, 4) < ProtocolVersion::MAX);
+        assert!(ProtocolVersion(1, 10) > ProtocolVersion(1, 5));
+        assert_eq!(ProtocolVersion::MAX.to_string(), "1.5");
+    }
+}
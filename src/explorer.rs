@@ -0,0 +1,91 @@
+//! Block explorer URLs for transactions and addresses, with defaults for the public networks and
+//! templates of the user's own kept across sessions.
+
+use std::collections::BTreeMap;
+
+use bitcoincash::Network;
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+const EXPLORERS_KEY: &str = "bch-tx-editor.explorers";
+
+/// URL templates, with `{txid}` and `{address}` replaced by the one to show.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Explorer {
+    pub tx_url: String,
+    pub address_url: String,
+}
+
+impl Explorer {
+    /// A public explorer of `network`, if it has one.
+    pub fn default_for(network: Network) -> Option<Self> {
+        let (tx_url, address_url) = match network {
+            Network::Bitcoin => (
+                "https://blockchair.com/bitcoin-cash/transaction/{txid}",
+                "https://blockchair.com/bitcoin-cash/address/{address}",
+            ),
+            Network::Testnet => (
+                "https://tbch.loping.net/tx/{txid}",
+                "https://tbch.loping.net/address/{address}",
+            ),
+            Network::Testnet4 => (
+                "https://tbch4.loping.net/tx/{txid}",
+                "https://tbch4.loping.net/address/{address}",
+            ),
+            Network::Scalenet => (
+                "https://sbch.loping.net/tx/{txid}",
+                "https://sbch.loping.net/address/{address}",
+            ),
+            Network::Chipnet => (
+                "https://chipnet.imaginary.cash/tx/{txid}",
+                "https://chipnet.imaginary.cash/address/{address}",
+            ),
+            Network::Regtest => return None,
+        };
+        Some(Self {
+            tx_url: tx_url.to_string(),
+            address_url: address_url.to_string(),
+        })
+    }
+
+    pub fn tx_link(&self, txid: &str) -> String {
+        self.tx_url.replace("{txid}", txid)
+    }
+
+    pub fn address_link(&self, address: &str) -> String {
+        self.address_url.replace("{address}", address)
+    }
+}
+
+/// The user's explorers, by the value of the network dropdown, in place of the defaults.
+pub fn load() -> BTreeMap<String, Explorer> {
+    LocalStorage::get(EXPLORERS_KEY).unwrap_or_default()
+}
+
+pub fn save(explorers: &BTreeMap<String, Explorer>) {
+    if let Err(e) = LocalStorage::set(EXPLORERS_KEY, explorers) {
+        error!("Saving explorers failed: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::Network;
+
+    use super::Explorer;
+
+    #[test]
+    fn test_links() {
+        let explorer = Explorer {
+            tx_url: "https://example.com/tx/{txid}?view=raw".to_string(),
+            address_url: "https://example.com/{address}".to_string(),
+        };
+        assert_eq!(explorer.tx_link("ab"), "https://example.com/tx/ab?view=raw");
+        assert_eq!(
+            explorer.address_link("bitcoincash:qq"),
+            "https://example.com/bitcoincash:qq"
+        );
+        assert!(Explorer::default_for(Network::Regtest).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Exchange rates for showing approximate fiat values. Purely informational, nothing in the
+//! transaction depends on them.
+
+use anyhow::{anyhow, Result};
+use gloo::net::http::Request;
+use serde_json::Value;
+
+use crate::backends::FIAT_RATES;
+use crate::macros::StrEnum;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+    pub enum RateSource {
+        #[default]
+        Off = "off",
+        CoinGecko = "coingecko",
+        Kraken = "kraken",
+    }
+}
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+    pub enum FiatCurrency {
+        #[default]
+        Usd = "USD",
+        Eur = "EUR",
+        Gbp = "GBP",
+        Jpy = "JPY",
+    }
+}
+
+/// Price of one BCH in `currency`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FiatRate {
+    pub currency: FiatCurrency,
+    pub rate: f64,
+}
+
+impl FiatRate {
+    pub fn format(self, sats: u64) -> String {
+        let value = sats as f64 / 100_000_000.0 * self.rate;
+        format!("≈ {value:.2} {}", self.currency.to_str())
+    }
+}
+
+impl RateSource {
+    fn url(self, currency: FiatCurrency) -> Option<String> {
+        let currency = currency.to_str();
+        match self {
+            RateSource::Off => None,
+            RateSource::CoinGecko => Some(format!(
+                "https://api.coingecko.com/api/v3/simple/price?ids=bitcoin-cash&vs_currencies={}",
+                currency.to_lowercase()
+            )),
+            RateSource::Kraken => Some(format!(
+                "https://api.kraken.com/0/public/Ticker?pair=BCH{currency}"
+            )),
+        }
+    }
+
+    fn parse_response(self, currency: FiatCurrency, json: &str) -> Result<f64> {
+        let json: Value = serde_json::from_str(json)?;
+        let rate = match self {
+            RateSource::Off => None,
+            RateSource::CoinGecko => {
+                json["bitcoin-cash"][currency.to_str().to_lowercase()].as_f64()
+            }
+            // Kraken names the pair in the result differently from the request, take the only one
+            RateSource::Kraken => json["result"]
+                .as_object()
+                .and_then(|result| result.values().next())
+                .and_then(|ticker| ticker["c"][0].as_str())
+                .and_then(|price| price.parse().ok()),
+        };
+        rate.ok_or_else(|| anyhow!("Unexpected response from {}", self.to_str()))
+    }
+
+    /// Fetch the current rate, `None` if the source is [`RateSource::Off`].
+    pub async fn fetch(self, currency: FiatCurrency) -> Result<Option<FiatRate>> {
+        let Some(url) = self.url(currency) else {
+            return Ok(None);
+        };
+        crate::offline::ensure_online()?;
+        let text = FIAT_RATES
+            .run(async {
+                let response = Request::get(&url).send().await?;
+                if !response.ok() {
+                    return Err(anyhow!(
+                        "{} returned HTTP {}",
+                        self.to_str(),
+                        response.status()
+                    ));
+                }
+                Ok(response.text().await?)
+            })
+            .await??;
+        let rate = self.parse_response(currency, &text)?;
+        Ok(Some(FiatRate { currency, rate }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FiatCurrency, FiatRate, RateSource};
+
+    #[test]
+    fn test_parse_response() {
+        let coingecko = r#"{"bitcoin-cash":{"eur":312.5}}"#;
+        assert_eq!(
+            RateSource::CoinGecko
+                .parse_response(FiatCurrency::Eur, coingecko)
+                .unwrap(),
+            312.5
+        );
+        assert!(RateSource::CoinGecko
+            .parse_response(FiatCurrency::Usd, coingecko)
+            .is_err());
+
+        let kraken =
+            r#"{"error":[],"result":{"BCHUSD":{"a":["350.1","1","1.000"],"c":["350.25","0.1"]}}}"#;
+        assert_eq!(
+            RateSource::Kraken
+                .parse_response(FiatCurrency::Usd, kraken)
+                .unwrap(),
+            350.25
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let rate = FiatRate {
+            currency: FiatCurrency::Usd,
+            rate: 400.0,
+        };
+        assert_eq!(rate.format(12_345_678), "≈ 49.38 USD");
+    }
+}
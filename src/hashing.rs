@@ -0,0 +1,89 @@
+//! Hashing data the way scripts do, for the hash calculator: the digests OP_SHA256, OP_HASH256,
+//! OP_HASH160 and OP_RIPEMD160 leave on the stack, to compare with or push into a script. Also
+//! flipping the byte order of hashes, which explorers show reversed from how they're serialized.
+
+use anyhow::{bail, Result};
+use bitcoincash::hashes::{hash160, hex::ToHex, ripemd160, sha256, sha256d, Hash};
+use serde::{Deserialize, Serialize};
+
+use crate::hex_format;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum HashInputFormat {
+        #[default]
+        Hex = "hex",
+        Utf8 = "utf8",
+    }
+}
+
+/// The bytes `input` stands for in `format`.
+pub fn input_data(input: &str, format: HashInputFormat) -> Result<Vec<u8>> {
+    Ok(match format {
+        HashInputFormat::Hex => hex_format::parse(input)?,
+        HashInputFormat::Utf8 => input.as_bytes().to_vec(),
+    })
+}
+
+/// The digests of `data` in hex by the opcode computing them, in byte order as pushed.
+pub fn digests(data: &[u8]) -> [(&'static str, String); 4] {
+    [
+        ("OP_SHA256", sha256::Hash::hash(data).to_hex()),
+        // Displaying sha256d hashes reverses them, as for txids
+        ("OP_HASH256", sha256d::Hash::hash(data).as_ref().to_hex()),
+        ("OP_HASH160", hash160::Hash::hash(data).to_hex()),
+        ("OP_RIPEMD160", ripemd160::Hash::hash(data).to_hex()),
+    ]
+}
+
+/// Size of txids, token category IDs and block hashes.
+pub const HASH_SIZE: usize = 32;
+
+/// The hex of the hash in `input`, 32 bytes in either order, in the reverse byte order.
+pub fn reverse_hash(input: &str) -> Result<String> {
+    let mut hash = hex_format::parse(input)?;
+    if hash.len() != HASH_SIZE {
+        bail!("Hashes are {HASH_SIZE} bytes, this is {} bytes", hash.len());
+    }
+    hash.reverse();
+    Ok(hash.to_hex())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digests, input_data, reverse_hash, HashInputFormat};
+
+    #[test]
+    fn test_digests() {
+        let digests = digests(&[]);
+        assert_eq!(
+            digests[0].1,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            digests[1].1,
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+        assert_eq!(digests[2].1, "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb");
+        assert_eq!(digests[3].1, "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+    }
+
+    #[test]
+    fn test_input_data() {
+        assert_eq!(
+            input_data("0x01 ab", HashInputFormat::Hex).unwrap(),
+            [0x01, 0xab]
+        );
+        assert_eq!(input_data("ab", HashInputFormat::Utf8).unwrap(), b"ab");
+        assert!(input_data("xyz", HashInputFormat::Hex).is_err());
+    }
+
+    #[test]
+    fn test_reverse_hash() {
+        let txid = "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f";
+        let internal = "9f96ade4b41d5433f4eda31e1738ec2b36f6e7d1420d94a6af99801a88f7f7ff";
+        assert_eq!(reverse_hash(txid).unwrap(), internal);
+        assert_eq!(reverse_hash(&format!(" 0x{internal}\n")).unwrap(), txid);
+        assert!(reverse_hash("abcd").is_err());
+    }
+}
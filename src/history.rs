@@ -0,0 +1,80 @@
+/// Maximum number of states kept for undoing.
+const MAX_UNDO: usize = 100;
+
+/// Undo/redo history of editor states.
+pub struct History<T> {
+    past: Vec<T>,
+    present: Option<T>,
+    future: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            past: vec![],
+            present: None,
+            future: vec![],
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    /// Record the current state. Nothing happens if it is unchanged, which is also the case right
+    /// after an undo or redo restored it.
+    pub fn record(&mut self, state: T) {
+        if self.present.as_ref() == Some(&state) {
+            return;
+        }
+        if let Some(present) = self.present.replace(state) {
+            if self.past.len() == MAX_UNDO {
+                self.past.remove(0);
+            }
+            self.past.push(present);
+        }
+        self.future.clear();
+    }
+
+    /// Step back, returning the state to restore.
+    pub fn undo(&mut self) -> Option<T> {
+        let previous = self.past.pop()?;
+        self.future.extend(self.present.replace(previous.clone()));
+        Some(previous)
+    }
+
+    /// Step forward, returning the state to restore.
+    pub fn redo(&mut self) -> Option<T> {
+        let next = self.future.pop()?;
+        self.past.extend(self.present.replace(next.clone()));
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::default();
+        history.record(1);
+        history.record(2);
+        history.record(3);
+        assert_eq!(history.undo(), Some(2));
+        // Restoring the state records it again
+        history.record(2);
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(2));
+        history.record(4);
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some(2));
+    }
+}
@@ -0,0 +1,88 @@
+//! Translations of the UI.
+//!
+//! UI strings are written in English and passed through [`Locale::translate`], usually via
+//! `Context::t`. Every other language has a JSON file in `locales/` mapping English strings to
+//! their translation. Strings missing from it are shown in English, so a translation can be
+//! contributed bit by bit: copy an existing file, translate the values, and add a variant below.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::{js_reexport::navigator_language, macros::StrEnum};
+
+const LOCALE_KEY: &str = "bch-tx-editor.locale";
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum Locale {
+        #[default]
+        En = "en",
+        Es = "es",
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// Name of the language in that language.
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+        }
+    }
+
+    fn catalog(self) -> Option<&'static Catalog> {
+        static ES: OnceLock<Catalog> = OnceLock::new();
+        let (cell, json) = match self {
+            Locale::En => return None,
+            Locale::Es => (&ES, include_str!("../locales/es.json")),
+        };
+        Some(cell.get_or_init(|| serde_json::from_str(json).unwrap()))
+    }
+
+    pub fn translate(self, text: &'static str) -> &'static str {
+        self.catalog()
+            .and_then(|catalog| catalog.get(text))
+            .map_or(text, String::as_str)
+    }
+
+    /// The locale chosen before, or else the browser's language if there is a translation.
+    pub fn load() -> Self {
+        LocalStorage::get::<Locale>(LOCALE_KEY)
+            .ok()
+            .or_else(|| {
+                let language = navigator_language()?;
+                Locale::from_str(language.split('-').next()?)
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        if let Err(e) = LocalStorage::set(LOCALE_KEY, self) {
+            error!("Saving the language failed: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Locale;
+
+    #[test]
+    fn test_catalogs() {
+        assert_eq!(Locale::En.translate("Network:"), "Network:");
+        for locale in Locale::ALL {
+            if let Some(catalog) = locale.catalog() {
+                assert!(catalog.values().all(|t| !t.is_empty()));
+            }
+        }
+        assert_eq!(Locale::Es.translate("Network:"), "Red:");
+        assert_eq!(Locale::Es.translate("Not translated"), "Not translated");
+    }
+}
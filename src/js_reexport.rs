@@ -1,15 +1,118 @@
+use gloo::file::{Blob, ObjectUrl};
+use leptos::{
+    prelude::{document, window},
+    web_sys::{
+        js_sys::{Reflect, Uint8Array},
+        HtmlElement,
+    },
+};
 use wasm_bindgen::prelude::wasm_bindgen;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
 
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(catch, js_name = cashAssemblyToBin, js_namespace = ["window", "reexports"])]
-    fn impl_cash_assembly_to_bin(script: &str) -> Result<Box<[u8]>, JsValue>;
+    #[wasm_bindgen(catch, js_name = writeText, js_namespace = ["navigator", "clipboard"])]
+    async fn impl_clipboard_write_text(text: &str) -> Result<JsValue, JsValue>;
 
-    #[wasm_bindgen(js_name = disassembleBytecodeBCH, js_namespace = ["window", "reexports"])]
-    pub fn bin_to_cash_assembly(bytecode: Box<[u8]>) -> String;
+    #[wasm_bindgen(catch, js_name = readText, js_namespace = ["navigator", "clipboard"])]
+    async fn impl_clipboard_read_text() -> Result<JsValue, JsValue>;
 }
 
-pub fn cash_assembly_to_bin(script: &str) -> Result<Box<[u8]>, anyhow::Error> {
-    impl_cash_assembly_to_bin(script).map_err(|e| anyhow::anyhow!(e.as_string().unwrap()))
+#[wasm_bindgen(inline_js = r#"
+export async function deriveKey(passphrase, salt, iterations) {
+    const base = await crypto.subtle.importKey(
+        "raw", new TextEncoder().encode(passphrase), "PBKDF2", false, ["deriveKey"]);
+    return crypto.subtle.deriveKey(
+        { name: "PBKDF2", salt, iterations, hash: "SHA-256" },
+        base, { name: "AES-GCM", length: 256 }, false, ["encrypt", "decrypt"]);
+}
+
+export async function aesGcm(encrypt, key, iv, data) {
+    const algorithm = { name: "AES-GCM", iv };
+    const result = encrypt
+        ? await crypto.subtle.encrypt(algorithm, key, data)
+        : await crypto.subtle.decrypt(algorithm, key, data);
+    return new Uint8Array(result);
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = deriveKey)]
+    async fn impl_derive_key(
+        passphrase: &str,
+        salt: &[u8],
+        iterations: u32,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = aesGcm)]
+    async fn impl_aes_gcm(
+        encrypt: bool,
+        key: &JsValue,
+        iv: &[u8],
+        data: &[u8],
+    ) -> Result<JsValue, JsValue>;
+}
+
+pub async fn clipboard_write_text(text: &str) -> Result<(), anyhow::Error> {
+    impl_clipboard_write_text(text)
+        .await
+        .map_err(|e| anyhow::anyhow!("Clipboard write failed: {e:?}"))?;
+    Ok(())
+}
+
+pub async fn clipboard_read_text() -> Result<String, anyhow::Error> {
+    impl_clipboard_read_text()
+        .await
+        .map_err(|e| anyhow::anyhow!("Clipboard read failed: {e:?}"))?
+        .as_string()
+        .ok_or_else(|| anyhow::anyhow!("Clipboard does not contain text"))
+}
+
+/// Save `text` as a file called `name` in the browser's downloads.
+pub fn download_text(name: &str, text: &str) -> Result<(), anyhow::Error> {
+    let url = ObjectUrl::from(Blob::new_with_options(text, Some("text/plain")));
+    let link = document()
+        .create_element("a")
+        .map_err(|e| anyhow::anyhow!("Download failed: {e:?}"))?;
+    link.set_attribute("href", &url)
+        .and_then(|()| link.set_attribute("download", name))
+        .map_err(|e| anyhow::anyhow!("Download failed: {e:?}"))?;
+    link.unchecked_into::<HtmlElement>().click();
+    Ok(())
+}
+
+/// An AES-256-GCM `CryptoKey` derived from `passphrase` with PBKDF2-SHA256, not extractable.
+pub async fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> Result<JsValue, anyhow::Error> {
+    impl_derive_key(passphrase, salt, iterations)
+        .await
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {e:?}"))
+}
+
+/// Encrypt or decrypt `data` with AES-GCM. Decrypting fails if the key is wrong or the data was
+/// tampered with.
+pub async fn aes_gcm(
+    encrypt: bool,
+    key: &JsValue,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let result = impl_aes_gcm(encrypt, key, iv, data).await.map_err(|e| {
+        if encrypt {
+            anyhow::anyhow!("Encryption failed: {e:?}")
+        } else {
+            anyhow::anyhow!("Decryption failed, wrong passphrase or corrupted data")
+        }
+    })?;
+    Ok(Uint8Array::new(&result).to_vec())
+}
+
+/// The browser's preferred language, like `en-US`.
+pub fn navigator_language() -> Option<String> {
+    let navigator = Reflect::get(&window(), &"navigator".into()).ok()?;
+    Reflect::get(&navigator, &"language".into())
+        .ok()?
+        .as_string()
 }
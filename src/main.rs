@@ -1,56 +1,99 @@
 #![deny(rust_2018_idioms)]
 #[macro_use]
-mod macros;
+extern crate bch_tx_edit_core;
+
+mod airdrop;
+mod authchain;
+mod autosave;
+mod backends;
+mod chain;
+mod coin_selection;
 mod components;
 mod electrum_client;
+mod explorer;
+mod fiat;
+mod hashing;
+mod history;
+mod i18n;
 pub mod js_reexport;
-pub mod partially_signed;
-pub mod util;
+mod minting;
+mod multisig;
+mod networks;
+mod offline;
+mod op_return;
+mod opcode_reference;
+mod opcodes;
+mod pay_to_many;
+mod preferences;
+mod proxy;
+mod sensitive;
+mod servers;
+pub mod shortcuts;
+pub mod snapshot;
+mod sweep;
+mod templates;
+#[cfg(test)]
+mod test_util;
+mod timelock;
+mod token_summary;
+mod tx_codec;
+mod vault;
+mod verify;
+mod vm_number;
+mod watch_only;
+
+use std::collections::BTreeMap;
 
-use anyhow::Result;
-use bitcoincash::consensus::encode;
-use bitcoincash::hashes::hex::{FromHex, ToHex};
-use bitcoincash::psbt::serialize::{Deserialize, Serialize};
+use autosave::{Saved, SavedTab};
+use bch_tx_edit_core::macros;
+pub use bch_tx_edit_core::{
+    cash_assembly, hex_format, partially_signed, signing, util, validation, verification, vm_limits,
+};
 use bitcoincash::secp256k1::Secp256k1;
-use bitcoincash::{Network, PackedLockTime, Transaction};
-use components::script_input::{ScriptDisplayFormat, ScriptInputValue};
-use components::ParsedInput;
+use bitcoincash::Network;
+use components::network_editor::NetworkEditor;
+use components::preferences::PreferencesPanel;
+use components::tracker::{self, TrackerPanel};
+use components::tx_editor::{TabState, TxEditor};
+use components::vault_prompt::VaultPrompt;
+use components::watch_only::WatchOnlyPanel;
+use futures::future::{abortable, AbortHandle};
+use gloo::timers::callback::Timeout;
+use leptos::logging::error;
 use leptos::prelude::{
-    event_target_value, mount_to_body, AddAnyAttr, ClassAttribute, ElementChild, For, Get,
-    GlobalAttributes, OnAttribute, PropAttribute, Read, ReadSignal, RwSignal, Set, StoredValue,
-    Write,
+    event_target_checked, event_target_value, mount_to_body, window_event_listener, Callback,
+    ClassAttribute, CollectView, Effect, ElementChild, For, Get, GetUntracked, GetValue,
+    GlobalAttributes, OnAttribute, PropAttribute, Read, ReadSignal, ReadUntracked, RwSignal, Set,
+    SetValue, Show, Signal, StoredValue, Track, Trigger, Update, With, WithUntracked, Write,
 };
-use leptos::{component, logging::log, view, IntoView};
+use leptos::task::spawn_local;
+use leptos::{component, ev, view, IntoView};
 use macros::StrEnum;
 
-use crate::components::tx_input::{TxInput, TxInputState};
-use crate::components::tx_output::{TxOutput, TxOutputState};
-use crate::partially_signed::PartiallySignedTransaction;
+use crate::chain::{Server, ServerKind};
+use crate::coin_selection::Utxo;
+use crate::explorer::Explorer;
+use crate::fiat::{FiatCurrency, FiatRate, RateSource};
+use crate::hex_format::{HexCase, HexFormat, HexSpacing};
+use crate::i18n::Locale;
+use crate::networks::CustomNetwork;
+use crate::preferences::Preferences;
+use crate::snapshot::EditorSnapshot;
+use crate::util::{cash_addr_prefix, AmountUnit};
+use crate::vault::{Sealed, VaultKey};
+use crate::watch_only::WatchOnly;
 
-impl StrEnum for Network {
-    fn to_str(self) -> &'static str {
-        match self {
-            Network::Bitcoin => "mainnet",
-            Network::Testnet => "testnet3",
-            Network::Regtest => "regtest",
-            Network::Testnet4 => "testnet4",
-            Network::Scalenet => "scalenet",
-            Network::Chipnet => "chipnet",
-        }
-    }
+/// Prefix of the values of custom networks in the network dropdown.
+const CUSTOM_PREFIX: &str = "custom:";
 
-    fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "mainnet" => Some(Network::Bitcoin),
-            "testnet3" => Some(Network::Testnet),
-            "regtest" => Some(Network::Regtest),
-            "testnet4" => Some(Network::Testnet4),
-            "scalenet" => Some(Network::Scalenet),
-            "chipnet" => Some(Network::Chipnet),
-            _ => None,
-        }
-    }
-}
+const NETWORKS: [Network; 6] = [
+    Network::Bitcoin,
+    Network::Testnet,
+    Network::Regtest,
+    Network::Testnet4,
+    Network::Scalenet,
+    Network::Chipnet,
+];
 
 fn main() {
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
@@ -60,325 +103,926 @@ fn main() {
 #[component]
 fn App() -> impl IntoView {
     let secp = StoredValue::new(Secp256k1::new());
-    let network = RwSignal::new(Network::Bitcoin);
-    let tx_inputs = RwSignal::new(vec![TxInputState::new(0, 0)]);
-    let tx_outputs = RwSignal::new(vec![TxOutputState::new(0, 0)]);
-    let tx_version = RwSignal::new(2i32);
-    let tx_locktime = RwSignal::new(0u32);
-    let tx_hex = RwSignal::new(String::new());
-    let tx_hex_errored = RwSignal::new(false);
-    let tx_input_id = RwSignal::new(1);
-    let tx_output_id = RwSignal::new(1);
-    let serialize_message = RwSignal::new(String::new());
-
+    let preferences = RwSignal::new(Preferences::load());
+    let show_preferences = RwSignal::new(false);
+    let network = RwSignal::new(preferences.get_untracked().network);
+    let custom_networks = RwSignal::new(networks::load());
+    // The selected custom network, while `network` holds the built-in one it's based on
+    let custom_network = RwSignal::new(None::<CustomNetwork>);
+    let show_networks = RwSignal::new(false);
+    // Electrum server chosen in place of the network's own, empty for none
+    let electrum_server = RwSignal::new(String::new());
+    let server_kind = RwSignal::new(ServerKind::Electrum);
+    let rest_url = RwSignal::new(String::new());
+    let node_url = RwSignal::new(String::new());
+    // WebSocket proxies of the Electrum servers, by server URL
+    let proxies = RwSignal::new(proxy::load());
+    let proxy_error = RwSignal::new(String::new());
+    let explorers = RwSignal::new(explorer::load());
+    let wallets = RwSignal::new(Vec::<WatchOnly>::new());
+    // Watch-only wallets saved encrypted, until the passphrase is entered to open them
+    let sealed_wallets = RwSignal::new(None::<Sealed>);
+    let show_wallets = RwSignal::new(false);
+    // UTXOs of the addresses of the watch-only wallets, by address
+    let watched_utxos = RwSignal::new(BTreeMap::<String, Vec<Utxo>>::new());
+    // Addresses of each watch-only wallet, by name, once scanned
+    let wallet_addresses = RwSignal::new(BTreeMap::<String, Vec<String>>::new());
+    let wallet_status = RwSignal::new(String::new());
+    let rescan_wallets = Trigger::new();
+    let tip_height = RwSignal::new(None::<u32>);
+    let fastest_server = RwSignal::new(None::<String>);
+    let amount_unit = RwSignal::new(AmountUnit::Sats);
+    let hex_format = RwSignal::new(HexFormat::default());
+    let rate_source = RwSignal::new(RateSource::Off);
+    let fiat_currency = RwSignal::new(FiatCurrency::Usd);
+    let fiat_rate = RwSignal::new(None::<FiatRate>);
+    let fiat_error = RwSignal::new(String::new());
+    let locale = RwSignal::new(Locale::load());
     let ctx = Context {
+        locale: locale.read_only(),
         network: network.read_only(),
+        custom_network: custom_network.read_only(),
+        electrum_server: electrum_server.read_only(),
+        server_kind: server_kind.read_only(),
+        rest_url: rest_url.read_only(),
+        node_url: node_url.read_only(),
+        explorers: explorers.read_only(),
+        watched_utxos: watched_utxos.read_only(),
+        tip_height: tip_height.read_only(),
+        fastest_server: fastest_server.read_only(),
+        preferences: preferences.read_only(),
+        amount_unit: amount_unit.read_only(),
+        hex_format: hex_format.read_only(),
+        fiat_rate: fiat_rate.read_only(),
+        next_key: StoredValue::new(0),
     };
+    // Before the first tab, so its cards are tracked too
+    tracker::set_enabled(preferences.get_untracked().debug_panel);
+    Effect::new(move |_| tracker::set_enabled(preferences.read().debug_panel));
+    let tabs = RwSignal::new(Vec::<TabState>::new());
+    let active_tab = RwSignal::new(0usize);
+    // Autosaved tabs from a previous session, until the user decides whether to restore them.
+    let autosave_offer = RwSignal::new(None::<Vec<SavedTab>>);
+    // Whether the autosaved tabs are encrypted and haven't been unlocked yet
+    let autosave_locked = RwSignal::new(false);
+    // Key of the encrypted saved tabs while unlocked
+    let vault_key = RwSignal::new_local(None::<VaultKey>);
 
-    let new_tx_input = move |t: &mut Vec<TxInputState>| {
-        let id = tx_input_id();
-        tx_input_id.set(id + 1);
-        t.push(TxInputState::new(id, t.len()));
+    let new_tab = move |name: String, snapshot: Option<EditorSnapshot>| {
+        let tab = TabState::new(ctx.next_key(), name, snapshot);
+        tabs.write().push(tab);
+        active_tab.set(tab.key);
     };
-    let new_tx_output = move |t: &mut Vec<TxOutputState>| {
-        let id = tx_output_id();
-        tx_output_id.set(id + 1);
-        t.push(TxOutputState::new(id, t.len()));
-    };
-    let delete_tx_input = move |key_to_remove| {
-        let mut tx_inputs = tx_inputs.write();
-        let index_to_remove = tx_inputs
-            .iter()
-            .enumerate()
-            .find(|(_, t)| t.key == key_to_remove)
-            .unwrap()
-            .0;
-        let removed = tx_inputs.remove(index_to_remove);
-        removed.dispose();
-        for (i, tx) in tx_inputs.iter().enumerate().skip(index_to_remove) {
-            tx.index.set(i);
+    let close_tab = move |key: usize| {
+        let tabs = &mut *tabs.write();
+        let Some(i) = tabs.iter().position(|t| t.key == key) else {
+            return;
+        };
+        tabs.remove(i).dispose();
+        if tabs.is_empty() {
+            let tab = TabState::new(ctx.next_key(), ctx.tab_name(1), None);
+            tabs.push(tab);
         }
-    };
-    let delete_tx_output = move |key_to_remove| {
-        let mut tx_outputs = tx_outputs.write();
-        let index_to_remove = tx_outputs
-            .iter()
-            .enumerate()
-            .find(|(_, t)| t.key == key_to_remove)
-            .unwrap()
-            .0;
-        let removed = tx_outputs.remove(index_to_remove);
-        removed.dispose();
-        for (i, tx) in tx_outputs.iter().enumerate().skip(index_to_remove) {
-            tx.index.set(i);
+        if active_tab.get_untracked() == key {
+            active_tab.set(tabs[i.min(tabs.len() - 1)].key);
         }
     };
-    let serialize_tx = move || -> Result<String> {
-        let input = tx_inputs
-            .read()
-            .iter()
-            .map(|&tx_input| tx_input.try_into())
-            .collect::<Result<_, _>>()?;
-        let output = tx_outputs
-            .read()
-            .iter()
-            .map(|&tx_output| tx_output.try_into())
-            .collect::<Result<_, _>>()?;
-        let tx = PartiallySignedTransaction {
-            version: tx_version.get(),
-            lock_time: PackedLockTime(tx_locktime.get()),
-            input,
-            output,
-        };
-        let tx_serialized = tx.serialize();
-        let mut sm = serialize_message.write();
-        if sm.is_empty() || sm.ends_with('.') {
-            *sm = format!("{} bytes", tx_serialized.len());
-        } else {
-            *sm = format!("{} bytes.", tx_serialized.len());
-        }
-        Ok(tx_serialized.to_hex())
+    let add_tab = move |_| {
+        new_tab(ctx.tab_name(tabs.read_untracked().len() + 1), None);
     };
-    let deserialize_tx = move || -> Result<()> {
-        serialize_message.set(String::new());
-        let hex = Vec::from_hex(&tx_hex.read())?;
-        let tx = PartiallySignedTransaction::deserialize(&hex)
-            .or_else::<encode::Error, _>(|_| Ok(Transaction::deserialize(&hex)?.into()))?;
-        let mut tx_inputs = tx_inputs.write();
-        let mut tx_outputs = tx_outputs.write();
+    new_tab(ctx.tab_name(1), None);
 
-        if tx_inputs.len() > tx.input.len() {
-            for tx_input in tx_inputs.drain(tx.input.len()..) {
-                tx_input.dispose();
+    let select_network = move |value: &str| match value.strip_prefix(CUSTOM_PREFIX) {
+        Some(name) => {
+            let custom = custom_networks
+                .read_untracked()
+                .iter()
+                .find(|n| n.name == name)
+                .cloned();
+            if let Some(custom) = custom {
+                network.set(custom.base);
+                custom_network.set(Some(custom));
             }
         }
-
-        if tx_outputs.len() > tx.output.len() {
-            for tx_output in tx_outputs.drain(tx.output.len()..) {
-                tx_output.dispose();
+        None => {
+            if let Some(selected) = Network::from_str(value) {
+                custom_network.set(None);
+                network.set(selected);
             }
         }
-
-        for _ in tx_inputs.len()..tx.input.len() {
-            new_tx_input(&mut tx_inputs);
+    };
+    let set_explorer_url = move |url: String, set: fn(&mut Explorer, String)| {
+        let key = ctx.network_key();
+        explorers.update(|explorers| {
+            let explorer = explorers.entry(key.clone()).or_insert_with(|| {
+                ctx.default_explorer().unwrap_or(Explorer {
+                    tx_url: String::new(),
+                    address_url: String::new(),
+                })
+            });
+            set(explorer, url);
+            if Some(&*explorer) == ctx.default_explorer().as_ref() {
+                explorers.remove(&key);
+            }
+        });
+        explorer::save(&explorers.get_untracked());
+    };
+    // Restore the choice of the last session over the default network, to not validate addresses
+    // against the wrong one after a refresh
+    if let Some(selection) = networks::Selection::load() {
+        select_network(&selection.network);
+        electrum_server.set(selection.electrum_server);
+        server_kind.set(selection.server_kind);
+        rest_url.set(selection.rest_url);
+        node_url.set(selection.node_url);
+    }
+    Effect::new(move |_| {
+        networks::Selection {
+            network: ctx.network_key(),
+            electrum_server: electrum_server.get(),
+            server_kind: server_kind.get(),
+            rest_url: rest_url.get(),
+            node_url: node_url.get(),
         }
-        for _ in tx_outputs.len()..tx.output.len() {
-            new_tx_output(&mut tx_outputs);
+        .save()
+    });
+    // Fall back to the base network when the selected custom one is deleted
+    Effect::new(move |_| {
+        let deleted = custom_network.with(|selected| {
+            selected
+                .as_ref()
+                .is_some_and(|s| !custom_networks.read().iter().any(|n| n == s))
+        });
+        if deleted {
+            custom_network.set(None);
         }
+    });
 
-        tx_version.set(tx.version);
-        tx_locktime.set(tx.lock_time.0);
-
-        for (i, input) in tx.input.iter().enumerate() {
-            tx_inputs[i].update_from_txin(input);
+    offline::set(preferences.get_untracked().offline);
+    Effect::new(move |_| offline::set(ctx.offline()));
+    Effect::new(move |_| proxy::set(proxies.get()));
+    Effect::new(move |_| backends::set_limits(preferences.read().backend_limits));
+    // Wipes the key material typed in and the key of the saved tabs, returning whether there was any
+    let clear_sensitive = move || {
+        let cleared = sensitive::clear() || vault_key.with_untracked(Option::is_some);
+        vault_key.set(None);
+        cleared
+    };
+    // Set when the auto-lock cleared sensitive data, until the user dismisses the notice
+    let locked = RwSignal::new(false);
+    let lock_timer = StoredValue::new_local(None::<Timeout>);
+    let restart_lock_timer = move || {
+        let minutes = preferences.read_untracked().auto_lock_minutes;
+        let timer = (minutes > 0).then(|| {
+            Timeout::new(minutes.saturating_mul(60_000), move || {
+                if clear_sensitive() {
+                    locked.set(true);
+                }
+            })
+        });
+        // Dropping the previous timer cancels it
+        lock_timer.set_value(timer);
+    };
+    Effect::new(move |_| {
+        preferences.track();
+        restart_lock_timer();
+    });
+    window_event_listener(ev::keydown, move |_| restart_lock_timer());
+    window_event_listener(ev::pointerdown, move |_| restart_lock_timer());
+    // Follow the watch-only wallets, scanning them again whenever they, the server or its proxy
+    // change
+    let wallet_sync = StoredValue::new(None::<AbortHandle>);
+    Effect::new(move |_| {
+        rescan_wallets.track();
+        proxies.track();
+        if let Some(sync) = wallet_sync.get_value() {
+            sync.abort();
         }
-
-        for (i, output) in tx.output.iter().enumerate() {
-            let script_pubkey_hex = output.script_pubkey.to_hex();
-            if script_pubkey_hex.starts_with("6a") {
-                // OP_RETURN script
-                tx_outputs[i]
-                    .script_display_format
-                    .set(ScriptDisplayFormat::Asm);
-            } else {
-                tx_outputs[i]
-                    .script_display_format
-                    .set(ScriptDisplayFormat::Addr);
+        wallet_addresses.set(BTreeMap::new());
+        watched_utxos.set(BTreeMap::new());
+        wallet_status.set(String::new());
+        let (wallets, prefix) = (wallets.get(), ctx.cash_addr_prefix());
+        if wallets.is_empty() || ctx.offline() {
+            return;
+        }
+        let Some(server) = ctx.electrum_server() else {
+            wallet_status.set(
+                ctx.t("No Electrum server is set for this network")
+                    .to_string(),
+            );
+            return;
+        };
+        let (following, sync) = abortable(async move {
+            let on_update = |address: &str, utxos| {
+                watched_utxos.update(|w| {
+                    w.insert(address.to_string(), utxos);
+                })
+            };
+            let on_scanned = |wallet: &str, addresses| {
+                wallet_addresses.update(|w| {
+                    w.insert(wallet.to_string(), addresses);
+                })
+            };
+            watch_only::follow(&server, &wallets, &prefix, on_scanned, on_update).await
+        });
+        wallet_sync.set_value(Some(sync));
+        spawn_local(async move {
+            if let Ok(Err(e)) = following.await {
+                wallet_status.set(e.to_string());
             }
-            tx_outputs[i]
-                .script_pubkey
-                .set(ScriptInputValue::Hex(script_pubkey_hex));
-            tx_outputs[i].value.set(output.value);
-
-            tx_outputs[i]
-                .token_data_state
-                .update_from_token_data(output.token.as_ref());
+        });
+    });
+    // Check the bundled servers of the network, to use the fastest to respond by default
+    Effect::new(move |_| {
+        fastest_server.set(None);
+        let checked = network.get();
+        if ctx.offline() || ctx.custom_network.read().is_some() {
+            return;
+        }
+        spawn_local(async move {
+            match servers::fastest(servers::defaults(checked)).await {
+                // Unless the network changed in the meantime
+                Ok((url, _)) if network.get_untracked() == checked => fastest_server.set(Some(url)),
+                Ok(_) => {}
+                Err(e) => error!("No bundled Electrum server responded: {e}"),
+            }
+        });
+    });
+    // Follow the chain tip on the Electrum server, for locktimes relative to it
+    let tip_sync = StoredValue::new(None::<AbortHandle>);
+    Effect::new(move |_| {
+        proxies.track();
+        if let Some(sync) = tip_sync.get_value() {
+            sync.abort();
+        }
+        tip_height.set(None);
+        if ctx.offline() {
+            return;
         }
-        Ok(())
+        let Some(server) = ctx.electrum_server() else {
+            return;
+        };
+        let (following, sync) = abortable(async move {
+            chain::follow_tip(&server, |height| tip_height.set(Some(height))).await
+        });
+        tip_sync.set_value(Some(sync));
+        spawn_local(async move {
+            if let Ok(Err(e)) = following.await {
+                error!("Following the chain tip failed: {e}");
+                tip_height.set(None);
+            }
+        });
+    });
+    let set_offline = move |offline: bool| {
+        offline::set(offline);
+        preferences.update(|p| p.offline = offline);
+        preferences.get_untracked().save();
     };
-    let reset = move |_| {
-        let tx_inputs = &mut *tx_inputs.write();
-        let tx_outputs = &mut *tx_outputs.write();
 
-        for tx_input in tx_inputs.drain(..) {
-            tx_input.dispose();
+    Effect::new(move |_| {
+        let (source, currency) = (rate_source.get(), fiat_currency.get());
+        fiat_rate.set(None);
+        fiat_error.set(String::new());
+        if ctx.offline() {
+            return;
         }
-        for tx_output in tx_outputs.drain(..) {
-            tx_output.dispose();
+        spawn_local(async move {
+            match source.fetch(currency).await {
+                Ok(rate) => fiat_rate.set(rate),
+                Err(e) => fiat_error.set(e.to_string()),
+            }
+        });
+    });
+
+    let saved_tabs = move || -> Vec<SavedTab> {
+        tabs.read()
+            .iter()
+            .map(|t| SavedTab {
+                name: t.name.get(),
+                snapshot: t
+                    .snapshot
+                    .get()
+                    .unwrap_or_else(|| preferences.get_untracked().editor_snapshot()),
+            })
+            .collect()
+    };
+    let offer_autosave = move |saved: Vec<SavedTab>| {
+        let blank = preferences.get_untracked().editor_snapshot();
+        let worth_restoring = saved.len() > 1 || saved.iter().any(|t| t.snapshot != blank);
+        autosave_offer.set(worth_restoring.then_some(saved));
+    };
+    match autosave::load() {
+        Some(Saved::Plain(saved)) => offer_autosave(saved),
+        Some(Saved::Encrypted(_)) => autosave_locked.set(true),
+        None => {}
+    }
+    match watch_only::load() {
+        Some(watch_only::Saved::Plain(saved)) => wallets.set(saved),
+        Some(watch_only::Saved::Encrypted(sealed)) => sealed_wallets.set(Some(sealed)),
+        None => {}
+    }
+    // Saving in plain text would overwrite the encrypted tabs or wallets
+    let encrypted =
+        autosave_locked.get_untracked() || sealed_wallets.with_untracked(Option::is_some);
+    if encrypted && !preferences.get_untracked().encrypt_saved {
+        preferences.update(|p| p.encrypt_saved = true);
+        preferences.get_untracked().save();
+    }
+    Effect::new(move |_| {
+        let saved = saved_tabs();
+        // Leave the saved tabs alone until the user decides whether to restore them
+        if autosave_offer.with(Option::is_some) || autosave_locked.get() {
+            return;
+        }
+        if !preferences.read().encrypt_saved {
+            autosave::save(&saved);
+        } else if let Some(key) = vault_key.get() {
+            spawn_local(async move { autosave::save_encrypted(&key, &saved).await });
         }
-        new_tx_input(tx_inputs);
-        new_tx_output(tx_outputs);
-        tx_version.set(2);
-        tx_locktime.set(0);
+        // Otherwise locked, the last encrypted save stays until the passphrase is entered again
+    });
+    // Open the encrypted wallets once the passphrase is entered, keeping those added meanwhile
+    Effect::new(move |_| {
+        let (Some(key), Some(sealed)) = (vault_key.get(), sealed_wallets.get()) else {
+            return;
+        };
+        spawn_local(async move {
+            match watch_only::open(&key, &sealed).await {
+                Ok(saved) => {
+                    wallets.update(|wallets| {
+                        let added = std::mem::replace(wallets, saved);
+                        for wallet in added {
+                            if !wallets.iter().any(|w| w.name == wallet.name) {
+                                wallets.push(wallet);
+                            }
+                        }
+                    });
+                    sealed_wallets.set(None);
+                }
+                Err(e) => wallet_status.set(format!("{e:#}")),
+            }
+        });
+    });
+    Effect::new(move |_| {
+        let saved = wallets.get();
+        // Leave the encrypted wallets alone until they're opened
+        if sealed_wallets.with(Option::is_some) {
+            return;
+        }
+        if !preferences.read().encrypt_saved {
+            watch_only::save(&saved);
+        } else if let Some(key) = vault_key.get() {
+            spawn_local(async move { watch_only::save_encrypted(&key, &saved).await });
+        }
+    });
+    let unlock_autosave = Callback::new(move |saved: Vec<SavedTab>| {
+        if autosave_locked.get_untracked() {
+            autosave_locked.set(false);
+            offer_autosave(saved);
+        }
+    });
+    let discard_locked_autosave = Callback::new(move |()| {
+        autosave_locked.set(false);
+        sealed_wallets.set(None);
+    });
+    let restore_autosave = move |_| {
+        let Some(saved) = autosave_offer.write().take() else {
+            return;
+        };
+        for tab in tabs.write().drain(..) {
+            tab.dispose();
+        }
+        for SavedTab { name, snapshot } in saved {
+            new_tab(name, Some(snapshot));
+        }
+        if let Some(tab) = tabs.read_untracked().first() {
+            active_tab.set(tab.key);
+        }
+    };
+    let discard_autosave = move |_| {
+        autosave::clear();
+        autosave_offer.set(None);
     };
 
     view! {
-        <div class="flex gap-3 justify-between">
+        <Show when=move || ctx.offline()>
+            <div class="border border-green-700 rounded-md p-2 mb-3 text-green-600">
+                {move || ctx.t("Offline: nothing is sent over the network, this session is safe on an airgapped machine.")}
+            </div>
+        </Show>
+        <Show when=locked>
+            <div class="border border-yellow-700 rounded-md p-2 mb-3 flex flex-wrap items-center gap-3">
+                <span>{move || ctx.t("Locked while idle: keys and other sensitive data were cleared and must be entered again.")}</span>
+                <button
+                    class="border border-solid rounded-md border-stone-600 px-2 py-1"
+                    on:click=move |_| locked.set(false)
+                >
+                    {move || ctx.t("OK")}
+                </button>
+            </div>
+        </Show>
+        <Show when=move || preferences.read().encrypt_saved && vault_key.with(Option::is_none)>
+            <VaultPrompt
+                ctx
+                key=vault_key
+                on_unlock=unlock_autosave
+                on_discard=discard_locked_autosave
+            />
+        </Show>
+        <Show when=move || autosave_offer.with(Option::is_some)>
+            <div class="border border-yellow-700 rounded-md p-2 mb-3 flex flex-wrap items-center gap-3">
+                <span>{move || ctx.t("Found autosaved transactions from a previous session.")}</span>
+                <button
+                    class="border border-solid rounded-md border-stone-600 px-2 py-1"
+                    on:click=restore_autosave
+                >
+                    {move || ctx.t("Restore")}
+                </button>
+                <button
+                    class="border border-solid rounded-md border-stone-600 px-2 py-1"
+                    on:click=discard_autosave
+                >
+                    {move || ctx.t("Discard")}
+                </button>
+            </div>
+        </Show>
+        <div class="flex flex-wrap items-end gap-1 mb-3 border-b border-stone-600">
+            <For each=tabs key=|tab| tab.key let:tab>
+                <div
+                    class="flex items-center border border-b-0 border-stone-600 rounded-t-md px-2 py-1"
+                    class=("bg-stone-800", move || active_tab() == tab.key)
+                >
+                    <input
+                        class="bg-inherit w-32"
+                        on:focus=move |_| active_tab.set(tab.key)
+                        on:input=move |e| tab.name.set(event_target_value(&e))
+                        prop:value=tab.name
+                    />
+                    <button class="ml-1 px-1" title=move || ctx.t("Close tab") on:click=move |_| close_tab(tab.key)>
+                        "×"
+                    </button>
+                </div>
+            </For>
+            <button class="px-2 py-1" title=move || ctx.t("New tab") on:click=add_tab>
+                "+"
+            </button>
+        </div>
+        <div class="mb-3">
             <div class="table">
                 <div class="table-row">
-                    <div class="table-cell pr-1 pb-1">
-                        <label for="tx_version">TX version:</label>
+                    <div class="table-cell pr-1">
+                        <label for="network">{move || ctx.t("Network:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <select
+                            id="network"
+                            class="bg-inherit border rounded ml-1 p-1 disabled:opacity-30"
+                            on:input=move |e| select_network(&event_target_value(&e))
+                            prop:value=move || ctx.network_key()
+                        >
+                            {NETWORKS
+                                .map(|n| view! { <option value=n.to_str()>{n.to_str()}</option> })
+                                .collect_view()}
+                            {move || {
+                                custom_networks
+                                    .get()
+                                    .into_iter()
+                                    .map(|n| view! { <option value=format!("{CUSTOM_PREFIX}{}", n.name)>{n.name}</option> })
+                                    .collect_view()
+                            }}
+                        </select>
+                        <button
+                            class="border border-solid rounded border-stone-600 px-1 ml-1 text-sm"
+                            class=("bg-stone-900", show_networks)
+                            title=move || ctx.t("Define networks with their own address prefix and servers")
+                            on:click=move |_| show_networks.update(|s| *s = !*s)
+                        >
+                            {move || ctx.t("Custom")}
+                        </button>
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="electrum_server">{move || ctx.t("Electrum server:")}</label>
                     </div>
-                    <div class="table-cell pb-1">
-                        <ParsedInput value={tx_version} {..} id="tx_version" placeholder="2"/>
+                    <div class="table-cell">
+                        <input
+                            id="electrum_server"
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            placeholder=move || ctx.default_electrum_server().unwrap_or_default()
+                            on:change=move |e| electrum_server.set(event_target_value(&e).trim().to_string())
+                            prop:value=electrum_server
+                        />
                     </div>
                 </div>
                 <div class="table-row">
                     <div class="table-cell pr-1">
-                        <label for="tx_locktime">Locktime:</label>
+                        <label for="electrum_proxy">{move || ctx.t("WebSocket proxy:")}</label>
                     </div>
                     <div class="table-cell">
-                        <ParsedInput value={tx_locktime} {..} id="tx_locktime" placeholder="0"/>
+                        <input
+                            id="electrum_proxy"
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            title=move || ctx.t("Connect to this Electrum server through a proxy, {server} standing for the server's URL")
+                            placeholder=move || ctx.t("None")
+                            on:change=move |e| {
+                                let Some(server) = ctx.electrum_server() else {
+                                    return;
+                                };
+                                let proxy = event_target_value(&e).trim().to_string();
+                                if !proxy.is_empty() {
+                                    if let Err(e) = proxy::validate(&proxy) {
+                                        proxy_error.set(e.to_string());
+                                        return;
+                                    }
+                                }
+                                proxy_error.set(String::new());
+                                proxies.update(|p| match proxy.is_empty() {
+                                    true => {
+                                        p.remove(&server);
+                                    }
+                                    false => {
+                                        p.insert(server, proxy);
+                                    }
+                                });
+                                proxy::save(&proxies.get_untracked());
+                            }
+                            prop:value=move || {
+                                let server = ctx.electrum_server().unwrap_or_default();
+                                proxies.with(|p| p.get(&server).cloned().unwrap_or_default())
+                            }
+                        />
+                        <span class="text-red-700 text-sm ml-1">{proxy_error}</span>
                     </div>
                 </div>
-            </div>
-            <div class="table">
                 <div class="table-row">
                     <div class="table-cell pr-1">
-                        <label for="tx_locktime">Network:</label>
+                        <label for="chain_backend">{move || ctx.t("Chain data:")}</label>
                     </div>
                     <div class="table-cell">
                         <select
-                            class="bg-inherit border rounded ml-1 p-1 disabled:opacity-30"
+                            id="chain_backend"
+                            class="bg-inherit border rounded p-1 ml-1"
+                            title=move || ctx.t("Where transactions are fetched from and broadcast to")
+                            on:input=move |e| server_kind.set(ServerKind::from_str(&event_target_value(&e)).unwrap())
+                            prop:value=move || server_kind.get().to_str()
+                        >
+                            <option value=ServerKind::Electrum.to_str()>Electrum</option>
+                            <option value=ServerKind::Rest.to_str()>{move || ctx.t("REST API")}</option>
+                            <option value=ServerKind::Node.to_str()>{move || ctx.t("Own node")}</option>
+                        </select>
+                        <input
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            class:hidden=move || server_kind.get() != ServerKind::Rest
+                            placeholder=move || ctx.t("Blockbook API base URL")
+                            on:change=move |e| rest_url.set(event_target_value(&e).trim().to_string())
+                            prop:value=rest_url
+                        />
+                        <input
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            class:hidden=move || server_kind.get() != ServerKind::Node
+                            placeholder=move || ctx.t("JSON-RPC proxy URL")
+                            title=move || ctx.t("A proxy in front of the node's JSON-RPC that allows requests from this page and adds the credentials")
+                            on:change=move |e| node_url.set(event_target_value(&e).trim().to_string())
+                            prop:value=node_url
+                        />
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="explorer_tx_url">{move || ctx.t("Block explorer:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <input
+                            id="explorer_tx_url"
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            placeholder="https://…/tx/{txid}"
+                            on:change=move |e| set_explorer_url(event_target_value(&e).trim().to_string(), |x, url| x.tx_url = url)
+                            prop:value=move || ctx.explorer_templates().map(|x| x.tx_url).unwrap_or_default()
+                        />
+                        <input
+                            class="border border-solid rounded border-stone-600 ml-1 px-1 w-72 bg-stone-900 placeholder:text-stone-600"
+                            placeholder="https://…/address/{address}"
+                            on:change=move |e| set_explorer_url(event_target_value(&e).trim().to_string(), |x, url| x.address_url = url)
+                            prop:value=move || ctx.explorer_templates().map(|x| x.address_url).unwrap_or_default()
+                        />
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="amount_unit">{move || ctx.t("Amounts in:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <select
+                            id="amount_unit"
+                            class="bg-inherit border rounded ml-1 p-1"
                             on:input=move |e| {
-                                network.set(Network::from_str(&event_target_value(&e)).unwrap())
+                                amount_unit.set(AmountUnit::from_str(&event_target_value(&e)).unwrap())
                             }
-                            prop:value={move || network().to_str()}
+                            prop:value={move || amount_unit().to_str()}
                         >
-                            <option value={Network::Bitcoin.to_str()}>mainnet</option>
-                            <option value={Network::Testnet.to_str()}>testnet3</option>
-                            <option value={Network::Regtest.to_str()}>regtest</option>
-                            <option value={Network::Testnet4.to_str()}>testnet4</option>
-                            <option value={Network::Scalenet.to_str()}>scalenet</option>
-                            <option value={Network::Chipnet.to_str()}>chipnet</option>
+                            <option value={AmountUnit::Sats.to_str()}>sats</option>
+                            <option value={AmountUnit::Bch.to_str()}>BCH</option>
                         </select>
                     </div>
                 </div>
-            </div>
-        </div>
-        <div class="flex flex-wrap gap-3 mt-3">
-            <div class="basis-[32rem] grow">
-                <p class="mb-1">Inputs</p>
-                <ol start="0">
-                    <For
-                        each=move || 0..tx_inputs.read().len()
-                        key=move |i| tx_inputs.read()[*i].key
-                        let:i
-                    >
-                        {
-                            let tx_input = tx_inputs.read()[i];
-                            view! {
-                                <li class="border border-solid rounded-md border-stone-600 p-1 mb-2 bg-stone-800">
-                                    <TxInput tx_input secp ctx/>
-                                    <div class="flex justify-between">
-                                        <button
-                                            on:click=move |_| delete_tx_input(tx_input.key)
-                                            class="border border-solid rounded border-stone-600 px-2 bg-red-950"
-                                        >
-                                            "−"
-                                        </button>
-                                        <span class="text-sm mr-4">"#"{tx_input.index}</span>
-                                    </div>
-                                </li>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="hex_case">{move || ctx.t("Hex format:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <select
+                            id="hex_case"
+                            class="bg-inherit border rounded ml-1 p-1"
+                            on:input=move |e| {
+                                let case = HexCase::from_str(&event_target_value(&e)).unwrap();
+                                hex_format.update(|f| f.case = case);
                             }
-                        }
-                    </For>
-                </ol>
-                <button
-                    on:click=move |_| new_tx_input(&mut tx_inputs.write())
-                    class="border border-solid rounded border-stone-600 px-2"
-                >
-                    "+"
-                </button>
-            </div>
-            <div class="basis-[32rem] grow">
-                <p class="mb-1">Outputs</p>
-                <ol start="0">
-                    <For
-                        each=move || 0..tx_outputs.read().len()
-                        key=move |i| tx_outputs.read()[*i].key
-                        let:i
-                    >
-                        {
-                            let tx_output = tx_outputs.read()[i];
-                            view! {
-                                <li class="border border-solid rounded border-stone-600 p-1 bg-stone-800 mb-2">
-                                    <TxOutput tx_output ctx/>
-                                    <div class="flex justify-between">
-                                        <button
-                                            on:click=move |_| delete_tx_output(tx_output.key)
-                                            class="border border-solid rounded border-stone-600 px-2 bg-red-950"
-                                        >"−"</button>
-                                        <span class="text-sm mr-4">"#"{tx_output.index}</span>
-                                    </div>
-                                </li>
+                            prop:value={move || hex_format.read().case.to_str()}
+                        >
+                            <option value={HexCase::Lower.to_str()}>{move || ctx.t("lowercase")}</option>
+                            <option value={HexCase::Upper.to_str()}>{move || ctx.t("uppercase")}</option>
+                        </select>
+                        <select
+                            class="bg-inherit border rounded ml-1 p-1"
+                            title=move || ctx.t("Spacing")
+                            on:input=move |e| {
+                                let spacing = HexSpacing::from_str(&event_target_value(&e)).unwrap();
+                                hex_format.update(|f| f.spacing = spacing);
                             }
-                        }
-                    </For>
-                </ol>
-                <button
-                    on:click=move |_| new_tx_output(&mut tx_outputs.write())
-                    class="border border-solid rounded border-stone-600 px-2"
-                >
-                    "+"
-                </button>
+                            prop:value={move || hex_format.read().spacing.to_str()}
+                        >
+                            <option value={HexSpacing::None.to_str()}>{move || ctx.t("no spacing")}</option>
+                            <option value={HexSpacing::Byte.to_str()}>{move || ctx.t("space between bytes")}</option>
+                            <option value={HexSpacing::Field.to_str()}>{move || ctx.t("space between fields")}</option>
+                        </select>
+                        <input
+                            class="border border-solid rounded border-stone-600 px-1 ml-1 w-20 bg-stone-900 placeholder:text-stone-600"
+                            placeholder=move || ctx.t("no wrap")
+                            title=move || ctx.t("Characters per line")
+                            on:change=move |e| {
+                                let wrap = event_target_value(&e).trim().parse().unwrap_or_default();
+                                hex_format.update(|f| f.wrap = wrap);
+                            }
+                            prop:value=move || match hex_format.read().wrap {
+                                0 => String::new(),
+                                wrap => wrap.to_string(),
+                            }
+                        />
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="rate_source">{move || ctx.t("Fiat values:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <select
+                            id="rate_source"
+                            class="bg-inherit border rounded ml-1 p-1 disabled:opacity-30"
+                            disabled=move || ctx.offline()
+                            on:input=move |e| {
+                                rate_source.set(RateSource::from_str(&event_target_value(&e)).unwrap())
+                            }
+                            prop:value={move || rate_source().to_str()}
+                        >
+                            <option value={RateSource::Off.to_str()}>{move || ctx.t("off")}</option>
+                            <option value={RateSource::CoinGecko.to_str()}>CoinGecko</option>
+                            <option value={RateSource::Kraken.to_str()}>Kraken</option>
+                        </select>
+                        <select
+                            class="bg-inherit border rounded ml-1 p-1 disabled:opacity-30"
+                            disabled=move || rate_source() == RateSource::Off || ctx.offline()
+                            on:input=move |e| {
+                                fiat_currency.set(FiatCurrency::from_str(&event_target_value(&e)).unwrap())
+                            }
+                            prop:value={move || fiat_currency().to_str()}
+                        >
+                            <option value={FiatCurrency::Usd.to_str()}>USD</option>
+                            <option value={FiatCurrency::Eur.to_str()}>EUR</option>
+                            <option value={FiatCurrency::Gbp.to_str()}>GBP</option>
+                            <option value={FiatCurrency::Jpy.to_str()}>JPY</option>
+                        </select>
+                        <span class="text-sm text-red-700 ml-1">{fiat_error}</span>
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="locale">{move || ctx.t("Language:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <select
+                            id="locale"
+                            class="bg-inherit border rounded ml-1 p-1"
+                            on:input=move |e| {
+                                let new_locale = Locale::from_str(&event_target_value(&e)).unwrap();
+                                new_locale.save();
+                                locale.set(new_locale);
+                            }
+                            prop:value={move || locale().to_str()}
+                        >
+                            {Locale::ALL
+                                .map(|l| view! { <option value=l.to_str()>{l.name()}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+                </div>
+                <div class="table-row">
+                    <div class="table-cell pr-1">
+                        <label for="offline">{move || ctx.t("Offline mode:")}</label>
+                    </div>
+                    <div class="table-cell">
+                        <input
+                            id="offline"
+                            type="checkbox"
+                            class="ml-1"
+                            title=move || ctx.t("Disable everything that uses the network")
+                            on:change=move |e| set_offline(event_target_checked(&e))
+                            prop:checked=move || ctx.offline()
+                        />
+                    </div>
+                </div>
             </div>
-        </div>
-        <div class="mt-3">
             <button
-                class="border border-solid rounded border-stone-600 px-1"
-                on:click=move |_| {
-                    match serialize_tx() {
-                        Ok(tx) => {
-                            tx_hex_errored.set(false);
-                            tx_hex.set(tx);
-                        }
-                        Err(e) => {
-                            tx_hex_errored.set(true);
-                            tx_hex.set(e.to_string());
-                        }
-                    }
-                }
+                class="border border-solid rounded border-stone-600 px-1 mt-1 text-sm"
+                class=("bg-stone-900", show_preferences)
+                on:click=move |_| show_preferences.update(|s| *s = !*s)
             >
-                "Serialize"
+                {move || ctx.t("Preferences")}
             </button>
             <button
-                class="border border-solid rounded border-stone-600 px-1 mx-1"
-                on:click=move |_| {
-                    match deserialize_tx() {
-                        Ok(_) => (),
-                        Err(e) => {
-                            log!("Deserialization error: {e}");
-                            tx_hex_errored.set(true);
-                        }
-                    }
-                }
+                class="border border-solid rounded border-stone-600 px-1 mt-1 ml-1 text-sm"
+                class=("bg-stone-900", show_wallets)
+                title=move || ctx.t("Addresses and xpubs whose coins can be spent with the coin selector")
+                on:click=move |_| show_wallets.update(|s| *s = !*s)
             >
-                "Deserialize"
+                {move || ctx.t("Watch-only wallets")}
             </button>
             <button
-                class="border border-solid rounded border-stone-600 px-1 mx-1 ml-3 bg-red-950"
-                on:click=reset
+                class="border border-solid rounded border-stone-600 px-1 mt-1 ml-1 text-sm"
+                title=move || ctx.t("Wipe keys and other sensitive data from memory")
+                on:click=move |_| {
+                    clear_sensitive();
+                }
             >
-                "Reset"
+                {move || ctx.t("Clear sensitive data")}
             </button>
-            <span>{serialize_message}</span>
-            <textarea
-                spellcheck="false"
-                class="border border-solid rounded border-stone-600 px-1 w-full placeholder:text-stone-600 font-mono grow my-1"
-                class=("bg-stone-900", move || !tx_hex_errored())
-                class=("bg-red-950", tx_hex_errored)
-                on:input=move |_| tx_hex_errored.set(false)
-                on:change=move |e| tx_hex.set(event_target_value(&e))
-                prop:value={tx_hex}
-            />
         </div>
+        <Show when=show_networks>
+            <NetworkEditor networks=custom_networks ctx/>
+        </Show>
+        <Show when=show_wallets>
+            <WatchOnlyPanel
+                ctx
+                wallets
+                addresses=wallet_addresses.read_only()
+                utxos=watched_utxos.read_only()
+                rescan=rescan_wallets
+                status=wallet_status.read_only()
+            />
+        </Show>
+        <Show when=show_preferences>
+            <PreferencesPanel preferences ctx/>
+        </Show>
+        <Show when=move || preferences.read().debug_panel>
+            <TrackerPanel ctx/>
+        </Show>
+        <For each=tabs key=|tab| tab.key let:tab>
+            <div class:hidden=move || active_tab() != tab.key>
+                <TxEditor tab secp ctx active=Signal::derive(move || active_tab() == tab.key)/>
+            </div>
+        </For>
     }
 }
 
 #[derive(Copy, Clone)]
 struct Context {
+    locale: ReadSignal<Locale>,
+    /// For a custom network, the built-in one it follows the rules of.
     network: ReadSignal<Network>,
+    custom_network: ReadSignal<Option<CustomNetwork>>,
+    /// Electrum server chosen in place of the network's own, empty for none.
+    electrum_server: ReadSignal<String>,
+    server_kind: ReadSignal<ServerKind>,
+    /// Base URL of the REST API, used in place of Electrum when chosen.
+    rest_url: ReadSignal<String>,
+    /// URL of the JSON-RPC proxy of the user's node, used in place of Electrum when chosen.
+    node_url: ReadSignal<String>,
+    /// Block explorers chosen in place of the defaults, by [`Context::network_key`].
+    explorers: ReadSignal<BTreeMap<String, Explorer>>,
+    /// UTXOs of the watch-only wallets, by address.
+    watched_utxos: ReadSignal<BTreeMap<String, Vec<Utxo>>>,
+    /// Height of the chain tip on the Electrum server, while following it.
+    tip_height: ReadSignal<Option<u32>>,
+    /// The bundled Electrum server of the network that responded first, once checked.
+    fastest_server: ReadSignal<Option<String>>,
+    preferences: ReadSignal<Preferences>,
+    amount_unit: ReadSignal<AmountUnit>,
+    hex_format: ReadSignal<HexFormat>,
+    /// Exchange rate for informational fiat values, if enabled and fetched.
+    fiat_rate: ReadSignal<Option<FiatRate>>,
+    /// Source of keys for tabs, inputs and outputs, unique across all tabs so that element ids
+    /// don't collide.
+    next_key: StoredValue<usize>,
+}
+
+impl Context {
+    fn next_key(self) -> usize {
+        let key = self.next_key.get_value();
+        self.next_key.set_value(key + 1);
+        key
+    }
+
+    /// `text` in the current language. Tracks the language when called in a reactive context.
+    fn t(self, text: &'static str) -> &'static str {
+        self.locale.get().translate(text)
+    }
+
+    /// Whether offline mode is on, and features using the network are disabled.
+    fn offline(self) -> bool {
+        self.preferences.read().offline
+    }
+
+    /// The selected network as the value of the network dropdown.
+    fn network_key(self) -> String {
+        match &*self.custom_network.read() {
+            Some(custom) => format!("{CUSTOM_PREFIX}{}", custom.name),
+            None => self.network.get().to_str().to_string(),
+        }
+    }
+
+    /// Block explorer of the selected network, the one chosen or else a public one.
+    fn explorer_templates(self) -> Option<Explorer> {
+        let chosen = self.explorers.read().get(&self.network_key()).cloned();
+        chosen.or_else(|| self.default_explorer())
+    }
+
+    /// Public block explorer of the selected network, custom networks have none.
+    fn default_explorer(self) -> Option<Explorer> {
+        match &*self.custom_network.read() {
+            Some(_) => None,
+            None => Explorer::default_for(self.network.get()),
+        }
+    }
+
+    /// Link to `txid` on the block explorer, except while offline as opening it would leak it.
+    fn tx_link(self, txid: &str) -> Option<String> {
+        let explorer = self.explorer_templates().filter(|_| !self.offline())?;
+        Some(explorer.tx_link(txid)).filter(|_| !explorer.tx_url.is_empty())
+    }
+
+    /// Link to `address` on the block explorer, except while offline.
+    fn address_link(self, address: &str) -> Option<String> {
+        let explorer = self.explorer_templates().filter(|_| !self.offline())?;
+        Some(explorer.address_link(address)).filter(|_| !explorer.address_url.is_empty())
+    }
+
+    /// CashAddr prefix of the selected network. Tracks it when called in a reactive context.
+    fn cash_addr_prefix(self) -> String {
+        match &*self.custom_network.read() {
+            Some(custom) => custom.cash_addr_prefix.clone(),
+            None => cash_addr_prefix(self.network.get()).to_string(),
+        }
+    }
+
+    /// Electrum server to use, the one chosen or else the network's own.
+    fn electrum_server(self) -> Option<String> {
+        let chosen = self.electrum_server.get();
+        match chosen.is_empty() {
+            true => self.default_electrum_server(),
+            false => Some(chosen),
+        }
+    }
+
+    /// Server for fetching and broadcasting transactions: the REST API or node when chosen and
+    /// set, else Electrum.
+    fn chain_server(self) -> Option<Server> {
+        let rest_url = self.rest_url.get();
+        let node_url = self.node_url.get();
+        match self.server_kind.get() {
+            ServerKind::Rest if !rest_url.is_empty() => Some(Server::Rest(rest_url)),
+            ServerKind::Node if !node_url.is_empty() => Some(Server::Node(node_url)),
+            _ => self.electrum_server().map(Server::Electrum),
+        }
+    }
+
+    /// Electrum server of the selected network, if one is known.
+    fn default_electrum_server(self) -> Option<String> {
+        match &*self.custom_network.read() {
+            Some(custom) => custom.electrum_servers.first().cloned(),
+            None => self.fastest_server.get().or_else(|| {
+                servers::defaults(self.network.get())
+                    .first()
+                    .map(|url| url.to_string())
+            }),
+        }
+    }
+
+    fn tab_name(self, number: usize) -> String {
+        format!(
+            "{} {number}",
+            self.locale.get_untracked().translate("Transaction")
+        )
+    }
 }
 
 // #[component]
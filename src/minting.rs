@@ -0,0 +1,215 @@
+//! Minting a collection of NFTs in a batch, their commitments being a fixed prefix followed by
+//! an incrementing index.
+
+use anyhow::{bail, Result};
+use bitcoincash::{
+    blockdata::token::{Capability, OutputData, Structure, MAX_CONSENSUS_COMMITMENT_LENGTH},
+    hashes::hex::ToHex,
+    TokenID, TxOut,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        token_data::{NftCapability, TokenDataSnapshot},
+        tx_output::TxOutputSnapshot,
+    },
+    util::{cash_addr_to_script, dust_threshold, script_num},
+};
+
+/// Outputs minted at once are capped, a transaction with more would be far from standard anyway.
+pub const MAX_SERIES_LENGTH: u64 = 1000;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum IndexEncoding {
+        /// A script number, as read by covenants with `OP_BIN2NUM`.
+        #[default]
+        Number = "number",
+        /// Unsigned big-endian, so commitments sort by index.
+        BigEndian = "big-endian",
+        /// ASCII digits, for commitments that read as text.
+        Decimal = "decimal",
+    }
+}
+
+impl IndexEncoding {
+    /// `index` in this encoding, padded to `width` bytes for the fixed width encodings.
+    pub fn encode(self, index: u64, width: usize) -> Result<Vec<u8>> {
+        let bytes = match self {
+            IndexEncoding::Number => return Ok(script_num(i64::try_from(index)?)),
+            IndexEncoding::BigEndian => {
+                let bytes = index.to_be_bytes();
+                let significant = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+                bytes[significant..].to_vec()
+            }
+            IndexEncoding::Decimal => index.to_string().into_bytes(),
+        };
+        if bytes.len() > width && width > 0 {
+            bail!("{index} doesn't fit in {width} bytes");
+        }
+        let padding = if self == IndexEncoding::Decimal {
+            b'0'
+        } else {
+            0
+        };
+        let mut padded = vec![padding; width.saturating_sub(bytes.len())];
+        padded.extend(bytes);
+        Ok(padded)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Series {
+    pub prefix: Vec<u8>,
+    pub start: u64,
+    pub count: u64,
+    pub encoding: IndexEncoding,
+    /// Bytes the index is padded to, 0 for no padding. Ignored for script numbers.
+    pub width: usize,
+}
+
+impl Series {
+    pub fn commitments(&self) -> Result<Vec<Vec<u8>>> {
+        if self.count == 0 {
+            bail!("Enter how many NFTs to mint");
+        }
+        if self.count > MAX_SERIES_LENGTH {
+            bail!("At most {MAX_SERIES_LENGTH} NFTs can be minted at once");
+        }
+        let Some(end) = self.start.checked_add(self.count - 1) else {
+            bail!("The indexes of the series go past {}", u64::MAX);
+        };
+        (self.start..=end)
+            .map(|index| {
+                let mut commitment = self.prefix.clone();
+                commitment.extend(self.encoding.encode(index, self.width)?);
+                if commitment.len() > usize::from(MAX_CONSENSUS_COMMITMENT_LENGTH) {
+                    bail!(
+                        "The commitment of NFT {index} is {} bytes, the maximum is {} bytes",
+                        commitment.len(),
+                        MAX_CONSENSUS_COMMITMENT_LENGTH
+                    );
+                }
+                Ok(commitment)
+            })
+            .collect()
+    }
+}
+
+/// Output sending an NFT of `category` to `address`, with the minimum non-dust value.
+pub fn nft_output(
+    category: &TokenID,
+    address: &str,
+    capability: NftCapability,
+    commitment: &[u8],
+) -> Result<TxOutputSnapshot> {
+    let mut bitfield = Structure::HasNFT as u8 | Capability::from(capability) as u8;
+    if !commitment.is_empty() {
+        bitfield |= Structure::HasCommitmentLength as u8;
+    }
+    let txout = TxOut {
+        value: 0,
+        script_pubkey: cash_addr_to_script(address)?,
+        token: Some(OutputData {
+            id: *category,
+            bitfield,
+            amount: 0,
+            commitment: commitment.to_vec(),
+        }),
+    };
+    Ok(TxOutputSnapshot {
+        value: dust_threshold(&txout),
+        script_pubkey: ScriptInputValue::Addr(address.to_string()),
+        script_display_format: ScriptDisplayFormat::TokenAddr,
+        token_data: TokenDataSnapshot {
+            cashtoken_enabled: true,
+            category_id: category.to_hex(),
+            has_nft: true,
+            nft_capability: capability,
+            nft_commitment_hex: commitment.to_hex(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// An output per NFT of the series, all sent to `address`.
+pub fn build(
+    category: &TokenID,
+    series: &Series,
+    capability: NftCapability,
+    address: &str,
+) -> Result<Vec<TxOutputSnapshot>> {
+    if address.trim().is_empty() {
+        bail!("Enter the address receiving the NFTs");
+    }
+    series
+        .commitments()?
+        .iter()
+        .map(|commitment| nft_output(category, address.trim(), capability, commitment))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{hashes::hex::FromHex, TokenID};
+
+    use super::{build, IndexEncoding, Series};
+    use crate::components::token_data::NftCapability;
+
+    const ADDRESS: &str = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+    const CATEGORY: &str = "efc44ce628940675b075d0e0059b9ddd165499a0656831f31f4f0adddb3bdd55";
+
+    #[test]
+    fn test_encode_index() {
+        assert_eq!(IndexEncoding::Number.encode(0, 4).unwrap(), b"");
+        assert_eq!(IndexEncoding::Number.encode(128, 0).unwrap(), [0x80, 0]);
+        assert_eq!(IndexEncoding::BigEndian.encode(258, 0).unwrap(), [1, 2]);
+        assert_eq!(
+            IndexEncoding::BigEndian.encode(258, 4).unwrap(),
+            [0, 0, 1, 2]
+        );
+        assert!(IndexEncoding::BigEndian.encode(258, 1).is_err());
+        assert_eq!(IndexEncoding::Decimal.encode(7, 3).unwrap(), b"007");
+        assert_eq!(IndexEncoding::Decimal.encode(1234, 0).unwrap(), b"1234");
+    }
+
+    #[test]
+    fn test_build() {
+        let category = TokenID::from_hex(CATEGORY).unwrap();
+        let series = Series {
+            prefix: b"card-".to_vec(),
+            start: 9,
+            count: 3,
+            encoding: IndexEncoding::Decimal,
+            width: 2,
+        };
+        let outputs = build(&category, &series, NftCapability::Immutable, ADDRESS).unwrap();
+        let commitments = outputs
+            .iter()
+            .map(|o| o.token_data.nft_commitment_hex.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commitments,
+            vec!["636172642d3039", "636172642d3130", "636172642d3131"]
+        );
+        assert!(outputs.iter().all(|o| o.value > 546));
+        assert!(build(&category, &series, NftCapability::Immutable, "").is_err());
+
+        let too_long = Series {
+            prefix: vec![0; 39],
+            ..series.clone()
+        };
+        assert!(too_long.commitments().is_err());
+        let overflowing = Series {
+            start: u64::MAX,
+            count: 2,
+            ..series.clone()
+        };
+        assert!(overflowing.commitments().is_err());
+        let empty = Series { count: 0, ..series };
+        assert!(empty.commitments().is_err());
+    }
+}
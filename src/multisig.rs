@@ -0,0 +1,175 @@
+//! P2SH multisig setups, as Electron Cash creates them.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{
+    blockdata::{opcodes::all::OP_CHECKMULTISIG, script::Builder},
+    consensus::Encodable,
+    secp256k1::{Secp256k1, Verification},
+    util::bip32::{ChildNumber, ExtendedPubKey},
+    PublicKey, Script,
+};
+
+use crate::partially_signed::UnsignedScriptSig;
+
+/// A cosigner of a multisig, either a plain public key or an extended public key with the
+/// derivation path of the key used.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Cosigner {
+    Pubkey(PublicKey),
+    Xpub {
+        xpub: ExtendedPubKey,
+        path: Vec<u32>,
+    },
+}
+
+impl FromStr for Cosigner {
+    type Err = anyhow::Error;
+
+    /// Accepts a hex public key, or an xpub followed by a non-hardened path such as
+    /// `xpub.../0/5`. The path defaults to `0/0`, the first receiving address.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(pubkey) = s.parse::<PublicKey>() {
+            return Ok(Self::Pubkey(pubkey));
+        }
+        let mut parts = s.split('/');
+        let xpub = parts
+            .next()
+            .unwrap_or_default()
+            .parse::<ExtendedPubKey>()
+            .context("Expected a public key or an xpub")?;
+        let mut path = parts
+            .map(|n| n.parse::<u32>().context("Invalid derivation path"))
+            .collect::<Result<Vec<_>>>()?;
+        if path.is_empty() {
+            path = vec![0, 0];
+        }
+        if path.iter().any(|n| *n >= 1 << 31) {
+            bail!("Hardened derivation is not possible from an xpub");
+        }
+        Ok(Self::Xpub { xpub, path })
+    }
+}
+
+impl Cosigner {
+    pub fn pubkey<C: Verification>(&self, secp: &Secp256k1<C>) -> Result<PublicKey> {
+        match self {
+            Self::Pubkey(pubkey) => Ok(*pubkey),
+            Self::Xpub { xpub, path } => {
+                let path = path
+                    .iter()
+                    .map(|&index| ChildNumber::Normal { index })
+                    .collect::<Vec<_>>();
+                Ok(xpub.derive_pub(secp, &path)?.to_pub())
+            }
+        }
+    }
+
+    /// The key as Electron Cash writes it into unsigned transactions, so it can later find the
+    /// private key to sign with.
+    pub fn x_pubkey(&self) -> Vec<u8> {
+        match self {
+            Self::Pubkey(pubkey) => pubkey.to_bytes(),
+            Self::Xpub { xpub, path } => {
+                let mut result = vec![0xff];
+                result.extend_from_slice(&xpub.encode());
+                for &n in path {
+                    if n < 0xffff {
+                        (n as u16).consensus_encode(&mut result).unwrap();
+                    } else {
+                        0xffffu16.consensus_encode(&mut result).unwrap();
+                        n.consensus_encode(&mut result).unwrap();
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+/// The m-of-n multisig setup of a P2SH address, with keys sorted like Electron Cash does.
+pub struct Multisig {
+    pub m: usize,
+    /// Cosigners and their public keys, sorted by public key.
+    pub cosigners: Vec<(Cosigner, PublicKey)>,
+}
+
+impl Multisig {
+    pub fn new<C: Verification>(
+        secp: &Secp256k1<C>,
+        m: usize,
+        cosigners: Vec<Cosigner>,
+    ) -> Result<Self> {
+        if m == 0 || m > cosigners.len() || cosigners.len() > 16 {
+            bail!("Invalid {m}-of-{} multisig", cosigners.len());
+        }
+        let mut cosigners = cosigners
+            .into_iter()
+            .map(|c| {
+                let pubkey = c.pubkey(secp)?;
+                Ok((c, pubkey))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        cosigners.sort_by_key(|(_, pubkey)| pubkey.to_bytes());
+        Ok(Self { m, cosigners })
+    }
+
+    fn script(&self, push_key: impl Fn(Builder, &(Cosigner, PublicKey)) -> Builder) -> Script {
+        let mut builder = Builder::new().push_int(self.m as i64);
+        for cosigner in &self.cosigners {
+            builder = push_key(builder, cosigner);
+        }
+        builder
+            .push_int(self.cosigners.len() as i64)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script()
+    }
+
+    pub fn redeem_script(&self) -> Script {
+        self.script(|builder, (_, pubkey)| builder.push_key(pubkey))
+    }
+
+    /// Script sig of an input spending this multisig, with all signatures missing.
+    pub fn unsigned_script_sig(&self) -> UnsignedScriptSig {
+        let fake_redeem_script =
+            self.script(|builder, (cosigner, _)| builder.push_slice(&cosigner.x_pubkey()));
+        let mut builder = Builder::new().push_slice(&[]);
+        for _ in 0..self.m {
+            builder = builder.push_slice(&[0xff]);
+        }
+        UnsignedScriptSig::from_raw_script(
+            builder
+                .push_slice(fake_redeem_script.as_bytes())
+                .into_script(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::secp256k1::Secp256k1;
+
+    use super::{Cosigner, Multisig};
+
+    #[test]
+    fn test_unsigned_script_sig() {
+        let secp = Secp256k1::verification_only();
+        let cosigners = [
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8/0/3",
+            "030a72c3eb8d023aa1638587293e427819265fd307db1d67de8e5c4129f654bf49",
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ]
+        .map(|c| c.parse::<Cosigner>().unwrap());
+        let multisig = Multisig::new(&secp, 2, cosigners.to_vec()).unwrap();
+        let p2sh = multisig.redeem_script().to_p2sh();
+        let script_sig = multisig.unsigned_script_sig();
+        assert_eq!(script_sig.script_pubkey(&secp), Some(p2sh));
+
+        assert!("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8/2147483648"
+            .parse::<Cosigner>()
+            .is_err());
+        assert!(Multisig::new(&secp, 4, cosigners.to_vec()).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Networks beyond the built-in ones, like private regtest-style chains, defined with their own
+//! CashAddr prefix and Electrum servers and kept across sessions.
+
+use anyhow::{bail, Result};
+use bitcoincash::Network;
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::chain::ServerKind;
+
+const NETWORKS_KEY: &str = "bch-tx-editor.networks";
+const SELECTION_KEY: &str = "bch-tx-editor.network-selection";
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CustomNetwork {
+    pub name: String,
+    pub cash_addr_prefix: String,
+    /// Built-in network whose rules the chain follows, like its legacy address versions.
+    #[serde(with = "crate::preferences::network_str")]
+    pub base: Network,
+    /// WebSocket URLs of its Electrum servers, the first one is used.
+    pub electrum_servers: Vec<String>,
+}
+
+impl CustomNetwork {
+    /// Check the definition before it's added to `existing`.
+    pub fn validate(&self, existing: &[CustomNetwork]) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("The network needs a name");
+        }
+        if existing.iter().any(|n| n.name == self.name) {
+            bail!("There already is a network named {}", self.name);
+        }
+        // The CashAddr checksum covers the prefix as lowercase letters and digits
+        let prefix = &self.cash_addr_prefix;
+        if prefix.is_empty()
+            || !prefix
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        {
+            bail!("The CashAddr prefix must be lowercase letters and digits");
+        }
+        if let Some(url) = self
+            .electrum_servers
+            .iter()
+            .find(|url| !url.starts_with("ws://") && !url.starts_with("wss://"))
+        {
+            bail!("{url} isn't a WebSocket URL, starting with wss:// or ws://");
+        }
+        Ok(())
+    }
+}
+
+/// The network and server last chosen, restored on load in place of the default network.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Selection {
+    /// The value of the network dropdown, a built-in network or a prefixed custom one.
+    pub network: String,
+    /// Electrum server chosen in place of the network's own, empty for none.
+    pub electrum_server: String,
+    pub server_kind: ServerKind,
+    /// Base URL of the REST API used when `server_kind` is [`ServerKind::Rest`].
+    pub rest_url: String,
+    /// URL of the node's JSON-RPC proxy used when `server_kind` is [`ServerKind::Node`].
+    pub node_url: String,
+}
+
+impl Selection {
+    pub fn load() -> Option<Self> {
+        LocalStorage::get(SELECTION_KEY).ok()
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = LocalStorage::set(SELECTION_KEY, self) {
+            error!("Saving the network selection failed: {e}");
+        }
+    }
+}
+
+pub fn load() -> Vec<CustomNetwork> {
+    LocalStorage::get(NETWORKS_KEY).unwrap_or_default()
+}
+
+pub fn save(networks: &[CustomNetwork]) {
+    if let Err(e) = LocalStorage::set(NETWORKS_KEY, networks) {
+        error!("Saving networks failed: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::Network;
+
+    use super::CustomNetwork;
+
+    #[test]
+    fn test_validate() {
+        let network = CustomNetwork {
+            name: "Private".to_string(),
+            cash_addr_prefix: "bchpriv".to_string(),
+            base: Network::Regtest,
+            electrum_servers: vec!["ws://localhost:60003".to_string()],
+        };
+        assert!(network.validate(&[]).is_ok());
+        assert!(network.validate(&[network.clone()]).is_err());
+        for prefix in ["", "BCHPRIV", "bch:priv"] {
+            let invalid = CustomNetwork {
+                cash_addr_prefix: prefix.to_string(),
+                ..network.clone()
+            };
+            assert!(invalid.validate(&[]).is_err());
+        }
+        let invalid = CustomNetwork {
+            electrum_servers: vec!["localhost:60003".to_string()],
+            ..network.clone()
+        };
+        assert!(invalid.validate(&[]).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+//! Offline mode, in which nothing is sent over the network, so the editor can be trusted on an
+//! airgapped signing machine.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{bail, Result};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub fn set(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Fail while offline mode is on. Checked before every request, so a feature the UI fails to
+/// disable still can't reach the network.
+pub fn ensure_online() -> Result<()> {
+    if OFFLINE.load(Ordering::Relaxed) {
+        bail!("Offline mode is on");
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! Building OP_RETURN data carrier scripts from a list of pushes.
+
+use anyhow::{bail, Result};
+use bitcoincash::{
+    blockdata::{
+        opcodes::all::*,
+        script::{Builder, Instruction},
+    },
+    hashes::hex::{FromHex, ToHex},
+    Script,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::util::push_minimal;
+use crate::vm_number;
+
+/// Largest OP_RETURN locking script relayed under standardness rules, including the OP_RETURN.
+pub const MAX_STANDARD_OP_RETURN_SIZE: usize = 223;
+
+str_enum! {
+    #[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum PushFormat {
+        #[default]
+        Utf8 = "utf8",
+        Hex = "hex",
+        Number = "number",
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PushField {
+    pub format: PushFormat,
+    pub value: String,
+}
+
+impl PushField {
+    pub fn data(&self) -> Result<Vec<u8>> {
+        Ok(match self.format {
+            PushFormat::Utf8 => self.value.as_bytes().to_vec(),
+            PushFormat::Hex => {
+                let mut hex = self.value.clone();
+                hex.retain(|c| !c.is_ascii_whitespace());
+                Vec::from_hex(&hex)?
+            }
+            PushFormat::Number => vm_number::encode(&self.value)?,
+        })
+    }
+}
+
+pub fn build_script(fields: &[PushField]) -> Result<Script> {
+    let mut builder = Builder::new().push_opcode(OP_RETURN);
+    for (i, field) in fields.iter().enumerate() {
+        let data = field
+            .data()
+            .map_err(|e| anyhow::anyhow!("Field {}: {e}", i + 1))?;
+        builder = push_minimal(builder, &data);
+    }
+    Ok(builder.into_script())
+}
+
+/// Split an OP_RETURN script into hex fields, `None` if it isn't OP_RETURN followed by pushes.
+pub fn parse_script(script: &Script) -> Option<Vec<PushField>> {
+    let mut instructions = script.instructions_minimal();
+    if instructions.next()?.ok()? != Instruction::Op(OP_RETURN) {
+        return None;
+    }
+    let mut fields = Vec::new();
+    for ins in instructions {
+        let data = match ins.ok()? {
+            Instruction::PushBytes(data) => data.to_vec(),
+            Instruction::Op(op) if op == OP_PUSHNUM_NEG1 => vec![0x81],
+            Instruction::Op(op)
+                if (OP_PUSHNUM_1.to_u8()..=OP_PUSHNUM_16.to_u8()).contains(&op.to_u8()) =>
+            {
+                vec![op.to_u8() - OP_PUSHNUM_1.to_u8() + 1]
+            }
+            Instruction::Op(_) => return None,
+        };
+        fields.push(PushField {
+            format: PushFormat::Hex,
+            value: data.to_hex(),
+        });
+    }
+    Some(fields)
+}
+
+pub fn check_size(script: &Script) -> Result<()> {
+    if script.len() > MAX_STANDARD_OP_RETURN_SIZE {
+        bail!(
+            "{} bytes exceeds the standard limit of {MAX_STANDARD_OP_RETURN_SIZE}",
+            script.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::hashes::hex::ToHex;
+
+    use super::{build_script, parse_script, PushField, PushFormat};
+
+    fn field(format: PushFormat, value: &str) -> PushField {
+        PushField {
+            format,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_and_parse() {
+        let script = build_script(&[
+            field(PushFormat::Hex, "534c5000"),
+            field(PushFormat::Utf8, "hi"),
+            field(PushFormat::Number, "5"),
+            field(PushFormat::Number, "1000"),
+            field(PushFormat::Hex, ""),
+        ])
+        .unwrap();
+        assert_eq!(script.to_hex(), "6a04534c50000268695502e80300");
+
+        let fields = parse_script(&script).unwrap();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(build_script(&fields).unwrap(), script);
+
+        assert!(build_script(&[field(PushFormat::Hex, "zz")]).is_err());
+        assert!(parse_script(&build_script(&[]).unwrap())
+            .unwrap()
+            .is_empty());
+    }
+}
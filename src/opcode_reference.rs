@@ -0,0 +1,925 @@
+//! Reference documentation of the BCH opcodes, shown when pointing at them in ASM, so the editor
+//! doubles as a way to learn Script.
+
+/// The network upgrade an opcode got its current meaning in.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Upgrade {
+    Original,
+    Bip65,
+    Bip112,
+    May2018,
+    Nov2018,
+    May2020,
+    May2022,
+    May2023,
+    May2026,
+}
+
+impl Upgrade {
+    pub fn label(self) -> &'static str {
+        match self {
+            Upgrade::Original => "the original protocol",
+            Upgrade::Bip65 => "BIP65 (December 2015)",
+            Upgrade::Bip112 => "BIP112 (July 2016)",
+            Upgrade::May2018 => "the May 2018 upgrade",
+            Upgrade::Nov2018 => "the November 2018 upgrade",
+            Upgrade::May2020 => "the May 2020 upgrade",
+            Upgrade::May2022 => "the May 2022 upgrade",
+            Upgrade::May2023 => "the May 2023 upgrade (CashTokens)",
+            Upgrade::May2026 => "the May 2026 upgrade (loops, functions and bitwise)",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct OpcodeDoc {
+    pub name: &'static str,
+    pub number: u8,
+    pub upgrade: Upgrade,
+    pub description: &'static str,
+}
+
+/// Pushes of 1 to 75 bytes, whose opcode is their length.
+const PUSHBYTES: OpcodeDoc = OpcodeDoc {
+    name: "OP_PUSHBYTES_N",
+    number: 0x01,
+    upgrade: Upgrade::Original,
+    description: "Push the bytes that follow, as many as the number of the opcode.",
+};
+
+/// Every named opcode, by number, then those of the upcoming upgrade.
+pub const OPCODES: &[OpcodeDoc] = &[
+    OpcodeDoc {
+        name: "OP_0",
+        number: 0x00,
+        upgrade: Upgrade::Original,
+        description: "Push an empty item, which counts as zero and false.",
+    },
+    OpcodeDoc {
+        name: "OP_PUSHDATA_1",
+        number: 0x4c,
+        upgrade: Upgrade::Original,
+        description: "Push the number of bytes given by the next byte.",
+    },
+    OpcodeDoc {
+        name: "OP_PUSHDATA_2",
+        number: 0x4d,
+        upgrade: Upgrade::Original,
+        description: "Push the number of bytes given by the next two bytes, little-endian.",
+    },
+    OpcodeDoc {
+        name: "OP_PUSHDATA_4",
+        number: 0x4e,
+        upgrade: Upgrade::Original,
+        description: "Push the number of bytes given by the next four bytes, little-endian.",
+    },
+    OpcodeDoc {
+        name: "OP_1NEGATE",
+        number: 0x4f,
+        upgrade: Upgrade::Original,
+        description: "Push the number -1.",
+    },
+    OpcodeDoc {
+        name: "OP_RESERVED",
+        number: 0x50,
+        upgrade: Upgrade::Original,
+        description: "Reserved, fails the script if executed.",
+    },
+    OpcodeDoc {
+        name: "OP_1",
+        number: 0x51,
+        upgrade: Upgrade::Original,
+        description: "Push the number 1.",
+    },
+    OpcodeDoc {
+        name: "OP_2",
+        number: 0x52,
+        upgrade: Upgrade::Original,
+        description: "Push the number 2.",
+    },
+    OpcodeDoc {
+        name: "OP_3",
+        number: 0x53,
+        upgrade: Upgrade::Original,
+        description: "Push the number 3.",
+    },
+    OpcodeDoc {
+        name: "OP_4",
+        number: 0x54,
+        upgrade: Upgrade::Original,
+        description: "Push the number 4.",
+    },
+    OpcodeDoc {
+        name: "OP_5",
+        number: 0x55,
+        upgrade: Upgrade::Original,
+        description: "Push the number 5.",
+    },
+    OpcodeDoc {
+        name: "OP_6",
+        number: 0x56,
+        upgrade: Upgrade::Original,
+        description: "Push the number 6.",
+    },
+    OpcodeDoc {
+        name: "OP_7",
+        number: 0x57,
+        upgrade: Upgrade::Original,
+        description: "Push the number 7.",
+    },
+    OpcodeDoc {
+        name: "OP_8",
+        number: 0x58,
+        upgrade: Upgrade::Original,
+        description: "Push the number 8.",
+    },
+    OpcodeDoc {
+        name: "OP_9",
+        number: 0x59,
+        upgrade: Upgrade::Original,
+        description: "Push the number 9.",
+    },
+    OpcodeDoc {
+        name: "OP_10",
+        number: 0x5a,
+        upgrade: Upgrade::Original,
+        description: "Push the number 10.",
+    },
+    OpcodeDoc {
+        name: "OP_11",
+        number: 0x5b,
+        upgrade: Upgrade::Original,
+        description: "Push the number 11.",
+    },
+    OpcodeDoc {
+        name: "OP_12",
+        number: 0x5c,
+        upgrade: Upgrade::Original,
+        description: "Push the number 12.",
+    },
+    OpcodeDoc {
+        name: "OP_13",
+        number: 0x5d,
+        upgrade: Upgrade::Original,
+        description: "Push the number 13.",
+    },
+    OpcodeDoc {
+        name: "OP_14",
+        number: 0x5e,
+        upgrade: Upgrade::Original,
+        description: "Push the number 14.",
+    },
+    OpcodeDoc {
+        name: "OP_15",
+        number: 0x5f,
+        upgrade: Upgrade::Original,
+        description: "Push the number 15.",
+    },
+    OpcodeDoc {
+        name: "OP_16",
+        number: 0x60,
+        upgrade: Upgrade::Original,
+        description: "Push the number 16.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP",
+        number: 0x61,
+        upgrade: Upgrade::Original,
+        description: "Do nothing.",
+    },
+    OpcodeDoc {
+        name: "OP_VER",
+        number: 0x62,
+        upgrade: Upgrade::Original,
+        description: "Reserved, fails the script if executed.",
+    },
+    OpcodeDoc {
+        name: "OP_IF",
+        number: 0x63,
+        upgrade: Upgrade::Original,
+        description: "Pop an item and run the statements up to OP_ELSE or OP_ENDIF if it's true.",
+    },
+    OpcodeDoc {
+        name: "OP_NOTIF",
+        number: 0x64,
+        upgrade: Upgrade::Original,
+        description: "Pop an item and run the statements up to OP_ELSE or OP_ENDIF if it's false.",
+    },
+    OpcodeDoc {
+        name: "OP_BEGIN",
+        number: 0x65,
+        upgrade: Upgrade::May2026,
+        description: "Begin a loop, repeated until OP_UNTIL pops a true item.",
+    },
+    OpcodeDoc {
+        name: "OP_UNTIL",
+        number: 0x66,
+        upgrade: Upgrade::May2026,
+        description: "Pop an item and go back to the matching OP_BEGIN unless it's true.",
+    },
+    OpcodeDoc {
+        name: "OP_ELSE",
+        number: 0x67,
+        upgrade: Upgrade::Original,
+        description: "Run the following statements if the preceding ones of OP_IF or OP_NOTIF weren't.",
+    },
+    OpcodeDoc {
+        name: "OP_ENDIF",
+        number: 0x68,
+        upgrade: Upgrade::Original,
+        description: "End an OP_IF or OP_NOTIF block.",
+    },
+    OpcodeDoc {
+        name: "OP_VERIFY",
+        number: 0x69,
+        upgrade: Upgrade::Original,
+        description: "Pop an item and fail the script unless it's true.",
+    },
+    OpcodeDoc {
+        name: "OP_RETURN",
+        number: 0x6a,
+        upgrade: Upgrade::Original,
+        description: "Fail the script, making the output unspendable, which data carrier outputs use.",
+    },
+    OpcodeDoc {
+        name: "OP_TOALTSTACK",
+        number: 0x6b,
+        upgrade: Upgrade::Original,
+        description: "Move the top item to the alt stack.",
+    },
+    OpcodeDoc {
+        name: "OP_FROMALTSTACK",
+        number: 0x6c,
+        upgrade: Upgrade::Original,
+        description: "Move the top item of the alt stack back to the stack.",
+    },
+    OpcodeDoc {
+        name: "OP_2DROP",
+        number: 0x6d,
+        upgrade: Upgrade::Original,
+        description: "Drop the top two items.",
+    },
+    OpcodeDoc {
+        name: "OP_2DUP",
+        number: 0x6e,
+        upgrade: Upgrade::Original,
+        description: "Duplicate the top two items: a b → a b a b.",
+    },
+    OpcodeDoc {
+        name: "OP_3DUP",
+        number: 0x6f,
+        upgrade: Upgrade::Original,
+        description: "Duplicate the top three items: a b c → a b c a b c.",
+    },
+    OpcodeDoc {
+        name: "OP_2OVER",
+        number: 0x70,
+        upgrade: Upgrade::Original,
+        description: "Copy the pair of items below the top pair: a b c d → a b c d a b.",
+    },
+    OpcodeDoc {
+        name: "OP_2ROT",
+        number: 0x71,
+        upgrade: Upgrade::Original,
+        description: "Move the fifth and sixth items to the top: a b c d e f → c d e f a b.",
+    },
+    OpcodeDoc {
+        name: "OP_2SWAP",
+        number: 0x72,
+        upgrade: Upgrade::Original,
+        description: "Swap the top two pairs of items: a b c d → c d a b.",
+    },
+    OpcodeDoc {
+        name: "OP_IFDUP",
+        number: 0x73,
+        upgrade: Upgrade::Original,
+        description: "Duplicate the top item if it's true.",
+    },
+    OpcodeDoc {
+        name: "OP_DEPTH",
+        number: 0x74,
+        upgrade: Upgrade::Original,
+        description: "Push the number of items on the stack.",
+    },
+    OpcodeDoc {
+        name: "OP_DROP",
+        number: 0x75,
+        upgrade: Upgrade::Original,
+        description: "Drop the top item.",
+    },
+    OpcodeDoc {
+        name: "OP_DUP",
+        number: 0x76,
+        upgrade: Upgrade::Original,
+        description: "Duplicate the top item.",
+    },
+    OpcodeDoc {
+        name: "OP_NIP",
+        number: 0x77,
+        upgrade: Upgrade::Original,
+        description: "Drop the item below the top: a b → b.",
+    },
+    OpcodeDoc {
+        name: "OP_OVER",
+        number: 0x78,
+        upgrade: Upgrade::Original,
+        description: "Copy the item below the top: a b → a b a.",
+    },
+    OpcodeDoc {
+        name: "OP_PICK",
+        number: 0x79,
+        upgrade: Upgrade::Original,
+        description: "Pop n and copy the item n deep to the top.",
+    },
+    OpcodeDoc {
+        name: "OP_ROLL",
+        number: 0x7a,
+        upgrade: Upgrade::Original,
+        description: "Pop n and move the item n deep to the top.",
+    },
+    OpcodeDoc {
+        name: "OP_ROT",
+        number: 0x7b,
+        upgrade: Upgrade::Original,
+        description: "Move the third item to the top: a b c → b c a.",
+    },
+    OpcodeDoc {
+        name: "OP_SWAP",
+        number: 0x7c,
+        upgrade: Upgrade::Original,
+        description: "Swap the top two items.",
+    },
+    OpcodeDoc {
+        name: "OP_TUCK",
+        number: 0x7d,
+        upgrade: Upgrade::Original,
+        description: "Copy the top item below the second: a b → b a b.",
+    },
+    OpcodeDoc {
+        name: "OP_CAT",
+        number: 0x7e,
+        upgrade: Upgrade::May2018,
+        description: "Concatenate the top two items: a b → ab.",
+    },
+    OpcodeDoc {
+        name: "OP_SPLIT",
+        number: 0x7f,
+        upgrade: Upgrade::May2018,
+        description: "Pop n and split the item below at byte n into two.",
+    },
+    OpcodeDoc {
+        name: "OP_NUM2BIN",
+        number: 0x80,
+        upgrade: Upgrade::May2018,
+        description: "Pop a size and pad the number below to that many bytes.",
+    },
+    OpcodeDoc {
+        name: "OP_BIN2NUM",
+        number: 0x81,
+        upgrade: Upgrade::May2018,
+        description: "Convert the top item to a minimally encoded number.",
+    },
+    OpcodeDoc {
+        name: "OP_SIZE",
+        number: 0x82,
+        upgrade: Upgrade::Original,
+        description: "Push the length in bytes of the top item, keeping it.",
+    },
+    OpcodeDoc {
+        name: "OP_INVERT",
+        number: 0x83,
+        upgrade: Upgrade::Original,
+        description: "Disabled, fails the script if executed.",
+    },
+    OpcodeDoc {
+        name: "OP_AND",
+        number: 0x84,
+        upgrade: Upgrade::May2018,
+        description: "Bitwise AND of the top two items, of equal length.",
+    },
+    OpcodeDoc {
+        name: "OP_OR",
+        number: 0x85,
+        upgrade: Upgrade::May2018,
+        description: "Bitwise OR of the top two items, of equal length.",
+    },
+    OpcodeDoc {
+        name: "OP_XOR",
+        number: 0x86,
+        upgrade: Upgrade::May2018,
+        description: "Bitwise XOR of the top two items, of equal length.",
+    },
+    OpcodeDoc {
+        name: "OP_EQUAL",
+        number: 0x87,
+        upgrade: Upgrade::Original,
+        description: "Pop two items and push 1 if they're byte for byte equal, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_EQUALVERIFY",
+        number: 0x88,
+        upgrade: Upgrade::Original,
+        description: "OP_EQUAL then OP_VERIFY.",
+    },
+    OpcodeDoc {
+        name: "OP_DEFINE",
+        number: 0x89,
+        upgrade: Upgrade::May2026,
+        description: "Pop a function number and the bytecode below it, defining the function.",
+    },
+    OpcodeDoc {
+        name: "OP_INVOKE",
+        number: 0x8a,
+        upgrade: Upgrade::May2026,
+        description: "Pop a function number and run the bytecode defined for it.",
+    },
+    OpcodeDoc {
+        name: "OP_1ADD",
+        number: 0x8b,
+        upgrade: Upgrade::Original,
+        description: "Add 1 to the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_1SUB",
+        number: 0x8c,
+        upgrade: Upgrade::Original,
+        description: "Subtract 1 from the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_LSHIFTNUM",
+        number: 0x8d,
+        upgrade: Upgrade::May2026,
+        description: "Pop n and multiply the number below by 2 to the power of n.",
+    },
+    OpcodeDoc {
+        name: "OP_RSHIFTNUM",
+        number: 0x8e,
+        upgrade: Upgrade::May2026,
+        description: "Pop n and divide the number below by 2 to the power of n, rounding down.",
+    },
+    OpcodeDoc {
+        name: "OP_NEGATE",
+        number: 0x8f,
+        upgrade: Upgrade::Original,
+        description: "Negate the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_ABS",
+        number: 0x90,
+        upgrade: Upgrade::Original,
+        description: "Replace the top number with its absolute value.",
+    },
+    OpcodeDoc {
+        name: "OP_NOT",
+        number: 0x91,
+        upgrade: Upgrade::Original,
+        description: "Replace the top number with 1 if it's 0, or 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_0NOTEQUAL",
+        number: 0x92,
+        upgrade: Upgrade::Original,
+        description: "Replace the top number with 0 if it's 0, or 1 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_ADD",
+        number: 0x93,
+        upgrade: Upgrade::Original,
+        description: "Add the top two numbers: a b → a+b.",
+    },
+    OpcodeDoc {
+        name: "OP_SUB",
+        number: 0x94,
+        upgrade: Upgrade::Original,
+        description: "Subtract the top number from the one below: a b → a-b.",
+    },
+    OpcodeDoc {
+        name: "OP_MUL",
+        number: 0x95,
+        upgrade: Upgrade::May2022,
+        description: "Multiply the top two numbers: a b → a*b.",
+    },
+    OpcodeDoc {
+        name: "OP_DIV",
+        number: 0x96,
+        upgrade: Upgrade::May2018,
+        description: "Divide the second number by the top one, rounding towards zero: a b → a/b.",
+    },
+    OpcodeDoc {
+        name: "OP_MOD",
+        number: 0x97,
+        upgrade: Upgrade::May2018,
+        description: "Remainder of dividing the second number by the top one: a b → a%b.",
+    },
+    OpcodeDoc {
+        name: "OP_LSHIFTBIN",
+        number: 0x98,
+        upgrade: Upgrade::May2026,
+        description: "Pop n and shift the bits of the item below left by n, keeping its length.",
+    },
+    OpcodeDoc {
+        name: "OP_RSHIFTBIN",
+        number: 0x99,
+        upgrade: Upgrade::May2026,
+        description: "Pop n and shift the bits of the item below right by n, keeping its length.",
+    },
+    OpcodeDoc {
+        name: "OP_BOOLAND",
+        number: 0x9a,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if both top numbers are non-zero, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_BOOLOR",
+        number: 0x9b,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if either of the top numbers is non-zero, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_NUMEQUAL",
+        number: 0x9c,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if the top two numbers are equal, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_NUMEQUALVERIFY",
+        number: 0x9d,
+        upgrade: Upgrade::Original,
+        description: "OP_NUMEQUAL then OP_VERIFY.",
+    },
+    OpcodeDoc {
+        name: "OP_NUMNOTEQUAL",
+        number: 0x9e,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if the top two numbers differ, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_LESSTHAN",
+        number: 0x9f,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if a < b, 0 otherwise, b being the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_GREATERTHAN",
+        number: 0xa0,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if a > b, 0 otherwise, b being the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_LESSTHANOREQUAL",
+        number: 0xa1,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if a <= b, 0 otherwise, b being the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_GREATERTHANOREQUAL",
+        number: 0xa2,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if a >= b, 0 otherwise, b being the top number.",
+    },
+    OpcodeDoc {
+        name: "OP_MIN",
+        number: 0xa3,
+        upgrade: Upgrade::Original,
+        description: "Keep the smaller of the top two numbers.",
+    },
+    OpcodeDoc {
+        name: "OP_MAX",
+        number: 0xa4,
+        upgrade: Upgrade::Original,
+        description: "Keep the larger of the top two numbers.",
+    },
+    OpcodeDoc {
+        name: "OP_WITHIN",
+        number: 0xa5,
+        upgrade: Upgrade::Original,
+        description: "Push 1 if min <= x < max, 0 otherwise: x min max → result.",
+    },
+    OpcodeDoc {
+        name: "OP_RIPEMD160",
+        number: 0xa6,
+        upgrade: Upgrade::Original,
+        description: "Replace the top item with its RIPEMD-160 hash.",
+    },
+    OpcodeDoc {
+        name: "OP_SHA1",
+        number: 0xa7,
+        upgrade: Upgrade::Original,
+        description: "Replace the top item with its SHA-1 hash.",
+    },
+    OpcodeDoc {
+        name: "OP_SHA256",
+        number: 0xa8,
+        upgrade: Upgrade::Original,
+        description: "Replace the top item with its SHA-256 hash.",
+    },
+    OpcodeDoc {
+        name: "OP_HASH160",
+        number: 0xa9,
+        upgrade: Upgrade::Original,
+        description: "Replace the top item with the RIPEMD-160 of its SHA-256, as in P2PKH and P2SH.",
+    },
+    OpcodeDoc {
+        name: "OP_HASH256",
+        number: 0xaa,
+        upgrade: Upgrade::Original,
+        description: "Replace the top item with its double SHA-256.",
+    },
+    OpcodeDoc {
+        name: "OP_CODESEPARATOR",
+        number: 0xab,
+        upgrade: Upgrade::Original,
+        description: "Make signatures cover only the bytecode after this point.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKSIG",
+        number: 0xac,
+        upgrade: Upgrade::Original,
+        description: "Pop a public key and a signature, and push 1 if the signature of the transaction is valid, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKSIGVERIFY",
+        number: 0xad,
+        upgrade: Upgrade::Original,
+        description: "OP_CHECKSIG then OP_VERIFY.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKMULTISIG",
+        number: 0xae,
+        upgrade: Upgrade::Original,
+        description: "Pop n public keys and m signatures, each counts first, and push 1 if the signatures match keys in order, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKMULTISIGVERIFY",
+        number: 0xaf,
+        upgrade: Upgrade::Original,
+        description: "OP_CHECKMULTISIG then OP_VERIFY.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP1",
+        number: 0xb0,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKLOCKTIMEVERIFY",
+        number: 0xb1,
+        upgrade: Upgrade::Bip65,
+        description: "Fail unless the transaction's locktime has reached the top number, keeping it.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKSEQUENCEVERIFY",
+        number: 0xb2,
+        upgrade: Upgrade::Bip112,
+        description: "Fail unless the input's relative locktime has reached the top number, keeping it.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP4",
+        number: 0xb3,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP5",
+        number: 0xb4,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP6",
+        number: 0xb5,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP7",
+        number: 0xb6,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP8",
+        number: 0xb7,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP9",
+        number: 0xb8,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_NOP10",
+        number: 0xb9,
+        upgrade: Upgrade::Original,
+        description: "Do nothing, reserved for upgrades.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKDATASIG",
+        number: 0xba,
+        upgrade: Upgrade::Nov2018,
+        description: "Pop a public key, a message and a signature, and push 1 if the signature of the message is valid, 0 otherwise.",
+    },
+    OpcodeDoc {
+        name: "OP_CHECKDATASIGVERIFY",
+        number: 0xbb,
+        upgrade: Upgrade::Nov2018,
+        description: "OP_CHECKDATASIG then OP_VERIFY.",
+    },
+    OpcodeDoc {
+        name: "OP_REVERSEBYTES",
+        number: 0xbc,
+        upgrade: Upgrade::May2020,
+        description: "Reverse the bytes of the top item, converting between endiannesses.",
+    },
+    OpcodeDoc {
+        name: "OP_INPUTINDEX",
+        number: 0xc0,
+        upgrade: Upgrade::May2022,
+        description: "Push the index of the input being evaluated.",
+    },
+    OpcodeDoc {
+        name: "OP_ACTIVEBYTECODE",
+        number: 0xc1,
+        upgrade: Upgrade::May2022,
+        description: "Push the bytecode being evaluated, from the last OP_CODESEPARATOR.",
+    },
+    OpcodeDoc {
+        name: "OP_TXVERSION",
+        number: 0xc2,
+        upgrade: Upgrade::May2022,
+        description: "Push the version of the transaction.",
+    },
+    OpcodeDoc {
+        name: "OP_TXINPUTCOUNT",
+        number: 0xc3,
+        upgrade: Upgrade::May2022,
+        description: "Push the number of inputs of the transaction.",
+    },
+    OpcodeDoc {
+        name: "OP_TXOUTPUTCOUNT",
+        number: 0xc4,
+        upgrade: Upgrade::May2022,
+        description: "Push the number of outputs of the transaction.",
+    },
+    OpcodeDoc {
+        name: "OP_TXLOCKTIME",
+        number: 0xc5,
+        upgrade: Upgrade::May2022,
+        description: "Push the locktime of the transaction.",
+    },
+    OpcodeDoc {
+        name: "OP_UTXOVALUE",
+        number: 0xc6,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push the value in sats of the output it spends.",
+    },
+    OpcodeDoc {
+        name: "OP_UTXOBYTECODE",
+        number: 0xc7,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push the locking bytecode of the output it spends.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPOINTTXHASH",
+        number: 0xc8,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push the hash of the transaction it spends from.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPOINTINDEX",
+        number: 0xc9,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push the index of the output it spends.",
+    },
+    OpcodeDoc {
+        name: "OP_INPUTBYTECODE",
+        number: 0xca,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push its unlocking bytecode.",
+    },
+    OpcodeDoc {
+        name: "OP_INPUTSEQUENCENUMBER",
+        number: 0xcb,
+        upgrade: Upgrade::May2022,
+        description: "Pop an input index and push its sequence number.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPUTVALUE",
+        number: 0xcc,
+        upgrade: Upgrade::May2022,
+        description: "Pop an output index and push its value in sats.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPUTBYTECODE",
+        number: 0xcd,
+        upgrade: Upgrade::May2022,
+        description: "Pop an output index and push its locking bytecode.",
+    },
+    OpcodeDoc {
+        name: "OP_UTXOTOKENCATEGORY",
+        number: 0xce,
+        upgrade: Upgrade::May2023,
+        description: "Pop an input index and push the token category of the output it spends, followed by its NFT capability if not immutable.",
+    },
+    OpcodeDoc {
+        name: "OP_UTXOTOKENCOMMITMENT",
+        number: 0xcf,
+        upgrade: Upgrade::May2023,
+        description: "Pop an input index and push the NFT commitment of the output it spends.",
+    },
+    OpcodeDoc {
+        name: "OP_UTXOTOKENAMOUNT",
+        number: 0xd0,
+        upgrade: Upgrade::May2023,
+        description: "Pop an input index and push the fungible token amount of the output it spends.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPUTTOKENCATEGORY",
+        number: 0xd1,
+        upgrade: Upgrade::May2023,
+        description: "Pop an output index and push its token category, followed by its NFT capability if not immutable.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPUTTOKENCOMMITMENT",
+        number: 0xd2,
+        upgrade: Upgrade::May2023,
+        description: "Pop an output index and push its NFT commitment.",
+    },
+    OpcodeDoc {
+        name: "OP_OUTPUTTOKENAMOUNT",
+        number: 0xd3,
+        upgrade: Upgrade::May2023,
+        description: "Pop an output index and push its fungible token amount.",
+    },
+];
+
+/// The documentation of the opcode named `name`, as
+/// [`opcode_name_in`](crate::cash_assembly::opcode_name_in) writes it.
+pub fn lookup(name: &str) -> Option<OpcodeDoc> {
+    if let Some(number) = name
+        .strip_prefix("OP_PUSHBYTES_")
+        .and_then(|n| n.parse::<u8>().ok())
+        .filter(|n| (0x01..=0x4b).contains(n))
+    {
+        return Some(OpcodeDoc {
+            name: PUSHBYTES.name,
+            number,
+            ..PUSHBYTES
+        });
+    }
+    OPCODES.iter().find(|doc| doc.name == name).copied()
+}
+
+/// One line documenting the opcode named `name`, for tooltips.
+pub fn tooltip(name: &str) -> Option<String> {
+    let doc = lookup(name)?;
+    Some(format!(
+        "{name} (0x{:02x}): {} Since {}.",
+        doc.number,
+        doc.description,
+        doc.upgrade.label()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup, tooltip, OPCODES};
+    use crate::cash_assembly::{opcode_name_in, InstructionSet};
+
+    #[test]
+    fn test_opcodes() {
+        for doc in OPCODES {
+            assert_eq!(
+                opcode_name_in(doc.number, InstructionSet::Current),
+                doc.name
+            );
+        }
+        // Every opcode but the pushes of a given size is documented
+        for set in [InstructionSet::Current, InstructionSet::Upcoming] {
+            for byte in (0..=u8::MAX).filter(|b| !(0x01..=0x4b).contains(b)) {
+                let name = opcode_name_in(byte, set);
+                assert_eq!(
+                    lookup(&name).is_some(),
+                    !name.starts_with("OP_UNKNOWN"),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tooltip() {
+        assert_eq!(
+            tooltip("OP_CAT").unwrap(),
+            "OP_CAT (0x7e): Concatenate the top two items: a b → ab. Since the May 2018 upgrade."
+        );
+        assert_eq!(lookup("OP_PUSHBYTES_20").unwrap().number, 20);
+        assert!(tooltip("OP_PUSHBYTES_20")
+            .unwrap()
+            .starts_with("OP_PUSHBYTES_20 (0x14)"));
+        assert!(lookup("OP_PUSHBYTES_76").is_none());
+        assert!(lookup("OP_FOO").is_none());
+    }
+}
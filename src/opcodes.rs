@@ -0,0 +1,390 @@
+//! Opcodes offered by the script palette, and inserting them into CashAssembly.
+
+use std::ops::Range;
+
+use anyhow::Result;
+use bitcoincash::hashes::hex::ToHex;
+
+use crate::op_return::PushField;
+
+pub struct Category {
+    pub name: &'static str,
+    pub opcodes: &'static [&'static str],
+}
+
+/// The BCH opcodes, grouped like in the specification, leaving out disabled and reserved ones.
+pub const CATEGORIES: &[Category] = &[
+    Category {
+        name: "Constants",
+        opcodes: &[
+            "OP_0",
+            "OP_1NEGATE",
+            "OP_1",
+            "OP_2",
+            "OP_3",
+            "OP_4",
+            "OP_5",
+            "OP_6",
+            "OP_7",
+            "OP_8",
+            "OP_9",
+            "OP_10",
+            "OP_11",
+            "OP_12",
+            "OP_13",
+            "OP_14",
+            "OP_15",
+            "OP_16",
+        ],
+    },
+    Category {
+        name: "Flow control",
+        opcodes: &[
+            "OP_NOP",
+            "OP_IF",
+            "OP_NOTIF",
+            "OP_ELSE",
+            "OP_ENDIF",
+            "OP_BEGIN",
+            "OP_UNTIL",
+            "OP_DEFINE",
+            "OP_INVOKE",
+            "OP_VERIFY",
+            "OP_RETURN",
+        ],
+    },
+    Category {
+        name: "Stack",
+        opcodes: &[
+            "OP_TOALTSTACK",
+            "OP_FROMALTSTACK",
+            "OP_2DROP",
+            "OP_2DUP",
+            "OP_3DUP",
+            "OP_2OVER",
+            "OP_2ROT",
+            "OP_2SWAP",
+            "OP_IFDUP",
+            "OP_DEPTH",
+            "OP_DROP",
+            "OP_DUP",
+            "OP_NIP",
+            "OP_OVER",
+            "OP_PICK",
+            "OP_ROLL",
+            "OP_ROT",
+            "OP_SWAP",
+            "OP_TUCK",
+        ],
+    },
+    Category {
+        name: "Splice",
+        opcodes: &[
+            "OP_CAT",
+            "OP_SPLIT",
+            "OP_NUM2BIN",
+            "OP_BIN2NUM",
+            "OP_SIZE",
+            "OP_REVERSEBYTES",
+        ],
+    },
+    Category {
+        name: "Bitwise logic",
+        opcodes: &[
+            "OP_AND",
+            "OP_OR",
+            "OP_XOR",
+            "OP_LSHIFTBIN",
+            "OP_RSHIFTBIN",
+            "OP_EQUAL",
+            "OP_EQUALVERIFY",
+        ],
+    },
+    Category {
+        name: "Arithmetic",
+        opcodes: &[
+            "OP_1ADD",
+            "OP_1SUB",
+            "OP_NEGATE",
+            "OP_ABS",
+            "OP_NOT",
+            "OP_0NOTEQUAL",
+            "OP_ADD",
+            "OP_SUB",
+            "OP_MUL",
+            "OP_DIV",
+            "OP_MOD",
+            "OP_LSHIFTNUM",
+            "OP_RSHIFTNUM",
+            "OP_BOOLAND",
+            "OP_BOOLOR",
+            "OP_NUMEQUAL",
+            "OP_NUMEQUALVERIFY",
+            "OP_NUMNOTEQUAL",
+            "OP_LESSTHAN",
+            "OP_GREATERTHAN",
+            "OP_LESSTHANOREQUAL",
+            "OP_GREATERTHANOREQUAL",
+            "OP_MIN",
+            "OP_MAX",
+            "OP_WITHIN",
+        ],
+    },
+    Category {
+        name: "Crypto",
+        opcodes: &[
+            "OP_RIPEMD160",
+            "OP_SHA1",
+            "OP_SHA256",
+            "OP_HASH160",
+            "OP_HASH256",
+            "OP_CODESEPARATOR",
+            "OP_CHECKSIG",
+            "OP_CHECKSIGVERIFY",
+            "OP_CHECKMULTISIG",
+            "OP_CHECKMULTISIGVERIFY",
+            "OP_CHECKDATASIG",
+            "OP_CHECKDATASIGVERIFY",
+        ],
+    },
+    Category {
+        name: "Locktime",
+        opcodes: &["OP_CHECKLOCKTIMEVERIFY", "OP_CHECKSEQUENCEVERIFY"],
+    },
+    Category {
+        name: "Introspection",
+        opcodes: &[
+            "OP_INPUTINDEX",
+            "OP_ACTIVEBYTECODE",
+            "OP_TXVERSION",
+            "OP_TXINPUTCOUNT",
+            "OP_TXOUTPUTCOUNT",
+            "OP_TXLOCKTIME",
+            "OP_UTXOVALUE",
+            "OP_UTXOBYTECODE",
+            "OP_OUTPOINTTXHASH",
+            "OP_OUTPOINTINDEX",
+            "OP_INPUTBYTECODE",
+            "OP_INPUTSEQUENCENUMBER",
+            "OP_OUTPUTVALUE",
+            "OP_OUTPUTBYTECODE",
+        ],
+    },
+    Category {
+        name: "Tokens",
+        opcodes: &[
+            "OP_UTXOTOKENCATEGORY",
+            "OP_UTXOTOKENCOMMITMENT",
+            "OP_UTXOTOKENAMOUNT",
+            "OP_OUTPUTTOKENCATEGORY",
+            "OP_OUTPUTTOKENCOMMITMENT",
+            "OP_OUTPUTTOKENAMOUNT",
+        ],
+    },
+];
+
+/// CashAssembly pushing the data of `field`.
+pub fn push_asm(field: &PushField) -> Result<String> {
+    let data = field.data()?;
+    Ok(if data.is_empty() {
+        "OP_0".to_string()
+    } else {
+        format!("<0x{}>", data.to_hex())
+    })
+}
+
+/// Byte index in `s` of the DOM caret position `caret`, which counts UTF-16 code units.
+fn byte_index(s: &str, caret: usize) -> usize {
+    let mut units = 0;
+    for (i, c) in s.char_indices() {
+        if units >= caret {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    s.len()
+}
+
+/// Insert `token` into `asm` at `caret`, separated from its neighbours by spaces. `caret` and the
+/// returned caret after the token count UTF-16 code units, like the DOM does.
+pub fn insert_token(asm: &str, caret: usize, token: &str) -> (String, usize) {
+    let (before, after) = asm.split_at(byte_index(asm, caret));
+    let mut result = before.to_string();
+    if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+        result.push(' ');
+    }
+    result.push_str(token);
+    let caret = result.encode_utf16().count();
+    if !after.is_empty() && !after.starts_with(char::is_whitespace) {
+        result.push(' ');
+    }
+    result.push_str(after);
+    (result, caret)
+}
+
+/// The opcode being typed in `asm` at `caret`: the letters, digits and underscores right before
+/// it, after a separator.
+pub fn word_before(asm: &str, caret: usize) -> &str {
+    let before = &asm[..byte_index(asm, caret)];
+    let start = before
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+    match &before[start..] {
+        // Hex and numbers aren't opcodes
+        word if word.starts_with(|c: char| c.is_ascii_digit()) => "",
+        // Nor is anything in a string
+        _ if before[..start].ends_with(['"', '\'']) => "",
+        word => word,
+    }
+}
+
+/// The word of letters, digits and underscores around `caret` in `asm`, to document the opcode
+/// the caret is in.
+pub fn word_at(asm: &str, caret: usize) -> &str {
+    let at = byte_index(asm, caret);
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let start = asm[..at].rfind(|c| !is_word(c)).map_or(0, |i| i + 1);
+    let end = asm[at..]
+        .find(|c| !is_word(c))
+        .map_or(asm.len(), |i| at + i);
+    &asm[start..end]
+}
+
+/// Replace the word before `caret` in `asm` with `opcode`, returning the caret after it.
+pub fn complete_word(asm: &str, caret: usize, opcode: &str) -> (String, usize) {
+    let at = byte_index(asm, caret);
+    let start = at - word_before(asm, caret).len();
+    let result = format!("{}{opcode}{}", &asm[..start], &asm[at..]);
+    (result, asm[..start].encode_utf16().count() + opcode.len())
+}
+
+/// The lines of `asm` with any of the byte ranges `marks` in them, and line `line` in any case,
+/// by 1-based number, each split into text that's marked or not.
+pub fn marked_lines<'a>(
+    asm: &'a str,
+    marks: &[Range<usize>],
+    line: usize,
+) -> Vec<(usize, Vec<(&'a str, bool)>)> {
+    let mut line_start = 0;
+    asm.split('\n')
+        .enumerate()
+        .filter_map(|(i, text)| {
+            let (start, end) = (line_start, line_start + text.len());
+            line_start = end + 1;
+            let mut segments = vec![];
+            let mut at = start;
+            for mark in marks.iter().filter(|m| m.start >= start && m.end <= end) {
+                if mark.start > at {
+                    segments.push((&asm[at..mark.start], false));
+                }
+                segments.push((&asm[mark.clone()], true));
+                at = mark.end;
+            }
+            if segments.is_empty() && i + 1 != line {
+                return None;
+            }
+            if at < end {
+                segments.push((&asm[at..end], false));
+            }
+            Some((i + 1, segments))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete_word, insert_token, marked_lines, push_asm, word_at, word_before};
+    use crate::op_return::{PushField, PushFormat};
+
+    #[test]
+    fn test_insert_token() {
+        assert_eq!(insert_token("", 0, "OP_DUP"), ("OP_DUP".into(), 6));
+        assert_eq!(
+            insert_token("OP_DUP", 6, "OP_HASH160"),
+            ("OP_DUP OP_HASH160".into(), 17)
+        );
+        assert_eq!(
+            insert_token("OP_DUP OP_EQUAL", 7, "<0x01>"),
+            ("OP_DUP <0x01> OP_EQUAL".into(), 13)
+        );
+        assert_eq!(
+            insert_token("OP_DUPOP_EQUAL", 6, "OP_1"),
+            ("OP_DUP OP_1 OP_EQUAL".into(), 11)
+        );
+        assert_eq!(
+            insert_token("<\"€\"> OP_DROP", 5, "OP_1"),
+            ("<\"€\"> OP_1 OP_DROP".into(), 10)
+        );
+        assert_eq!(insert_token("OP_1", 99, "OP_2"), ("OP_1 OP_2".into(), 9));
+    }
+
+    #[test]
+    fn test_word_before() {
+        assert_eq!(word_before("OP_DUP OP_HA", 12), "OP_HA");
+        assert_eq!(word_before("OP_DUP OP_HA", 6), "OP_DUP");
+        assert_eq!(word_before("<OP_", 4), "OP_");
+        assert_eq!(word_before("OP_1 ", 5), "");
+        assert_eq!(word_before("0xab", 4), "");
+        assert_eq!(word_before("\"€ ch", 5), "ch");
+        assert_eq!(word_before("'ch", 3), "");
+    }
+
+    #[test]
+    fn test_word_at() {
+        assert_eq!(word_at("OP_DUP OP_HASH160", 0), "OP_DUP");
+        assert_eq!(word_at("OP_DUP OP_HASH160", 6), "OP_DUP");
+        assert_eq!(word_at("OP_DUP OP_HASH160", 10), "OP_HASH160");
+        assert_eq!(word_at("<\"€\"> OP_1", 8), "OP_1");
+        assert_eq!(word_at("OP_1  OP_2", 5), "");
+    }
+
+    #[test]
+    fn test_complete_word() {
+        assert_eq!(
+            complete_word("OP_DUP hash1 OP_EQUAL", 12, "OP_HASH160"),
+            ("OP_DUP OP_HASH160 OP_EQUAL".into(), 17)
+        );
+        assert_eq!(
+            complete_word("<\"€\"> OP_D", 10, "OP_DROP"),
+            ("<\"€\"> OP_DROP".into(), 13)
+        );
+    }
+
+    #[test]
+    fn test_marked_lines() {
+        let asm = "OP_1\nOP_FOO OP_2 OP_BAR\nOP_3\n<";
+        assert_eq!(
+            marked_lines(asm, &[5..11, 17..23], 4),
+            [
+                (
+                    2,
+                    vec![("OP_FOO", true), (" OP_2 ", false), ("OP_BAR", true)]
+                ),
+                (4, vec![("<", false)]),
+            ]
+        );
+        assert!(marked_lines(asm, &[], 0).is_empty());
+    }
+
+    #[test]
+    fn test_push_asm() {
+        let field = |format, value: &str| PushField {
+            format,
+            value: value.into(),
+        };
+        assert_eq!(
+            push_asm(&field(PushFormat::Utf8, "hi")).unwrap(),
+            "<0x6869>"
+        );
+        assert_eq!(
+            push_asm(&field(PushFormat::Hex, "ab cd")).unwrap(),
+            "<0xabcd>"
+        );
+        assert_eq!(
+            push_asm(&field(PushFormat::Number, "-1")).unwrap(),
+            "<0x81>"
+        );
+        assert_eq!(push_asm(&field(PushFormat::Utf8, "")).unwrap(), "OP_0");
+        assert!(push_asm(&field(PushFormat::Hex, "zz")).is_err());
+    }
+}
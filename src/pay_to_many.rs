@@ -0,0 +1,66 @@
+//! Electron Cash's pay-to-many syntax: one `address, amount` payment per line, with `!` as the
+//! amount to send everything left.
+
+use anyhow::{Context as _, Result};
+
+use crate::{
+    components::{
+        script_input::{ScriptDisplayFormat, ScriptInputValue},
+        tx_output::TxOutputSnapshot,
+    },
+    util::{cash_addr_to_script, AmountUnit},
+};
+
+pub fn parse(s: &str, unit: AmountUnit) -> Result<Vec<TxOutputSnapshot>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let parse_line = || -> Result<TxOutputSnapshot> {
+                let (address, amount) = line
+                    .trim()
+                    .rsplit_once([',', ' ', '\t'])
+                    .context("Expected an address and an amount")?;
+                let address = address.trim_end_matches([',', ' ', '\t']);
+                cash_addr_to_script(address)?;
+                let send_max = amount == "!";
+                Ok(TxOutputSnapshot {
+                    value: if send_max { 0 } else { unit.parse(amount)? },
+                    script_pubkey: ScriptInputValue::Addr(address.to_string()),
+                    script_display_format: ScriptDisplayFormat::Addr,
+                    send_max,
+                    ..Default::default()
+                })
+            };
+            parse_line().with_context(|| format!("Line {}", i + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::util::AmountUnit;
+
+    const ADDRESS: &str = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+
+    #[test]
+    fn test_parse() {
+        let outputs = parse(
+            &format!("{ADDRESS}, 0.001\n\n{ADDRESS} 1.5\n{ADDRESS},!\n"),
+            AmountUnit::Bch,
+        )
+        .unwrap();
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0].value, 100_000);
+        assert_eq!(outputs[1].value, 150_000_000);
+        assert!(outputs[2].send_max && !outputs[1].send_max);
+
+        assert_eq!(
+            parse(&format!("{ADDRESS}, 1000"), AmountUnit::Sats).unwrap()[0].value,
+            1000
+        );
+        assert!(parse(&format!("{ADDRESS}\n"), AmountUnit::Bch).is_err());
+        assert!(parse("qqq, 1", AmountUnit::Bch).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Defaults for new transactions, inputs and outputs, kept across sessions.
+
+use bitcoincash::Network;
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::backends::BackendLimits;
+use crate::coin_selection::DEFAULT_FEE_RATE;
+use crate::components::script_input::ScriptDisplayFormat;
+use crate::components::tx_input::TxInputSnapshot;
+use crate::components::tx_output::TxOutputSnapshot;
+use crate::partially_signed::SignatureType;
+use crate::snapshot::EditorSnapshot;
+use crate::util::AddressFormat;
+
+const PREFERENCES_KEY: &str = "bch-tx-editor.preferences";
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Network selected on startup.
+    #[serde(with = "network_str")]
+    pub network: Network,
+    pub tx_version: i32,
+    pub sequence: u32,
+    pub input_script_format: ScriptDisplayFormat,
+    pub output_script_format: ScriptDisplayFormat,
+    pub address_format: AddressFormat,
+    /// How unsigned inputs will be signed, for estimating fees.
+    pub signature_type: SignatureType,
+    /// Sats per byte paid by outputs sending the max or paying the fee, and the rate coin
+    /// selection and sweeping start from.
+    pub fee_rate: f64,
+    /// Disable every feature using the network.
+    pub offline: bool,
+    /// Timeouts, concurrency and rate limits of the network backends in place of their defaults.
+    pub backend_limits: BackendLimits,
+    /// Minutes of inactivity after which sensitive data is cleared, 0 to never clear it.
+    pub auto_lock_minutes: u32,
+    /// Encrypt the autosaved tabs and watch-only wallets under a passphrase asked for on load.
+    pub encrypt_saved: bool,
+    /// Show the panel tracking the signals of every card, for finding leaks.
+    pub debug_panel: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        let input = TxInputSnapshot::default();
+        let output = TxOutputSnapshot::default();
+        Self {
+            network: Network::Bitcoin,
+            tx_version: EditorSnapshot::default().version,
+            sequence: input.sequence,
+            input_script_format: input.script_sig_format,
+            output_script_format: output.script_display_format,
+            address_format: AddressFormat::default(),
+            signature_type: SignatureType::default(),
+            fee_rate: DEFAULT_FEE_RATE,
+            offline: false,
+            backend_limits: BackendLimits::default(),
+            auto_lock_minutes: 15,
+            encrypt_saved: false,
+            debug_panel: false,
+        }
+    }
+}
+
+impl Preferences {
+    pub fn input_snapshot(self) -> TxInputSnapshot {
+        TxInputSnapshot {
+            sequence: self.sequence,
+            script_sig_format: self.input_script_format,
+            ..Default::default()
+        }
+    }
+
+    pub fn output_snapshot(self) -> TxOutputSnapshot {
+        TxOutputSnapshot {
+            script_display_format: self.output_script_format,
+            ..Default::default()
+        }
+    }
+
+    /// A new transaction with one blank input and output.
+    pub fn editor_snapshot(self) -> EditorSnapshot {
+        EditorSnapshot {
+            version: self.tx_version,
+            locktime: 0,
+            inputs: vec![self.input_snapshot()],
+            outputs: vec![self.output_snapshot()],
+        }
+    }
+
+    pub fn load() -> Self {
+        LocalStorage::get(PREFERENCES_KEY).unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        if let Err(e) = LocalStorage::set(PREFERENCES_KEY, self) {
+            error!("Saving preferences failed: {e}");
+        }
+    }
+}
+
+pub mod network_str {
+    use bitcoincash::Network;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::macros::StrEnum;
+
+    pub fn serialize<S: Serializer>(network: &Network, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(network.to_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Network, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Network::from_str(&s).ok_or_else(|| D::Error::custom(format!("Unknown network {s}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::Network;
+
+    use super::Preferences;
+    use crate::components::script_input::ScriptDisplayFormat;
+    use crate::snapshot::EditorSnapshot;
+
+    #[test]
+    fn test_preferences() {
+        assert!(Preferences::default().editor_snapshot() == EditorSnapshot::default());
+
+        let preferences = Preferences {
+            network: Network::Chipnet,
+            sequence: 0,
+            input_script_format: ScriptDisplayFormat::Asm,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&preferences).unwrap();
+        assert!(json.contains(r#""network":"chipnet""#));
+        assert!(serde_json::from_str::<Preferences>(&json).unwrap() == preferences);
+        assert_eq!(preferences.input_snapshot().sequence, 0);
+
+        // Preferences saved by older versions lack newer fields
+        let old = serde_json::from_str::<Preferences>(r#"{"tx_version":1}"#).unwrap();
+        assert_eq!(old.tx_version, 1);
+        assert_eq!(old.sequence, Preferences::default().sequence);
+    }
+}
@@ -0,0 +1,85 @@
+//! WebSocket proxies that Electrum connections are routed through, chosen per server, for networks
+//! that only let some hosts through or to hide the user's address from the server. A proxy URL
+//! with `{server}` in it gets the server's URL there, for relays taking their target as a
+//! parameter, and one without is dialed as is, for relays forwarding to a fixed server.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use anyhow::{bail, Result};
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+
+const PROXIES_KEY: &str = "bch-tx-editor.proxies";
+
+/// Proxy URLs by the URL of the server they lead to.
+static PROXIES: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+pub fn set(proxies: BTreeMap<String, String>) {
+    *PROXIES.lock().unwrap() = proxies;
+}
+
+/// The URL to dial for the Electrum server at `url`, its proxy's if it has one.
+pub fn route(url: &str) -> String {
+    match PROXIES.lock().unwrap().get(url) {
+        Some(proxy) => via(proxy, url),
+        None => url.to_string(),
+    }
+}
+
+fn via(proxy: &str, url: &str) -> String {
+    proxy.replace("{server}", &encode_component(url))
+}
+
+/// `s` with everything but the unreserved characters of URLs percent-encoded.
+fn encode_component(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+pub fn validate(proxy: &str) -> Result<()> {
+    if !proxy.starts_with("wss://") && !proxy.starts_with("ws://") {
+        bail!("{proxy} isn't a WebSocket URL, starting with wss:// or ws://");
+    }
+    Ok(())
+}
+
+pub fn load() -> BTreeMap<String, String> {
+    LocalStorage::get(PROXIES_KEY).unwrap_or_default()
+}
+
+pub fn save(proxies: &BTreeMap<String, String>) {
+    if let Err(e) = LocalStorage::set(PROXIES_KEY, proxies) {
+        error!("Saving WebSocket proxies failed: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, via};
+
+    #[test]
+    fn test_via() {
+        let server = "wss://electrum.imaginary.cash:50004";
+        assert_eq!(
+            via("wss://relay.example/connect?to={server}", server),
+            "wss://relay.example/connect?to=wss%3A%2F%2Felectrum.imaginary.cash%3A50004"
+        );
+        assert_eq!(
+            via("wss://proxy.example:443", server),
+            "wss://proxy.example:443"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("wss://relay.example/?to={server}").is_ok());
+        assert!(validate("https://relay.example").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Key material such as WIFs and mnemonics typed into the editor. Fields holding it register here,
+//! so it can be wiped on demand or once the page has been idle for the auto-lock delay, and has to
+//! be entered again to sign.
+
+use std::cell::RefCell;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use leptos::prelude::{IsDisposed, RwSignal, Update, WithUntracked};
+
+thread_local! {
+    static FIELDS: RefCell<Vec<RwSignal<String>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Wipe `field` whenever sensitive data is cleared.
+pub fn register(field: RwSignal<String>) {
+    FIELDS.with_borrow_mut(|fields| {
+        fields.retain(|f| !f.is_disposed());
+        fields.push(field);
+    });
+}
+
+/// Overwrite the bytes of `s` before emptying it, so the key doesn't linger in freed memory.
+pub fn zeroize(s: &mut String) {
+    // SAFETY: zeros are valid UTF-8
+    for byte in unsafe { s.as_bytes_mut() } {
+        // SAFETY: `byte` is a valid reference, the volatile write keeps the store from being
+        // optimized away as dead
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+    s.clear();
+}
+
+/// Wipe every registered field, returning whether any of them held data.
+pub fn clear() -> bool {
+    let fields = FIELDS.with_borrow_mut(|fields| {
+        fields.retain(|f| !f.is_disposed());
+        fields.clone()
+    });
+    let mut cleared = false;
+    for field in fields {
+        if field.with_untracked(|s| !s.is_empty()) {
+            field.update(zeroize);
+            cleared = true;
+        }
+    }
+    cleared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zeroize;
+
+    #[test]
+    fn test_zeroize() {
+        let mut s = String::from("L1aW4aubDFB7yfras2S1mN3bqg9nwySY8nkoLmJebSLD5BWv3ENZ");
+        let capacity = s.capacity();
+        zeroize(&mut s);
+        assert!(s.is_empty());
+        assert_eq!(s.capacity(), capacity);
+        // SAFETY: the bytes were initialized before being zeroed
+        let bytes = unsafe { std::slice::from_raw_parts(s.as_ptr(), capacity) };
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,70 @@
+//! Public Electrum servers bundled for each network, checked on startup so the fastest one to
+//! respond is used unless the user chooses another.
+
+use anyhow::{bail, Result};
+use bitcoincash::Network;
+use futures::future::select_ok;
+use leptos::web_sys::js_sys::Date;
+
+use crate::chain::{Connection, Server};
+
+/// WebSocket URLs of the public Electrum servers of `network`, the first used until checked.
+pub fn defaults(network: Network) -> &'static [&'static str] {
+    match network {
+        Network::Bitcoin => &[
+            "wss://electrum.imaginary.cash:50004",
+            "wss://bch.imaginary.cash:50004",
+            "wss://cashnode.bch.ninja:50004",
+            "wss://blackie.c3-soft.com:50004",
+            "wss://bch.loping.net:50004",
+        ],
+        Network::Chipnet => &[
+            "wss://chipnet.imaginary.cash:50004",
+            "wss://chipnet.bch.ninja:50004",
+        ],
+        Network::Testnet4 => &["wss://testnet4.imaginary.cash:50004"],
+        _ => &[],
+    }
+}
+
+/// Connect to the Electrum server at `url` and ping it, returning how long that took in
+/// milliseconds.
+pub async fn check(url: &str) -> Result<f64> {
+    let start = Date::now();
+    let Connection::Electrum(client) = Server::Electrum(url.to_string()).connect().await? else {
+        unreachable!("Electrum servers connect with Electrum");
+    };
+    client.server_ping().await?;
+    Ok(Date::now() - start)
+}
+
+/// Check all of `urls` at once, returning the first to respond with its time, or the error of the
+/// last to fail if none does.
+pub async fn fastest(urls: &[&str]) -> Result<(String, f64)> {
+    if urls.is_empty() {
+        bail!("There are no servers to check");
+    }
+    let checks = urls
+        .iter()
+        .map(|url| Box::pin(async move { anyhow::Ok((url.to_string(), check(url).await?)) }));
+    let (fastest, _) = select_ok(checks).await?;
+    Ok(fastest)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::Network;
+
+    use super::defaults;
+
+    #[test]
+    fn test_defaults() {
+        assert!(!defaults(Network::Bitcoin).is_empty());
+        assert!(!defaults(Network::Chipnet).is_empty());
+        for network in [Network::Bitcoin, Network::Chipnet, Network::Testnet4] {
+            assert!(defaults(network)
+                .iter()
+                .all(|url| url.starts_with("wss://")));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Keyboard shortcuts of the editor, kept in one table so the cheat sheet can't go out of date.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Action {
+    AddInput,
+    AddOutput,
+    Serialize,
+    Deserialize,
+    Undo,
+    Redo,
+    FocusHex,
+    NextCard,
+    PrevCard,
+    ShowHelp,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Modifier {
+    None,
+    /// Ctrl, or Cmd on macOS.
+    Ctrl,
+    CtrlShift,
+    Alt,
+}
+
+pub struct Shortcut {
+    pub modifier: Modifier,
+    /// Lowercase key name, as in [`key_name`].
+    pub key: &'static str,
+    pub action: Action,
+    pub description: &'static str,
+}
+
+impl Shortcut {
+    /// Alt shortcuts don't clash with typing, so they also work while a text field has focus.
+    /// Anything else is left to the text field, in particular its own Ctrl+Z.
+    pub fn works_in_text_field(&self) -> bool {
+        self.modifier == Modifier::Alt
+    }
+
+    pub fn label(&self) -> String {
+        let key = match self.key {
+            "?" => "?".to_string(),
+            key => key.to_uppercase(),
+        };
+        match self.modifier {
+            Modifier::None => key,
+            Modifier::Ctrl => format!("Ctrl+{key}"),
+            Modifier::CtrlShift => format!("Ctrl+Shift+{key}"),
+            Modifier::Alt => format!("Alt+{key}"),
+        }
+    }
+}
+
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "i",
+        action: Action::AddInput,
+        description: "Add an input",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "o",
+        action: Action::AddOutput,
+        description: "Add an output",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "s",
+        action: Action::Serialize,
+        description: "Serialize the transaction",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "d",
+        action: Action::Deserialize,
+        description: "Deserialize the transaction hex",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "h",
+        action: Action::FocusHex,
+        description: "Jump to the transaction hex",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "j",
+        action: Action::NextCard,
+        description: "Go to the next input or output",
+    },
+    Shortcut {
+        modifier: Modifier::Alt,
+        key: "k",
+        action: Action::PrevCard,
+        description: "Go to the previous input or output",
+    },
+    Shortcut {
+        modifier: Modifier::Ctrl,
+        key: "z",
+        action: Action::Undo,
+        description: "Undo",
+    },
+    Shortcut {
+        modifier: Modifier::Ctrl,
+        key: "y",
+        action: Action::Redo,
+        description: "Redo",
+    },
+    Shortcut {
+        modifier: Modifier::CtrlShift,
+        key: "z",
+        action: Action::Redo,
+        description: "Redo",
+    },
+    Shortcut {
+        modifier: Modifier::None,
+        key: "?",
+        action: Action::ShowHelp,
+        description: "Show this list",
+    },
+];
+
+/// Lowercase name of a pressed key. With Alt held, macOS reports a special character as the key,
+/// so letters fall back to the physical key code.
+pub fn key_name(key: &str, code: &str) -> String {
+    let is_plain = key.len() == 1 && key.chars().all(|c| c.is_ascii_graphic());
+    match code.strip_prefix("Key") {
+        Some(letter) if !is_plain => letter.to_lowercase(),
+        _ => key.to_lowercase(),
+    }
+}
+
+pub fn find(
+    key: &str,
+    code: &str,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    in_text_field: bool,
+) -> Option<&'static Shortcut> {
+    let modifier = match (ctrl, alt, shift) {
+        (true, false, false) => Modifier::Ctrl,
+        (true, false, true) => Modifier::CtrlShift,
+        (false, true, _) => Modifier::Alt,
+        // Shift is needed to type "?" on most layouts
+        (false, false, _) => Modifier::None,
+        _ => return None,
+    };
+    let key = key_name(key, code);
+    SHORTCUTS.iter().find(|s| {
+        s.modifier == modifier && s.key == key && (!in_text_field || s.works_in_text_field())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, Action};
+
+    fn action(key: &str, code: &str, ctrl: bool, alt: bool, shift: bool) -> Option<Action> {
+        find(key, code, ctrl, alt, shift, false).map(|s| s.action)
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(action("z", "KeyZ", true, false, false), Some(Action::Undo));
+        assert_eq!(action("Z", "KeyZ", true, false, true), Some(Action::Redo));
+        assert_eq!(
+            action("i", "KeyI", false, true, false),
+            Some(Action::AddInput)
+        );
+        // Alt+J on macOS
+        assert_eq!(
+            action("∆", "KeyJ", false, true, false),
+            Some(Action::NextCard)
+        );
+        assert_eq!(
+            action("?", "Slash", false, false, true),
+            Some(Action::ShowHelp)
+        );
+        assert_eq!(action("i", "KeyI", false, false, false), None);
+
+        assert!(find("z", "KeyZ", true, false, false, true).is_none());
+        assert!(find("?", "Slash", false, false, true, true).is_none());
+        assert!(find("s", "KeyS", false, true, false, true).is_some());
+    }
+}
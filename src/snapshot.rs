@@ -0,0 +1,201 @@
+use anyhow::{bail, Context as _, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::components::{tx_input::TxInputSnapshot, tx_output::TxOutputSnapshot};
+
+/// Plain copy of everything in the editor, used for undo/redo.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorSnapshot {
+    pub version: i32,
+    pub locktime: u32,
+    pub inputs: Vec<TxInputSnapshot>,
+    pub outputs: Vec<TxOutputSnapshot>,
+}
+
+impl Default for EditorSnapshot {
+    fn default() -> Self {
+        Self {
+            version: 2,
+            locktime: 0,
+            inputs: vec![TxInputSnapshot::default()],
+            outputs: vec![TxOutputSnapshot::default()],
+        }
+    }
+}
+
+/// Schema version of saved editor state, see [`EditorSnapshot::to_versioned_json`]. Changes to the
+/// snapshots that `#[serde(default)]` can't absorb bump it and add a migration to [`MIGRATIONS`].
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// Upgrades of the JSON of an [`EditorSnapshot`], each from the schema version of its index to
+/// the next.
+const MIGRATIONS: [fn(&mut Value) -> Result<()>; SCHEMA_VERSION as usize] = [
+    // Autosaves from before the schema was versioned, which are otherwise identical to version 1
+    |_| Ok(()),
+];
+
+impl EditorSnapshot {
+    /// JSON of the snapshot tagged with its schema version, for keeping it outside the editor.
+    pub fn to_versioned_json(&self) -> Value {
+        let mut json = serde_json::to_value(self).expect("snapshots always serialize");
+        json["schema"] = SCHEMA_VERSION.into();
+        json
+    }
+
+    /// Read a snapshot saved by any version of the editor, migrating it to the current schema.
+    /// Snapshots from newer versions are read as far as they can be.
+    pub fn from_versioned_json(mut json: Value) -> Result<Self> {
+        let schema = match json
+            .as_object_mut()
+            .context("Not a saved transaction")?
+            .remove("schema")
+        {
+            Some(schema) => schema.as_u64().context("Invalid schema version")?,
+            None => 0,
+        };
+        for migrate in MIGRATIONS.iter().skip(schema as usize) {
+            migrate(&mut json)?;
+        }
+        serde_json::from_value(json).with_context(|| match schema > SCHEMA_VERSION {
+            true => format!("Saved by a newer version of the editor, with schema {schema}"),
+            false => "Invalid saved transaction".to_string(),
+        })
+    }
+}
+
+/// `#[serde(with)]` module storing an [`EditorSnapshot`] in its versioned form.
+pub mod versioned {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    use super::EditorSnapshot;
+
+    pub fn serialize<S: Serializer>(snapshot: &EditorSnapshot, s: S) -> Result<S::Ok, S::Error> {
+        snapshot.to_versioned_json().serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<EditorSnapshot, D::Error> {
+        EditorSnapshot::from_versioned_json(Value::deserialize(d)?)
+            .map_err(|e| D::Error::custom(format!("{e:#}")))
+    }
+}
+
+/// A single input or output, as copied to the clipboard to move it between transactions.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClipboardEntry {
+    Input(TxInputSnapshot),
+    Output(TxOutputSnapshot),
+}
+
+impl ClipboardEntry {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("snapshots always serialize")
+    }
+
+    pub fn input_from_json(json: &str) -> Result<TxInputSnapshot> {
+        match serde_json::from_str(json)? {
+            ClipboardEntry::Input(input) => Ok(input),
+            ClipboardEntry::Output(_) => bail!("Clipboard contains an output, not an input"),
+        }
+    }
+
+    pub fn output_from_json(json: &str) -> Result<TxOutputSnapshot> {
+        match serde_json::from_str(json)? {
+            ClipboardEntry::Output(output) => Ok(output),
+            ClipboardEntry::Input(_) => bail!("Clipboard contains an input, not an output"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bch_tx_edit_core::test_util::{tx, unsigned_input, TXID};
+    use bitcoincash::{hashes::hex::FromHex, psbt::serialize::Serialize as _, TxOut, Txid};
+    use serde_json::json;
+
+    use super::{ClipboardEntry, EditorSnapshot, SCHEMA_VERSION};
+    use crate::coin_selection::{subtract_fee, DEFAULT_FEE_RATE};
+    use crate::components::{
+        script_input::ScriptInputValue, tx_input::TxInputSnapshot, tx_output::TxOutputSnapshot,
+    };
+    use crate::partially_signed::{PartiallySignedTransaction, SignatureType};
+
+    #[test]
+    fn test_versioned_round_trip() {
+        let snapshot = EditorSnapshot {
+            locktime: 800_000,
+            ..Default::default()
+        };
+        let json = snapshot.to_versioned_json();
+        assert_eq!(json["schema"], SCHEMA_VERSION);
+        assert!(EditorSnapshot::from_versioned_json(json).unwrap() == snapshot);
+    }
+
+    #[test]
+    fn test_versioned_migration() {
+        // Saved before the schema was versioned
+        let mut json = serde_json::to_value(EditorSnapshot::default()).unwrap();
+        assert!(
+            EditorSnapshot::from_versioned_json(json.clone()).unwrap() == EditorSnapshot::default()
+        );
+
+        json["schema"] = 99.into();
+        json["added_later"] = true.into();
+        assert!(EditorSnapshot::from_versioned_json(json.clone()).is_ok());
+        json["inputs"] = "changed later".into();
+        let e = EditorSnapshot::from_versioned_json(json).unwrap_err();
+        assert!(e.to_string().contains("newer version"));
+
+        assert!(EditorSnapshot::from_versioned_json(json!([])).is_err());
+        assert!(EditorSnapshot::from_versioned_json(json!({"schema": "1"})).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_round_trip() {
+        let output = TxOutputSnapshot {
+            value: 1000,
+            label: "change".to_string(),
+            ..Default::default()
+        };
+        let json = ClipboardEntry::Output(output.clone()).to_json();
+        assert!(ClipboardEntry::output_from_json(&json).unwrap() == output);
+        assert!(ClipboardEntry::input_from_json(&json).is_err());
+
+        let input = TxInputSnapshot {
+            vout: 3,
+            ..Default::default()
+        };
+        let json = ClipboardEntry::Input(input.clone()).to_json();
+        assert!(ClipboardEntry::input_from_json(&json).unwrap() == input);
+        assert!(ClipboardEntry::output_from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_subtract_fee_round_trip() {
+        let output = TxOutputSnapshot {
+            value: 100_000,
+            script_pubkey: ScriptInputValue::Hex(format!("76a914{}88ac", "11".repeat(20))),
+            subtract_fee: true,
+            label: "payment".to_string(),
+            ..Default::default()
+        };
+        // As the editor builds it, taking the fee off the outputs that pay it
+        let build = |output: &TxOutputSnapshot| {
+            let input = unsigned_input(Txid::from_hex(TXID).unwrap(), 0, None);
+            let mut tx = tx(vec![input], vec![TxOut::try_from(output).unwrap()]);
+            if output.subtract_fee {
+                subtract_fee(&mut tx, &[0], DEFAULT_FEE_RATE, SignatureType::Ecdsa).unwrap();
+            }
+            tx.serialize()
+        };
+        let serialized = build(&output);
+        let tx = PartiallySignedTransaction::deserialize_any(&serialized).unwrap();
+        let loaded = output.with_txout(&tx.output[0]);
+        assert!(loaded.value < output.value);
+        assert!(!loaded.subtract_fee);
+        assert_eq!(loaded.label, output.label);
+        assert_eq!(build(&loaded), serialized);
+    }
+}
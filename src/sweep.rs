@@ -0,0 +1,177 @@
+//! Sweeping the coins of a private key, such as a paper wallet's, to another address: every UTXO
+//! of its P2PKH address spent to one output, less the fee.
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{
+    consensus::serialize,
+    secp256k1::{Secp256k1, Signing},
+    Network, PackedLockTime, PrivateKey, Script, Sequence, Transaction, TxIn, TxOut, Witness,
+};
+
+use crate::chain::{Connection, Server};
+use crate::coin_selection::{Utxo, P2PKH_DUST};
+use crate::partially_signed::{
+    MaybeUnsignedTxIn, PartiallySignedTransaction, UnsignedScriptSig, UnsignedTxIn,
+    P2PKH_INPUT_SIZE,
+};
+use crate::signing::sign_p2pkh;
+use crate::watch_only::scripthash;
+
+/// Extra size of an input signed with an uncompressed public key, 65 bytes instead of 33.
+const UNCOMPRESSED_EXTRA_SIZE: usize = 32;
+
+/// Parse `wif`, failing if it's for mainnet and `network` isn't, or the other way around.
+pub fn parse_key(wif: &str, network: Network) -> Result<PrivateKey> {
+    let key = PrivateKey::from_wif(wif.trim()).context("Invalid WIF private key")?;
+    if (key.network == Network::Bitcoin) != (network == Network::Bitcoin) {
+        bail!("The key is for another network");
+    }
+    Ok(key)
+}
+
+/// The UTXOs of `script`, at `address`, on the Electrum `server`.
+pub async fn fetch_utxos(server: &str, script: &Script, address: &str) -> Result<Vec<Utxo>> {
+    let Connection::Electrum(client) = Server::Electrum(server.to_string()).connect().await? else {
+        unreachable!("Electrum servers connect with Electrum");
+    };
+    client
+        .blockchain_scripthash_listunspent(&scripthash(script))
+        .await?
+        .into_iter()
+        .map(|entry| {
+            let mut utxo = Utxo::try_from(entry)?;
+            utxo.address = address.to_string();
+            Ok(utxo)
+        })
+        .collect()
+}
+
+pub struct Sweep {
+    pub tx: PartiallySignedTransaction,
+    pub fee: u64,
+    /// UTXOs left out because they hold tokens, which need an output of their own.
+    pub skipped_tokens: usize,
+}
+
+/// Spend the UTXOs of `key` without tokens to `destination` at `fee_rate` sats per byte, signing
+/// the inputs if `sign`, or else leaving them unsigned for a wallet to sign.
+pub fn build<C: Signing>(
+    secp: &Secp256k1<C>,
+    key: &PrivateKey,
+    utxos: &[Utxo],
+    destination: Script,
+    fee_rate: f64,
+    sign: bool,
+) -> Result<Sweep> {
+    let (spent, skipped): (Vec<_>, Vec<_>) =
+        utxos.iter().partition(|utxo| !utxo.token.cashtoken_enabled);
+    if spent.is_empty() {
+        bail!("There are no coins without tokens to sweep");
+    }
+    let mut output = TxOut {
+        value: 0,
+        script_pubkey: destination,
+        token: None,
+    };
+    let input_size = match key.compressed {
+        true => P2PKH_INPUT_SIZE,
+        false => P2PKH_INPUT_SIZE + UNCOMPRESSED_EXTRA_SIZE,
+    };
+    // Version, locktime, and input and output counts
+    let size = 4 + 4 + 3 + 1 + serialize(&output).len() + spent.len() * input_size;
+    let fee = (size as f64 * fee_rate).ceil() as u64;
+    let total = spent.iter().map(|utxo| utxo.value).sum::<u64>();
+    output.value = total.saturating_sub(fee);
+    if output.value < P2PKH_DUST {
+        bail!("The {total} sats of the key don't cover the fee of {fee} sats");
+    }
+
+    let mut tx = Transaction {
+        version: 2,
+        lock_time: PackedLockTime(0),
+        input: spent
+            .iter()
+            .map(|utxo| TxIn {
+                previous_output: utxo.outpoint,
+                script_sig: Script::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::default(),
+            })
+            .collect(),
+        output: vec![output],
+    };
+    let tx = if sign {
+        for (i, utxo) in spent.iter().enumerate() {
+            sign_p2pkh(secp, &mut tx, i, key, utxo.value, None);
+        }
+        PartiallySignedTransaction::from(tx)
+    } else {
+        let script_pubkey = Script::new_p2pkh(&key.public_key(secp).pubkey_hash());
+        PartiallySignedTransaction {
+            version: tx.version,
+            lock_time: tx.lock_time,
+            input: spent
+                .iter()
+                .map(|utxo| {
+                    MaybeUnsignedTxIn::Unsigned(UnsignedTxIn {
+                        previous_output: utxo.outpoint,
+                        unsigned_script_sig: UnsignedScriptSig::from_script_pubkey(
+                            script_pubkey.clone(),
+                        ),
+                        sequence: Sequence::MAX,
+                        value: utxo.value,
+                        token: None,
+                    })
+                })
+                .collect(),
+            output: tx.output,
+        }
+    };
+    Ok(Sweep {
+        tx,
+        fee,
+        skipped_tokens: skipped.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{secp256k1::Secp256k1, Network, Script};
+
+    use super::{build, parse_key};
+    use crate::test_util::utxo;
+
+    const WIF: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+
+    #[test]
+    fn test_parse_key() {
+        assert!(parse_key(WIF, Network::Bitcoin).is_ok());
+        assert!(parse_key(WIF, Network::Chipnet).is_err());
+        assert!(parse_key("KwDiBf89", Network::Bitcoin).is_err());
+    }
+
+    #[test]
+    fn test_build() {
+        let secp = Secp256k1::new();
+        let key = parse_key(WIF, Network::Bitcoin).unwrap();
+        let destination = Script::new_p2pkh(&key.public_key(&secp).pubkey_hash());
+        let mut token_utxo = utxo(1, 1000);
+        token_utxo.token.cashtoken_enabled = true;
+        let utxos = [utxo(0, 10_000), token_utxo, utxo(2, 5000)];
+
+        let sweep = build(&secp, &key, &utxos, destination.clone(), 1.0, false).unwrap();
+        assert_eq!(sweep.skipped_tokens, 1);
+        assert_eq!(sweep.tx.input.len(), 2);
+        // 12 bytes of version, locktime and counts, a 34 byte output and two 148 byte inputs
+        assert_eq!(sweep.fee, 342);
+        assert_eq!(sweep.tx.output[0].value, 15_000 - 342);
+        assert!(!sweep.tx.is_signed());
+
+        let signed = build(&secp, &key, &utxos, destination.clone(), 1.0, true).unwrap();
+        assert!(signed.tx.is_signed());
+        assert_eq!(signed.tx.output, sweep.tx.output);
+
+        assert!(build(&secp, &key, &utxos[..2], destination.clone(), 1000.0, true).is_err());
+        assert!(build(&secp, &key, &utxos[1..2], destination, 1.0, true).is_err());
+    }
+}
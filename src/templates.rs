@@ -0,0 +1,164 @@
+//! Starting points for common transactions, and presets saved by the user.
+
+use gloo::storage::{LocalStorage, Storage};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{
+    script_input::{ScriptDisplayFormat, ScriptInputValue},
+    token_data::{NftCapability, TokenDataSnapshot},
+    tx_input::{TxInputSnapshot, UtxoPubkeyData},
+    tx_output::TxOutputSnapshot,
+};
+use crate::snapshot::EditorSnapshot;
+
+const PRESETS_KEY: &str = "bch-tx-editor.presets";
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub snapshot: EditorSnapshot,
+}
+
+fn unsigned_input(label: &str, utxo_amount: u64) -> TxInputSnapshot {
+    TxInputSnapshot {
+        unsigned: true,
+        utxo_pubkey: UtxoPubkeyData::Addr(String::new()),
+        utxo_amount,
+        label: label.to_string(),
+        ..Default::default()
+    }
+}
+
+fn address_output(label: &str, value: u64) -> TxOutputSnapshot {
+    TxOutputSnapshot {
+        value,
+        label: label.to_string(),
+        ..Default::default()
+    }
+}
+
+fn asm_output(label: &str, value: u64, asm: &str) -> TxOutputSnapshot {
+    TxOutputSnapshot {
+        value,
+        script_pubkey: ScriptInputValue::Asm(asm.to_string()),
+        script_display_format: ScriptDisplayFormat::Asm,
+        label: label.to_string(),
+        ..Default::default()
+    }
+}
+
+fn fungible_tokens(amount: u64) -> TokenDataSnapshot {
+    TokenDataSnapshot {
+        cashtoken_enabled: true,
+        has_ft_amount: true,
+        ft_amount: amount,
+        ..Default::default()
+    }
+}
+
+fn template(name: &str, inputs: Vec<TxInputSnapshot>, outputs: Vec<TxOutputSnapshot>) -> Template {
+    Template {
+        name: name.to_string(),
+        snapshot: EditorSnapshot {
+            inputs,
+            outputs,
+            ..Default::default()
+        },
+    }
+}
+
+/// Templates shipped with the editor. Amounts are placeholders, and labels say what else to fill
+/// in.
+pub fn builtin() -> Vec<Template> {
+    vec![
+        template(
+            "Simple P2PKH spend",
+            vec![unsigned_input("UTXO to spend: fill in txid, vout and address", 100_000)],
+            vec![
+                address_output("Recipient", 50_000),
+                address_output("Change", 49_000),
+            ],
+        ),
+        template(
+            "2-of-3 multisig spend",
+            vec![TxInputSnapshot {
+                script_sig: ScriptInputValue::Asm(
+                    "OP_0 <sig_a> <sig_b> <OP_2 <pubkey_a> <pubkey_b> <pubkey_c> OP_3 OP_CHECKMULTISIG>"
+                        .to_string(),
+                ),
+                script_sig_format: ScriptDisplayFormat::Asm,
+                label: "P2SH multisig UTXO: fill in the signatures and public keys".to_string(),
+                ..Default::default()
+            }],
+            vec![address_output("Recipient", 99_000)],
+        ),
+        template(
+            "OP_RETURN data carrier",
+            vec![unsigned_input("Funding UTXO", 10_000)],
+            vec![
+                asm_output("Data", 0, "OP_RETURN <\"hello\">"),
+                address_output("Change", 9_000),
+            ],
+        ),
+        template(
+            "CashToken transfer",
+            vec![
+                TxInputSnapshot {
+                    token_data: fungible_tokens(1000),
+                    ..unsigned_input("Token UTXO: fill in the token category", 1000)
+                },
+                unsigned_input("UTXO paying the fee", 10_000),
+            ],
+            vec![
+                TxOutputSnapshot {
+                    token_data: fungible_tokens(1000),
+                    ..address_output("Token recipient: use the same category", 1000)
+                },
+                address_output("Change", 9_000),
+            ],
+        ),
+        template(
+            "Token genesis",
+            vec![unsigned_input(
+                "Genesis UTXO: must be output 0 of its transaction, its txid becomes the category",
+                10_000,
+            )],
+            vec![
+                TxOutputSnapshot {
+                    token_data: TokenDataSnapshot {
+                        has_nft: true,
+                        nft_capability: NftCapability::Minting,
+                        ..fungible_tokens(1_000_000)
+                    },
+                    ..address_output("Token output: the category is the genesis input's txid", 1000)
+                },
+                address_output("Change", 8_000),
+            ],
+        ),
+    ]
+}
+
+pub fn load_presets() -> Vec<Template> {
+    LocalStorage::get(PRESETS_KEY).unwrap_or_default()
+}
+
+pub fn save_presets(presets: &[Template]) {
+    if let Err(e) = LocalStorage::set(PRESETS_KEY, presets) {
+        error!("Saving presets failed: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::builtin;
+
+    #[test]
+    fn test_builtin_templates() {
+        let templates = builtin();
+        for (i, t) in templates.iter().enumerate() {
+            assert!(!t.snapshot.inputs.is_empty() && !t.snapshot.outputs.is_empty());
+            assert!(templates[..i].iter().all(|other| other.name != t.name));
+        }
+    }
+}
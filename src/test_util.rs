@@ -0,0 +1,17 @@
+//! Fixtures of the editor's own types, next to the transactions of the core's `test_util`.
+
+use bch_tx_edit_core::test_util::TXID;
+use bitcoincash::OutPoint;
+
+use crate::coin_selection::Utxo;
+use crate::components::token_data::TokenDataSnapshot;
+
+/// UTXO of `value` sats without a token, at output `vout` of [`TXID`].
+pub fn utxo(vout: u32, value: u64) -> Utxo {
+    Utxo {
+        outpoint: OutPoint::new(TXID.parse().unwrap(), vout),
+        value,
+        address: String::new(),
+        token: TokenDataSnapshot::default(),
+    }
+}
@@ -0,0 +1,289 @@
+//! Outputs locked until a block height or time with OP_CHECKLOCKTIMEVERIFY, paid through P2SH.
+//! Besides plain timelocks, hash time locked contracts for atomic swaps.
+
+use std::str::FromStr;
+
+use anyhow::{bail, ensure, Context as _, Result};
+use bitcoincash::{
+    blockdata::{
+        opcodes::all::{
+            OP_CHECKSIG, OP_CLTV, OP_DROP, OP_ELSE, OP_ENDIF, OP_EQUALVERIFY, OP_IF, OP_SHA256,
+        },
+        script::Builder,
+    },
+    hashes::{hex::ToHex, sha256, Hash},
+    PublicKey, Script,
+};
+
+use crate::util::cash_addr_to_script;
+
+/// Locktimes below this are block heights, above it unix timestamps.
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+
+/// Whoever can spend the output once the lock expires.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Recipient {
+    Pubkey(PublicKey),
+    /// A P2PKH locking script, from a cash address.
+    PubkeyHash(Script),
+}
+
+impl FromStr for Recipient {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(pubkey) = s.parse::<PublicKey>() {
+            return Ok(Self::Pubkey(pubkey));
+        }
+        let script = cash_addr_to_script(s).context("Expected a public key or an address")?;
+        ensure!(script.is_p2pkh(), "Only P2PKH addresses can be locked");
+        Ok(Self::PubkeyHash(script))
+    }
+}
+
+/// Seconds since the unix epoch of a UTC `YYYY-MM-DD` date with an optional `HH:MM` time, as
+/// `<input type="datetime-local">` produces.
+pub fn parse_date(s: &str) -> Result<u32> {
+    let s = s.trim();
+    let (date, time) = s.split_once(['T', ' ']).unwrap_or((s, "00:00"));
+    let [year, month, day] = parse_fields(date, '-').context("Expected a date as YYYY-MM-DD")?;
+    let [hour, minute] = parse_fields(time, ':').context("Expected a time as HH:MM")?;
+    ensure!(
+        (1..=12).contains(&month) && (1..=31).contains(&day) && hour < 24 && minute < 60,
+        "Invalid date"
+    );
+    // Days since 1970-01-01 in the proleptic Gregorian calendar, with years starting in March
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    let seconds = days * 86_400 + hour * 3600 + minute * 60;
+    u32::try_from(seconds)
+        .ok()
+        .filter(|&t| t >= LOCKTIME_THRESHOLD)
+        .context("The date must be between 1985 and 2106")
+}
+
+fn parse_fields<const N: usize>(s: &str, separator: char) -> Option<[i64; N]> {
+    let fields = s
+        .split(separator)
+        .map(|f| f.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    fields.try_into().ok()
+}
+
+/// Locktime from a block height, or a date as accepted by [`parse_date`].
+pub fn parse_locktime(by_date: bool, s: &str) -> Result<u32> {
+    if by_date {
+        return parse_date(s);
+    }
+    let height = s.trim().parse::<u32>().context("Invalid block height")?;
+    ensure!(
+        (1..LOCKTIME_THRESHOLD).contains(&height),
+        "Block heights must be between 1 and {LOCKTIME_THRESHOLD}"
+    );
+    Ok(height)
+}
+
+impl Recipient {
+    /// Script checking a signature of the recipient, which is on the stack.
+    fn check_sig_script(&self) -> Script {
+        match self {
+            Self::Pubkey(pubkey) => Builder::new()
+                .push_key(pubkey)
+                .push_opcode(OP_CHECKSIG)
+                .into_script(),
+            Self::PubkeyHash(p2pkh) => p2pkh.clone(),
+        }
+    }
+
+    /// CashAssembly pushing what [`Self::check_sig_script`] needs, to be filled in.
+    fn unlocking_asm(&self) -> &'static str {
+        match self {
+            Self::Pubkey(_) => "<sig>",
+            Self::PubkeyHash(_) => "<sig> <pubkey>",
+        }
+    }
+}
+
+fn concat(builder: Builder, script: &Script) -> Script {
+    let mut bytes = builder.into_script().into_bytes();
+    bytes.extend_from_slice(script.as_bytes());
+    Script::from(bytes)
+}
+
+fn cltv_builder(builder: Builder, locktime: u32) -> Builder {
+    builder
+        .push_int(locktime.into())
+        .push_opcode(OP_CLTV)
+        .push_opcode(OP_DROP)
+}
+
+/// Output spendable by the recipient once the transaction locktime reaches `locktime`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cltv {
+    pub locktime: u32,
+    pub recipient: Recipient,
+}
+
+impl Cltv {
+    pub fn new(locktime: u32, recipient: Recipient) -> Result<Self> {
+        if locktime == 0 {
+            bail!("The locktime must be above 0");
+        }
+        Ok(Self {
+            locktime,
+            recipient,
+        })
+    }
+
+    pub fn redeem_script(&self) -> Script {
+        concat(
+            cltv_builder(Builder::new(), self.locktime),
+            &self.recipient.check_sig_script(),
+        )
+    }
+
+    /// Script sig of an input spending the output, as CashAssembly with the signature and public
+    /// key left to fill in.
+    pub fn spending_script_sig_asm(&self) -> String {
+        format!(
+            "{} <0x{}>",
+            self.recipient.unlocking_asm(),
+            self.redeem_script().to_hex()
+        )
+    }
+}
+
+/// Hash time locked contract: the recipient can claim the output by revealing the preimage of
+/// `hash`, or the refund key can take it back once the transaction locktime reaches `timeout`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Htlc {
+    pub hash: sha256::Hash,
+    pub recipient: Recipient,
+    pub refund: Recipient,
+    pub timeout: u32,
+}
+
+impl Htlc {
+    pub fn redeem_script(&self) -> Script {
+        let claim = Builder::new()
+            .push_opcode(OP_IF)
+            .push_opcode(OP_SHA256)
+            .push_slice(&self.hash)
+            .push_opcode(OP_EQUALVERIFY);
+        let claim = concat(claim, &self.recipient.check_sig_script());
+        let refund = cltv_builder(
+            Builder::from(claim.into_bytes()).push_opcode(OP_ELSE),
+            self.timeout,
+        );
+        let refund = concat(refund, &self.refund.check_sig_script());
+        Builder::from(refund.into_bytes())
+            .push_opcode(OP_ENDIF)
+            .into_script()
+    }
+
+    /// Script sig claiming the output, as CashAssembly. Without a preimage, it is left to fill in.
+    pub fn claim_script_sig_asm(&self, preimage: Option<&[u8]>) -> Result<String> {
+        let preimage = match preimage {
+            Some(preimage) => {
+                ensure!(
+                    sha256::Hash::hash(preimage) == self.hash,
+                    "The preimage doesn't match the hash"
+                );
+                format!("<0x{}>", preimage.to_hex())
+            }
+            None => "<preimage>".to_string(),
+        };
+        Ok(format!(
+            "{} {preimage} OP_1 <0x{}>",
+            self.recipient.unlocking_asm(),
+            self.redeem_script().to_hex()
+        ))
+    }
+
+    /// Script sig taking the output back after the timeout, as CashAssembly.
+    pub fn refund_script_sig_asm(&self) -> String {
+        format!(
+            "{} OP_0 <0x{}>",
+            self.refund.unlocking_asm(),
+            self.redeem_script().to_hex()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::hashes::hex::ToHex;
+    use bitcoincash::hashes::{sha256, Hash};
+
+    use super::{parse_date, parse_locktime, Cltv, Htlc, Recipient};
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("2024-01-01").unwrap(), 1_704_067_200);
+        assert_eq!(parse_date("2024-02-29T12:30").unwrap(), 1_709_209_800);
+        assert!(parse_date("1980-01-01").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+        assert!(parse_date("2024/01/01").is_err());
+    }
+
+    #[test]
+    fn test_redeem_script() {
+        let pubkey = "030a72c3eb8d023aa1638587293e427819265fd307db1d67de8e5c4129f654bf49";
+        let cltv = Cltv::new(800_000, pubkey.parse::<Recipient>().unwrap()).unwrap();
+        assert_eq!(
+            cltv.redeem_script().to_hex(),
+            format!("0300350cb17521{pubkey}ac")
+        );
+
+        let address = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+        let cltv = Cltv::new(800_000, address.parse::<Recipient>().unwrap()).unwrap();
+        assert_eq!(
+            cltv.redeem_script().to_hex(),
+            "0300350cb17576a914f5bf48b397dae70be82b3cca4793f8eb2b6cdac988ac"
+        );
+        assert!(Cltv::new(0, cltv.recipient).is_err());
+        assert!(parse_locktime(false, "500000000").is_err());
+        assert_eq!(parse_locktime(false, " 800000").unwrap(), 800_000);
+    }
+
+    #[test]
+    fn test_htlc() {
+        let htlc = Htlc {
+            hash: sha256::Hash::hash(b"secret"),
+            recipient: "030a72c3eb8d023aa1638587293e427819265fd307db1d67de8e5c4129f654bf49"
+                .parse()
+                .unwrap(),
+            refund: "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2"
+                .parse()
+                .unwrap(),
+            timeout: 800_000,
+        };
+        let redeem_script = htlc.redeem_script().to_hex();
+        assert_eq!(
+            redeem_script,
+            format!(
+                "63a820{}8821030a72c3eb8d023aa1638587293e427819265fd307db1d67de8e5c4129f654bf49ac\
+                 670300350cb17576a914f5bf48b397dae70be82b3cca4793f8eb2b6cdac988ac68",
+                htlc.hash.to_hex()
+            )
+        );
+        assert_eq!(
+            htlc.claim_script_sig_asm(Some(b"secret")).unwrap(),
+            format!("<sig> <0x736563726574> OP_1 <0x{redeem_script}>")
+        );
+        assert!(htlc.claim_script_sig_asm(Some(b"wrong")).is_err());
+        assert_eq!(
+            htlc.refund_script_sig_asm(),
+            format!("<sig> <pubkey> OP_0 <0x{redeem_script}>")
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-category totals of the tokens a transaction spends and creates.
+
+use anyhow::Result;
+use bitcoincash::{
+    blockdata::token::{OutputData, Structure},
+    hashes::hex::ToHex,
+    TokenID, TxOut,
+};
+
+use crate::components::script_input::{ScriptDisplayFormat, ScriptInputValue};
+use crate::components::token_data::{NftCapability, TokenDataSnapshot};
+use crate::components::tx_output::TxOutputSnapshot;
+use crate::partially_signed::{MaybeUnsignedTxIn, PartiallySignedTransaction};
+use crate::util::{cash_addr_to_script, dust_threshold};
+use crate::validation::is_genesis_category;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CategorySummary {
+    pub category: TokenID,
+    /// Whether the category is created by this transaction.
+    pub genesis: bool,
+    pub ft_in: i128,
+    pub ft_out: i128,
+    pub nfts_in: Vec<NftCapability>,
+    pub nfts_out: Vec<NftCapability>,
+}
+
+impl CategorySummary {
+    /// Fungible tokens created, or burned if negative.
+    pub fn ft_delta(&self) -> i128 {
+        self.ft_out - self.ft_in
+    }
+
+    /// NFTs created, or burned if negative.
+    pub fn nft_delta(&self) -> i64 {
+        self.nfts_out.len() as i64 - self.nfts_in.len() as i64
+    }
+
+    /// Output returning the fungible tokens the outputs don't spend to `address`, `None` if
+    /// there are none.
+    pub fn token_change(&self, address: &str) -> Option<Result<TxOutputSnapshot>> {
+        let excess = u64::try_from(-self.ft_delta()).ok().filter(|&e| e > 0)?;
+        Some(
+            ft_output(&self.category, address, excess).map(|output| TxOutputSnapshot {
+                label: "Token change".to_string(),
+                ..output
+            }),
+        )
+    }
+}
+
+/// Output sending `ft_amount` fungible tokens of `category` to `address`, with the minimum
+/// non-dust value.
+pub fn ft_output(category: &TokenID, address: &str, ft_amount: u64) -> Result<TxOutputSnapshot> {
+    let txout = TxOut {
+        value: 0,
+        script_pubkey: cash_addr_to_script(address)?,
+        token: Some(OutputData {
+            id: *category,
+            bitfield: Structure::HasAmount as u8,
+            amount: i64::try_from(ft_amount)?,
+            commitment: vec![],
+        }),
+    };
+    Ok(TxOutputSnapshot {
+        value: dust_threshold(&txout),
+        script_pubkey: ScriptInputValue::Addr(address.to_string()),
+        script_display_format: ScriptDisplayFormat::TokenAddr,
+        token_data: TokenDataSnapshot {
+            cashtoken_enabled: true,
+            category_id: category.to_hex(),
+            has_ft_amount: true,
+            ft_amount,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TokenSummary {
+    /// In order of first appearance, inputs first.
+    pub categories: Vec<CategorySummary>,
+    /// False if some inputs are signed, so the tokens they spend are unknown and left out.
+    pub inputs_known: bool,
+}
+
+pub fn summarize(tx: &PartiallySignedTransaction) -> TokenSummary {
+    let mut summary = TokenSummary {
+        categories: vec![],
+        inputs_known: true,
+    };
+    for txin in &tx.input {
+        let token = match txin {
+            MaybeUnsignedTxIn::Unsigned(txin) => txin.token.as_ref(),
+            MaybeUnsignedTxIn::Signed(_) => continue,
+        };
+        if let Some(token) = token {
+            let c = category(tx, &mut summary.categories, token);
+            c.ft_in += i128::from(token.amount);
+            if token.has_nft() {
+                c.nfts_in.push(NftCapability::of(token));
+            }
+        }
+    }
+    for token in tx.output.iter().filter_map(|txout| txout.token.as_ref()) {
+        let c = category(tx, &mut summary.categories, token);
+        c.ft_out += i128::from(token.amount);
+        if token.has_nft() {
+            c.nfts_out.push(NftCapability::of(token));
+        }
+    }
+    summary.inputs_known = tx
+        .input
+        .iter()
+        .all(|txin| matches!(txin, MaybeUnsignedTxIn::Unsigned(_)));
+    summary
+}
+
+fn category<'a>(
+    tx: &PartiallySignedTransaction,
+    categories: &'a mut Vec<CategorySummary>,
+    token: &OutputData,
+) -> &'a mut CategorySummary {
+    let i = match categories.iter().position(|c| c.category == token.id) {
+        Some(i) => i,
+        None => {
+            categories.push(CategorySummary {
+                category: token.id,
+                genesis: is_genesis_category(tx, &token.id),
+                ft_in: 0,
+                ft_out: 0,
+                nfts_in: vec![],
+                nfts_out: vec![],
+            });
+            categories.len() - 1
+        }
+    };
+    &mut categories[i]
+}
+
+#[cfg(test)]
+mod tests {
+    use bch_tx_edit_core::test_util::{output, signed_input, token, tx, unsigned_input, TXID};
+    use bitcoincash::{hashes::hex::FromHex, TokenID, Txid};
+
+    use super::summarize;
+    use crate::components::token_data::NftCapability;
+
+    const ADDRESS: &str = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+
+    #[test]
+    fn test_summarize() {
+        let txid = Txid::from_hex(TXID).unwrap();
+        let id = TokenID::from_hex(TXID).unwrap();
+        // Spend 100 FT and a minting NFT, send 60 FT, mint an immutable NFT and burn 40 FT
+        let summary = summarize(&tx(
+            vec![
+                unsigned_input(txid, 1, Some(token(id, 0x10, 100))),
+                unsigned_input(txid, 2, Some(token(id, 0x62, 0))),
+            ],
+            vec![
+                output(Some(token(id, 0x10, 60))),
+                output(Some(token(id, 0x62, 0))),
+                output(Some(token(id, 0x60, 0))),
+                output(None),
+            ],
+        ));
+        assert!(summary.inputs_known);
+        assert_eq!(summary.categories.len(), 1);
+        let category = &summary.categories[0];
+        assert!(!category.genesis);
+        assert_eq!((category.ft_in, category.ft_out), (100, 60));
+        assert_eq!(category.ft_delta(), -40);
+        assert_eq!(category.nfts_in, vec![NftCapability::Minting]);
+        assert_eq!(
+            category.nfts_out,
+            vec![NftCapability::Minting, NftCapability::Immutable]
+        );
+        assert_eq!(category.nft_delta(), 1);
+        let change = category.token_change(ADDRESS).unwrap().unwrap();
+        assert_eq!(change.token_data.ft_amount, 40);
+        assert_eq!(change.token_data.category_id, TXID);
+        assert!(category.token_change("qqq").unwrap().is_err());
+
+        // Genesis, with a signed input whose tokens are unknown
+        let summary = summarize(&tx(
+            vec![unsigned_input(txid, 0, None), signed_input(3)],
+            vec![output(Some(token(id, 0x10, 1000)))],
+        ));
+        assert!(!summary.inputs_known);
+        assert!(summary.categories[0].genesis);
+        assert_eq!(summary.categories[0].ft_delta(), 1000);
+        assert!(summary.categories[0].token_change(ADDRESS).is_none());
+    }
+}
@@ -0,0 +1,60 @@
+//! Encoding and decoding transactions in the codec web worker, so the page stays responsive while
+//! large transactions are converted.
+
+use std::cell::RefCell;
+
+use anyhow::{bail, Result};
+use bch_tx_edit_core::codec::{CodecRequest, CodecResponse, TxCodec};
+use futures::StreamExt;
+use gloo::worker::{reactor::ReactorBridge, Spawnable};
+
+use crate::hex_format::HexFormat;
+use crate::partially_signed::PartiallySignedTransaction;
+
+/// Loader Trunk generates for the worker, see `index.html`.
+const LOADER_PATH: &str = "./codec_worker_loader.js";
+
+thread_local! {
+    static BRIDGE: RefCell<Option<ReactorBridge<TxCodec>>> = const { RefCell::new(None) };
+}
+
+/// Send `request` to the worker, which is spawned on first use and shared by all tabs, on a bridge
+/// of its own. Dropping the bridge cancels the request.
+fn send(request: CodecRequest) -> ReactorBridge<TxCodec> {
+    let bridge = BRIDGE.with_borrow_mut(|bridge| {
+        bridge
+            .get_or_insert_with(|| TxCodec::spawner().spawn_with_loader(LOADER_PATH))
+            .fork()
+    });
+    bridge.send_input(request);
+    bridge
+}
+
+/// Parse hex like [`hex_format::parse`](crate::hex_format::parse), calling `on_progress` with the
+/// characters done and the total between chunks. Dropping the future cancels the decoding.
+pub async fn decode(
+    hex: String,
+    on_progress: impl Fn(usize, usize),
+) -> Result<PartiallySignedTransaction> {
+    let mut bridge = send(CodecRequest::Decode(hex));
+    while let Some(response) = bridge.next().await {
+        match response {
+            CodecResponse::Progress { done, total } => on_progress(done, total),
+            CodecResponse::Decoded(Ok(bytes)) => {
+                return Ok(PartiallySignedTransaction::deserialize_any(&bytes)?)
+            }
+            CodecResponse::Decoded(Err(e)) => bail!(e),
+            CodecResponse::Encoded(_) => break,
+        }
+    }
+    bail!("The codec worker stopped")
+}
+
+/// Hex of `fields`, the serialization of a transaction split into its fields.
+pub async fn encode(fields: Vec<Vec<u8>>, format: HexFormat) -> Result<String> {
+    let mut bridge = send(CodecRequest::Encode { fields, format });
+    match bridge.next().await {
+        Some(CodecResponse::Encoded(hex)) => Ok(hex),
+        _ => bail!("The codec worker stopped"),
+    }
+}
@@ -0,0 +1,75 @@
+//! Encryption of saved data under a passphrase, with AES-256-GCM keyed by PBKDF2 through the
+//! browser's WebCrypto, so the labels and notes in autosaved tabs and the xpubs of watch-only
+//! wallets aren't readable from the browser profile.
+
+use anyhow::{anyhow, Result};
+use bitcoincash::hashes::hex::{FromHex, ToHex};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+
+use crate::js_reexport::{aes_gcm, derive_key};
+
+/// PBKDF2 iterations, as recommended by OWASP for PBKDF2-HMAC-SHA256.
+const ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const IV_LEN: usize = 12;
+
+/// Encrypted data as saved, with everything but the passphrase needed to decrypt it.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sealed {
+    salt: String,
+    iv: String,
+    ciphertext: String,
+}
+
+/// A key derived from the passphrase, kept in memory while saved data is unlocked. Derivation is
+/// deliberately slow, so it's done once rather than on every save.
+#[derive(Clone)]
+pub struct VaultKey {
+    key: JsValue,
+    salt: [u8; SALT_LEN],
+}
+
+impl VaultKey {
+    /// A key for `passphrase` with a fresh salt, for encrypting data saved from now on.
+    pub async fn new(passphrase: &str) -> Result<Self> {
+        let mut salt = [0; SALT_LEN];
+        getrandom::getrandom(&mut salt)?;
+        Self::derive(passphrase, salt).await
+    }
+
+    /// The key `sealed` was encrypted with, and its decrypted text. Fails if `passphrase` is
+    /// wrong.
+    pub async fn unlock(passphrase: &str, sealed: &Sealed) -> Result<(Self, String)> {
+        let salt = Vec::from_hex(&sealed.salt)?
+            .try_into()
+            .map_err(|_| anyhow!("Invalid salt length"))?;
+        let key = Self::derive(passphrase, salt).await?;
+        let text = key.open(sealed).await?;
+        Ok((key, text))
+    }
+
+    async fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self> {
+        let key = derive_key(passphrase, &salt, ITERATIONS).await?;
+        Ok(Self { key, salt })
+    }
+
+    pub async fn seal(&self, text: &str) -> Result<Sealed> {
+        // A fresh IV for every message, GCM breaks if one is reused with the same key
+        let mut iv = [0; IV_LEN];
+        getrandom::getrandom(&mut iv)?;
+        let ciphertext = aes_gcm(true, &self.key, &iv, text.as_bytes()).await?;
+        Ok(Sealed {
+            salt: self.salt.to_hex(),
+            iv: iv.to_hex(),
+            ciphertext: ciphertext.to_hex(),
+        })
+    }
+
+    pub async fn open(&self, sealed: &Sealed) -> Result<String> {
+        let iv = Vec::from_hex(&sealed.iv)?;
+        let ciphertext = Vec::from_hex(&sealed.ciphertext)?;
+        let text = aes_gcm(false, &self.key, &iv, &ciphertext).await?;
+        Ok(String::from_utf8(text)?)
+    }
+}
@@ -0,0 +1,190 @@
+//! Checking the signatures of the signed inputs of the transaction being edited, in the standard
+//! templates [`verify_input`] recognizes, against the outputs they spend,
+//! found in pasted previous transactions, fetched from the server, or for unsigned inputs taken
+//! from their own fields.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{
+    consensus::deserialize,
+    secp256k1::{Secp256k1, Verification},
+    Transaction, TxIn, TxOut, Txid,
+};
+
+use crate::chain::{ChainBackend, Server};
+use crate::hex_format;
+use crate::partially_signed::{MaybeUnsignedTxIn, PartiallySignedTransaction};
+use crate::verification::{verify_input, Verdict};
+
+/// The transactions in `text`, in hex one per line, by txid.
+pub fn parse_transactions(text: &str) -> Result<HashMap<Txid, Transaction>> {
+    let mut transactions = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let bytes = hex_format::parse(line).with_context(|| format!("Line {}", i + 1))?;
+        if bytes.is_empty() {
+            continue;
+        }
+        let tx = deserialize::<Transaction>(&bytes)
+            .with_context(|| format!("Line {} isn't a transaction", i + 1))?;
+        transactions.insert(tx.txid(), tx);
+    }
+    Ok(transactions)
+}
+
+/// Fetch the transactions spent by `tx` that aren't in `previous` from `server` into it.
+pub async fn fetch_missing(
+    server: &Server,
+    tx: &PartiallySignedTransaction,
+    previous: &mut HashMap<Txid, Transaction>,
+) -> Result<()> {
+    let mut missing = tx
+        .input
+        .iter()
+        .map(|txin| txin.previous_output().txid)
+        .filter(|txid| !previous.contains_key(txid))
+        .collect::<Vec<_>>();
+    missing.sort();
+    missing.dedup();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let connection = server.connect().await?;
+    for txid in missing {
+        let fetched = connection.transaction(&txid).await?;
+        // A server returning another transaction than asked for can't be trusted with this one
+        if fetched.txid() != txid {
+            bail!("The server returned a different transaction for {txid}");
+        }
+        previous.insert(txid, fetched);
+    }
+    Ok(())
+}
+
+/// `tx` with the unsigned inputs' scriptSigs left empty, as signatures don't commit to them.
+fn to_transaction(tx: &PartiallySignedTransaction) -> Transaction {
+    Transaction {
+        version: tx.version,
+        lock_time: tx.lock_time,
+        input: tx
+            .input
+            .iter()
+            .map(|txin| TxIn {
+                previous_output: *txin.previous_output(),
+                script_sig: txin.script_sig().cloned().unwrap_or_default(),
+                sequence: txin.sequence(),
+                witness: Default::default(),
+            })
+            .collect(),
+        output: tx.output.clone(),
+    }
+}
+
+/// The output spent by each input of `tx`, from `previous` or else an unsigned input's fields.
+fn utxos<C: Verification>(
+    secp: &Secp256k1<C>,
+    tx: &PartiallySignedTransaction,
+    previous: &HashMap<Txid, Transaction>,
+) -> Vec<Option<TxOut>> {
+    tx.input
+        .iter()
+        .map(|txin| {
+            let outpoint = txin.previous_output();
+            let fetched = previous
+                .get(&outpoint.txid)
+                .and_then(|tx| tx.output.get(outpoint.vout as usize));
+            match (fetched, txin) {
+                (Some(output), _) => Some(output.clone()),
+                (None, MaybeUnsignedTxIn::Unsigned(txin)) => Some(TxOut {
+                    value: txin.value,
+                    script_pubkey: txin.unsigned_script_sig.script_pubkey(secp)?,
+                    token: txin.token.clone(),
+                }),
+                (None, MaybeUnsignedTxIn::Signed(_)) => None,
+            }
+        })
+        .collect()
+}
+
+/// Check each signed input of `tx`, `None` for the unsigned ones.
+pub fn verify<C: Verification>(
+    secp: &Secp256k1<C>,
+    tx: &PartiallySignedTransaction,
+    previous: &HashMap<Txid, Transaction>,
+) -> Vec<Option<Verdict>> {
+    let utxos = utxos(secp, tx, previous);
+    let transaction = to_transaction(tx);
+    tx.input
+        .iter()
+        .enumerate()
+        .map(|(i, txin)| {
+            matches!(txin, MaybeUnsignedTxIn::Signed(_))
+                .then(|| verify_input(secp, &transaction, i, &utxos))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bitcoincash::{
+        consensus::serialize, hashes::hex::ToHex, secp256k1::Secp256k1, OutPoint, PackedLockTime,
+        PrivateKey, Script, Sequence, Transaction, TxIn, TxOut,
+    };
+
+    use super::{parse_transactions, verify};
+    use crate::partially_signed::PartiallySignedTransaction;
+    use crate::signing::sign_p2pkh;
+    use crate::verification::Verdict;
+
+    #[test]
+    fn test_verify() {
+        let secp = Secp256k1::new();
+        let key =
+            PrivateKey::from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").unwrap();
+        let script_pubkey = Script::new_p2pkh(&key.public_key(&secp).pubkey_hash());
+        let previous = Transaction {
+            version: 2,
+            lock_time: PackedLockTime(0),
+            input: vec![],
+            output: vec![TxOut {
+                value: 5000,
+                script_pubkey: script_pubkey.clone(),
+                token: None,
+            }],
+        };
+        let mut tx = Transaction {
+            version: 2,
+            lock_time: PackedLockTime(0),
+            input: vec![TxIn {
+                previous_output: OutPoint {
+                    txid: previous.txid(),
+                    vout: 0,
+                },
+                script_sig: Script::new(),
+                sequence: Sequence::MAX,
+                witness: Default::default(),
+            }],
+            output: vec![TxOut {
+                value: 4000,
+                script_pubkey,
+                token: None,
+            }],
+        };
+        sign_p2pkh(&secp, &mut tx, 0, &key, 5000, None);
+        let tx = PartiallySignedTransaction::from(tx);
+
+        let text = format!("# Funding\n{}\n\n", serialize(&previous).to_hex());
+        let previous = parse_transactions(&text).unwrap();
+        assert_eq!(
+            verify(&secp, &tx, &previous),
+            [Some(Verdict::Valid("P2PKH, ECDSA signature".to_string()))]
+        );
+        assert!(matches!(
+            &verify(&secp, &tx, &HashMap::new())[..],
+            [Some(Verdict::Unchecked(_))]
+        ));
+        assert!(parse_transactions("0200").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Script numbers, the little-endian sign and magnitude encoding of integers on the VM stack, in
+//! the 64-bit range of the VM: up to 8 bytes, so ±(2^63 - 1), without `i64::MIN`.
+
+use anyhow::{bail, Context as _, Result};
+
+use crate::util::script_num;
+
+/// Largest number of bytes of a script number the VM does arithmetic on.
+pub const MAX_SCRIPT_NUM_SIZE: usize = 8;
+
+/// Encode the decimal integer `s`, failing outside the range of script numbers.
+pub fn encode(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    let n = s
+        .parse::<i64>()
+        .ok()
+        .filter(|&n| n != i64::MIN)
+        .with_context(|| match s.trim_start_matches(['-', '+']).parse::<u128>() {
+            Ok(_) => format!("{s} is outside the range of script numbers, ±{}", i64::MAX),
+            Err(_) => format!("{s} isn't an integer"),
+        })?;
+    Ok(script_num(n))
+}
+
+/// Decode a script number of up to 8 bytes, minimally encoded or not.
+pub fn decode(data: &[u8]) -> Result<i64> {
+    let Some((&last, _)) = data.split_last() else {
+        return Ok(0);
+    };
+    if data.len() > MAX_SCRIPT_NUM_SIZE {
+        bail!(
+            "Numbers are at most {MAX_SCRIPT_NUM_SIZE} bytes, this is {} bytes",
+            data.len()
+        );
+    }
+    let mut abs = 0u64;
+    for (i, &byte) in data.iter().enumerate() {
+        let byte = if i == data.len() - 1 {
+            byte & 0x7f
+        } else {
+            byte
+        };
+        abs |= u64::from(byte) << (8 * i);
+    }
+    // Without the sign bit 8 bytes hold at most i64::MAX
+    let abs = abs as i64;
+    Ok(match last & 0x80 {
+        0 => abs,
+        _ => -abs,
+    })
+}
+
+/// Whether `data` is the shortest encoding of its number, as the VM requires of numbers.
+pub fn is_minimal(data: &[u8]) -> bool {
+    decode(data).is_ok_and(|n| script_num(n) == data)
+}
+
+/// Decode a minimally encoded script number of up to 8 bytes.
+pub fn parse_script_num(data: &[u8]) -> Result<i64> {
+    let n = decode(data)?;
+    if script_num(n) != data {
+        bail!("Not a minimally encoded number");
+    }
+    Ok(n)
+}
+
+/// The script number `data` plus one, as covenants advance a counter kept in an NFT commitment.
+pub fn increment_script_num(data: &[u8]) -> Result<Vec<u8>> {
+    let n = parse_script_num(data)?;
+    match n.checked_add(1) {
+        Some(n) => Ok(script_num(n)),
+        None => bail!("{n} can't be incremented"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, increment_script_num, is_minimal, parse_script_num};
+    use crate::util::script_num;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("0").unwrap(), b"");
+        assert_eq!(encode(" -1 ").unwrap(), [0x81]);
+        assert_eq!(encode("128").unwrap(), [0x80, 0x00]);
+        assert_eq!(encode(&i64::MAX.to_string()).unwrap().len(), 8);
+        assert_eq!(encode(&(-i64::MAX).to_string()).unwrap().len(), 8);
+        let e = encode(&i64::MIN.to_string()).unwrap_err();
+        assert!(e.to_string().contains("outside the range"));
+        assert!(encode("9223372036854775808")
+            .unwrap_err()
+            .to_string()
+            .contains("outside the range"));
+        assert!(encode("1.5")
+            .unwrap_err()
+            .to_string()
+            .contains("isn't an integer"));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(&[0x01, 0x00]).unwrap(), 1);
+        assert_eq!(decode(&[0x80]).unwrap(), 0);
+        assert_eq!(decode(&[0xff; 8]).unwrap(), -i64::MAX);
+        assert!(decode(&[0x01; 9]).is_err());
+        assert!(is_minimal(&[0x80, 0x00]));
+        assert!(!is_minimal(&[0x01, 0x00]));
+        assert!(!is_minimal(&[0x01; 9]));
+    }
+
+    #[test]
+    fn test_parse_script_num() {
+        for n in [0, 1, 127, 128, -1, -255, 1_000_000, i64::MAX, -i64::MAX] {
+            assert_eq!(parse_script_num(&script_num(n)).unwrap(), n);
+        }
+        assert!(parse_script_num(&[0x01, 0x00]).is_err());
+        assert!(parse_script_num(&[0x80]).is_err());
+        assert!(parse_script_num(&[0x01; 9]).is_err());
+    }
+
+    #[test]
+    fn test_increment_script_num() {
+        assert_eq!(increment_script_num(&[]).unwrap(), [0x01]);
+        assert_eq!(increment_script_num(&[0x7f]).unwrap(), [0x80, 0x00]);
+        assert_eq!(increment_script_num(&[0x81]).unwrap(), b"");
+        assert!(increment_script_num(&script_num(i64::MAX)).is_err());
+        assert!(increment_script_num(&[0x01, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Watch-only wallets, addresses or an xpub whose coins are followed on the Electrum server to
+//! spend from with the coin selector, kept across sessions. The addresses of an xpub are found by
+//! scanning its chains up to a gap limit, as Electron Cash does.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context as _, Result};
+use bitcoincash::{
+    hashes::{hex::ToHex, sha256, Hash},
+    secp256k1::{Secp256k1, Verification},
+    util::bip32::{ChildNumber, ExtendedPubKey},
+    Script,
+};
+use futures::StreamExt;
+use gloo::storage::{LocalStorage, Storage};
+use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
+use leptos::logging::error;
+use serde::{Deserialize, Serialize};
+
+use crate::chain::{Connection, Server};
+use crate::coin_selection::Utxo;
+use crate::electrum_client::ElectrumClient;
+use crate::util::{cash_addr_to_script, script_to_cash_addr};
+use crate::vault::{Sealed, VaultKey};
+
+const WALLETS_KEY: &str = "bch-tx-editor.watch-only";
+
+/// Unused addresses in a row after which scanning a chain of an xpub stops, Electron Cash's default.
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WatchOnly {
+    pub name: String,
+    pub source: WatchSource,
+    /// For an xpub, the unused addresses in a row after which scanning a chain stops.
+    #[serde(default = "default_gap_limit")]
+    pub gap_limit: u32,
+}
+
+fn default_gap_limit() -> u32 {
+    DEFAULT_GAP_LIMIT
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchSource {
+    Addresses(Vec<String>),
+    /// An xpub of a BIP44-style account, with receiving addresses at `0/i` and change at `1/i`.
+    Xpub(String),
+}
+
+impl WatchOnly {
+    /// A wallet from an xpub, or from CashAddrs one per line.
+    pub fn parse(name: &str, text: &str, gap_limit: u32) -> Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("The wallet needs a name");
+        }
+        if gap_limit == 0 {
+            bail!("The gap limit must be at least 1");
+        }
+        let text = text.trim();
+        let source = if text.parse::<ExtendedPubKey>().is_ok() {
+            WatchSource::Xpub(text.to_string())
+        } else {
+            let addresses = text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|address| {
+                    cash_addr_to_script(address)
+                        .with_context(|| format!("{address} isn't an xpub or a CashAddr"))?;
+                    Ok(address.to_string())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if addresses.is_empty() {
+                bail!("Enter addresses or an xpub to watch");
+            }
+            WatchSource::Addresses(addresses)
+        };
+        Ok(Self {
+            name: name.to_string(),
+            source,
+            gap_limit,
+        })
+    }
+}
+
+/// The P2PKH address at `chain/index` of `xpub`, with the CashAddr `prefix`.
+pub fn derive_address<C: Verification>(
+    secp: &Secp256k1<C>,
+    xpub: &ExtendedPubKey,
+    chain: u32,
+    index: u32,
+    prefix: &str,
+) -> Result<String> {
+    let path = [
+        ChildNumber::Normal { index: chain },
+        ChildNumber::Normal { index },
+    ];
+    let pubkey = xpub.derive_pub(secp, &path)?.to_pub();
+    script_to_cash_addr(&Script::new_p2pkh(&pubkey.pubkey_hash()), prefix)
+}
+
+/// Addresses of the receiving and change chains of `xpub`, each derived until `gap_limit` in a
+/// row have no history on the server.
+async fn scan<T: ClientT + SubscriptionClientT>(
+    client: &ElectrumClient<T>,
+    xpub: &str,
+    gap_limit: u32,
+    prefix: &str,
+) -> Result<Vec<String>> {
+    let secp = Secp256k1::verification_only();
+    let xpub = xpub.parse::<ExtendedPubKey>()?;
+    let mut addresses = Vec::new();
+    for chain in 0..2 {
+        let mut unused = 0;
+        let mut index = 0;
+        while unused < gap_limit {
+            let address = derive_address(&secp, &xpub, chain, index, prefix)?;
+            let history = client
+                .blockchain_scripthash_get_history(&scripthash(&cash_addr_to_script(&address)?))
+                .await?;
+            unused = if history.is_empty() { unused + 1 } else { 0 };
+            addresses.push(address);
+            index += 1;
+        }
+    }
+    Ok(addresses)
+}
+
+/// The Electrum scripthash of `script`, the reversed SHA-256 of it in hex.
+pub fn scripthash(script: &Script) -> String {
+    let mut hash = sha256::Hash::hash(script.as_bytes()).into_inner();
+    hash.reverse();
+    hash.to_hex()
+}
+
+/// Subscribe to the addresses of `wallets` on the Electrum `server`, passing those of each wallet
+/// to `on_scanned` once found, and the UTXOs of each address to `on_update` once and again
+/// whenever they change. Runs until the connection fails.
+pub async fn follow(
+    server: &str,
+    wallets: &[WatchOnly],
+    prefix: &str,
+    on_scanned: impl Fn(&str, Vec<String>),
+    on_update: impl Fn(&str, Vec<Utxo>),
+) -> Result<()> {
+    let Connection::Electrum(client) = Server::Electrum(server.to_string()).connect().await? else {
+        unreachable!("Electrum servers connect with Electrum");
+    };
+    let refresh = |scripthash: &str, address: &str| {
+        let (client, scripthash, address) = (&client, scripthash.to_string(), address.to_string());
+        async move {
+            let utxos = client
+                .blockchain_scripthash_listunspent(&scripthash)
+                .await?
+                .into_iter()
+                .map(|entry| {
+                    let mut utxo = Utxo::try_from(entry)?;
+                    utxo.address.clone_from(&address);
+                    Ok(utxo)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            anyhow::Ok((address, utxos))
+        }
+    };
+    // Subscribe to notifications first, to not miss changes in between
+    let mut notifications = client.scripthash_notifications().await?;
+    let mut by_scripthash = BTreeMap::new();
+    for wallet in wallets {
+        let addresses = match &wallet.source {
+            WatchSource::Addresses(addresses) => addresses.clone(),
+            WatchSource::Xpub(xpub) => scan(&client, xpub, wallet.gap_limit, prefix).await?,
+        };
+        on_scanned(&wallet.name, addresses.clone());
+        for address in addresses {
+            let scripthash = scripthash(&cash_addr_to_script(&address)?);
+            client.blockchain_scripthash_subscribe(&scripthash).await?;
+            let (address, utxos) = refresh(&scripthash, &address).await?;
+            on_update(&address, utxos);
+            by_scripthash.insert(scripthash, address);
+        }
+    }
+    while let Some(scripthash) = notifications.next().await {
+        let scripthash = scripthash?;
+        let Some(address) = by_scripthash.get(&scripthash) else {
+            continue;
+        };
+        let (address, utxos) = refresh(&scripthash, address).await?;
+        on_update(&address, utxos);
+    }
+    bail!("{server} closed the connection")
+}
+
+/// The saved wallets, encrypted as JSON when saved data is encrypted, as their xpubs reveal the
+/// whole history of an account.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Saved {
+    Encrypted(Sealed),
+    Plain(Vec<WatchOnly>),
+}
+
+pub fn load() -> Option<Saved> {
+    LocalStorage::get(WALLETS_KEY).ok()
+}
+
+pub fn save(wallets: &[WatchOnly]) {
+    if let Err(e) = LocalStorage::set(WALLETS_KEY, wallets) {
+        error!("Saving watch-only wallets failed: {e}");
+    }
+}
+
+pub async fn save_encrypted(key: &VaultKey, wallets: &[WatchOnly]) {
+    let saving = async {
+        let sealed = key.seal(&serde_json::to_string(wallets)?).await?;
+        LocalStorage::set(WALLETS_KEY, Saved::Encrypted(sealed))?;
+        anyhow::Ok(())
+    };
+    if let Err(e) = saving.await {
+        error!("Saving watch-only wallets failed: {e}");
+    }
+}
+
+/// Decrypt wallets saved with [`save_encrypted`], failing if `key` isn't the one they were
+/// encrypted with.
+pub async fn open(key: &VaultKey, sealed: &Sealed) -> Result<Vec<WatchOnly>> {
+    Ok(serde_json::from_str(&key.open(sealed).await?)?)
+}
+
+pub fn clear() {
+    LocalStorage::delete(WALLETS_KEY);
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincash::{hashes::hex::FromHex, secp256k1::Secp256k1, Script};
+
+    use super::{derive_address, scripthash, WatchOnly, WatchSource};
+
+    #[test]
+    fn test_scripthash() {
+        // The example of the Electrum protocol documentation
+        let script = Script::from(
+            Vec::from_hex("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac").unwrap(),
+        );
+        assert_eq!(
+            scripthash(&script),
+            "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        let wallet = WatchOnly::parse("Cold", xpub, 20).unwrap();
+        assert_eq!(wallet.source, WatchSource::Xpub(xpub.to_string()));
+        assert!(WatchOnly::parse("Cold", xpub, 0).is_err());
+
+        let address = "bitcoincash:qp3wjpa3tjlj042z2wv7hahsldgwhwy0rq9sywjpyy";
+        let wallet = WatchOnly::parse("Hot", &format!("{address}\n\n"), 20).unwrap();
+        assert_eq!(
+            wallet.source,
+            WatchSource::Addresses(vec![address.to_string()])
+        );
+        assert!(WatchOnly::parse("", address, 20).is_err());
+        assert!(WatchOnly::parse("Typo", "bitcoincash:qp3wjpa3", 20).is_err());
+    }
+
+    #[test]
+    fn test_derive_address() {
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+            .parse()
+            .unwrap();
+        let secp = Secp256k1::verification_only();
+        let receiving = derive_address(&secp, &xpub, 0, 0, "bitcoincash").unwrap();
+        let change = derive_address(&secp, &xpub, 1, 0, "bchtest").unwrap();
+        assert!(receiving.starts_with("bitcoincash:q"));
+        assert!(change.starts_with("bchtest:q"));
+        assert_ne!(
+            receiving,
+            derive_address(&secp, &xpub, 0, 1, "bitcoincash").unwrap()
+        );
+    }
+}